@@ -105,7 +105,7 @@ fn read_extra_model_paths(install_root: &Path) -> Vec<PathBuf> {
 }
 
 fn read_extra_model_paths_file(base_dir: &Path, yaml_path: &Path) -> Vec<PathBuf> {
-    let Ok(content) = fs::read_to_string(&yaml_path) else {
+    let Ok(content) = fs::read_to_string(yaml_path) else {
         return Vec::new();
     };
 
@@ -103,7 +103,7 @@ pub fn collect_model_files(root: &Path) -> Vec<ModelItem> {
         })
         .collect();
 
-    models.sort_by(|left, right| right.size.cmp(&left.size));
+    models.sort_by_key(|item| std::cmp::Reverse(item.size));
     models
 }
 
@@ -116,7 +116,7 @@ pub fn source_from_models(
         return None;
     }
 
-    models.sort_by(|left, right| right.size.cmp(&left.size));
+    models.sort_by_key(|item| std::cmp::Reverse(item.size));
     let total_size = models.iter().map(|model| model.size).sum();
 
     Some(AssetSource {
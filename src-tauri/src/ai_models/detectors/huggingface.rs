@@ -74,7 +74,7 @@ fn collect_huggingface_models(root: &Path) -> Vec<ModelItem> {
         })
         .collect();
 
-    models.sort_by(|left, right| right.size.cmp(&left.size));
+    models.sort_by_key(|item| std::cmp::Reverse(item.size));
     models
 }
 
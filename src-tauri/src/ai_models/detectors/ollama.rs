@@ -46,7 +46,7 @@ impl ModelDetector for OllamaDetector {
             }
         }
 
-        merged_models.sort_by(|left, right| right.size.cmp(&left.size));
+        merged_models.sort_by_key(|item| std::cmp::Reverse(item.size));
         let total_size = referenced_blobs
             .iter()
             .filter_map(|path| file_size(path))
@@ -1,18 +1,14 @@
 #![cfg(target_os = "windows")]
 
 use crate::ai_models::model_file_rules::mft_model_min_size;
-use crate::ai_models::types::{AiModelPhaseDuration, AiModelScanProgress, AssetSource, ModelItem};
+use crate::ai_models::types::{
+    AiModelPhaseDuration, AiModelScanProgress, AssetSource, CoveredRoot, ModelItem,
+};
 use crate::scanner::big_files_engine::mft_core;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-#[derive(Debug, Clone)]
-pub struct CoveredRoot {
-    pub source_name: String,
-    pub path: PathBuf,
-}
-
 pub fn discover_models_via_mft<F>(
     covered_roots: &[CoveredRoot],
     scan_started_at: &Instant,
@@ -1,9 +1,31 @@
 mod detectors;
+#[cfg(target_os = "windows")]
 mod mft_discovery;
 mod model_file_rules;
 mod scanner;
 mod types;
 
+/// 非 Windows 平台没有 MFT，深度发现只返回一条提示
+#[cfg(not(target_os = "windows"))]
+mod mft_discovery {
+    use super::types::{AiModelPhaseDuration, AiModelScanProgress, AssetSource, CoveredRoot};
+    use std::time::Instant;
+
+    pub fn discover_models_via_mft<F>(
+        _covered_roots: &[CoveredRoot],
+        _scan_started_at: &Instant,
+        _progress: &F,
+    ) -> (Vec<AssetSource>, Vec<String>, Vec<AiModelPhaseDuration>)
+    where
+        F: Fn(AiModelScanProgress) + Sync,
+    {
+        (
+            Vec::new(),
+            vec!["深度发现依赖 NTFS MFT，仅支持Windows系统".to_string()],
+            Vec::new(),
+        )
+    }
+}
 pub use model_file_rules::is_supported_model_extension;
 pub use scanner::scan_ai_model_assets_with_progress;
 pub use types::AiModelScanResult;
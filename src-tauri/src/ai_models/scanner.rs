@@ -1,8 +1,8 @@
 use crate::ai_models::detectors::create_detectors;
-use crate::ai_models::mft_discovery::{discover_models_via_mft, CoveredRoot};
+use crate::ai_models::mft_discovery::discover_models_via_mft;
 use crate::ai_models::model_file_rules::is_model_file_path;
 use crate::ai_models::types::{
-    AiModelPhaseDuration, AiModelScanProgress, AiModelScanResult, AssetSource,
+    AiModelPhaseDuration, AiModelScanProgress, AiModelScanResult, AssetSource, CoveredRoot,
 };
 use rayon::prelude::*;
 use std::collections::HashSet;
@@ -208,13 +208,13 @@ fn dedupe_models_by_path(mut sources: Vec<AssetSource>) -> Vec<AssetSource> {
 
         source
             .models
-            .sort_by(|left, right| right.size.cmp(&left.size));
+            .sort_by_key(|item| std::cmp::Reverse(item.size));
         source.model_count = source.models.len();
         source.total_size = source.models.iter().map(|model| model.size).sum();
         deduped_sources.push(source);
     }
 
-    deduped_sources.sort_by(|left, right| right.total_size.cmp(&left.total_size));
+    deduped_sources.sort_by_key(|item| std::cmp::Reverse(item.total_size));
     deduped_sources
 }
 
@@ -235,7 +235,7 @@ fn merge_sources_by_name(sources: Vec<AssetSource>) -> Vec<AssetSource> {
             }
             existing_source
                 .models
-                .sort_by(|left, right| right.size.cmp(&left.size));
+                .sort_by_key(|item| std::cmp::Reverse(item.size));
             existing_source.model_count = existing_source.models.len();
             existing_source.total_size =
                 existing_source.models.iter().map(|model| model.size).sum();
@@ -248,7 +248,7 @@ fn merge_sources_by_name(sources: Vec<AssetSource>) -> Vec<AssetSource> {
         }
     }
 
-    merged_sources.sort_by(|left, right| right.total_size.cmp(&left.total_size));
+    merged_sources.sort_by_key(|item| std::cmp::Reverse(item.total_size));
     merged_sources
 }
 
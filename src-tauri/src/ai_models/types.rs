@@ -43,3 +43,10 @@ pub struct AiModelScanProgress {
     pub elapsed_ms: u128,
     pub stage_elapsed_ms: u128,
 }
+
+/// 已由配置层探测器覆盖的目录，MFT 深度发现时跳过这些目录下的模型
+#[derive(Debug, Clone)]
+pub struct CoveredRoot {
+    pub source_name: String,
+    pub path: PathBuf,
+}
@@ -0,0 +1,131 @@
+// ============================================================================
+// CBS 归档日志
+//
+// C:\Windows\Logs\CBS 是组件服务（TrustedInstaller / Windows 更新）的日志目录。
+// 当前日志 CBS.log 超过大小上限后会被改名为 CbsPersist_<时间>.log，再压缩成同名 .cab，
+// 这些归档不会自动清理，长期更新的系统上经常累积到几个 GB。
+// 通用扫描引擎把整个 CBS 目录视为系统保护路径，这里只处理已轮转的 CbsPersist_* 归档，
+// 正在写入的 CBS.log 以及 FilterList.log 等其他文件一律保留。
+//
+// 归档刚生成时可能仍被 TrustedInstaller 占用（正在压缩），删除失败时标记为重启后删除。
+// ============================================================================
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::reboot_fallback::delete_with_reboot_fallback;
+use super::safety_constants::is_reparse_point;
+use crate::scanner::DeleteResult;
+
+/// 轮转归档的文件名前缀（小写）
+const CBS_ARCHIVE_PREFIX: &str = "cbspersist_";
+
+/// 轮转归档的扩展名：压缩前的 .log 和压缩后的 .cab
+const CBS_ARCHIVE_EXTENSIONS: &[&str] = &["log", "cab"];
+
+/// CBS 归档日志汇总
+#[derive(Debug, Clone, Serialize)]
+pub struct CbsLogInfo {
+    /// CBS 日志目录
+    pub cbs_dir: String,
+    /// 归档总大小（字节）
+    pub total_size: u64,
+    /// 归档文件数
+    pub file_count: usize,
+}
+
+/// 统计可清理的 CBS 轮转归档
+pub fn get_cbs_log_info() -> std::io::Result<CbsLogInfo> {
+    let cbs_dir = cbs_dir()?;
+    let files = cbs_archive_files(&cbs_dir);
+    Ok(CbsLogInfo {
+        cbs_dir: cbs_dir.to_string_lossy().into_owned(),
+        total_size: files.iter().map(|(_, size)| size).sum(),
+        file_count: files.len(),
+    })
+}
+
+/// 删除 CBS 轮转归档，保留当前的 CBS.log；被占用的归档标记为重启后删除
+pub fn clean_cbs_logs() -> std::io::Result<DeleteResult> {
+    let cbs_dir = cbs_dir()?;
+    let result = delete_with_reboot_fallback(cbs_archive_files(&cbs_dir));
+
+    log::info!(
+        "CBS 归档日志清理完成: 成功 {}, 重启后删除 {}, 失败 {}",
+        result.success_count,
+        result.reboot_pending_count,
+        result.failed_count
+    );
+    Ok(result)
+}
+
+/// 定位 CBS 日志目录并确认可以读取，保留系统错误类型供命令层区分不存在和无权限
+fn cbs_dir() -> std::io::Result<PathBuf> {
+    let system_root = std::env::var_os("SystemRoot")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("C:\\Windows"));
+    let dir = system_root.join("Logs").join("CBS");
+    fs::read_dir(&dir).map_err(|e| {
+        let message = match e.kind() {
+            std::io::ErrorKind::NotFound => format!("未找到 CBS 日志目录: {}", dir.display()),
+            std::io::ErrorKind::PermissionDenied => {
+                format!("没有权限读取 CBS 日志目录: {}", dir.display())
+            }
+            _ => format!("无法读取 CBS 日志目录 {}: {}", dir.display(), e),
+        };
+        std::io::Error::new(e.kind(), message)
+    })?;
+    Ok(dir)
+}
+
+/// 是否为已轮转的 CBS 归档（CbsPersist_*.log / CbsPersist_*.cab）
+fn is_cbs_archive_name(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.starts_with(CBS_ARCHIVE_PREFIX)
+        && Path::new(&name)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| CBS_ARCHIVE_EXTENSIONS.contains(&ext))
+}
+
+/// 列出 CBS 目录下（不递归）的轮转归档及其大小
+fn cbs_archive_files(cbs_dir: &Path) -> Vec<(PathBuf, u64)> {
+    let Ok(entries) = fs::read_dir(cbs_dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            (is_cbs_archive_name(&entry.file_name().to_string_lossy())
+                && metadata.is_file()
+                && !is_reparse_point(&metadata))
+            .then(|| (entry.path(), metadata.len()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cbs_archives_exclude_active_log() {
+        let root = std::env::temp_dir().join(format!("lightc-cbs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("CbsPersist_old")).unwrap();
+        fs::write(root.join("CBS.log"), vec![0u8; 10]).unwrap();
+        fs::write(root.join("FilterList.log"), vec![0u8; 20]).unwrap();
+        fs::write(root.join("CbsPersist_20240101000000.cab"), vec![0u8; 30]).unwrap();
+        fs::write(root.join("CbsPersist_20240201000000.log"), vec![0u8; 40]).unwrap();
+        fs::write(root.join("CbsPersist_20240301000000.txt"), vec![0u8; 50]).unwrap();
+
+        let files = cbs_archive_files(&root);
+        let _ = fs::remove_dir_all(&root);
+
+        let total: u64 = files.iter().map(|(_, size)| size).sum();
+        assert_eq!(files.len(), 2);
+        assert_eq!(total, 70);
+    }
+}
@@ -0,0 +1,182 @@
+// ============================================================================
+// NTFS 压缩
+//
+// 大文件视图里的旧归档、视频用户往往还想保留，删除不是唯一选择。对这些文件开启
+// NTFS 透明压缩（FSCTL_SET_COMPRESSION），文件仍可正常打开，只是占用空间变小。
+// 压缩前后用 GetCompressedFileSizeW 读取实际分配大小，向用户报告省下的空间。
+// 已经压缩过的格式（mp4、zip 等）效果有限，由调用方根据结果自行提示。
+// ============================================================================
+
+use serde::Serialize;
+use std::path::Path;
+
+/// 压缩结果
+#[derive(Debug, Clone, Serialize)]
+pub struct CompressResult {
+    pub path: String,
+    /// 文件逻辑大小（字节）
+    pub logical_size: u64,
+    /// 压缩前占用的磁盘空间（字节）
+    pub before_size: u64,
+    /// 压缩后占用的磁盘空间（字节）
+    pub after_size: u64,
+    /// 节省的空间（字节）
+    pub saved_size: u64,
+}
+
+/// 对单个文件开启 NTFS 压缩，返回压缩前后的占用空间
+pub fn compress_file(path: &str) -> Result<CompressResult, String> {
+    let file_path = Path::new(path);
+    let metadata = std::fs::symlink_metadata(file_path)
+        .map_err(|e| format!("无法读取文件 {}: {}", path, e))?;
+    if !metadata.is_file() {
+        return Err(format!("只支持压缩普通文件: {}", path));
+    }
+    if is_system_file(file_path) {
+        return Err(format!("不允许压缩系统文件: {}", path));
+    }
+
+    let before_size = allocated_size(path)?;
+    set_compression(path)?;
+    let after_size = allocated_size(path)?;
+    log::info!(
+        "NTFS 压缩完成: {} ({} -> {} 字节)",
+        path,
+        before_size,
+        after_size
+    );
+
+    Ok(CompressResult {
+        path: path.to_string(),
+        logical_size: metadata.len(),
+        before_size,
+        after_size,
+        saved_size: before_size.saturating_sub(after_size),
+    })
+}
+
+/// Windows 目录下的文件和启动相关文件压缩或移走后可能导致系统无法启动
+pub(super) fn is_system_file(path: &Path) -> bool {
+    let path_lower = path.to_string_lossy().to_lowercase();
+    let windir = std::env::var("SystemRoot")
+        .unwrap_or_else(|_| "C:\\Windows".to_string())
+        .to_lowercase();
+    if path_lower.starts_with(&format!("{}\\", windir.trim_end_matches('\\'))) {
+        return true;
+    }
+    let file_name = path_lower.rsplit(['\\', '/']).next().unwrap_or_default();
+    matches!(
+        file_name,
+        "pagefile.sys" | "hiberfil.sys" | "swapfile.sys" | "bootmgr"
+    )
+}
+
+#[cfg(target_os = "windows")]
+fn allocated_size(path: &str) -> Result<u64, String> {
+    use winapi::um::errhandlingapi::{GetLastError, SetLastError};
+    use winapi::um::fileapi::{GetCompressedFileSizeW, INVALID_FILE_SIZE};
+
+    let wide = super::enhanced_delete::windows_api::to_wide_string(path);
+    let mut high: u32 = 0;
+    let low = unsafe {
+        SetLastError(0);
+        GetCompressedFileSizeW(wide.as_ptr(), &mut high)
+    };
+    // 低 32 位恰好为 0xFFFFFFFF 时也是合法大小，必须再看错误码
+    if low == INVALID_FILE_SIZE {
+        let err = unsafe { GetLastError() };
+        if err != 0 {
+            return Err(format!("读取占用空间失败: {} (错误码: {})", path, err));
+        }
+    }
+    Ok(((high as u64) << 32) | low as u64)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn allocated_size(_path: &str) -> Result<u64, String> {
+    Err("此功能仅支持Windows系统".to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn set_compression(path: &str) -> Result<(), String> {
+    use winapi::shared::minwindef::{DWORD, LPVOID};
+    use winapi::um::errhandlingapi::{GetLastError, SetLastError};
+    use winapi::um::fileapi::{CreateFileW, OPEN_EXISTING};
+    use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+    use winapi::um::ioapiset::DeviceIoControl;
+    use winapi::um::winnt::{FILE_SHARE_READ, GENERIC_READ, GENERIC_WRITE};
+
+    const FSCTL_SET_COMPRESSION: DWORD = 0x0009_C040;
+    const COMPRESSION_FORMAT_DEFAULT: u16 = 1;
+    const ERROR_SHARING_VIOLATION: DWORD = 32;
+    const ERROR_NOT_SUPPORTED: DWORD = 50;
+    const ERROR_INVALID_FUNCTION: DWORD = 1;
+
+    let wide = super::enhanced_delete::windows_api::to_wide_string(path);
+    let handle = unsafe {
+        CreateFileW(
+            wide.as_ptr(),
+            GENERIC_READ | GENERIC_WRITE,
+            FILE_SHARE_READ,
+            std::ptr::null_mut(),
+            OPEN_EXISTING,
+            0,
+            std::ptr::null_mut(),
+        )
+    };
+    if handle == INVALID_HANDLE_VALUE {
+        let err = unsafe { GetLastError() };
+        if err == ERROR_SHARING_VIOLATION {
+            return Err(format!("文件正被其他程序使用，无法压缩: {}", path));
+        }
+        return Err(format!("无法打开文件 {} (错误码: {})", path, err));
+    }
+
+    let mut format = COMPRESSION_FORMAT_DEFAULT;
+    let mut returned: DWORD = 0;
+    let ok = unsafe {
+        SetLastError(0);
+        DeviceIoControl(
+            handle,
+            FSCTL_SET_COMPRESSION,
+            &mut format as *mut _ as LPVOID,
+            std::mem::size_of::<u16>() as DWORD,
+            std::ptr::null_mut(),
+            0,
+            &mut returned,
+            std::ptr::null_mut(),
+        )
+    };
+    let err = unsafe { GetLastError() };
+    unsafe { CloseHandle(handle) };
+
+    if ok == 0 {
+        // FAT32、exFAT 和 ReFS 不支持 NTFS 压缩
+        if err == ERROR_NOT_SUPPORTED || err == ERROR_INVALID_FUNCTION {
+            return Err(format!("文件所在的文件系统不支持 NTFS 压缩: {}", path));
+        }
+        return Err(format!("设置 NTFS 压缩失败: {} (错误码: {})", path, err));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_compression(_path: &str) -> Result<(), String> {
+    Err("此功能仅支持Windows系统".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_system_files_are_not_compressed() {
+        let windir = std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".to_string());
+        assert!(is_system_file(Path::new(&format!(
+            "{}\\System32\\ntoskrnl.exe",
+            windir
+        ))));
+        assert!(is_system_file(Path::new("C:\\pagefile.sys")));
+        assert!(!is_system_file(Path::new("D:\\Archive\\backup-2019.zip")));
+    }
+}
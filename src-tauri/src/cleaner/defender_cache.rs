@@ -0,0 +1,165 @@
+// ============================================================================
+// Windows Defender 扫描历史缓存
+//
+// C:\ProgramData\Microsoft\Windows Defender 在保护列表里，通用删除引擎一律拒绝，
+// 但其中的扫描结果和缓存管理文件会不断累积，是"ProgramData 为什么这么大"的常见答案。
+// 这里只处理 Defender 下次扫描时会重建的扫描历史缓存：Scans\History 下的
+// CacheManager、Results，以及 Scans 目录下的 mpcache-*.bin。
+// Quarantine（隔离区）和 Service\DetectionHistory（威胁检测记录，关联隔离文件）永远不碰。
+//
+// 这些文件大多被 MsMpEng.exe 占用，删除失败时标记为重启后删除。
+// ============================================================================
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::reboot_fallback::delete_with_reboot_fallback;
+use super::safety_constants::{is_reparse_point, is_reparse_point_dir};
+use crate::scanner::DeleteResult;
+
+/// 可清理的扫描历史子目录（相对 Windows Defender 数据目录）
+const DEFENDER_CACHE_SUBDIRS: &[&str] =
+    &["Scans\\History\\CacheManager", "Scans\\History\\Results"];
+
+/// Scans 目录下的扫描缓存文件名前缀（小写）
+const SCAN_CACHE_FILE_PREFIX: &str = "mpcache-";
+
+/// Defender 扫描历史缓存汇总
+#[derive(Debug, Clone, Serialize)]
+pub struct DefenderCacheInfo {
+    /// Windows Defender 数据目录
+    pub defender_dir: String,
+    /// 缓存总大小（字节）
+    pub total_size: u64,
+    /// 缓存文件数
+    pub file_count: usize,
+}
+
+/// 统计可清理的 Defender 扫描历史缓存
+pub fn get_defender_cache_info() -> std::io::Result<DefenderCacheInfo> {
+    let defender_dir = defender_dir()?;
+    let files = defender_cache_files(&defender_dir);
+    Ok(DefenderCacheInfo {
+        defender_dir: defender_dir.to_string_lossy().into_owned(),
+        total_size: files.iter().map(|(_, size)| size).sum(),
+        file_count: files.len(),
+    })
+}
+
+/// 删除 Defender 扫描历史缓存，被占用的文件标记为重启后删除
+pub fn clean_defender_cache() -> std::io::Result<DeleteResult> {
+    let defender_dir = defender_dir()?;
+    let result = delete_with_reboot_fallback(defender_cache_files(&defender_dir));
+
+    log::info!(
+        "Defender 扫描历史缓存清理完成: 成功 {}, 重启后删除 {}, 失败 {}",
+        result.success_count,
+        result.reboot_pending_count,
+        result.failed_count
+    );
+    Ok(result)
+}
+
+/// 定位 Defender 数据目录并确认可以读取
+///
+/// 保留系统错误类型：目录不存在和没有权限读取需要分别提示，命令层据此区分。
+fn defender_dir() -> std::io::Result<PathBuf> {
+    let program_data = std::env::var_os("ProgramData")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("C:\\ProgramData"));
+    let dir = program_data.join("Microsoft").join("Windows Defender");
+    fs::read_dir(&dir).map_err(|e| {
+        let message = match e.kind() {
+            std::io::ErrorKind::NotFound => {
+                format!("未找到 Windows Defender 数据目录: {}", dir.display())
+            }
+            std::io::ErrorKind::PermissionDenied => {
+                format!("没有权限读取 Windows Defender 数据目录: {}", dir.display())
+            }
+            _ => format!(
+                "无法读取 Windows Defender 数据目录 {}: {}",
+                dir.display(),
+                e
+            ),
+        };
+        std::io::Error::new(e.kind(), message)
+    })?;
+    Ok(dir)
+}
+
+/// 列出可清理的缓存文件及其大小；只返回文件，目录结构保留给 Defender 继续使用
+fn defender_cache_files(defender_dir: &Path) -> Vec<(PathBuf, u64)> {
+    let mut files = Vec::new();
+
+    for subdir in DEFENDER_CACHE_SUBDIRS {
+        let dir = subdir
+            .split('\\')
+            .fold(defender_dir.to_path_buf(), |dir, part| dir.join(part));
+        files.extend(
+            WalkDir::new(&dir)
+                .follow_links(false)
+                .into_iter()
+                .filter_entry(|entry| !is_reparse_point_dir(entry))
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_type().is_file())
+                .filter_map(|entry| {
+                    Some((entry.path().to_path_buf(), entry.metadata().ok()?.len()))
+                }),
+        );
+    }
+
+    if let Ok(entries) = fs::read_dir(defender_dir.join("Scans")) {
+        files.extend(entries.filter_map(|entry| entry.ok()).filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            let metadata = entry.metadata().ok()?;
+            (name.starts_with(SCAN_CACHE_FILE_PREFIX)
+                && metadata.is_file()
+                && !is_reparse_point(&metadata))
+            .then(|| (entry.path(), metadata.len()))
+        }));
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defender_cache_excludes_quarantine_and_detection_history() {
+        let root = std::env::temp_dir().join(format!("lightc-defender-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let history = root.join("Scans").join("History");
+        fs::create_dir_all(history.join("CacheManager")).unwrap();
+        fs::create_dir_all(history.join("Results").join("Quick")).unwrap();
+        fs::create_dir_all(history.join("Service").join("DetectionHistory")).unwrap();
+        fs::create_dir_all(root.join("Quarantine").join("Entries")).unwrap();
+        fs::write(history.join("CacheManager").join("a.bin"), vec![0u8; 10]).unwrap();
+        fs::write(
+            history.join("Results").join("Quick").join("b"),
+            vec![0u8; 20],
+        )
+        .unwrap();
+        fs::write(root.join("Scans").join("mpcache-1A2B.bin"), vec![0u8; 30]).unwrap();
+        fs::write(root.join("Scans").join("mpenginedb.db"), vec![0u8; 40]).unwrap();
+        fs::write(
+            history.join("Service").join("DetectionHistory").join("c"),
+            vec![0u8; 50],
+        )
+        .unwrap();
+        fs::write(
+            root.join("Quarantine").join("Entries").join("d"),
+            vec![0u8; 60],
+        )
+        .unwrap();
+
+        let files = defender_cache_files(&root);
+        let _ = fs::remove_dir_all(&root);
+
+        let total: u64 = files.iter().map(|(_, size)| size).sum();
+        assert_eq!(files.len(), 3);
+        assert_eq!(total, 60);
+    }
+}
@@ -4,26 +4,48 @@
 // ============================================================================
 
 use log::{debug, error, info, warn};
+use std::collections::BTreeSet;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::scanner::{DeleteResult, FileInfo};
+use crate::scanner::{
+    is_config_msi_path, is_msi_install_in_progress, DeleteResult, FileInfo, JunkCategory,
+    MSI_INSTALL_IN_PROGRESS_MESSAGE,
+};
 
 // ============================================================================
 // 安全保护配置 — 统一从共享模块引用
 // ============================================================================
 
+use super::enhanced_delete::DeleteFailureReason;
+use super::long_path::extended_path;
+use super::restart_manager::{
+    describe_locking_processes, find_locking_processes, lock_query_files,
+};
 use super::safety_constants::{
-    is_rebuildable_system_cache_path, PROTECTED_EXTENSIONS_IN_WINDOWS, PROTECTED_FILES,
-    PROTECTED_PATH_PREFIXES,
+    find_protected_data_file, is_protected_data_path, is_rebuildable_system_cache_path,
+    is_recycle_bin_path, is_reparse_point, is_reparse_point_dir, PROTECTED_EXTENSIONS_IN_WINDOWS,
+    PROTECTED_FILES, PROTECTED_PATH_PREFIXES,
 };
 
+/// 单个路径的删除结果
+enum DeleteOutcome {
+    /// 已删除，附带释放的大小
+    Deleted(u64),
+    /// 文件被占用，已标记为重启后删除
+    RebootPending(u64),
+    /// 删除前路径已不存在（扫描后被应用自行清理）
+    NotFound,
+}
+
 /// 删除引擎
 pub struct DeleteEngine {
     /// 是否使用安全删除模式（移动到回收站而非直接删除）
     safe_mode: bool,
     /// 是否跳过正在使用的文件
     skip_in_use: bool,
+    /// 删除后是否清理留下的空目录
+    prune_empty_dirs: bool,
 }
 
 impl DeleteEngine {
@@ -32,6 +54,7 @@ impl DeleteEngine {
         DeleteEngine {
             safe_mode: false,  // 默认直接删除
             skip_in_use: true, // 默认跳过正在使用的文件
+            prune_empty_dirs: false,
         }
     }
 
@@ -47,22 +70,35 @@ impl DeleteEngine {
         self
     }
 
+    /// 设置删除后是否清理空目录
+    ///
+    /// 只清理扫描根目录内部的空目录，扫描根目录本身和范围外的目录保持不动。
+    pub fn with_prune_empty_dirs(mut self, enabled: bool) -> Self {
+        self.prune_empty_dirs = enabled;
+        self
+    }
+
     /// 删除文件列表
     pub fn delete_files(&self, files: &[FileInfo]) -> DeleteResult {
         let mut result = DeleteResult::new();
 
         info!("开始删除 {} 个文件", files.len());
+        let mut deleted_paths = Vec::new();
 
         for file in files {
             match self.delete_single_file(&file.path, file.size) {
-                Ok((freed, marked_for_reboot)) => {
-                    if marked_for_reboot {
-                        result.add_reboot_pending(freed);
-                        debug!("已标记重启删除: {}", file.path);
-                    } else {
-                        result.add_success(freed);
-                        debug!("成功删除: {}", file.path);
-                    }
+                Ok(DeleteOutcome::Deleted(freed)) => {
+                    result.add_success(freed);
+                    deleted_paths.push(PathBuf::from(&file.path));
+                    debug!("成功删除: {}", file.path);
+                }
+                Ok(DeleteOutcome::RebootPending(freed)) => {
+                    result.add_reboot_pending(freed);
+                    debug!("已标记重启删除: {}", file.path);
+                }
+                Ok(DeleteOutcome::NotFound) => {
+                    result.add_skipped_notfound();
+                    debug!("文件已不存在，跳过: {}", file.path);
                 }
                 Err(e) => {
                     result.add_failure(file.path.clone(), e);
@@ -78,12 +114,14 @@ impl DeleteEngine {
                 }
             }
         }
+        self.prune_after_delete(&deleted_paths);
 
         info!(
-            "删除完成: 成功 {} 个, 失败 {} 个, 待重启 {} 个, 释放空间 {} 字节",
+            "删除完成: 成功 {} 个, 失败 {} 个, 待重启 {} 个, 已不存在 {} 个, 释放空间 {} 字节",
             result.success_count,
             result.failed_count,
             result.reboot_pending_count,
+            result.skipped_notfound,
             result.freed_size
         );
 
@@ -95,20 +133,25 @@ impl DeleteEngine {
         let mut result = DeleteResult::new();
 
         info!("开始删除 {} 个路径", paths.len());
+        let mut deleted_paths = Vec::new();
 
         for path in paths {
             let file_path = Path::new(path);
             let size = self.get_path_size(file_path);
 
             match self.delete_single_file(path, size) {
-                Ok((freed, marked_for_reboot)) => {
-                    if marked_for_reboot {
-                        result.add_reboot_pending(freed);
-                        debug!("已标记重启删除: {}", path);
-                    } else {
-                        result.add_success(freed);
-                        debug!("成功删除: {}", path);
-                    }
+                Ok(DeleteOutcome::Deleted(freed)) => {
+                    result.add_success(freed);
+                    deleted_paths.push(PathBuf::from(path));
+                    debug!("成功删除: {}", path);
+                }
+                Ok(DeleteOutcome::RebootPending(freed)) => {
+                    result.add_reboot_pending(freed);
+                    debug!("已标记重启删除: {}", path);
+                }
+                Ok(DeleteOutcome::NotFound) => {
+                    result.add_skipped_notfound();
+                    debug!("路径已不存在，跳过: {}", path);
                 }
                 Err(e) => {
                     result.add_failure(path.clone(), e);
@@ -116,26 +159,96 @@ impl DeleteEngine {
                 }
             }
         }
+        self.prune_after_delete(&deleted_paths);
 
         info!(
-            "删除完成: 成功 {} 个, 失败 {} 个, 待重启 {} 个, 释放空间 {} 字节",
+            "删除完成: 成功 {} 个, 失败 {} 个, 待重启 {} 个, 已不存在 {} 个, 释放空间 {} 字节",
             result.success_count,
             result.failed_count,
             result.reboot_pending_count,
+            result.skipped_notfound,
             result.freed_size
         );
 
         result
     }
 
+    /// 按垃圾分类的扫描根目录清理删除后留下的空目录
+    fn prune_after_delete(&self, deleted_paths: &[PathBuf]) {
+        if !self.prune_empty_dirs || deleted_paths.is_empty() {
+            return;
+        }
+
+        let scan_roots: Vec<PathBuf> = JunkCategory::all()
+            .iter()
+            .flat_map(|category| category.get_scan_paths())
+            .flat_map(|scan_path| scan_path.resolve_all())
+            .collect();
+        let pruned = self.prune_empty_parents(deleted_paths, &scan_roots);
+        if pruned > 0 {
+            info!("已清理 {} 个空目录", pruned);
+        }
+    }
+
+    /// 自底向上删除已删除路径的空父目录，返回删除的目录数
+    ///
+    /// # 中文说明
+    /// 只处理位于某个扫描根目录内部的父目录，向上查找到扫描根目录为止（不含根目录本身）；
+    /// 受保护路径和重解析点目录跳过。`remove_dir` 只能删除空目录，
+    /// 其他进程刚写入文件的目录会自然失败并保留。
+    fn prune_empty_parents(&self, deleted_paths: &[PathBuf], scan_roots: &[PathBuf]) -> usize {
+        let roots: Vec<PathBuf> = scan_roots.iter().map(|root| lowercase_path(root)).collect();
+        let mut candidates = BTreeSet::new();
+        for deleted in deleted_paths {
+            let mut current = deleted.parent();
+            while let Some(dir) = current {
+                let dir_lower = lowercase_path(dir);
+                let inside_root = roots
+                    .iter()
+                    .any(|root| dir_lower.starts_with(root) && dir_lower != *root);
+                if !inside_root || !candidates.insert(dir.to_path_buf()) {
+                    break;
+                }
+                current = dir.parent();
+            }
+        }
+
+        // 深层目录优先，保证子目录先于父目录删除
+        let mut dirs: Vec<PathBuf> = candidates.into_iter().collect();
+        dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+
+        let mut pruned = 0;
+        for dir in dirs {
+            if self.is_protected_path(&dir) {
+                continue;
+            }
+            let target = extended_path(&dir);
+            match fs::symlink_metadata(&target) {
+                Ok(metadata) if metadata.is_dir() && !is_reparse_point(&metadata) => {}
+                _ => continue,
+            }
+            if fs::remove_dir(&target).is_ok() {
+                debug!("已删除空目录: {}", dir.display());
+                pruned += 1;
+            }
+        }
+        pruned
+    }
+
     /// 删除单个文件或目录（多层安全检查）
-    /// 返回 (释放大小, 是否标记为重启删除)
-    fn delete_single_file(&self, path: &str, size: u64) -> Result<(u64, bool), String> {
+    fn delete_single_file(&self, path: &str, size: u64) -> Result<DeleteOutcome, String> {
         let file_path = Path::new(path);
 
-        // 检查路径是否存在
-        if !file_path.exists() {
-            return Err("文件不存在".to_string());
+        // 删除前重新确认路径仍存在（超长路径需要扩展前缀，否则会被误判为不存在）。
+        // 浏览器等应用会在扫描后自行清理缓存，这类文件不算删除失败；
+        // 拒绝访问等其他错误按普通失败处理，不能当作已删除
+        let fs_path = extended_path(file_path);
+        match fs::symlink_metadata(&fs_path) {
+            Ok(_) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(DeleteOutcome::NotFound);
+            }
+            Err(e) => return Err(format!("无法读取文件信息: {}", e)),
         }
 
         // 安全检查第1层：检查是否为受保护路径
@@ -143,39 +256,52 @@ impl DeleteEngine {
             return Err("系统保护路径，禁止删除".to_string());
         }
 
+        // 安全检查第1.5层：安装进行中的 Config.Msi 回滚数据
+        if is_config_msi_path(path) && is_msi_install_in_progress() {
+            return Err(MSI_INSTALL_IN_PROGRESS_MESSAGE.to_string());
+        }
+
         // 安全检查第2层：检查是否在允许删除的范围内
         if !self.is_in_allowed_scope(file_path) {
             warn!("路径不在允许删除范围内: {}", path);
         }
 
         // 尝试删除
-        if file_path.is_dir() {
+        if fs_path.is_dir() {
+            // 目录整体删除前确认其中没有聊天数据库等用户数据
+            if let Some(data_file) = find_protected_data_file(&fs_path) {
+                error!("安全拦截: 目录中包含用户数据文件 {}", data_file.display());
+                return Err("目录中包含聊天数据库等用户数据文件，禁止删除".to_string());
+            }
             self.delete_directory(file_path, size)
         } else {
             self.delete_file(file_path, size)
         }
     }
 
-    /// 删除文件，返回 (大小, 是否标记为重启删除)
-    fn delete_file(&self, path: &Path, size: u64) -> Result<(u64, bool), String> {
+    /// 删除文件
+    fn delete_file(&self, path: &Path, size: u64) -> Result<DeleteOutcome, String> {
+        let target = extended_path(path);
         // 尝试删除文件
-        match fs::remove_file(path) {
-            Ok(_) => Ok((size, false)),
+        match fs::remove_file(&target) {
+            Ok(_) => Ok(DeleteOutcome::Deleted(size)),
+            // 检查存在后到删除前被其他进程删掉
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(DeleteOutcome::NotFound),
             Err(e) => {
                 // 检查是否是权限问题或文件正在使用
                 if e.kind() == std::io::ErrorKind::PermissionDenied {
                     // 尝试移除只读属性后再删除
-                    if let Ok(metadata) = fs::metadata(path) {
+                    if let Ok(metadata) = fs::metadata(&target) {
                         let mut permissions = metadata.permissions();
                         #[allow(clippy::permissions_set_readonly_false)]
                         permissions.set_readonly(false);
-                        if fs::set_permissions(path, permissions).is_ok() {
-                            if fs::remove_file(path).is_ok() {
-                                return Ok((size, false));
-                            }
+                        if fs::set_permissions(&target, permissions).is_ok()
+                            && fs::remove_file(&target).is_ok()
+                        {
+                            return Ok(DeleteOutcome::Deleted(size));
                         }
                     }
-                    Err(format!("权限不足: {}", e))
+                    Err(describe_io_error(path, &e, "删除失败"))
                 } else {
                     // 检测共享冲突（错误码 32，ERROR_SHARING_VIOLATION），
                     // 文件正被其他进程使用时无法直接删除，标记为重启后删除
@@ -194,43 +320,40 @@ impl DeleteEngine {
                             ) {
                                 Ok(_) => {
                                     info!("文件已标记为重启删除: {}", path_str);
-                                    return Ok((size, true)); // 标记成功，重启后删除
+                                    return Ok(DeleteOutcome::RebootPending(size));
                                 }
                                 Err(mark_err) => {
                                     warn!("标记重启删除失败: {} - {}", path_str, mark_err);
                                 }
                             }
                         }
-                        Err(format!("文件被系统占用: {}", e))
-                    } else {
-                        Err(format!("删除失败: {}", e))
                     }
+                    Err(describe_io_error(path, &e, "删除失败"))
                 }
             }
         }
     }
 
-    /// 删除目录，返回 (大小, 是否标记为重启删除)
-    fn delete_directory(&self, path: &Path, size: u64) -> Result<(u64, bool), String> {
-        match fs::remove_dir_all(path) {
-            Ok(_) => Ok((size, false)),
-            Err(e) => {
-                if e.kind() == std::io::ErrorKind::PermissionDenied {
-                    Err(format!("权限不足: {}", e))
-                } else {
-                    Err(format!("删除目录失败: {}", e))
-                }
-            }
+    /// 删除目录
+    fn delete_directory(&self, path: &Path, size: u64) -> Result<DeleteOutcome, String> {
+        match fs::remove_dir_all(extended_path(path)) {
+            Ok(_) => Ok(DeleteOutcome::Deleted(size)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(DeleteOutcome::NotFound),
+            Err(e) => Err(describe_io_error(path, &e, "删除目录失败")),
         }
     }
 
     /// 获取路径大小
-    fn get_path_size(&self, path: &Path) -> u64 {
+    pub(super) fn get_path_size(&self, path: &Path) -> u64 {
+        let path = extended_path(path);
         if path.is_file() {
-            fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+            fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
         } else if path.is_dir() {
-            walkdir::WalkDir::new(path)
+            // 不进入 Junction 等重解析点目录，避免把链接目标重复计入释放空间
+            walkdir::WalkDir::new(&path)
+                .follow_links(false)
                 .into_iter()
+                .filter_entry(|e| e.depth() == 0 || !is_reparse_point_dir(e))
                 .filter_map(|e| e.ok())
                 .filter(|e| e.file_type().is_file())
                 .filter_map(|e| e.metadata().ok())
@@ -243,13 +366,28 @@ impl DeleteEngine {
 
     /// 检查是否为受保护的路径（多层安全检查）
     fn is_protected_path(&self, path: &Path) -> bool {
+        match self.protection_rule(path) {
+            Some(rule) => {
+                error!("安全拦截: {} {}", rule, path.display());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 返回拦截该路径的保护规则说明，未受保护时返回 None
+    pub(super) fn protection_rule(&self, path: &Path) -> Option<&'static str> {
         let path_str = path.to_string_lossy().to_lowercase();
 
+        // 第0层：回收站条目只能通过 Shell API 按盘清空（见 `empty_recycle_bin_files`）
+        if is_recycle_bin_path(&path_str) {
+            return Some("回收站内容，需通过清空回收站处理");
+        }
+
         // 第1层：检查路径前缀
         for protected in PROTECTED_PATH_PREFIXES {
             if path_str.starts_with(protected) && !is_rebuildable_system_cache_path(&path_str) {
-                error!("安全拦截: 尝试删除受保护路径 {}", path_str);
-                return true;
+                return Some("受保护的系统路径");
             }
         }
 
@@ -258,19 +396,22 @@ impl DeleteEngine {
             let name = file_name.to_string_lossy().to_lowercase();
             for protected in PROTECTED_FILES {
                 if name == *protected {
-                    error!("安全拦截: 尝试删除系统关键文件 {}", name);
-                    return true;
+                    return Some("系统关键文件");
                 }
             }
         }
 
+        // 第2.5层：社交软件数据库等用户数据文件（含位于其下的路径）
+        if is_protected_data_path(path) {
+            return Some("聊天数据库等用户数据文件");
+        }
+
         // 第3层：在Windows目录下保护特定扩展名
         if path_str.contains("\\windows\\") {
             if let Some(ext) = path.extension() {
                 let ext_str = ext.to_string_lossy().to_lowercase();
                 if PROTECTED_EXTENSIONS_IN_WINDOWS.contains(&ext_str.as_str()) {
-                    error!("安全拦截: 尝试删除Windows目录下的系统文件 {}", path_str);
-                    return true;
+                    return Some("Windows 目录下的系统文件");
                 }
             }
         }
@@ -286,22 +427,20 @@ impl DeleteEngine {
         for critical in &user_critical_paths {
             // 只保护根目录，不保护子目录
             if path_str.ends_with(critical) {
-                error!("安全拦截: 尝试删除用户关键目录 {}", path_str);
-                return true;
+                return Some("用户关键目录");
             }
         }
 
         // 第5层：检查是否是驱动器根目录
         if path_str.len() <= 3 && path_str.ends_with("\\") {
-            error!("安全拦截: 尝试删除驱动器根目录 {}", path_str);
-            return true;
+            return Some("驱动器根目录");
         }
 
-        false
+        None
     }
 
     /// 验证路径是否在允许删除的范围内
-    fn is_in_allowed_scope(&self, path: &Path) -> bool {
+    pub(super) fn is_in_allowed_scope(&self, path: &Path) -> bool {
         let path_str = path.to_string_lossy().to_lowercase();
 
         // 允许删除的路径范围
@@ -317,13 +456,23 @@ impl DeleteEngine {
             "\\appdata\\local\\microsoft\\windows\\explorer", // 缩略图缓存
             "\\windows\\temp",
             "\\windows\\prefetch",
+            "\\config.msi",
             "\\windows\\softwaredistribution\\download",
             "\\windows\\softwaredistribution\\deliveryoptimization",
             "\\deliveryoptimization\\",
             "\\programdata\\microsoft\\windows defender\\localcopy",
             "\\programdata\\microsoft\\windows defender\\support",
+            "\\microsoft\\windows\\wer\\reportqueue",
+            "\\microsoft\\windows\\wer\\reportarchive",
             "\\d3d_cache",
-            "\\$recycle.bin", // 回收站内容
+            "\\microsoft\\office\\unsavedfiles",
+            "\\officefilecache",
+            "\\npm-cache",
+            "\\.cargo\\registry\\cache",
+            "\\.nuget\\packages",
+            "\\steamapps\\shadercache",
+            "\\glcache",
+            "\\crashdumps",
             "\\.log",
             "\\.tmp",
             "\\.bak",
@@ -350,6 +499,104 @@ impl DeleteEngine {
     }
 }
 
+/// 逐个删除回收站分类中选中的条目
+///
+/// 通用删除引擎拦截 `$Recycle.Bin` 路径，回收站条目在这里单独处理。不调用按盘清空的
+/// `SHEmptyRecycleBinW`：那会连同未选中的条目一起销毁却不计入结果。每个条目删除
+/// `$R` 数据后再删除同后缀的 `$I` 元数据，Explorer 随即不再显示该条目。
+pub fn empty_recycle_bin_files(files: &[FileInfo]) -> DeleteResult {
+    let mut result = DeleteResult::new();
+    for file in files {
+        // 只接受当前用户 SID 目录下的 $R 数据条目，其他用户的回收站和元数据文件一律拒绝
+        if !is_recycle_bin_path(&file.path)
+            || !crate::scanner::recycle_bin::is_current_user_entry_path(&file.path)
+        {
+            result.add_failure(file.path.clone(), "不是有效的回收站条目".to_string());
+            continue;
+        }
+
+        match delete_recycle_bin_entry(Path::new(&file.path)) {
+            Ok(()) => result.add_success(file.size),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => result.add_skipped_notfound(),
+            Err(e) => {
+                warn!("删除回收站条目失败: {} - {}", file.path, e);
+                let reason = describe_io_error(Path::new(&file.path), &e, "删除回收站条目失败");
+                result.add_failure(file.path.clone(), reason);
+            }
+        }
+    }
+
+    info!(
+        "回收站条目删除完成: 成功 {}, 失败 {}",
+        result.success_count, result.failed_count
+    );
+    result
+}
+
+/// 删除一个回收站条目的 `$R` 数据和对应的 `$I` 元数据
+///
+/// `$R` 可能是被删除的整个文件夹；重解析点只移除链接本身。元数据已不存在时视为成功。
+fn delete_recycle_bin_entry(data_path: &Path) -> std::io::Result<()> {
+    let target = extended_path(data_path);
+    let metadata = fs::symlink_metadata(&target)?;
+    if metadata.is_dir() && !is_reparse_point(&metadata) {
+        fs::remove_dir_all(&target)?;
+    } else if metadata.is_dir() {
+        fs::remove_dir(&target)?;
+    } else {
+        fs::remove_file(&target)?;
+    }
+
+    let Some(suffix) = data_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.get(2..))
+    else {
+        return Ok(());
+    };
+    let info_path = data_path.with_file_name(format!("$I{}", suffix));
+    match fs::remove_file(extended_path(&info_path)) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// 按系统错误码生成失败原因，`fallback` 用于无法归类的错误
+///
+/// 错误码与系统语言无关；共享冲突时通过重启管理器查询占用进程并写入原因，
+/// 与增强删除引擎一样让用户知道该关闭哪个程序。查询失败时只保留错误描述。
+fn describe_io_error(path: &Path, e: &std::io::Error, fallback: &str) -> String {
+    let code = e.raw_os_error();
+    match code.and_then(DeleteFailureReason::from_os_error) {
+        Some(DeleteFailureReason::FileLocked) => {
+            let lock_files = lock_query_files(path);
+            let lock_paths: Vec<&Path> = lock_files.iter().map(|p| p.as_path()).collect();
+            match find_locking_processes(&lock_paths) {
+                Ok(processes) if !processes.is_empty() => format!(
+                    "文件被 {} 占用: {}",
+                    describe_locking_processes(&processes),
+                    e
+                ),
+                Ok(_) => format!("文件被系统占用: {}", e),
+                Err(query_err) => {
+                    debug!("查询占用进程失败: {} - {}", path.display(), query_err);
+                    format!("文件被系统占用: {}", e)
+                }
+            }
+        }
+        Some(reason) => format!("{}: {}", reason.display_message(), e),
+        None => match code {
+            Some(code) => format!("{} (错误码 {}): {}", fallback, code, e),
+            None => format!("{}: {}", fallback, e),
+        },
+    }
+}
+
+/// 路径比较不区分大小写
+fn lowercase_path(path: &Path) -> PathBuf {
+    PathBuf::from(path.to_string_lossy().to_lowercase())
+}
+
 impl Default for DeleteEngine {
     fn default() -> Self {
         Self::new()
@@ -377,5 +624,105 @@ mod tests {
             "C:\\ProgramData\\Microsoft\\Windows Defender\\Quarantine\\entry.bin"
         )));
         assert!(!engine.is_protected_path(Path::new("C:\\Temp\\test.tmp")));
+        assert!(engine.is_protected_path(Path::new(
+            "C:\\Users\\Test\\Documents\\WeChat Files\\wxid\\Msg\\MicroMsg.db"
+        )));
+        // 所有驱动器的回收站条目都只能通过 Shell API 清空
+        let recycled = Path::new("D:\\$Recycle.Bin\\S-1-5-21-1\\$RABC123.txt");
+        assert!(engine.is_protected_path(recycled));
+        assert!(!engine.is_in_allowed_scope(recycled));
+        assert!(!engine.is_protected_path(Path::new("D:\\$Recycle.Bin.old\\a.tmp")));
+    }
+
+    #[test]
+    fn test_logs_folder_alone_is_not_in_scope() {
+        let engine = DeleteEngine::new();
+
+        // 应用日志按 .log 扩展名放行，任意名为 logs 的目录本身不扩大删除范围
+        assert!(engine.is_in_allowed_scope(Path::new(
+            "C:\\Users\\me\\AppData\\Roaming\\Slack\\logs\\main.log"
+        )));
+        assert!(!engine.is_in_allowed_scope(Path::new("D:\\Projects\\site\\logs\\report.csv")));
+    }
+
+    #[test]
+    fn test_describe_io_error_uses_os_error_code() {
+        let path = Path::new("C:\\Temp\\test.tmp");
+
+        let denied = std::io::Error::from_raw_os_error(5);
+        assert!(describe_io_error(path, &denied, "删除失败").starts_with("权限不足: "));
+
+        let unknown = std::io::Error::from_raw_os_error(1234);
+        assert!(describe_io_error(path, &unknown, "删除目录失败")
+            .starts_with("删除目录失败 (错误码 1234): "));
+
+        let custom = std::io::Error::other("boom");
+        assert_eq!(
+            describe_io_error(path, &custom, "删除失败"),
+            "删除失败: boom"
+        );
+    }
+
+    #[test]
+    fn test_recycle_bin_entry_removes_data_and_metadata() {
+        let root =
+            std::env::temp_dir().join(format!("lightc-recycle-entry-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("$RDIR01").join("nested")).unwrap();
+        fs::write(root.join("$RDIR01").join("nested").join("a.txt"), b"1").unwrap();
+        fs::write(root.join("$IDIR01"), b"meta").unwrap();
+        fs::write(root.join("$RFILE1.txt"), b"data").unwrap();
+        fs::write(root.join("$IFILE1.txt"), b"meta").unwrap();
+        fs::write(root.join("$RKEEP1.txt"), b"data").unwrap();
+        fs::write(root.join("$IKEEP1.txt"), b"meta").unwrap();
+
+        let dir_result = delete_recycle_bin_entry(&root.join("$RDIR01"));
+        let file_result = delete_recycle_bin_entry(&root.join("$RFILE1.txt"));
+        let remaining: Vec<bool> = ["$RDIR01", "$IDIR01", "$RFILE1.txt", "$IFILE1.txt"]
+            .iter()
+            .map(|name| root.join(name).exists())
+            .collect();
+        // 未选中的条目必须原样保留
+        let kept = root.join("$RKEEP1.txt").exists() && root.join("$IKEEP1.txt").exists();
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(dir_result.is_ok());
+        assert!(file_result.is_ok());
+        assert_eq!(remaining, vec![false; 4]);
+        assert!(kept);
+    }
+
+    #[test]
+    fn test_vanished_paths_are_skipped_not_failed() {
+        let missing = std::env::temp_dir()
+            .join(format!("lightc-vanished-{}", std::process::id()))
+            .join("already_gone.tmp");
+        let result = DeleteEngine::new().delete_paths(&[missing.to_string_lossy().into_owned()]);
+
+        assert_eq!(result.skipped_notfound, 1);
+        assert_eq!(result.failed_count, 0);
+        assert_eq!(result.success_count, 0);
+    }
+
+    #[test]
+    fn test_prune_empty_parents_stops_at_scan_root() {
+        let root = std::env::temp_dir().join(format!("lightc-prune-empty-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let scan_root = root.join("Cache");
+        let nested = scan_root.join("a").join("b");
+        let busy = scan_root.join("busy");
+        fs::create_dir_all(&nested).unwrap();
+        fs::create_dir_all(&busy).unwrap();
+        fs::write(busy.join("keep.bin"), b"1").unwrap();
+
+        let engine = DeleteEngine::new().with_prune_empty_dirs(true);
+        let deleted = vec![nested.join("old.tmp"), busy.join("old.tmp")];
+        let pruned = engine.prune_empty_parents(&deleted, std::slice::from_ref(&scan_root));
+
+        assert_eq!(pruned, 2);
+        assert!(!scan_root.join("a").exists());
+        assert!(busy.exists());
+        assert!(scan_root.exists());
+        let _ = fs::remove_dir_all(&root);
     }
 }
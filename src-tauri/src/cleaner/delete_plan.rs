@@ -0,0 +1,315 @@
+// ============================================================================
+// 删除计划预览
+//
+// 大批量清理前，平铺的几万条路径很难看出"到底删哪些目录"。这里把待删除路径
+// 按目录组织成树，每个节点汇总其下待删除项的大小和数量，并标出会被删除引擎
+// 安全规则拦截的路径，供界面折叠展示。不执行删除。
+//
+// 只有一个子目录的中间目录会合并显示（如 C:\Users\x\AppData\Local），
+// 已选目录下再选中的路径视为被覆盖，不重复计算。
+// ============================================================================
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::long_path::extended_path;
+use super::safety_constants::find_protected_data_file;
+use super::DeleteEngine;
+
+/// 删除计划树节点
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletePlanNode {
+    /// 显示名称（合并的中间目录以反斜杠连接）
+    pub name: String,
+    /// 节点完整路径
+    pub path: String,
+    /// 该节点本身是否为待删除路径
+    pub is_target: bool,
+    /// 是否为目录（中间节点总是目录）
+    pub is_dir: bool,
+    /// 将被删除的大小（字节），不含受保护的路径
+    pub size: u64,
+    /// 将被删除的路径数，不含受保护的路径
+    pub item_count: usize,
+    /// 子树中被安全规则拦截的路径数
+    pub protected_count: usize,
+    /// 拦截该路径的保护规则（仅待删除节点）
+    pub protection_rule: Option<String>,
+    /// 子节点（按大小降序）
+    pub children: Vec<DeletePlanNode>,
+}
+
+/// 删除计划
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletePlan {
+    /// 按盘符分组的根节点
+    pub roots: Vec<DeletePlanNode>,
+    /// 将被删除的总大小（字节）
+    pub total_size: u64,
+    /// 将被删除的路径数
+    pub item_count: usize,
+    /// 被安全规则拦截的路径数
+    pub protected_count: usize,
+    /// 已被选中的上级目录覆盖、不单独计算的路径数
+    pub covered_count: usize,
+    /// 已不存在的路径
+    pub missing_paths: Vec<String>,
+}
+
+/// 待删除路径的检查结果
+#[derive(Debug, Clone)]
+struct PlanItem {
+    path: String,
+    is_dir: bool,
+    size: u64,
+    protection_rule: Option<String>,
+}
+
+impl DeleteEngine {
+    /// 按目录树汇总待删除路径，标出受保护的路径，不会修改任何文件
+    pub fn build_delete_plan(&self, paths: &[String]) -> DeletePlan {
+        let checked: Vec<(String, Option<PlanItem>)> = paths
+            .par_iter()
+            .map(|path| (path.clone(), self.plan_item(path)))
+            .collect();
+
+        let mut items = Vec::with_capacity(checked.len());
+        let mut missing_paths = Vec::new();
+        for (path, item) in checked {
+            match item {
+                Some(item) => items.push(item),
+                None => missing_paths.push(path),
+            }
+        }
+
+        let mut plan = build_plan_tree(items);
+        plan.missing_paths = missing_paths;
+        plan
+    }
+
+    fn plan_item(&self, path: &str) -> Option<PlanItem> {
+        let file_path = Path::new(path);
+        let fs_path = extended_path(file_path);
+        let metadata = fs::symlink_metadata(&fs_path).ok()?;
+        let is_dir = metadata.is_dir();
+
+        // 与删除引擎一致：目录中含有用户数据文件时整个目录被拒绝
+        let protection_rule = match self.protection_rule(file_path) {
+            Some(rule) => Some(rule.to_string()),
+            None if is_dir => find_protected_data_file(&fs_path)
+                .map(|data_file| format!("目录中包含用户数据文件 {}", data_file.display())),
+            None => None,
+        };
+        let size = if protection_rule.is_some() {
+            0
+        } else if is_dir {
+            self.get_path_size(file_path)
+        } else {
+            metadata.len()
+        };
+
+        Some(PlanItem {
+            path: path.to_string(),
+            is_dir,
+            size,
+            protection_rule,
+        })
+    }
+}
+
+/// 构建过程中的节点，子节点以小写名称为键（Windows 路径不区分大小写）
+struct NodeBuilder {
+    name: String,
+    path: String,
+    item: Option<PlanItem>,
+    children: BTreeMap<String, NodeBuilder>,
+}
+
+impl NodeBuilder {
+    fn new(name: &str, path: String) -> Self {
+        NodeBuilder {
+            name: name.to_string(),
+            path,
+            item: None,
+            children: BTreeMap::new(),
+        }
+    }
+}
+
+/// 把已检查的路径组织成目录树（纯转换，不访问文件系统）
+fn build_plan_tree(mut items: Vec<PlanItem>) -> DeletePlan {
+    // 上级目录先插入，后面遇到已是待删除节点的祖先即可判定为被覆盖
+    items.sort_by_key(|item| path_components(&item.path).len());
+
+    let mut roots: BTreeMap<String, NodeBuilder> = BTreeMap::new();
+    let mut covered_count = 0;
+    for item in items {
+        if !insert_item(&mut roots, item) {
+            covered_count += 1;
+        }
+    }
+
+    let mut roots: Vec<DeletePlanNode> = roots.into_values().map(finish_node).collect();
+    sort_nodes(&mut roots);
+    DeletePlan {
+        total_size: roots.iter().map(|node| node.size).sum(),
+        item_count: roots.iter().map(|node| node.item_count).sum(),
+        protected_count: roots.iter().map(|node| node.protected_count).sum(),
+        roots,
+        covered_count,
+        missing_paths: Vec::new(),
+    }
+}
+
+fn path_components(path: &str) -> Vec<&str> {
+    path.split(['\\', '/'])
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// 插入一个待删除路径；路径本身或其上级目录已在计划中时返回 false
+fn insert_item(roots: &mut BTreeMap<String, NodeBuilder>, item: PlanItem) -> bool {
+    let components = path_components(&item.path);
+    let Some((first, rest)) = components.split_first() else {
+        return false;
+    };
+
+    let mut node = roots
+        .entry(first.to_lowercase())
+        .or_insert_with(|| NodeBuilder::new(first, first.to_string()));
+    for part in rest {
+        if node.item.is_some() {
+            return false;
+        }
+        let child_path = format!("{}\\{}", node.path, part);
+        node = node
+            .children
+            .entry(part.to_lowercase())
+            .or_insert_with(|| NodeBuilder::new(part, child_path));
+    }
+    if node.item.is_some() {
+        return false;
+    }
+    node.item = Some(item);
+    true
+}
+
+fn finish_node(builder: NodeBuilder) -> DeletePlanNode {
+    if let Some(item) = builder.item {
+        let protected = item.protection_rule.is_some();
+        return DeletePlanNode {
+            name: builder.name,
+            path: item.path,
+            is_target: true,
+            is_dir: item.is_dir,
+            size: item.size,
+            item_count: usize::from(!protected),
+            protected_count: usize::from(protected),
+            protection_rule: item.protection_rule,
+            children: Vec::new(),
+        };
+    }
+
+    let mut children: Vec<DeletePlanNode> =
+        builder.children.into_values().map(finish_node).collect();
+
+    // 只有一个子目录的中间目录与子目录合并，减少无意义的展开层级
+    if children.len() == 1 && !children[0].is_target {
+        let mut child = children.remove(0);
+        child.name = format!("{}\\{}", builder.name, child.name);
+        return child;
+    }
+
+    sort_nodes(&mut children);
+    DeletePlanNode {
+        name: builder.name,
+        path: builder.path,
+        is_target: false,
+        is_dir: true,
+        size: children.iter().map(|child| child.size).sum(),
+        item_count: children.iter().map(|child| child.item_count).sum(),
+        protected_count: children.iter().map(|child| child.protected_count).sum(),
+        protection_rule: None,
+        children,
+    }
+}
+
+fn sort_nodes(nodes: &mut [DeletePlanNode]) {
+    nodes.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.name.cmp(&b.name)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(path: &str, size: u64) -> PlanItem {
+        PlanItem {
+            path: path.to_string(),
+            is_dir: false,
+            size,
+            protection_rule: None,
+        }
+    }
+
+    #[test]
+    fn test_plan_tree_groups_by_directory_and_sums_sizes() {
+        let plan = build_plan_tree(vec![
+            item("C:\\Users\\a\\AppData\\Local\\Temp\\x.tmp", 10),
+            item("C:\\Users\\a\\AppData\\Local\\Temp\\y.tmp", 20),
+            item("C:\\Users\\a\\AppData\\Local\\Cache\\z.bin", 30),
+            item("D:\\Downloads\\old.zip", 40),
+        ]);
+
+        assert_eq!(plan.total_size, 100);
+        assert_eq!(plan.item_count, 4);
+        assert_eq!(plan.roots.len(), 2);
+        // 只有一个子目录的中间目录合并显示
+        assert_eq!(plan.roots[1].name, "D:\\Downloads");
+        assert_eq!(plan.roots[1].children[0].name, "old.zip");
+
+        let local = &plan.roots[0];
+        assert_eq!(local.name, "C:\\Users\\a\\AppData\\Local");
+        assert_eq!(local.path, "C:\\Users\\a\\AppData\\Local");
+        assert_eq!(local.size, 60);
+        assert_eq!(local.children[0].name, "Cache");
+        assert_eq!(local.children[0].size, 30);
+        assert_eq!(local.children[1].name, "Temp");
+        assert_eq!(local.children[1].item_count, 2);
+    }
+
+    #[test]
+    fn test_plan_tree_skips_covered_paths_and_counts_protected_separately() {
+        let mut dir = item("C:\\Temp\\build", 100);
+        dir.is_dir = true;
+        let mut protected = item("C:\\Windows\\System32\\drivers\\x.sys", 0);
+        protected.protection_rule = Some("受保护的系统路径".to_string());
+
+        let plan = build_plan_tree(vec![
+            item("c:\\temp\\BUILD\\obj\\a.o", 5),
+            dir,
+            item("C:\\Temp\\build", 100),
+            protected,
+        ]);
+
+        assert_eq!(plan.covered_count, 2);
+        assert_eq!(plan.item_count, 1);
+        assert_eq!(plan.total_size, 100);
+        assert_eq!(plan.protected_count, 1);
+
+        let root = &plan.roots[0];
+        assert_eq!(root.name, "C:");
+        assert_eq!(root.children.len(), 2);
+        assert_eq!(root.children[0].name, "Temp");
+        let build = &root.children[0].children[0];
+        assert_eq!(build.name, "build");
+        assert!(build.is_target && build.is_dir && build.children.is_empty());
+        let blocked = &root.children[1];
+        assert_eq!(blocked.name, "Windows\\System32\\drivers");
+        assert_eq!(blocked.protected_count, 1);
+        assert_eq!(blocked.item_count, 0);
+    }
+}
@@ -0,0 +1,196 @@
+// ============================================================================
+// 删除诊断
+//
+// 回答"这个文件为什么删不掉"：不执行删除，只按删除引擎的同一套规则
+// 检查路径是否受保护、是否被占用、被哪个进程占用，以及预计释放的大小。
+// ============================================================================
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::long_path::extended_path;
+use super::restart_manager::{describe_locking_processes, find_locking_processes, LockingProcess};
+use super::safety_constants::{find_protected_data_file, is_reparse_point_dir};
+use super::DeleteEngine;
+
+/// 目录诊断时最多检查的文件数，避免大目录逐个打开文件耗时过长
+const MAX_PROBED_FILES: usize = 5000;
+
+/// 最多返回的被占用文件数
+const MAX_LOCKED_FILES: usize = 20;
+
+/// 删除诊断结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteProbe {
+    /// 诊断的路径
+    pub path: String,
+    /// 路径是否存在
+    pub exists: bool,
+    /// 是否为目录
+    pub is_dir: bool,
+    /// 是否被安全规则拦截
+    pub protected: bool,
+    /// 拦截该路径的保护规则
+    pub protection_rule: Option<String>,
+    /// 是否在常规可清理范围内（范围外的路径仍可删除，只是不会自动重启删除）
+    pub in_allowed_scope: bool,
+    /// 是否有文件被其他进程占用
+    pub locked: bool,
+    /// 被占用的文件（目录诊断时最多返回 20 个）
+    pub locked_files: Vec<String>,
+    /// 占用文件的进程
+    pub locking_processes: Vec<LockingProcess>,
+    /// 预计释放的大小（字节）
+    pub estimated_size: u64,
+    /// 诊断结论
+    pub summary: String,
+}
+
+impl DeleteEngine {
+    /// 诊断路径能否删除，不会修改任何文件
+    ///
+    /// # 中文说明
+    /// 占用检测以不共享方式（share mode 为 0）打开文件：其他进程持有句柄时会返回
+    /// 共享冲突（错误码 32），再通过重启管理器查询持有句柄的进程。
+    pub fn probe_delete(&self, path: &str) -> DeleteProbe {
+        let file_path = Path::new(path);
+        let fs_path = extended_path(file_path);
+        let metadata = fs::symlink_metadata(&fs_path).ok();
+        let is_dir = metadata.as_ref().map(|m| m.is_dir()).unwrap_or(false);
+
+        let mut probe = DeleteProbe {
+            path: path.to_string(),
+            exists: metadata.is_some(),
+            is_dir,
+            protected: false,
+            protection_rule: None,
+            in_allowed_scope: self.is_in_allowed_scope(file_path),
+            locked: false,
+            locked_files: Vec::new(),
+            locking_processes: Vec::new(),
+            estimated_size: 0,
+            summary: String::new(),
+        };
+
+        if !probe.exists {
+            probe.summary = "路径不存在，无需删除".to_string();
+            return probe;
+        }
+
+        let rule = match self.protection_rule(file_path) {
+            Some(rule) => Some(rule.to_string()),
+            None if is_dir => find_protected_data_file(&fs_path)
+                .map(|data_file| format!("目录中包含用户数据文件 {}", data_file.display())),
+            None => None,
+        };
+        if let Some(rule) = rule {
+            probe.protected = true;
+            probe.summary = format!("受安全规则保护，禁止删除：{}", rule);
+            probe.protection_rule = Some(rule);
+            return probe;
+        }
+
+        probe.estimated_size = self.get_path_size(file_path);
+
+        let locked_files = find_locked_files(&fs_path, is_dir);
+        if !locked_files.is_empty() {
+            probe.locked = true;
+            let lock_paths: Vec<&Path> = locked_files.iter().map(|p| p.as_path()).collect();
+            match find_locking_processes(&lock_paths) {
+                Ok(processes) => probe.locking_processes = processes,
+                Err(e) => log::warn!("查询占用进程失败 {}: {}", path, e),
+            }
+            probe.locked_files = locked_files
+                .iter()
+                .take(MAX_LOCKED_FILES)
+                .map(|p| p.to_string_lossy().into_owned())
+                .collect();
+        }
+
+        probe.summary = if probe.locked && !probe.locking_processes.is_empty() {
+            format!(
+                "文件被占用：{}",
+                describe_locking_processes(&probe.locking_processes)
+            )
+        } else if probe.locked {
+            "文件被占用，未能确定占用进程（可能是系统进程或需要管理员权限）".to_string()
+        } else {
+            "未发现阻止删除的原因".to_string()
+        };
+        probe
+    }
+}
+
+/// 以不共享方式打开文件，返回被其他进程占用的文件
+fn find_locked_files(path: &Path, is_dir: bool) -> Vec<PathBuf> {
+    if !is_dir {
+        return if is_locked(path) {
+            vec![path.to_path_buf()]
+        } else {
+            Vec::new()
+        };
+    }
+
+    walkdir::WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !is_reparse_point_dir(e))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .take(MAX_PROBED_FILES)
+        .map(|e| e.into_path())
+        .filter(|file| is_locked(file))
+        .collect()
+}
+
+#[cfg(windows)]
+fn is_locked(path: &Path) -> bool {
+    use std::os::windows::fs::OpenOptionsExt;
+
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+    match fs::OpenOptions::new().read(true).share_mode(0).open(path) {
+        Ok(_) => false,
+        Err(e) => e.raw_os_error() == Some(ERROR_SHARING_VIOLATION),
+    }
+}
+
+#[cfg(not(windows))]
+fn is_locked(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_delete_reports_protection_rule() {
+        let engine = DeleteEngine::new();
+        assert_eq!(
+            engine.protection_rule(Path::new(r"C:\Windows\System32\test.dll")),
+            Some("受保护的系统路径")
+        );
+        assert_eq!(
+            engine.protection_rule(Path::new(r"C:\")),
+            Some("驱动器根目录")
+        );
+
+        let dir = std::env::temp_dir().join(format!("lightc-probe-delete-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("cache.tmp");
+        fs::write(&file, vec![0u8; 128]).unwrap();
+
+        let probe = engine.probe_delete(&file.to_string_lossy());
+        assert!(probe.exists);
+        assert!(!probe.protected);
+        assert!(!probe.locked);
+        assert_eq!(probe.estimated_size, 128);
+        assert!(file.exists());
+
+        let missing = engine.probe_delete(&dir.join("missing.tmp").to_string_lossy());
+        assert!(!missing.exists);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
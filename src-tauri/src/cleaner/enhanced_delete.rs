@@ -5,6 +5,7 @@
 // 1. Take Ownership - 获取文件所有权以删除受保护文件
 // 2. Delete on Reboot - 使用 MOVEFILE_DELAY_UNTIL_REBOOT 处理锁定文件
 // 3. 物理大小计算 - 返回实际释放的磁盘空间
+// 4. 占用进程 - 通过重启管理器查询占用文件的进程，可选请求其关闭后重试
 //
 // 安全机制：
 // - 严格的白名单路径检查，只在安全目录执行 Take Ownership
@@ -15,9 +16,7 @@
 use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::io;
-#[cfg(windows)]
-use std::os::windows::process::CommandExt;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
@@ -25,7 +24,19 @@ use std::time::{Duration, Instant};
 use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
 
-use super::safety_constants::is_rebuildable_system_cache_path;
+use super::long_path::extended_path;
+use super::restart_manager::{
+    close_locking_processes, describe_locking_processes, find_locking_processes, lock_query_files,
+    LockingProcess,
+};
+use super::safety_constants::{
+    find_protected_data_file, is_rebuildable_system_cache_path, is_recycle_bin_path,
+    is_reparse_point,
+};
+use crate::process_runner::{run_with_timeout, DEFAULT_COMMAND_TIMEOUT};
+use crate::scanner::{
+    is_config_msi_path, is_msi_install_in_progress, MSI_INSTALL_IN_PROGRESS_MESSAGE,
+};
 
 // ============================================================================
 // Windows API 绑定
@@ -100,6 +111,13 @@ pub(crate) mod windows_api {
         pub fn SHEmptyRecycleBinW(hwnd: *const u16, pszRootPath: *const u16, dwFlags: u32) -> i32;
     }
 
+    /// 文件路径统一加扩展长度前缀，超过 MAX_PATH 的深层缓存也能设置属性和标记删除
+    fn extended_path_string(path: &str) -> String {
+        super::extended_path(std::path::Path::new(path))
+            .to_string_lossy()
+            .into_owned()
+    }
+
     /// 将 Rust 字符串转换为 Windows 宽字符串
     pub fn to_wide_string(s: &str) -> Vec<u16> {
         use std::os::windows::ffi::OsStrExt;
@@ -125,7 +143,7 @@ pub(crate) mod windows_api {
     /// - 系统关键文件绝对禁止使用此功能
     /// - 用户会收到"需要重启完成清理"的提示
     pub fn mark_for_delete_on_reboot(path: &str) -> Result<(), String> {
-        let wide_path = to_wide_string(path);
+        let wide_path = to_wide_string(&extended_path_string(path));
 
         unsafe {
             let result = MoveFileExW(
@@ -145,7 +163,7 @@ pub(crate) mod windows_api {
 
     /// 移除文件的只读、隐藏、系统属性
     pub fn remove_protection_attributes(path: &str) -> Result<(), String> {
-        let wide_path = to_wide_string(path);
+        let wide_path = to_wide_string(&extended_path_string(path));
 
         unsafe {
             let attrs = GetFileAttributesW(wide_path.as_ptr());
@@ -231,6 +249,28 @@ pub(crate) mod windows_api {
     }
 }
 
+/// 非 Windows 平台的占位实现，删除流程的其余部分照常编译；
+/// 依赖系统 API 的步骤按失败处理，调用方会走各自的回退分支
+#[cfg(not(windows))]
+pub(crate) mod windows_api {
+    pub fn mark_for_delete_on_reboot(_path: &str) -> Result<(), String> {
+        Err("此功能仅支持Windows系统".to_string())
+    }
+
+    /// 没有只读/隐藏/系统属性需要移除
+    pub fn remove_protection_attributes(_path: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    pub fn get_cluster_size(_root_path: &str) -> Option<u32> {
+        None
+    }
+
+    pub fn empty_recycle_bin(_drive_root: Option<&str>) -> Result<(), String> {
+        Err("此功能仅支持Windows系统".to_string())
+    }
+}
+
 // ============================================================================
 // 删除结果类型
 // ============================================================================
@@ -255,6 +295,21 @@ pub enum DeleteFailureReason {
 }
 
 impl DeleteFailureReason {
+    /// 按 Windows 系统错误码归类失败原因
+    ///
+    /// 错误码与系统语言无关，不能依赖本地化的错误文本判断占用或权限问题。
+    pub fn from_os_error(code: i32) -> Option<Self> {
+        match code {
+            // ERROR_FILE_NOT_FOUND / ERROR_PATH_NOT_FOUND
+            2 | 3 => Some(Self::NotFound),
+            // ERROR_ACCESS_DENIED
+            5 => Some(Self::PermissionDenied),
+            // ERROR_SHARING_VIOLATION / ERROR_LOCK_VIOLATION
+            32 | 33 => Some(Self::FileLocked),
+            _ => None,
+        }
+    }
+
     /// 获取用户友好的中文描述
     pub fn display_message(&self) -> &str {
         match self {
@@ -297,6 +352,12 @@ pub struct FileDeleteResult {
     pub failure_reason: Option<DeleteFailureReason>,
     /// 是否标记为重启删除
     pub marked_for_reboot: bool,
+    /// 占用文件的进程（开启关闭占用程序且文件被占用时通过重启管理器查询）
+    #[serde(default)]
+    pub locking_processes: Vec<LockingProcess>,
+    /// 失败的具体说明（如 icacls 和属性处理的输出），原因分类保持不变以便按类汇总
+    #[serde(default)]
+    pub failure_detail: Option<String>,
 }
 
 /// 增强版删除结果
@@ -318,6 +379,9 @@ pub struct EnhancedDeleteResult {
     pub file_results: Vec<FileDeleteResult>,
     /// 是否需要重启完成清理
     pub needs_reboot: bool,
+    /// 按失败原因统计的文件数（键为 `DeleteFailureReason::display_message`）
+    #[serde(default)]
+    pub failure_breakdown: HashMap<String, usize>,
     /// 汇总消息（WeChat 风格）
     pub summary_message: String,
 }
@@ -333,6 +397,8 @@ pub struct EnhancedDeleteProgress {
     pub processed_count: usize,
     /// 本次删除的去重后文件总数。
     pub total_count: usize,
+    /// 完成百分比（0-100），总数为 0 时为 100。
+    pub percent: u8,
     /// 已成功删除的文件数量。
     pub success_count: usize,
     /// 已失败的文件数量。
@@ -346,10 +412,19 @@ pub struct EnhancedDeleteProgress {
 }
 
 /// 进度事件的最大发送间隔，保证单个批次处理较慢时界面仍能持续反馈。
-const DELETE_PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+/// 200ms 足以让进度条连续变化，每秒最多 5 次 IPC 对删除速度没有可感知影响。
+const DELETE_PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
 /// 常规批量进度间隔，避免每个文件发送 IPC 事件造成额外开销。
 const DELETE_PROGRESS_BATCH_SIZE: usize = 500;
 
+/// 按已处理数和总数计算完成百分比，总数未知或为 0 时视为已完成
+pub(crate) fn progress_percent(done: u64, total: u64) -> u8 {
+    if total == 0 {
+        return 100;
+    }
+    (done.min(total) * 100 / total) as u8
+}
+
 impl EnhancedDeleteResult {
     pub fn new() -> Self {
         Self {
@@ -361,6 +436,7 @@ impl EnhancedDeleteResult {
             skipped_size: 0,
             file_results: Vec::new(),
             needs_reboot: false,
+            failure_breakdown: HashMap::new(),
             summary_message: String::new(),
         }
     }
@@ -400,6 +476,29 @@ impl EnhancedDeleteResult {
             ));
         }
 
+        // 失败原因分类统计，已标记重启删除的文件单独计数，不算作失败
+        self.failure_breakdown.clear();
+        for file_result in &self.file_results {
+            match &file_result.failure_reason {
+                Some(DeleteFailureReason::MarkedForReboot) | None => {}
+                Some(reason) => {
+                    *self
+                        .failure_breakdown
+                        .entry(reason.display_message().to_string())
+                        .or_insert(0) += 1;
+                }
+            }
+        }
+        if !self.failure_breakdown.is_empty() {
+            let mut reasons: Vec<(&String, &usize)> = self.failure_breakdown.iter().collect();
+            reasons.sort_by(|left, right| right.1.cmp(left.1).then_with(|| left.0.cmp(right.0)));
+            let details: Vec<String> = reasons
+                .iter()
+                .map(|(reason, count)| format!("{} 个{}", count, reason))
+                .collect();
+            parts.push(format!("失败：{}", details.join("、")));
+        }
+
         self.summary_message = if parts.is_empty() {
             "没有文件被清理".to_string()
         } else {
@@ -447,6 +546,74 @@ const SAFE_OWNERSHIP_PATHS: &[&str] = &[
 // 使用内置管理员组 SID，避免中文 Windows 上的本地化组名导致 icacls 无法解析。
 const LOCAL_ADMINISTRATORS_SID: &str = "*S-1-5-32-544";
 
+/// 解析当前用户账户，供 icacls 使用
+///
+/// # 中文说明
+/// 优先使用进程令牌中的 SID（`*S-1-5-21-...` 形式不受本地化和改名影响），
+/// 取不到时退回 `%USERDOMAIN%\%USERNAME%`。
+fn current_user_account() -> Option<String> {
+    #[cfg(windows)]
+    if let Some(sid) = crate::scanner::recycle_bin::current_user_sid() {
+        return Some(format!("*{}", sid));
+    }
+
+    let user_name = std::env::var("USERNAME")
+        .ok()
+        .filter(|name| !name.is_empty())?;
+    match std::env::var("USERDOMAIN") {
+        Ok(domain) if !domain.is_empty() => Some(format!("{}\\{}", domain, user_name)),
+        _ => Some(user_name),
+    }
+}
+
+/// 选择 Take Ownership 的目标账户
+///
+/// # 中文说明
+/// 用户配置目录下的文件交给当前用户，标准账户也能接管自己的 AppData；
+/// 只有机器级目录才回退到内置管理员组。
+fn ownership_trustee(
+    path: &Path,
+    user_profile: Option<&str>,
+    user_account: Option<&str>,
+) -> Result<String, String> {
+    let normalize = |value: &str| {
+        value
+            .replace('/', "\\")
+            .trim_end_matches('\\')
+            .to_ascii_lowercase()
+    };
+    let normalized = normalize(&path.to_string_lossy());
+    let under_profile = user_profile
+        .map(normalize)
+        .filter(|profile| !profile.is_empty())
+        .is_some_and(|profile| {
+            normalized == profile || normalized.starts_with(&format!("{}\\", profile))
+        });
+
+    if !under_profile {
+        return Ok(LOCAL_ADMINISTRATORS_SID.to_string());
+    }
+
+    user_account.map(str::to_string).ok_or_else(|| {
+        format!(
+            "无法解析当前用户账户，不能获取用户目录文件的所有权: {}",
+            path.display()
+        )
+    })
+}
+
+/// 构造不弹出命令行窗口的 icacls 命令
+fn icacls_command() -> Command {
+    #[cfg_attr(not(windows), allow(unused_mut))]
+    let mut command = Command::new("icacls");
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW - 不显示命令行窗口
+    }
+    command
+}
+
 /// 增强删除引擎
 pub struct EnhancedDeleteEngine {
     /// 磁盘簇大小缓存
@@ -457,6 +624,8 @@ pub struct EnhancedDeleteEngine {
     enable_reboot_delete: bool,
     /// 是否尝试获取所有权
     enable_take_ownership: bool,
+    /// 文件被占用时是否请求占用程序关闭后重试
+    close_locking_apps: bool,
 }
 
 impl EnhancedDeleteEngine {
@@ -470,6 +639,7 @@ impl EnhancedDeleteEngine {
             cluster_sizes: Mutex::new(HashMap::new()),
             enable_reboot_delete: true,   // 默认启用，处理被占用的文件
             enable_take_ownership: false, // 默认禁用，icacls 调用很慢
+            close_locking_apps: false,    // 默认禁用，关闭程序需要用户明确同意
         }
     }
 
@@ -485,6 +655,15 @@ impl EnhancedDeleteEngine {
         self
     }
 
+    /// 设置文件被占用时是否请求占用程序关闭后重试
+    ///
+    /// 浏览器运行时会一直持有自己的缓存文件，请求其关闭后缓存才能删除。
+    /// 只发送正常关闭请求，占用者是系统服务时不处理。
+    pub fn with_close_locking_apps(mut self, enabled: bool) -> Self {
+        self.close_locking_apps = enabled;
+        self
+    }
+
     /// 计算文件的物理占用大小（按簇对齐）
     ///
     /// # 中文说明
@@ -496,7 +675,7 @@ impl EnhancedDeleteEngine {
             return 0;
         }
         let cluster_size = self.cluster_size as u64;
-        ((logical_size + cluster_size - 1) / cluster_size) * cluster_size
+        logical_size.div_ceil(cluster_size) * cluster_size
     }
 
     /// 按文件所在分区计算物理占用，避免深度清理 D/E 盘时套用 C 盘簇大小。
@@ -550,6 +729,7 @@ impl EnhancedDeleteEngine {
             phase: "cleaning".to_string(),
             processed_count: 0,
             total_count,
+            percent: progress_percent(0, total_count as u64),
             success_count: 0,
             failed_count: 0,
             reboot_pending_count: 0,
@@ -560,7 +740,9 @@ impl EnhancedDeleteEngine {
         // 进度事件只传递聚合数据，避免大批量文件删除时拖慢实际清理速度。
         let mut emit_progress = |processed: usize, current_result: &EnhancedDeleteResult| {
             let should_emit = processed == total_count
-                || processed.saturating_sub(1) % DELETE_PROGRESS_BATCH_SIZE == 0
+                || processed
+                    .saturating_sub(1)
+                    .is_multiple_of(DELETE_PROGRESS_BATCH_SIZE)
                 || last_progress_at.elapsed() >= DELETE_PROGRESS_INTERVAL;
             if !should_emit {
                 return;
@@ -570,6 +752,7 @@ impl EnhancedDeleteEngine {
                 phase: "cleaning".to_string(),
                 processed_count: processed,
                 total_count,
+                percent: progress_percent(processed as u64, total_count as u64),
                 success_count: current_result.success_count,
                 failed_count: current_result.failed_count,
                 reboot_pending_count: current_result.reboot_pending_count,
@@ -583,9 +766,8 @@ impl EnhancedDeleteEngine {
 
         // 分离回收站路径：回收站文件应通过 Shell API 清空，而非逐文件删除
         // 直接删除 $Recycle.Bin 下的文件需要 SYSTEM 权限，SHEmptyRecycleBinW 是标准方式
-        let (recycle_paths, normal_paths): (Vec<&String>, Vec<&String>) = paths
-            .iter()
-            .partition(|p| p.to_lowercase().contains("\\$recycle.bin"));
+        let (recycle_paths, normal_paths): (Vec<&String>, Vec<&String>) =
+            paths.iter().partition(|p| is_recycle_bin_path(p));
 
         // 回收站文件无法逐文件删除，按盘符调用 Shell API，避免一个异常卷拖垮其他卷。
         if !recycle_paths.is_empty() {
@@ -611,6 +793,8 @@ impl EnhancedDeleteEngine {
                             "回收站路径缺少有效盘符".to_string(),
                         )),
                         marked_for_reboot: false,
+                        locking_processes: Vec::new(),
+                        failure_detail: None,
                     });
                     // 非法回收站路径也算作已处理，保证进度总数在异常输入下仍能收敛到 100%。
                     processed_count += 1;
@@ -644,6 +828,8 @@ impl EnhancedDeleteEngine {
                                 physical_size,
                                 failure_reason: None,
                                 marked_for_reboot: false,
+                                locking_processes: Vec::new(),
+                                failure_detail: None,
                             });
                         }
                     }
@@ -662,6 +848,8 @@ impl EnhancedDeleteEngine {
                                     drive_root, error
                                 ))),
                                 marked_for_reboot: false,
+                                locking_processes: Vec::new(),
+                                failure_detail: None,
                             });
                         }
                     }
@@ -718,8 +906,9 @@ impl EnhancedDeleteEngine {
         let logical_size = self.get_file_size(file_path);
         let physical_size = self.calculate_physical_size_for_path(file_path, logical_size);
 
-        // 检查文件是否存在
-        if !file_path.exists() {
+        // 检查文件是否存在（超长路径需要扩展前缀，否则会被误判为不存在）
+        let fs_path = extended_path(file_path);
+        if !fs_path.exists() {
             return FileDeleteResult {
                 path: path.to_string(),
                 success: false,
@@ -727,11 +916,15 @@ impl EnhancedDeleteEngine {
                 physical_size,
                 failure_reason: Some(DeleteFailureReason::NotFound),
                 marked_for_reboot: false,
+                locking_processes: Vec::new(),
+                failure_detail: None,
             };
         }
 
-        // 安全检查
-        if self.is_system_protected(file_path) {
+        // 安全检查：目录整体删除时还要确认其中没有聊天数据库等用户数据
+        if self.is_system_protected(file_path)
+            || (fs_path.is_dir() && find_protected_data_file(&fs_path).is_some())
+        {
             return FileDeleteResult {
                 path: path.to_string(),
                 success: false,
@@ -739,6 +932,24 @@ impl EnhancedDeleteEngine {
                 physical_size,
                 failure_reason: Some(DeleteFailureReason::SystemProtected),
                 marked_for_reboot: false,
+                locking_processes: Vec::new(),
+                failure_detail: None,
+            };
+        }
+
+        // 安装进行中的 Config.Msi 回滚数据不能删除，也不能标记为重启后删除
+        if is_config_msi_path(path) && is_msi_install_in_progress() {
+            return FileDeleteResult {
+                path: path.to_string(),
+                success: false,
+                logical_size,
+                physical_size,
+                failure_reason: Some(DeleteFailureReason::Other(
+                    MSI_INSTALL_IN_PROGRESS_MESSAGE.to_string(),
+                )),
+                marked_for_reboot: false,
+                locking_processes: Vec::new(),
+                failure_detail: None,
             };
         }
 
@@ -753,18 +964,37 @@ impl EnhancedDeleteEngine {
                     physical_size,
                     failure_reason: None,
                     marked_for_reboot: false,
+                    locking_processes: Vec::new(),
+                    failure_detail: None,
                 }
             }
             Err(e) => {
-                // 只把 Windows 共享冲突视为“占用”，避免权限错误被错误安排到重启队列。
-                let is_locked = matches!(e.raw_os_error, Some(32 | 33));
-
-                if is_locked && self.enable_reboot_delete {
-                    // 尝试标记为重启删除
-                    if self.is_safe_for_ownership(file_path) {
-                        match windows_api::mark_for_delete_on_reboot(path) {
-                            Ok(_) => {
-                                info!("文件已标记为重启删除: {}", path);
+                let mut locking_processes = Vec::new();
+                let mut failure_detail = None;
+                let failure_reason =
+                    match e.raw_os_error.and_then(DeleteFailureReason::from_os_error) {
+                        // 只把 Windows 共享冲突视为“占用”，避免权限错误被错误安排到重启队列。
+                        // 每个占用失败都查询占用进程以便界面提示，是否请求它们关闭由选项决定。
+                        Some(DeleteFailureReason::FileLocked) => {
+                            let lock_files = lock_query_files(&fs_path);
+                            locking_processes = self.query_locking_processes(&lock_files);
+                            if self.close_locking_apps
+                                && !locking_processes.is_empty()
+                                && self.close_locking_apps_and_retry(file_path, &lock_files)
+                            {
+                                debug!("关闭占用程序后删除成功: {}", path);
+                                return FileDeleteResult {
+                                    path: path.to_string(),
+                                    success: true,
+                                    logical_size,
+                                    physical_size,
+                                    failure_reason: None,
+                                    marked_for_reboot: false,
+                                    locking_processes,
+                                    failure_detail: None,
+                                };
+                            }
+                            if self.try_mark_locked_for_reboot(file_path) {
                                 return FileDeleteResult {
                                     path: path.to_string(),
                                     success: false,
@@ -772,49 +1002,83 @@ impl EnhancedDeleteEngine {
                                     physical_size,
                                     failure_reason: Some(DeleteFailureReason::MarkedForReboot),
                                     marked_for_reboot: true,
+                                    locking_processes,
+                                    failure_detail: None,
                                 };
                             }
-                            Err(mark_err) => {
-                                warn!("标记重启删除失败: {} - {}", path, mark_err);
-                            }
+                            DeleteFailureReason::FileLocked
                         }
-                    }
-
-                    FileDeleteResult {
-                        path: path.to_string(),
-                        success: false,
-                        logical_size,
-                        physical_size,
-                        failure_reason: Some(DeleteFailureReason::FileLocked),
-                        marked_for_reboot: false,
-                    }
-                } else if e.raw_os_error == Some(5) || e.message.contains("权限") {
-                    FileDeleteResult {
-                        path: path.to_string(),
-                        success: false,
-                        logical_size,
-                        physical_size,
                         // 保留 icacls 和属性处理的具体结果，避免界面只能显示笼统的权限不足。
-                        failure_reason: Some(DeleteFailureReason::Other(format!(
-                            "权限不足：{}",
-                            e.message
-                        ))),
-                        marked_for_reboot: false,
-                    }
-                } else {
-                    FileDeleteResult {
-                        path: path.to_string(),
-                        success: false,
-                        logical_size,
-                        physical_size,
-                        failure_reason: Some(DeleteFailureReason::Other(e.message)),
-                        marked_for_reboot: false,
-                    }
+                        Some(DeleteFailureReason::PermissionDenied) => {
+                            failure_detail = Some(e.message);
+                            DeleteFailureReason::PermissionDenied
+                        }
+                        Some(reason) => reason,
+                        None => DeleteFailureReason::Other(e.message),
+                    };
+
+                FileDeleteResult {
+                    path: path.to_string(),
+                    success: false,
+                    logical_size,
+                    physical_size,
+                    failure_reason: Some(failure_reason),
+                    marked_for_reboot: false,
+                    locking_processes,
+                    failure_detail,
                 }
             }
         }
     }
 
+    /// 查询占用文件的进程，查询失败不影响删除流程
+    fn query_locking_processes(&self, lock_files: &[PathBuf]) -> Vec<LockingProcess> {
+        let paths: Vec<&Path> = lock_files.iter().map(|p| p.as_path()).collect();
+        match find_locking_processes(&paths) {
+            Ok(processes) => processes,
+            Err(e) => {
+                warn!("查询占用进程失败: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    /// 请求占用程序关闭后重新删除，返回是否删除成功
+    fn close_locking_apps_and_retry(&self, file_path: &Path, lock_files: &[PathBuf]) -> bool {
+        let paths: Vec<&Path> = lock_files.iter().map(|p| p.as_path()).collect();
+        match close_locking_processes(&paths) {
+            Ok(closed) => info!(
+                "已请求关闭占用程序: {} ({})",
+                describe_locking_processes(&closed),
+                file_path.display()
+            ),
+            Err(e) => {
+                warn!("关闭占用程序失败 {}: {}", file_path.display(), e);
+                return false;
+            }
+        }
+        self.try_delete(file_path).is_ok()
+    }
+
+    /// 被占用的文件在安全目录内时标记为重启删除，返回是否标记成功
+    fn try_mark_locked_for_reboot(&self, file_path: &Path) -> bool {
+        if !self.enable_reboot_delete || !self.is_safe_for_ownership(file_path) {
+            return false;
+        }
+
+        let path = file_path.to_string_lossy();
+        match windows_api::mark_for_delete_on_reboot(&path) {
+            Ok(_) => {
+                info!("文件已标记为重启删除: {}", path);
+                true
+            }
+            Err(mark_err) => {
+                warn!("标记重启删除失败: {} - {}", path, mark_err);
+                false
+            }
+        }
+    }
+
     /// 尝试删除文件（多策略）
     fn try_delete(&self, path: &Path) -> Result<(), DeleteAttemptError> {
         // 保留第一次删除的原始错误码，后续策略失败时仍能准确判断是否为共享冲突。
@@ -836,7 +1100,13 @@ impl EnhancedDeleteEngine {
         let path_string = path.to_string_lossy();
         let is_cache_path = is_rebuildable_system_cache_path(&path_string);
         if self.is_safe_for_ownership(path) && (self.enable_take_ownership || is_cache_path) {
-            if let Err(error) = self.delete_with_ownership(path) {
+            let ownership_result = self.delete_with_ownership(path);
+            crate::logger::record_audit(
+                "take_ownership_delete",
+                serde_json::json!({ "path": path_string }),
+                &ownership_result,
+            );
+            if let Err(error) = ownership_result {
                 attempt_messages.push(error);
             } else {
                 return Ok(());
@@ -852,10 +1122,11 @@ impl EnhancedDeleteEngine {
 
     /// 直接删除
     fn direct_delete(&self, path: &Path) -> io::Result<()> {
-        if path.is_dir() {
-            fs::remove_dir_all(path)
+        let target = extended_path(path);
+        if target.is_dir() {
+            fs::remove_dir_all(&target)
         } else {
-            fs::remove_file(path)
+            fs::remove_file(&target)
         }
     }
 
@@ -883,39 +1154,61 @@ impl EnhancedDeleteEngine {
     fn delete_with_ownership(&self, path: &Path) -> Result<(), String> {
         let path_str = path.to_string_lossy();
 
-        debug!("尝试获取所有权: {}", path_str);
+        let user_profile = std::env::var("USERPROFILE").ok();
+        let trustee = ownership_trustee(
+            path,
+            user_profile.as_deref(),
+            current_user_account().as_deref(),
+        )?;
+        // 机器级目录回退到管理员组，标准账户下 icacls 必然失败，错误中给出提示。
+        let hint = if trustee == LOCAL_ADMINISTRATORS_SID {
+            "，机器级目录需要以管理员身份运行"
+        } else {
+            ""
+        };
+
+        debug!("尝试获取所有权: {} -> {}", path_str, trustee);
 
         // 使用 icacls 获取所有权（不使用 /T 递归，提升性能）
-        let output = Command::new("icacls")
-            .arg(&*path_str)
-            .arg("/setowner")
-            .arg(LOCAL_ADMINISTRATORS_SID)
-            .arg("/C") // 继续处理错误
-            .arg("/Q") // 静默模式
-            .creation_flags(0x08000000) // CREATE_NO_WINDOW - 不显示命令行窗口
-            .output()
-            .map_err(|e| format!("执行 icacls 失败: {}", e))?;
+        let output = run_with_timeout(
+            icacls_command()
+                .arg(&*path_str)
+                .arg("/setowner")
+                .arg(&trustee)
+                .arg("/C") // 继续处理错误
+                .arg("/Q"), // 静默模式
+            DEFAULT_COMMAND_TIMEOUT,
+        )
+        .map_err(|e| format!("执行 icacls 失败: {}", e))?;
 
         if !output.status.success() {
             return Err(format!(
-                "获取所有权失败（退出码 {:?}）",
-                output.status.code()
+                "以 {} 获取所有权失败（退出码 {:?}）{}",
+                trustee,
+                output.status.code(),
+                hint
             ));
         }
 
         // 授予完全控制权限
-        let output = Command::new("icacls")
-            .arg(&*path_str)
-            .arg("/grant")
-            .arg(format!("{}:F", LOCAL_ADMINISTRATORS_SID))
-            .arg("/C")
-            .arg("/Q")
-            .creation_flags(0x08000000)
-            .output()
-            .map_err(|e| format!("执行 icacls 授权失败: {}", e))?;
+        let output = run_with_timeout(
+            icacls_command()
+                .arg(&*path_str)
+                .arg("/grant")
+                .arg(format!("{}:F", trustee))
+                .arg("/C")
+                .arg("/Q"),
+            DEFAULT_COMMAND_TIMEOUT,
+        )
+        .map_err(|e| format!("执行 icacls 授权失败: {}", e))?;
 
         if !output.status.success() {
-            return Err(format!("授权失败（退出码 {:?}）", output.status.code()));
+            return Err(format!(
+                "为 {} 授权失败（退出码 {:?}）{}",
+                trustee,
+                output.status.code(),
+                hint
+            ));
         }
 
         // 再次尝试删除
@@ -938,12 +1231,16 @@ impl EnhancedDeleteEngine {
         })
     }
 
-    /// 检查是否为系统保护文件（使用共享安全常量，与 delete_engine 保持一致）
+    /// 检查是否为系统保护文件或用户数据文件（使用共享安全常量，与 delete_engine 保持一致）
     fn is_system_protected(&self, path: &Path) -> bool {
         use super::safety_constants::{
-            is_rebuildable_system_cache_path, PROTECTED_FILES, PROTECTED_PATH_PREFIXES,
+            is_protected_data_path, PROTECTED_FILES, PROTECTED_PATH_PREFIXES,
         };
 
+        if is_protected_data_path(path) {
+            return true;
+        }
+
         let path_str = path.to_string_lossy().to_lowercase();
 
         for prefix in PROTECTED_PATH_PREFIXES {
@@ -971,10 +1268,11 @@ impl EnhancedDeleteEngine {
             return logical_size;
         }
 
-        if path.is_file() {
-            fs::metadata(path).map(|m| m.len()).unwrap_or(0)
-        } else if path.is_dir() {
-            self.calculate_dir_size(path)
+        let target = extended_path(path);
+        if target.is_file() {
+            fs::metadata(&target).map(|m| m.len()).unwrap_or(0)
+        } else if target.is_dir() {
+            self.calculate_dir_size(&target)
         } else {
             0
         }
@@ -984,11 +1282,13 @@ impl EnhancedDeleteEngine {
     fn calculate_dir_size(&self, path: &Path) -> u64 {
         // 为了性能，只计算直接子项，不递归遍历
         // 实际释放空间会在删除后由系统报告
+        // DirEntry::metadata 不跟随链接，重解析点按链接本身处理并跳过
         fs::read_dir(path)
             .map(|entries| {
                 entries
                     .filter_map(|e| e.ok())
                     .filter_map(|e| e.metadata().ok())
+                    .filter(|m| !is_reparse_point(m))
                     .map(|m| m.len())
                     .sum()
             })
@@ -1019,7 +1319,7 @@ fn align_physical_size(logical_size: u64, cluster_size: u32) -> u64 {
     }
 
     let cluster_size = cluster_size as u64;
-    ((logical_size + cluster_size - 1) / cluster_size) * cluster_size
+    logical_size.div_ceil(cluster_size) * cluster_size
 }
 
 /// 删除尝试错误，保留原始系统错误码用于区分占用和权限问题。
@@ -1090,6 +1390,36 @@ mod tests {
         assert!(!engine.is_safe_for_ownership(Path::new("C:\\Windows\\System32\\test.dll")));
     }
 
+    #[test]
+    fn test_ownership_trustee_selection() {
+        let profile = Some("C:\\Users\\Test");
+        let account = Some("*S-1-5-21-1-2-3-1001");
+
+        assert_eq!(
+            ownership_trustee(
+                Path::new("C:\\Users\\Test\\AppData\\Local\\Temp\\a.tmp"),
+                profile,
+                account
+            ),
+            Ok("*S-1-5-21-1-2-3-1001".to_string())
+        );
+        // 名称相近的其他用户目录不能算作当前用户配置目录
+        assert_eq!(
+            ownership_trustee(Path::new("C:\\Users\\Test2\\Temp\\a.tmp"), profile, account),
+            Ok(LOCAL_ADMINISTRATORS_SID.to_string())
+        );
+        assert_eq!(
+            ownership_trustee(Path::new("C:\\Windows\\Temp\\a.tmp"), profile, account),
+            Ok(LOCAL_ADMINISTRATORS_SID.to_string())
+        );
+        assert!(ownership_trustee(
+            Path::new("C:\\Users\\Test\\AppData\\Local\\Temp\\a.tmp"),
+            profile,
+            None
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_system_protected_check() {
         let engine = EnhancedDeleteEngine::new();
@@ -1099,6 +1429,83 @@ mod tests {
         assert!(!engine.is_system_protected(Path::new("C:\\Temp\\test.tmp")));
     }
 
+    #[test]
+    fn test_refuses_social_database_files() {
+        let dir =
+            std::env::temp_dir().join(format!("lightc-protected-data-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let attach_dir = dir.join("MsgAttach");
+        std::fs::create_dir_all(&attach_dir).unwrap();
+        let database = attach_dir.join("MicroMsg.db");
+        std::fs::write(&database, b"sqlite").unwrap();
+
+        let engine = EnhancedDeleteEngine::new();
+        let result = engine.delete_files(&[
+            database.to_string_lossy().to_string(),
+            attach_dir.to_string_lossy().to_string(),
+        ]);
+
+        assert_eq!(result.success_count, 0);
+        assert!(result
+            .file_results
+            .iter()
+            .all(|file| file.failure_reason == Some(DeleteFailureReason::SystemProtected)));
+        assert!(database.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_failure_reason_from_os_error() {
+        // 错误码归类不依赖系统语言
+        assert_eq!(
+            DeleteFailureReason::from_os_error(32),
+            Some(DeleteFailureReason::FileLocked)
+        );
+        assert_eq!(
+            DeleteFailureReason::from_os_error(5),
+            Some(DeleteFailureReason::PermissionDenied)
+        );
+        assert_eq!(
+            DeleteFailureReason::from_os_error(3),
+            Some(DeleteFailureReason::NotFound)
+        );
+        assert_eq!(DeleteFailureReason::from_os_error(1392), None);
+    }
+
+    /// 测试用单文件结果，`reason` 为空表示删除成功
+    fn file_result(reason: Option<DeleteFailureReason>) -> FileDeleteResult {
+        FileDeleteResult {
+            path: r"C:\Temp\a.tmp".to_string(),
+            success: reason.is_none(),
+            logical_size: 0,
+            physical_size: 0,
+            marked_for_reboot: reason == Some(DeleteFailureReason::MarkedForReboot),
+            failure_reason: reason,
+            locking_processes: Vec::new(),
+            failure_detail: None,
+        }
+    }
+
+    #[test]
+    fn test_failure_breakdown_in_summary() {
+        let mut result = EnhancedDeleteResult::new();
+        result.file_results = vec![
+            file_result(Some(DeleteFailureReason::FileLocked)),
+            file_result(Some(DeleteFailureReason::FileLocked)),
+            file_result(Some(DeleteFailureReason::SystemProtected)),
+            file_result(Some(DeleteFailureReason::MarkedForReboot)),
+            file_result(None),
+        ];
+        result.generate_summary();
+
+        assert_eq!(result.failure_breakdown.len(), 2);
+        assert_eq!(result.failure_breakdown["文件被系统占用"], 2);
+        assert_eq!(result.failure_breakdown["系统保护文件"], 1);
+        assert!(result
+            .summary_message
+            .contains("失败：2 个文件被系统占用、1 个系统保护文件"));
+    }
+
     #[test]
     fn test_recycle_drive_root() {
         // Shell API 按盘符清空，非法路径必须被拒绝而不能默认落到 C 盘。
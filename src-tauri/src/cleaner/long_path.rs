@@ -0,0 +1,90 @@
+// ============================================================================
+// 超长路径支持
+//
+// Chrome Service Worker、npm 等深层缓存经常超过 MAX_PATH（260 字符），
+// 未加前缀时 `fs::remove_*` / `metadata` 会直接失败，界面上显示为"删除失败"。
+// 删除和大小统计在调用文件系统前统一转换为 `\\?\` 扩展长度路径；
+// 安全检查和界面展示仍使用原始路径，避免前缀影响路径匹配。
+// ============================================================================
+
+use std::path::{Path, PathBuf};
+
+/// 转换为可用于文件系统调用的扩展长度路径
+///
+/// Windows 上先转为绝对路径（`\\?\` 路径不会再做 `.`、`..` 和分隔符规范化），
+/// 再添加前缀；其他平台原样返回。
+pub fn extended_path(path: &Path) -> PathBuf {
+    #[cfg(windows)]
+    {
+        let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+        match absolute.to_str() {
+            Some(value) => PathBuf::from(extend_absolute_path(value)),
+            // 无法表示为 UTF-8 的路径保持原样，交给系统按普通路径处理
+            None => absolute,
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        path.to_path_buf()
+    }
+}
+
+/// 为绝对路径添加扩展长度前缀，已带前缀的设备路径保持不变
+fn extend_absolute_path(path: &str) -> String {
+    if path.starts_with(r"\\?\") || path.starts_with(r"\\.\") {
+        return path.to_string();
+    }
+    match path.strip_prefix(r"\\") {
+        Some(unc) => format!(r"\\?\UNC\{}", unc),
+        None => format!(r"\\?\{}", path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn synthetic_long_path(root: &str) -> String {
+        let mut path = root.to_string();
+        while path.len() <= 300 {
+            path.push_str(r"\service_worker_cache_segment");
+        }
+        path.push_str(r"\entry.bin");
+        path
+    }
+
+    #[test]
+    fn test_extend_absolute_path() {
+        let long_path = synthetic_long_path(r"C:\Users\Test\AppData\Local\Temp");
+        assert!(long_path.len() > 260);
+        assert_eq!(
+            extend_absolute_path(&long_path),
+            format!(r"\\?\{}", long_path)
+        );
+        assert_eq!(
+            extend_absolute_path(r"\\server\share\cache\a.tmp"),
+            r"\\?\UNC\server\share\cache\a.tmp"
+        );
+        assert_eq!(extend_absolute_path(r"\\?\C:\Temp"), r"\\?\C:\Temp");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_long_path_create_and_remove() {
+        let root = std::env::temp_dir().join(format!("lightc-long-path-{}", std::process::id()));
+        let long_dir = PathBuf::from(synthetic_long_path(&root.to_string_lossy()))
+            .parent()
+            .unwrap()
+            .to_path_buf();
+        let file = long_dir.join("entry.bin");
+
+        std::fs::create_dir_all(extended_path(&long_dir)).unwrap();
+        std::fs::write(extended_path(&file), b"1234").unwrap();
+
+        assert_eq!(std::fs::metadata(extended_path(&file)).unwrap().len(), 4);
+        assert!(std::fs::remove_file(extended_path(&file)).is_ok());
+        assert!(!extended_path(&file).exists());
+        let _ = std::fs::remove_dir_all(extended_path(&root));
+    }
+}
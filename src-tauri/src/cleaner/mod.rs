@@ -2,11 +2,34 @@
 // 清理器模块 - 负责删除垃圾文件
 // ============================================================================
 
+mod cbs_logs;
+mod compress;
+mod defender_cache;
 mod delete_engine;
+mod delete_plan;
+mod delete_probe;
 mod enhanced_delete;
+pub(crate) mod long_path;
+mod move_file;
+mod path_explanation;
+mod path_status;
+pub(crate) mod pending_reboot;
 mod permanent_delete;
+mod reboot_fallback;
+pub(crate) mod reboot_status;
+mod relocate;
+pub(crate) mod restart_manager;
 pub(crate) mod safety_constants;
 
+pub use cbs_logs::*;
+pub use compress::*;
+pub use defender_cache::*;
 pub use delete_engine::*;
+pub use delete_plan::*;
+pub use delete_probe::*;
 pub use enhanced_delete::*;
+pub use move_file::*;
+pub use path_explanation::*;
+pub use path_status::*;
 pub use permanent_delete::*;
+pub use relocate::*;
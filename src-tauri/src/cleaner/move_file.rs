@@ -0,0 +1,148 @@
+// ============================================================================
+// 移动大文件到其他磁盘
+//
+// 大文件视图里的视频、安装包用户不一定想删，把它们挪到 D: 同样能释放 C: 空间。
+// 跨卷移动由 MoveFileWithProgressW + MOVEFILE_COPY_ALLOWED 完成（系统先复制再删除
+// 源文件，复制失败时源文件保持不动），复制过程通过回调上报进度。
+// ============================================================================
+
+use std::path::{Path, PathBuf};
+
+/// 将文件移动到目标目录，返回新路径
+///
+/// `on_progress(已复制字节, 总字节)` 只在跨卷复制时被调用；同卷移动是一次重命名。
+pub fn move_file(
+    src: &str,
+    dest_dir: &str,
+    on_progress: impl FnMut(u64, u64),
+) -> Result<String, String> {
+    let target = resolve_move_target(Path::new(src), Path::new(dest_dir))?;
+    let target_str = target.to_string_lossy().into_owned();
+    log::info!("移动文件: {} -> {}", src, target_str);
+    move_with_progress(src, &target_str, on_progress)?;
+    Ok(target_str)
+}
+
+/// 校验源文件和目标目录，计算目标路径
+fn resolve_move_target(src: &Path, dest_dir: &Path) -> Result<PathBuf, String> {
+    let metadata = std::fs::symlink_metadata(src)
+        .map_err(|e| format!("无法读取文件 {}: {}", src.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("只支持移动普通文件: {}", src.display()));
+    }
+    if super::compress::is_system_file(src) {
+        return Err(format!("不允许移动系统文件: {}", src.display()));
+    }
+    if !dest_dir.is_dir() {
+        return Err(format!("目标目录不存在: {}", dest_dir.display()));
+    }
+
+    let file_name = src
+        .file_name()
+        .ok_or_else(|| format!("无效的文件路径: {}", src.display()))?;
+    let target = dest_dir.join(file_name);
+    if src.parent() == Some(dest_dir) {
+        return Err("目标目录与文件当前所在目录相同".to_string());
+    }
+    // 不覆盖目标目录中的同名文件，避免用户的另一份数据被替换
+    if target.exists() {
+        return Err(format!("目标位置已存在同名文件: {}", target.display()));
+    }
+    Ok(target)
+}
+
+#[cfg(target_os = "windows")]
+fn move_with_progress(
+    src: &str,
+    target: &str,
+    mut on_progress: impl FnMut(u64, u64),
+) -> Result<(), String> {
+    use winapi::shared::minwindef::{DWORD, LPVOID};
+    use winapi::um::errhandlingapi::GetLastError;
+    use winapi::um::winbase::{
+        MoveFileWithProgressW, MOVEFILE_COPY_ALLOWED, MOVEFILE_WRITE_THROUGH, PROGRESS_CONTINUE,
+    };
+    use winapi::um::winnt::{HANDLE, LARGE_INTEGER};
+
+    const ERROR_DISK_FULL: DWORD = 112;
+    const ERROR_SHARING_VIOLATION: DWORD = 32;
+
+    unsafe extern "system" fn progress_routine(
+        total_file_size: LARGE_INTEGER,
+        total_bytes_transferred: LARGE_INTEGER,
+        _stream_size: LARGE_INTEGER,
+        _stream_bytes_transferred: LARGE_INTEGER,
+        _stream_number: DWORD,
+        _callback_reason: DWORD,
+        _source_file: HANDLE,
+        _destination_file: HANDLE,
+        data: LPVOID,
+    ) -> DWORD {
+        let callback = &mut *(data as *mut &mut dyn FnMut(u64, u64));
+        callback(
+            *total_bytes_transferred.QuadPart() as u64,
+            *total_file_size.QuadPart() as u64,
+        );
+        PROGRESS_CONTINUE
+    }
+
+    let src_wide = super::enhanced_delete::windows_api::to_wide_string(src);
+    let target_wide = super::enhanced_delete::windows_api::to_wide_string(target);
+    let mut callback: &mut dyn FnMut(u64, u64) = &mut on_progress;
+    // WRITE_THROUGH 保证复制的数据落盘后才删除源文件
+    let ok = unsafe {
+        MoveFileWithProgressW(
+            src_wide.as_ptr(),
+            target_wide.as_ptr(),
+            Some(progress_routine),
+            &mut callback as *mut &mut dyn FnMut(u64, u64) as LPVOID,
+            MOVEFILE_COPY_ALLOWED | MOVEFILE_WRITE_THROUGH,
+        )
+    };
+    if ok == 0 {
+        let err = unsafe { GetLastError() };
+        return Err(match err {
+            ERROR_DISK_FULL => format!("目标磁盘空间不足: {}", target),
+            ERROR_SHARING_VIOLATION => format!("文件正被其他程序使用，无法移动: {}", src),
+            _ => format!("移动文件失败: {} (错误码: {})", src, err),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn move_with_progress(
+    _src: &str,
+    _target: &str,
+    _on_progress: impl FnMut(u64, u64),
+) -> Result<(), String> {
+    Err("此功能仅支持Windows系统".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_move_target_refuses_overwrite_and_same_dir() {
+        let root = std::env::temp_dir().join(format!("lightc-move-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let src_dir = root.join("src");
+        let dest_dir = root.join("dest");
+        fs::create_dir_all(&src_dir).unwrap();
+        fs::create_dir_all(&dest_dir).unwrap();
+        let src = src_dir.join("video.mp4");
+        fs::write(&src, b"data").unwrap();
+
+        let target = resolve_move_target(&src, &dest_dir).unwrap();
+        assert_eq!(target, dest_dir.join("video.mp4"));
+        assert!(resolve_move_target(&src, &src_dir).is_err());
+        assert!(resolve_move_target(&src, &root.join("missing")).is_err());
+
+        fs::write(dest_dir.join("video.mp4"), b"other").unwrap();
+        let result = resolve_move_target(&src, &dest_dir);
+        let _ = fs::remove_dir_all(&root);
+        assert!(result.is_err());
+    }
+}
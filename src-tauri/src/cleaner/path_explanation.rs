@@ -0,0 +1,132 @@
+// ============================================================================
+// 路径说明
+//
+// 回答"为什么建议删除这个文件"：按扫描引擎的分类规则找出路径所属的垃圾分类，
+// 结合删除引擎的保护规则和常规清理范围给出建议，供界面提示使用。
+// 不执行删除，也不检查文件占用（占用诊断见 `probe_delete`）。
+// ============================================================================
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::long_path::extended_path;
+use super::DeleteEngine;
+use crate::scanner::{JunkCategory, ScanEngine};
+
+/// 路径说明
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathExplanation {
+    /// 说明的路径
+    pub path: String,
+    /// 路径是否存在
+    pub exists: bool,
+    /// 所属垃圾分类，不属于任何分类时为空
+    pub category: Option<JunkCategory>,
+    /// 分类显示名称
+    pub category_name: Option<String>,
+    /// 分类风险等级 (1-5, 1最安全)
+    pub risk_level: Option<u8>,
+    /// 分类说明
+    pub description: Option<String>,
+    /// 是否被删除引擎的安全规则拦截
+    pub protected: bool,
+    /// 拦截该路径的保护规则
+    pub protection_rule: Option<String>,
+    /// 是否在常规可清理范围内
+    pub in_allowed_scope: bool,
+    /// 建议操作："delete" 可以删除 / "review" 确认后再删除 / "keep" 建议保留
+    pub recommended_action: String,
+    /// 建议的理由
+    pub reason: String,
+}
+
+impl DeleteEngine {
+    /// 说明路径属于哪个垃圾分类、是否受保护以及建议的处理方式
+    pub fn explain_path(&self, path: &str) -> PathExplanation {
+        let file_path = Path::new(path);
+        let exists = fs::symlink_metadata(extended_path(file_path)).is_ok();
+        let protection_rule = self.protection_rule(file_path);
+        let in_allowed_scope = self.is_in_allowed_scope(file_path);
+        let category = if protection_rule.is_some() {
+            None
+        } else {
+            ScanEngine::new().category_for_path(file_path)
+        };
+        let (recommended_action, reason) =
+            recommend(category.as_ref(), protection_rule, in_allowed_scope);
+
+        PathExplanation {
+            path: path.to_string(),
+            exists,
+            category_name: category.as_ref().map(|c| c.display_name().to_string()),
+            risk_level: category.as_ref().map(|c| c.risk_level()),
+            description: category.as_ref().map(|c| c.description().to_string()),
+            category,
+            protected: protection_rule.is_some(),
+            protection_rule: protection_rule.map(str::to_string),
+            in_allowed_scope,
+            recommended_action: recommended_action.to_string(),
+            reason,
+        }
+    }
+}
+
+/// 风险等级不超过该值的分类建议直接删除，更高的建议确认后再删除
+const SAFE_RISK_LEVEL: u8 = 2;
+
+fn recommend(
+    category: Option<&JunkCategory>,
+    protection_rule: Option<&str>,
+    in_allowed_scope: bool,
+) -> (&'static str, String) {
+    if let Some(rule) = protection_rule {
+        return ("keep", format!("{}，不会被删除", rule));
+    }
+    match category {
+        Some(category) if category.risk_level() <= SAFE_RISK_LEVEL => (
+            "delete",
+            format!(
+                "属于「{}」：{}",
+                category.display_name(),
+                category.description()
+            ),
+        ),
+        Some(category) => (
+            "review",
+            format!(
+                "属于「{}」（风险等级 {}）：{}。建议确认后再删除",
+                category.display_name(),
+                category.risk_level(),
+                category.description()
+            ),
+        ),
+        None if in_allowed_scope => (
+            "review",
+            "位于临时或缓存目录，但不属于任何清理分类，建议确认后再删除".to_string(),
+        ),
+        None => ("keep", "不属于任何清理分类，建议保留".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_explain_path_recommendations() {
+        let protected = DeleteEngine::new().explain_path("C:\\Windows\\System32\\kernel32.dll");
+        assert!(protected.protected);
+        assert_eq!(protected.category, None);
+        assert_eq!(protected.recommended_action, "keep");
+
+        let (action, _) = recommend(Some(&JunkCategory::WindowsTemp), None, true);
+        assert_eq!(action, "delete");
+        let (action, reason) = recommend(Some(&JunkCategory::MemoryDump), None, true);
+        assert_eq!(action, "review");
+        assert!(reason.contains("风险等级 3"));
+        assert_eq!(recommend(None, None, true).0, "review");
+        assert_eq!(recommend(None, None, false).0, "keep");
+    }
+}
@@ -0,0 +1,87 @@
+// ============================================================================
+// 删除前路径校验
+//
+// 用户审阅扫描结果期间，浏览器等程序会自行清理缓存。删除前逐个 stat 已选路径，
+// 让前端移除已不存在的条目并刷新大小，避免这些文件在删除结果中显示为失败。
+// 只读取元数据，不遍历目录。
+// ============================================================================
+
+use std::fs;
+use std::path::Path;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::long_path::extended_path;
+
+/// 路径当前状态
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathStatus {
+    pub path: String,
+    /// 路径是否仍存在
+    pub exists: bool,
+    /// 是否为目录
+    pub is_dir: bool,
+    /// 当前文件大小（字节）；目录和不存在的路径为 0
+    pub size: u64,
+    /// 修改时间（Unix 秒），无法读取时为 0
+    pub modified: i64,
+}
+
+/// 批量读取路径的存在性和当前大小，返回顺序与输入一致
+pub fn validate_paths(paths: &[String]) -> Vec<PathStatus> {
+    // 已选条目可能有数万个，并行 stat 缩短机械硬盘和网络位置上的等待
+    paths.par_iter().map(|path| path_status(path)).collect()
+}
+
+fn path_status(path: &str) -> PathStatus {
+    // 不跟随符号链接：删除引擎删除的是链接本身
+    match fs::symlink_metadata(extended_path(Path::new(path))) {
+        Ok(metadata) => PathStatus {
+            path: path.to_string(),
+            exists: true,
+            is_dir: metadata.is_dir(),
+            size: if metadata.is_dir() { 0 } else { metadata.len() },
+            modified: metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|duration| duration.as_secs() as i64)
+                .unwrap_or(0),
+        },
+        Err(_) => PathStatus {
+            path: path.to_string(),
+            exists: false,
+            is_dir: false,
+            size: 0,
+            modified: 0,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_paths_reports_missing_and_current_size() {
+        let dir = std::env::temp_dir().join(format!("lightc-path-status-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("grown.tmp");
+        fs::write(&file, vec![0u8; 42]).unwrap();
+        let missing = dir.join("gone.tmp");
+
+        let statuses = validate_paths(&[
+            file.to_string_lossy().into_owned(),
+            missing.to_string_lossy().into_owned(),
+            dir.to_string_lossy().into_owned(),
+        ]);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(statuses[0].exists);
+        assert_eq!(statuses[0].size, 42);
+        assert!(!statuses[1].exists);
+        assert!(statuses[2].exists && statuses[2].is_dir);
+        assert_eq!(statuses[2].size, 0);
+    }
+}
@@ -0,0 +1,246 @@
+// ============================================================================
+// 重启删除队列 - 读取和修改 PendingFileRenameOperations
+//
+// MoveFileEx(MOVEFILE_DELAY_UNTIL_REBOOT) 标记的文件在重启前不可见，
+// 这里把队列中的"删除"项解析出来供用户查看，并允许撤销单个条目。
+//
+// 注册表格式：REG_MULTI_SZ，按 (源路径, 目标路径) 成对存放；
+// 目标为空字符串表示删除，源路径带 `\??\` 前缀，替换型目标带 `!` 前缀。
+// ============================================================================
+
+#[cfg(windows)]
+use winreg::enums::{RegType, HKEY_LOCAL_MACHINE, KEY_READ, KEY_SET_VALUE};
+#[cfg(windows)]
+use winreg::{RegKey, RegValue};
+
+#[cfg(windows)]
+const SESSION_MANAGER_KEY: &str = r"System\CurrentControlSet\Control\Session Manager";
+#[cfg(windows)]
+const PENDING_RENAME_VALUE: &str = "PendingFileRenameOperations";
+
+/// 队列中的一项重命名/删除操作（保留注册表原始写法，回写时不改动其他程序的条目）
+#[derive(Debug, Clone, PartialEq)]
+struct PendingRenameOperation {
+    source: String,
+    destination: String,
+}
+
+impl PendingRenameOperation {
+    fn is_delete(&self) -> bool {
+        self.destination.is_empty()
+    }
+}
+
+/// 读取重启后将被删除的路径列表
+pub fn get_pending_reboot_deletions() -> Result<Vec<String>, String> {
+    Ok(read_pending_operations()?
+        .iter()
+        .filter(|operation| operation.is_delete())
+        .map(|operation| normalize_nt_path(&operation.source))
+        .collect())
+}
+
+/// 从重启删除队列中移除指定路径
+///
+/// # 中文说明
+/// 只移除目标为空的删除项，安装程序登记的替换/重命名项保持原样；
+/// 队列清空后直接删除该值，与系统重启处理后的状态一致。写入 HKLM 需要管理员权限。
+pub fn cancel_pending_reboot_deletion(path: &str) -> Result<(), String> {
+    let operations = read_pending_operations()?;
+    let target = normalize_for_compare(path);
+    let remaining: Vec<PendingRenameOperation> = operations
+        .iter()
+        .filter(|operation| {
+            !(operation.is_delete() && normalize_for_compare(&operation.source) == target)
+        })
+        .cloned()
+        .collect();
+
+    if remaining.len() == operations.len() {
+        return Err(format!("重启删除队列中没有该路径: {}", path));
+    }
+
+    write_pending_operations(&remaining)?;
+    log::info!("已从重启删除队列移除: {}", path);
+    Ok(())
+}
+
+#[cfg(windows)]
+fn write_pending_operations(operations: &[PendingRenameOperation]) -> Result<(), String> {
+    let key = RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey_with_flags(SESSION_MANAGER_KEY, KEY_READ | KEY_SET_VALUE)
+        .map_err(|e| format!("打开 Session Manager 失败（需要管理员权限）: {}", e))?;
+
+    if operations.is_empty() {
+        key.delete_value(PENDING_RENAME_VALUE)
+            .map_err(|e| format!("清除重启删除队列失败: {}", e))
+    } else {
+        key.set_raw_value(
+            PENDING_RENAME_VALUE,
+            &RegValue {
+                bytes: encode_pending_operations(operations),
+                vtype: RegType::REG_MULTI_SZ,
+            },
+        )
+        .map_err(|e| format!("写入重启删除队列失败: {}", e))
+    }
+}
+
+#[cfg(not(windows))]
+fn write_pending_operations(_operations: &[PendingRenameOperation]) -> Result<(), String> {
+    Err("此功能仅支持Windows系统".to_string())
+}
+
+#[cfg(windows)]
+fn read_pending_operations() -> Result<Vec<PendingRenameOperation>, String> {
+    let key = RegKey::predef(HKEY_LOCAL_MACHINE)
+        .open_subkey_with_flags(SESSION_MANAGER_KEY, KEY_READ)
+        .map_err(|e| format!("打开 Session Manager 失败: {}", e))?;
+
+    match key.get_raw_value(PENDING_RENAME_VALUE) {
+        Ok(value) => {
+            let words: Vec<u16> = value
+                .bytes
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .collect();
+            Ok(parse_pending_operations(&words))
+        }
+        // 值不存在说明当前没有任何待重启操作
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("读取重启删除队列失败: {}", e)),
+    }
+}
+
+/// 非 Windows 平台没有重启删除队列
+#[cfg(not(windows))]
+fn read_pending_operations() -> Result<Vec<PendingRenameOperation>, String> {
+    Ok(Vec::new())
+}
+
+/// 解析 REG_MULTI_SZ 原始数据
+///
+/// winreg 的 `Vec<String>` 转换会去掉末尾所有空字符串，导致最后一项删除操作的空目标丢失，
+/// 因此这里按 NUL 逐个切分并成对读取。
+fn parse_pending_operations(words: &[u16]) -> Vec<PendingRenameOperation> {
+    let mut strings = Vec::new();
+    let mut current = Vec::new();
+    for &word in words {
+        if word == 0 {
+            strings.push(String::from_utf16_lossy(&current));
+            current.clear();
+        } else {
+            current.push(word);
+        }
+    }
+    if !current.is_empty() {
+        strings.push(String::from_utf16_lossy(&current));
+    }
+
+    strings
+        .chunks(2)
+        .take_while(|pair| !pair[0].is_empty())
+        .map(|pair| PendingRenameOperation {
+            source: pair[0].clone(),
+            destination: pair.get(1).cloned().unwrap_or_default(),
+        })
+        .collect()
+}
+
+#[cfg_attr(not(windows), allow(dead_code))]
+fn encode_pending_operations(operations: &[PendingRenameOperation]) -> Vec<u8> {
+    let mut words = Vec::new();
+    for operation in operations {
+        words.extend(operation.source.encode_utf16());
+        words.push(0);
+        words.extend(operation.destination.encode_utf16());
+        words.push(0);
+    }
+    // REG_MULTI_SZ 以额外的 NUL 结尾
+    words.push(0);
+    words.iter().flat_map(|word| word.to_le_bytes()).collect()
+}
+
+/// 去掉 NT 路径前缀，转换为用户可读的 Win32 路径
+fn normalize_nt_path(raw: &str) -> String {
+    let path = raw.trim_start_matches('!');
+    path.strip_prefix(r"\??\UNC\")
+        .map(|rest| format!(r"\\{}", rest))
+        .or_else(|| path.strip_prefix(r"\??\").map(str::to_string))
+        .unwrap_or_else(|| path.to_string())
+}
+
+fn normalize_for_compare(path: &str) -> String {
+    normalize_nt_path(path)
+        .replace('/', "\\")
+        .trim_end_matches('\\')
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_words(strings: &[&str]) -> Vec<u16> {
+        let mut words = Vec::new();
+        for value in strings {
+            words.extend(value.encode_utf16());
+            words.push(0);
+        }
+        words.push(0);
+        words
+    }
+
+    #[test]
+    fn test_parse_keeps_trailing_delete_operation() {
+        let words = to_words(&[
+            r"\??\C:\Temp\a.tmp",
+            "",
+            r"\??\C:\Setup\new.dll",
+            r"!\??\C:\Windows\old.dll",
+            r"\??\C:\Temp\b.tmp",
+            "",
+        ]);
+        let operations = parse_pending_operations(&words);
+
+        assert_eq!(operations.len(), 3);
+        assert!(operations[0].is_delete());
+        assert!(!operations[1].is_delete());
+        assert!(operations[2].is_delete());
+        assert_eq!(normalize_nt_path(&operations[2].source), r"C:\Temp\b.tmp");
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let operations = vec![
+            PendingRenameOperation {
+                source: r"\??\C:\Temp\a.tmp".to_string(),
+                destination: String::new(),
+            },
+            PendingRenameOperation {
+                source: r"\??\C:\Setup\new.dll".to_string(),
+                destination: r"!\??\C:\Windows\old.dll".to_string(),
+            },
+        ];
+        let bytes = encode_pending_operations(&operations);
+        let words: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+
+        assert_eq!(parse_pending_operations(&words), operations);
+    }
+
+    #[test]
+    fn test_normalize_nt_path() {
+        assert_eq!(normalize_nt_path(r"\??\C:\Temp\a.tmp"), r"C:\Temp\a.tmp");
+        assert_eq!(
+            normalize_nt_path(r"\??\UNC\server\share\a.tmp"),
+            r"\\server\share\a.tmp"
+        );
+        assert_eq!(
+            normalize_for_compare(r"\??\C:\Temp\A.TMP"),
+            normalize_for_compare(r"c:/temp/a.tmp")
+        );
+    }
+}
@@ -4,8 +4,9 @@
 // ⚠️ 警告：此模块执行直接物理删除，数据不可恢复！
 //
 // 【核心功能】
-// 直接使用 std::fs::remove_dir_all 和 std::fs::remove_file 从磁盘永久删除文件，
+// 自底向上逐个使用 std::fs::remove_file / remove_dir 从磁盘永久删除文件，
 // 而非移动到回收站。这是为了彻底清理已卸载软件的残留数据。
+// 部分失败时仍会返回真实的删除数量、释放空间和一级子目录明细。
 //
 // 【为什么需要检查 .exe 文件？】
 // 在删除前检查文件夹内是否存在 .exe/.dll/.sys 文件至关重要，原因如下：
@@ -31,15 +32,22 @@
 // - 权限不足时尝试获取所有权后重试
 // ============================================================================
 
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
 use log::{debug, info, warn};
 use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
 use serde::{Deserialize, Serialize};
 use walkdir::WalkDir;
 
+use super::enhanced_delete::{progress_percent, DeleteFailureReason};
+use super::long_path::extended_path;
+use super::safety_constants::{
+    find_protected_data_file, is_protected_data_path, is_reparse_point, is_reparse_point_dir,
+};
 #[cfg(windows)]
 use crate::cleaner::enhanced_delete::windows_api;
 
@@ -112,6 +120,112 @@ pub struct LeftoverDeleteResult {
     pub needs_manual_review: bool,
     /// 安全检查结果
     pub safety_check: SafetyCheckResult,
+    /// 按一级子目录汇总的删除明细，部分失败时用于定位残留位置
+    pub subfolder_breakdown: Vec<SubfolderDeleteStat>,
+}
+
+/// 一级子目录的删除统计
+///
+/// 顶层目录下的直属文件汇总到顶层目录自身，避免为每个文件单独生成一条记录。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubfolderDeleteStat {
+    /// 子目录路径
+    pub path: String,
+    /// 实际删除的文件数量
+    pub deleted_files: u32,
+    /// 实际释放的空间（字节）
+    pub freed_size: u64,
+    /// 删除失败的文件数量
+    pub failed_files: u32,
+}
+
+/// 永久删除进度（按文件计）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermanentDeleteProgress {
+    /// 已处理的文件数（删除成功、失败或跳过）
+    pub done: u64,
+    /// 删除前预先统计的文件总数
+    pub total: u64,
+    /// 完成百分比（0-100）
+    pub percent: u8,
+}
+
+/// 每处理这么多文件发送一次进度，避免逐文件回调
+const PERMANENT_PROGRESS_BATCH_SIZE: u64 = 200;
+
+/// 多个目录并发删除时共享的进度计数
+struct DeleteProgressTracker<'a> {
+    done: AtomicU64,
+    total: u64,
+    on_progress: &'a (dyn Fn(PermanentDeleteProgress) + Sync),
+}
+
+impl DeleteProgressTracker<'_> {
+    /// 计入已处理的文件，跨过批次边界时回调进度
+    fn advance(&self, count: u64) {
+        if count == 0 {
+            return;
+        }
+        let before = self.done.fetch_add(count, Ordering::Relaxed);
+        let after = before + count;
+        if before / PERMANENT_PROGRESS_BATCH_SIZE != after / PERMANENT_PROGRESS_BATCH_SIZE
+            || after >= self.total
+        {
+            self.emit(after);
+        }
+    }
+
+    fn emit(&self, done: u64) {
+        // 预统计后目录内容可能变化，已处理数不能超过总数
+        let done = done.min(self.total);
+        (self.on_progress)(PermanentDeleteProgress {
+            done,
+            total: self.total,
+            percent: progress_percent(done, self.total),
+        });
+    }
+}
+
+/// 逐文件自底向上删除的汇总结果
+#[derive(Debug, Default)]
+struct BottomUpDeleteOutcome {
+    deleted_files: u32,
+    freed_size: u64,
+    failed_files: u32,
+    /// 已处理的文件数（含失败和跳过），用于目录结束时补齐进度
+    visited_files: u64,
+    /// 保留第一条错误，便于界面给出可定位的失败原因
+    first_error: Option<String>,
+    /// 因被占用而删除失败的条目，只有它们会进入重启删除队列
+    locked_paths: Vec<PathBuf>,
+    subfolders: BTreeMap<PathBuf, SubfolderDeleteStat>,
+}
+
+impl BottomUpDeleteOutcome {
+    fn stat_for(&mut self, key: &Path) -> &mut SubfolderDeleteStat {
+        self.subfolders
+            .entry(key.to_path_buf())
+            .or_insert_with(|| SubfolderDeleteStat {
+                path: key.to_string_lossy().to_string(),
+                ..Default::default()
+            })
+    }
+
+    fn record_deleted(&mut self, key: &Path, size: u64) {
+        self.deleted_files += 1;
+        self.freed_size += size;
+        let stat = self.stat_for(key);
+        stat.deleted_files += 1;
+        stat.freed_size += size;
+    }
+
+    fn record_failed(&mut self, key: &Path, error: String) {
+        self.failed_files += 1;
+        self.stat_for(key).failed_files += 1;
+        if self.first_error.is_none() {
+            self.first_error = Some(error);
+        }
+    }
 }
 
 /// 永久删除的总体结果
@@ -183,6 +297,8 @@ const EXECUTABLE_EXTENSIONS: &[&str] = &["exe", "dll", "sys", "drv", "ocx", "cpl
 pub struct PermanentDeleteEngine {
     /// 是否启用重启删除回退
     enable_reboot_fallback: bool,
+    /// 并发删除的最大线程数，None 使用 rayon 全局线程池
+    max_concurrency: Option<usize>,
 }
 
 impl PermanentDeleteEngine {
@@ -192,9 +308,26 @@ impl PermanentDeleteEngine {
 
         PermanentDeleteEngine {
             enable_reboot_fallback: true,
+            max_concurrency: None,
         }
     }
 
+    /// 设置删除失败时是否将剩余内容标记为重启后删除
+    ///
+    /// 关闭后剩余内容只作为删除失败返回，不写入 PendingFileRenameOperations。
+    pub fn with_reboot_fallback(mut self, enabled: bool) -> Self {
+        self.enable_reboot_fallback = enabled;
+        self
+    }
+
+    /// 设置并发删除的最大线程数（1 表示逐个目录顺序删除）
+    ///
+    /// 机械硬盘上多个目录同时删除会导致磁头来回寻道，反而比顺序删除更慢。
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency.max(1));
+        self
+    }
+
     // ========================================================================
     // 安全检查协议（白名单 + 可执行文件检查）
     // ========================================================================
@@ -213,6 +346,17 @@ impl PermanentDeleteEngine {
         if let Some(reason) = self.check_protected_path(&path_str) {
             return SafetyCheckResult::InProtectedPath { reason };
         }
+        if is_protected_data_path(path) {
+            return SafetyCheckResult::InProtectedPath {
+                reason: "路径包含聊天数据库等用户数据文件".to_string(),
+            };
+        }
+        // 目录树中含有用户数据时整体拒绝，避免逐项删除后只剩数据库孤零零地留下
+        if let Some(data_file) = find_protected_data_file(&extended_path(path)) {
+            return SafetyCheckResult::InProtectedPath {
+                reason: format!("目录中包含用户数据文件: {}", data_file.display()),
+            };
+        }
 
         // ====================================================================
         // Check 2: 可执行文件检查
@@ -245,7 +389,9 @@ impl PermanentDeleteEngine {
 
         for entry in WalkDir::new(path)
             .max_depth(max_depth)
+            .follow_links(false)
             .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !is_reparse_point_dir(e))
             .filter_map(|e| e.ok())
         {
             if executables.len() >= max_results {
@@ -334,9 +480,22 @@ impl PermanentDeleteEngine {
     /// 执行永久删除（并发处理）
     ///
     /// 【中文说明】
-    /// 使用 rayon 线程池并发删除多个目录，确保 UI 保持响应。
+    /// 使用 rayon 线程池并发删除多个目录，确保 UI 保持响应；
+    /// 设置了 `with_max_concurrency` 时在独立的限定线程数线程池中执行。
     /// 每个目录删除前都会执行三重安全检查。
     pub fn delete_leftovers(&self, paths: Vec<String>) -> PermanentDeleteResult {
+        self.delete_leftovers_with_progress(paths, &|_| {})
+    }
+
+    /// 执行永久删除并按文件回调进度
+    ///
+    /// 删除前先统计通过安全检查的目录的文件总数，进度才能给出百分比；
+    /// 回调可能在多个删除线程中调用，只能做轻量级通知。
+    pub fn delete_leftovers_with_progress(
+        &self,
+        paths: Vec<String>,
+        on_progress: &(dyn Fn(PermanentDeleteProgress) + Sync),
+    ) -> PermanentDeleteResult {
         let start_time = std::time::Instant::now();
 
         // 使用原子计数器进行并发统计
@@ -347,63 +506,97 @@ impl PermanentDeleteEngine {
         let freed_size = AtomicU64::new(0);
 
         // 并发执行删除
-        let details: Vec<LeftoverDeleteResult> = paths
-            .par_iter()
-            .map(|path_str| {
-                let path = Path::new(path_str);
-
-                // 执行三重安全检查
-                let safety_check = self.perform_safety_checks(path);
-
-                match &safety_check {
-                    SafetyCheckResult::Safe => {
-                        // 通过安全检查，执行删除
-                        let result = self.delete_single_leftover(path);
-
-                        if result.success {
-                            success_count.fetch_add(1, Ordering::Relaxed);
+        let delete_all = || -> Vec<LeftoverDeleteResult> {
+            // 先执行三重安全检查，只统计通过检查的目录的文件数；
+            // 统计本身要遍历整棵目录树，放在受限线程池内，且不会进入受保护路径
+            let checked: Vec<(SafetyCheckResult, u64)> = paths
+                .par_iter()
+                .map(|path_str| {
+                    let path = Path::new(path_str);
+                    let safety_check = self.perform_safety_checks(path);
+                    let file_total = match safety_check {
+                        SafetyCheckResult::Safe => count_files(path),
+                        _ => 0,
+                    };
+                    (safety_check, file_total)
+                })
+                .collect();
+            let tracker = DeleteProgressTracker {
+                done: AtomicU64::new(0),
+                total: checked.iter().map(|(_, file_total)| file_total).sum(),
+                on_progress,
+            };
+            tracker.emit(0);
+
+            paths
+                .par_iter()
+                .zip(checked.into_par_iter())
+                .map(|(path_str, (safety_check, file_total))| {
+                    let path = Path::new(path_str);
+
+                    match &safety_check {
+                        SafetyCheckResult::Safe => {
+                            // 通过安全检查，执行删除
+                            let result = self.delete_single_leftover(path, file_total, &tracker);
+
+                            // 部分失败时已删除的文件同样真实释放了空间，统一计入总量
                             freed_size.fetch_add(result.freed_size, Ordering::Relaxed);
-                        } else if result.marked_for_reboot {
-                            reboot_pending_count.fetch_add(1, Ordering::Relaxed);
-                        } else {
-                            failed_count.fetch_add(1, Ordering::Relaxed);
+                            if result.success {
+                                success_count.fetch_add(1, Ordering::Relaxed);
+                            } else if result.marked_for_reboot {
+                                reboot_pending_count.fetch_add(1, Ordering::Relaxed);
+                            } else {
+                                failed_count.fetch_add(1, Ordering::Relaxed);
+                            }
+
+                            result
                         }
-
-                        result
-                    }
-                    SafetyCheckResult::ContainsExecutables { .. } => {
-                        // 包含可执行文件，标记为需要人工审核
-                        manual_review_count.fetch_add(1, Ordering::Relaxed);
-
-                        LeftoverDeleteResult {
-                            path: path_str.clone(),
-                            success: false,
-                            deleted_files: 0,
-                            freed_size: 0,
-                            failure_reason: Some(safety_check.display_message()),
-                            marked_for_reboot: false,
-                            needs_manual_review: true,
-                            safety_check,
+                        SafetyCheckResult::ContainsExecutables { .. } => {
+                            // 包含可执行文件，标记为需要人工审核
+                            manual_review_count.fetch_add(1, Ordering::Relaxed);
+
+                            LeftoverDeleteResult {
+                                path: path_str.clone(),
+                                success: false,
+                                deleted_files: 0,
+                                freed_size: 0,
+                                failure_reason: Some(safety_check.display_message()),
+                                marked_for_reboot: false,
+                                needs_manual_review: true,
+                                safety_check,
+                                subfolder_breakdown: Vec::new(),
+                            }
                         }
-                    }
-                    _ => {
-                        // 其他安全检查失败
-                        failed_count.fetch_add(1, Ordering::Relaxed);
-
-                        LeftoverDeleteResult {
-                            path: path_str.clone(),
-                            success: false,
-                            deleted_files: 0,
-                            freed_size: 0,
-                            failure_reason: Some(safety_check.display_message()),
-                            marked_for_reboot: false,
-                            needs_manual_review: false,
-                            safety_check,
+                        _ => {
+                            // 其他安全检查失败
+                            failed_count.fetch_add(1, Ordering::Relaxed);
+
+                            LeftoverDeleteResult {
+                                path: path_str.clone(),
+                                success: false,
+                                deleted_files: 0,
+                                freed_size: 0,
+                                failure_reason: Some(safety_check.display_message()),
+                                marked_for_reboot: false,
+                                needs_manual_review: false,
+                                safety_check,
+                                subfolder_breakdown: Vec::new(),
+                            }
                         }
                     }
+                })
+                .collect()
+        };
+        let details = match self.max_concurrency {
+            Some(threads) => match ThreadPoolBuilder::new().num_threads(threads).build() {
+                Ok(pool) => pool.install(delete_all),
+                Err(e) => {
+                    warn!("创建删除线程池失败，使用默认线程池: {}", e);
+                    delete_all()
                 }
-            })
-            .collect();
+            },
+            None => delete_all(),
+        };
 
         let duration_ms = start_time.elapsed().as_millis() as u64;
 
@@ -431,153 +624,265 @@ impl PermanentDeleteEngine {
     /// 删除单个残留目录
     ///
     /// 【中文说明】
-    /// 此函数执行实际的物理删除操作。首先尝试直接删除，
-    /// 如果遇到锁定文件，则自动切换到"重启后删除"队列。
-    fn delete_single_leftover(&self, path: &Path) -> LeftoverDeleteResult {
+    /// 逐文件自底向上删除，而不是一次性 remove_dir_all：后者中途失败时无法得知
+    /// 哪些文件已经消失，只能报告"0 个已删除"。逐文件删除可以在部分失败时
+    /// 仍然返回真实的删除数量、释放空间和子目录明细；剩余内容再交给重启删除队列。
+    fn delete_single_leftover(
+        &self,
+        path: &Path,
+        file_total: u64,
+        tracker: &DeleteProgressTracker,
+    ) -> LeftoverDeleteResult {
         let path_str = path.to_string_lossy().to_string();
 
-        // 先计算目录大小
-        let (total_size, file_count) = self.calculate_dir_size(path);
-
         // ====================================================================
         // ⚠️ 警告：以下代码执行永久删除，数据不可恢复！
         // ====================================================================
+        let outcome = self.delete_bottom_up(path, tracker);
+        // 遍历时无法读取的子目录里的文件不会逐个计入，目录结束时补齐到预统计数
+        tracker.advance(file_total.saturating_sub(outcome.visited_files));
+        let subfolder_breakdown: Vec<SubfolderDeleteStat> =
+            outcome.subfolders.values().cloned().collect();
+
+        // 以顶层目录是否仍存在作为最终判断，避免遗漏遍历阶段无法读取的条目
+        if !path.exists() {
+            info!(
+                "成功永久删除: {} ({} 文件, {} 字节)",
+                path_str, outcome.deleted_files, outcome.freed_size
+            );
+
+            return LeftoverDeleteResult {
+                path: path_str,
+                success: true,
+                deleted_files: outcome.deleted_files,
+                freed_size: outcome.freed_size,
+                failure_reason: None,
+                marked_for_reboot: false,
+                needs_manual_review: false,
+                safety_check: SafetyCheckResult::Safe,
+                subfolder_breakdown,
+            };
+        }
 
-        // 尝试直接删除整个目录
-        match self.try_remove_dir_all(path) {
-            Ok(()) => {
-                info!(
-                    "成功永久删除: {} ({} 文件, {} 字节)",
-                    path_str, file_count, total_size
-                );
-
-                LeftoverDeleteResult {
-                    path: path_str,
-                    success: true,
-                    deleted_files: file_count,
-                    freed_size: total_size,
-                    failure_reason: None,
-                    marked_for_reboot: false,
-                    needs_manual_review: false,
-                    safety_check: SafetyCheckResult::Safe,
-                }
-            }
-            Err(e) => {
-                warn!("直接删除失败: {} - {}", path_str, e);
-
-                // 尝试重启后删除
-                if self.enable_reboot_fallback {
-                    if self.mark_for_reboot_delete(path) {
-                        return LeftoverDeleteResult {
-                            path: path_str,
-                            success: false,
-                            deleted_files: 0,
-                            freed_size: 0,
-                            failure_reason: Some("已标记为重启后删除".to_string()),
-                            marked_for_reboot: true,
-                            needs_manual_review: false,
-                            safety_check: SafetyCheckResult::Safe,
-                        };
+        let error_detail = outcome
+            .first_error
+            .clone()
+            .unwrap_or_else(|| "目录仍然存在".to_string());
+        warn!(
+            "部分删除失败: {} - 已删除 {} 个文件, 失败 {} 个, 首个错误: {}",
+            path_str, outcome.deleted_files, outcome.failed_files, error_detail
+        );
+
+        // 被占用的条目尝试重启后删除；已删除部分照常计入结果
+        if self.enable_reboot_fallback && self.mark_for_reboot_delete(&outcome.locked_paths) {
+            return LeftoverDeleteResult {
+                path: path_str,
+                success: false,
+                deleted_files: outcome.deleted_files,
+                freed_size: outcome.freed_size,
+                failure_reason: Some(format!(
+                    "已删除 {} 个文件，被占用的 {} 个条目已标记为重启后删除",
+                    outcome.deleted_files,
+                    outcome.locked_paths.len()
+                )),
+                marked_for_reboot: true,
+                needs_manual_review: false,
+                safety_check: SafetyCheckResult::Safe,
+                subfolder_breakdown,
+            };
+        }
+
+        LeftoverDeleteResult {
+            path: path_str,
+            success: false,
+            deleted_files: outcome.deleted_files,
+            freed_size: outcome.freed_size,
+            failure_reason: Some(format!(
+                "部分删除失败：已删除 {} 个文件，{} 个文件删除失败（{}）",
+                outcome.deleted_files, outcome.failed_files, error_detail
+            )),
+            marked_for_reboot: false,
+            needs_manual_review: false,
+            safety_check: SafetyCheckResult::Safe,
+            subfolder_breakdown,
+        }
+    }
+
+    /// 自底向上逐个删除目录内容
+    ///
+    /// 【中文说明】
+    /// 先递归删除子项，再删除目录本身，保证父目录在子项全部删除后才会被移除。
+    /// 符号链接和 Junction 等重解析点只删除链接本身，绝不进入目标目录。
+    fn delete_bottom_up(
+        &self,
+        root: &Path,
+        tracker: &DeleteProgressTracker,
+    ) -> BottomUpDeleteOutcome {
+        let mut outcome = BottomUpDeleteOutcome::default();
+
+        match fs::symlink_metadata(extended_path(root)) {
+            Ok(metadata) if metadata.is_dir() && !is_reparse_point(&metadata) => {
+                self.delete_dir_contents(root, root, &mut outcome, tracker);
+                if let Err(e) = self.remove_with_attribute_retry(root, true) {
+                    debug!("顶层目录未能删除: {:?} - {}", root, e);
+                    if is_lock_error(&e) {
+                        outcome.locked_paths.push(root.to_path_buf());
                     }
                 }
+            }
+            Ok(metadata) => self.delete_entry(root, root, &metadata, &mut outcome, tracker),
+            Err(e) => outcome.record_failed(root, format!("无法读取: {}", e)),
+        }
+
+        outcome
+    }
 
-                LeftoverDeleteResult {
-                    path: path_str,
-                    success: false,
-                    deleted_files: 0,
-                    freed_size: 0,
-                    failure_reason: Some(format!("删除失败: {}", e)),
-                    marked_for_reboot: false,
-                    needs_manual_review: false,
-                    safety_check: SafetyCheckResult::Safe,
+    /// 递归删除目录下的全部子项
+    fn delete_dir_contents(
+        &self,
+        root: &Path,
+        dir: &Path,
+        outcome: &mut BottomUpDeleteOutcome,
+        tracker: &DeleteProgressTracker,
+    ) {
+        let entries = match fs::read_dir(extended_path(dir)) {
+            Ok(entries) => entries,
+            Err(e) => {
+                // 无法读取的目录计入失败，它和它的父目录稍后都会因非空而无法删除
+                let key = subfolder_key(root, dir);
+                outcome.record_failed(&key, format!("无法读取 {}: {}", dir.display(), e));
+                return;
+            }
+        };
+
+        for entry in entries {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    outcome.record_failed(&subfolder_key(root, dir), format!("无法读取: {}", e));
+                    continue;
                 }
+            };
+            // 用原始路径拼接子项，统计结果中的路径不带扩展前缀
+            let entry_path = dir.join(entry.file_name());
+            match fs::symlink_metadata(extended_path(&entry_path)) {
+                Ok(metadata) => self.delete_entry(root, &entry_path, &metadata, outcome, tracker),
+                Err(e) => outcome.record_failed(
+                    &subfolder_key(root, dir),
+                    format!("{}: {}", entry_path.display(), e),
+                ),
             }
         }
     }
 
-    /// 尝试删除整个目录
-    ///
-    /// 【中文说明】
-    /// 使用 std::fs::remove_dir_all 递归删除目录及其所有内容。
-    /// 如果遇到只读文件，会先尝试移除只读属性后重试。
-    fn try_remove_dir_all(&self, path: &Path) -> Result<(), String> {
-        // ====================================================================
-        // ⚠️ 警告：此操作将永久删除磁盘数据，不可恢复！
-        // ====================================================================
+    /// 删除单个条目：普通目录递归处理，重解析点只移除链接，文件计入释放空间
+    fn delete_entry(
+        &self,
+        root: &Path,
+        entry_path: &Path,
+        metadata: &fs::Metadata,
+        outcome: &mut BottomUpDeleteOutcome,
+        tracker: &DeleteProgressTracker,
+    ) {
+        let key = subfolder_key(root, entry_path.parent().unwrap_or(root));
+
+        if is_reparse_point(metadata) && (metadata.is_dir() || metadata.file_type().is_symlink()) {
+            // 链接本身不占用目标空间，只移除链接，不计入文件数量
+            let target = extended_path(entry_path);
+            if let Err(e) = fs::remove_dir(&target).or_else(|_| fs::remove_file(&target)) {
+                if is_lock_error(&e) {
+                    outcome.locked_paths.push(entry_path.to_path_buf());
+                }
+                outcome.record_failed(&key, format!("{}: {}", entry_path.display(), e));
+            }
+            return;
+        }
 
-        // 首先尝试直接删除
-        match fs::remove_dir_all(path) {
-            Ok(()) => return Ok(()),
-            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
-                debug!("权限不足，尝试移除保护属性后重试: {:?}", path);
+        if metadata.is_dir() {
+            self.delete_dir_contents(root, entry_path, outcome, tracker);
+            // 子项删除失败时目录必然非空，失败已在子项中记录，这里不重复计数
+            if let Err(e) = self.remove_with_attribute_retry(entry_path, true) {
+                debug!("目录未能删除: {:?} - {}", entry_path, e);
+                if is_lock_error(&e) {
+                    outcome.locked_paths.push(entry_path.to_path_buf());
+                }
             }
-            Err(e) => return Err(format!("{}", e)),
+            return;
         }
 
-        // 尝试移除所有文件的保护属性后重试
-        self.remove_all_protection_attributes(path);
+        outcome.visited_files += 1;
+        tracker.advance(1);
 
-        // ====================================================================
-        // ⚠️ 警告：此操作将永久删除磁盘数据，不可恢复！
-        // ====================================================================
-        fs::remove_dir_all(path).map_err(|e| format!("{}", e))
+        // 逐项删除时跳过聊天数据库等用户数据文件，所在目录会因非空而保留
+        if is_protected_data_path(entry_path) {
+            outcome.record_failed(
+                &key,
+                format!("{}: 用户数据文件，已跳过", entry_path.display()),
+            );
+            return;
+        }
+
+        match self.remove_with_attribute_retry(entry_path, false) {
+            Ok(()) => outcome.record_deleted(&key, metadata.len()),
+            Err(e) => {
+                if is_lock_error(&e) {
+                    outcome.locked_paths.push(entry_path.to_path_buf());
+                }
+                outcome.record_failed(&key, format!("{}: {}", entry_path.display(), e));
+            }
+        }
     }
 
-    /// 移除目录下所有文件的保护属性
-    fn remove_all_protection_attributes(&self, path: &Path) {
-        for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
-            let entry_path = entry.path().to_string_lossy().to_string();
-            #[cfg(windows)]
-            let _ = windows_api::remove_protection_attributes(&entry_path);
+    /// 删除单个文件或空目录，权限不足时移除保护属性后重试一次
+    fn remove_with_attribute_retry(&self, path: &Path, is_dir: bool) -> std::io::Result<()> {
+        let remove = |target: &Path| {
+            if is_dir {
+                fs::remove_dir(target)
+            } else {
+                fs::remove_file(target)
+            }
+        };
+
+        let target = extended_path(path);
+        match remove(&target) {
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                debug!("权限不足，尝试移除保护属性后重试: {:?}", path);
+                #[cfg(windows)]
+                let _ = windows_api::remove_protection_attributes(&path.to_string_lossy());
+                remove(&target)
+            }
+            other => other,
         }
     }
 
-    /// 标记目录为重启后删除
+    /// 将被占用而删除失败的条目标记为重启后删除
     ///
-    /// MoveFileExW + MOVEFILE_DELAY_UNTIL_REBOOT 要求目录在其所有内容
-    /// 被删除之后才能被删除。因此标记顺序必须是：最深子目录 → 文件 → 顶层目录。
-    fn mark_for_reboot_delete(&self, path: &Path) -> bool {
+    /// 只排队确实因占用失败的条目，绝不遍历整个目录：跳过的用户数据文件
+    /// 和因其他原因失败的条目都不会进入 PendingFileRenameOperations。
+    /// MoveFileExW + MOVEFILE_DELAY_UNTIL_REBOOT 要求目录在其内容之后删除，
+    /// 因此按路径深度从深到浅标记。
+    fn mark_for_reboot_delete(&self, locked_paths: &[PathBuf]) -> bool {
+        #[cfg_attr(not(windows), allow(unused_mut))]
         let mut any_marked = false;
 
-        // 收集所有条目并按深度从深到浅排序（目录优先于同深度文件）
-        let mut entries: Vec<(usize, std::path::PathBuf, bool)> = WalkDir::new(path)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .map(|e| (e.depth(), e.path().to_path_buf(), e.file_type().is_dir()))
+        let mut entries: Vec<&PathBuf> = locked_paths
+            .iter()
+            .filter(|entry_path| !is_protected_data_path(entry_path))
             .collect();
-        entries.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.2.cmp(&a.2)));
+        entries.sort_by_key(|entry_path| std::cmp::Reverse(entry_path.components().count()));
 
-        for (_depth, entry_path, _is_dir) in &entries {
-            let path_str = entry_path.to_string_lossy().to_string();
-            #[cfg(windows)]
+        #[cfg(windows)]
+        for entry_path in entries {
+            let path_str = extended_path(entry_path).to_string_lossy().to_string();
             if windows_api::mark_for_delete_on_reboot(&path_str).is_ok() {
                 any_marked = true;
             }
         }
+        #[cfg(not(windows))]
+        let _ = entries;
 
         any_marked
     }
-
-    /// 计算目录大小
-    fn calculate_dir_size(&self, path: &Path) -> (u64, u32) {
-        let mut total_size = 0u64;
-        let mut file_count = 0u32;
-
-        for entry in WalkDir::new(path)
-            .max_depth(20)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            if entry.file_type().is_file() {
-                if let Ok(metadata) = entry.metadata() {
-                    total_size += metadata.len();
-                    file_count += 1;
-                }
-            }
-        }
-
-        (total_size, file_count)
-    }
 }
 
 impl Default for PermanentDeleteEngine {
@@ -586,6 +891,38 @@ impl Default for PermanentDeleteEngine {
     }
 }
 
+/// 判断删除失败是否由共享冲突或锁定冲突（即文件被占用）引起
+fn is_lock_error(e: &std::io::Error) -> bool {
+    matches!(
+        e.raw_os_error()
+            .and_then(DeleteFailureReason::from_os_error),
+        Some(DeleteFailureReason::FileLocked)
+    )
+}
+
+/// 统计目录下的文件数（不进入重解析点目录），与逐文件删除的计数口径一致
+fn count_files(path: &Path) -> u64 {
+    WalkDir::new(extended_path(path))
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !is_reparse_point_dir(entry))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .count() as u64
+}
+
+/// 根据条目所在目录计算其所属的一级子目录；顶层目录的直属文件归入顶层目录自身
+fn subfolder_key(root: &Path, containing_dir: &Path) -> PathBuf {
+    match containing_dir
+        .strip_prefix(root)
+        .ok()
+        .and_then(|relative| relative.components().next())
+    {
+        Some(first) => root.join(first),
+        None => root.to_path_buf(),
+    }
+}
+
 // ============================================================================
 // 单元测试
 // ============================================================================
@@ -613,6 +950,64 @@ mod tests {
             .is_none());
     }
 
+    #[test]
+    fn test_bottom_up_delete_reports_subfolders() {
+        let root = std::env::temp_dir().join(format!(
+            "lightc-permanent-delete-test-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("cache").join("nested")).unwrap();
+        fs::write(root.join("top.txt"), b"1234").unwrap();
+        fs::write(root.join("cache").join("a.bin"), b"12").unwrap();
+        fs::write(root.join("cache").join("nested").join("b.bin"), b"123").unwrap();
+
+        let engine = PermanentDeleteEngine::new();
+        let total = count_files(&root);
+        let events = std::sync::Mutex::new(Vec::new());
+        let on_progress = |progress: PermanentDeleteProgress| {
+            events.lock().unwrap().push(progress.percent);
+        };
+        let tracker = DeleteProgressTracker {
+            done: AtomicU64::new(0),
+            total,
+            on_progress: &on_progress,
+        };
+        let outcome = engine.delete_bottom_up(&root, &tracker);
+
+        // 逐文件删除必须返回真实数量，并按一级子目录汇总
+        assert!(!root.exists());
+        assert_eq!(outcome.deleted_files, 3);
+        assert_eq!(outcome.freed_size, 9);
+        assert_eq!(outcome.failed_files, 0);
+        let cache_stat = &outcome.subfolders[&root.join("cache")];
+        assert_eq!(cache_stat.deleted_files, 2);
+        assert_eq!(cache_stat.freed_size, 5);
+        assert_eq!(outcome.subfolders[&root].deleted_files, 1);
+        assert_eq!(total, 3);
+        assert_eq!(outcome.visited_files, 3);
+        assert_eq!(events.lock().unwrap().last(), Some(&100));
+        assert!(outcome.locked_paths.is_empty());
+    }
+
+    #[test]
+    fn test_safety_check_rejects_tree_with_protected_data() {
+        let root = std::env::temp_dir().join(format!(
+            "lightc-permanent-delete-protected-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("Msg")).unwrap();
+        fs::write(root.join("Msg").join("MicroMsg.db"), b"data").unwrap();
+        fs::write(root.join("cache.tmp"), b"tmp").unwrap();
+
+        // 只检查顶层路径会放行，目录树中的聊天数据库必须让整个目录被拒绝
+        let engine = PermanentDeleteEngine::new();
+        let result = engine.perform_safety_checks(&root);
+        let _ = fs::remove_dir_all(&root);
+        assert!(matches!(result, SafetyCheckResult::InProtectedPath { .. }));
+    }
+
     #[test]
     fn test_executable_extensions() {
         assert!(EXECUTABLE_EXTENSIONS.contains(&"exe"));
@@ -0,0 +1,32 @@
+// ============================================================================
+// 删除失败时回退到重启后删除
+//
+// Defender 扫描缓存、CBS 归档等系统组件文件经常被服务进程占用，
+// 这些专属清理项共用"先直接删除，失败再标记为重启后删除"的流程。
+// ============================================================================
+
+use std::fs;
+use std::path::PathBuf;
+
+use super::enhanced_delete::windows_api::mark_for_delete_on_reboot;
+use crate::scanner::DeleteResult;
+
+/// 逐个删除文件，已不存在的计为跳过，删除失败的标记为重启后删除
+pub(crate) fn delete_with_reboot_fallback(files: Vec<(PathBuf, u64)>) -> DeleteResult {
+    let mut result = DeleteResult::new();
+
+    for (path, size) in files {
+        match fs::remove_file(&path) {
+            Ok(()) => result.add_success(size),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => result.add_skipped_notfound(),
+            Err(e) => match mark_for_delete_on_reboot(&path.to_string_lossy()) {
+                Ok(()) => result.add_reboot_pending(size),
+                Err(reboot_err) => result.add_failure(
+                    path.to_string_lossy().into_owned(),
+                    format!("{}；{}", e, reboot_err),
+                ),
+            },
+        }
+    }
+    result
+}
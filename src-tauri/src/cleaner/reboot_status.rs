@@ -0,0 +1,142 @@
+// ============================================================================
+// 重启提醒状态
+//
+// 清理过程中被标记为重启删除的文件需要用户重启后才真正释放空间。
+// 这里把待重启数量和大小持久化到数据目录，程序下次启动时仍能提醒用户；
+// 系统的 PendingFileRenameOperations 中已没有删除项时自动清除提醒。
+// ============================================================================
+
+use std::fs;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+/// 重启提醒状态文件名
+const REBOOT_STATUS_FILE: &str = "reboot_pending.json";
+
+/// 多个删除任务可能同时完成，串行化读改写避免计数互相覆盖。
+static REBOOT_STATUS_LOCK: Mutex<()> = Mutex::new(());
+
+/// 待重启完成的清理状态
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RebootStatus {
+    /// 是否需要重启完成清理
+    pub needs_reboot: bool,
+    /// 累计标记为重启删除的文件数
+    pub pending_file_count: usize,
+    /// 重启后预计释放的空间（字节，无法估算的项目按 0 计）
+    pub pending_size: u64,
+    /// 首次产生待重启项目的时间（RFC 3339）
+    pub queued_since: Option<String>,
+}
+
+impl RebootStatus {
+    /// 累加一次清理产生的待重启项目
+    fn accumulate(&mut self, count: usize, size: u64) {
+        if count == 0 {
+            return;
+        }
+        self.needs_reboot = true;
+        self.pending_file_count += count;
+        self.pending_size += size;
+        if self.queued_since.is_none() {
+            self.queued_since = Some(chrono::Local::now().to_rfc3339());
+        }
+    }
+}
+
+/// 记录一次清理产生的待重启项目；写入失败只记录日志，不影响删除结果
+pub fn record_reboot_pending(count: usize, size: u64) {
+    if count == 0 {
+        return;
+    }
+
+    let _guard = REBOOT_STATUS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut status = load_status();
+    status.accumulate(count, size);
+    save_status(&status);
+}
+
+/// 读取重启提醒状态
+///
+/// # 中文说明
+/// 用户重启后系统会清空 PendingFileRenameOperations 中的删除项，此时清除本地提醒；
+/// 读取注册表失败时保留提醒，宁可多提示一次也不丢失状态。
+pub fn get_reboot_pending_status() -> RebootStatus {
+    let _guard = REBOOT_STATUS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let status = load_status();
+    if !status.needs_reboot {
+        return status;
+    }
+
+    match super::pending_reboot::get_pending_reboot_deletions() {
+        Ok(pending) if pending.is_empty() => {
+            clear_status();
+            RebootStatus::default()
+        }
+        Ok(_) => status,
+        Err(e) => {
+            log::warn!("读取重启删除队列失败，保留重启提醒: {}", e);
+            status
+        }
+    }
+}
+
+fn load_status() -> RebootStatus {
+    let path = crate::data_dir::get_data_dir().join(REBOOT_STATUS_FILE);
+    match fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_default(),
+        Err(_) => RebootStatus::default(),
+    }
+}
+
+fn save_status(status: &RebootStatus) {
+    let dir = crate::data_dir::get_data_dir();
+    if let Err(e) = fs::create_dir_all(&dir) {
+        log::warn!("无法创建数据目录 {}: {}", dir.display(), e);
+        return;
+    }
+    let path = dir.join(REBOOT_STATUS_FILE);
+    let json = serde_json::to_string_pretty(status).unwrap_or_default();
+    // 先写临时文件再替换，写入中途崩溃不会留下半截的状态文件
+    let temp_path = path.with_extension("json.tmp");
+    if let Err(e) = fs::write(&temp_path, &json) {
+        log::warn!("无法写入重启提醒状态 {}: {}", temp_path.display(), e);
+        let _ = fs::remove_file(&temp_path);
+        return;
+    }
+    if let Err(e) = fs::rename(&temp_path, &path) {
+        log::warn!("无法保存重启提醒状态 {}: {}", path.display(), e);
+        let _ = fs::remove_file(&temp_path);
+    }
+}
+
+fn clear_status() {
+    let path = crate::data_dir::get_data_dir().join(REBOOT_STATUS_FILE);
+    if let Err(e) = fs::remove_file(&path) {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            log::warn!("无法清除重启提醒状态 {}: {}", path.display(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accumulate_keeps_first_queue_time() {
+        let mut status = RebootStatus::default();
+        status.accumulate(0, 100);
+        assert_eq!(status, RebootStatus::default());
+
+        status.accumulate(2, 1024);
+        let queued_since = status.queued_since.clone();
+        status.accumulate(3, 2048);
+
+        assert!(status.needs_reboot);
+        assert_eq!(status.pending_file_count, 5);
+        assert_eq!(status.pending_size, 3072);
+        assert_eq!(status.queued_since, queued_since);
+    }
+}
@@ -0,0 +1,348 @@
+// ============================================================================
+// 目录迁移 + 目录联接
+//
+// 常见的"把 AppData 挪出 C 盘"做法：把体积很大的缓存目录整体移动到其他磁盘，
+// 再在原位置创建目录联接（挂载点类型的重解析点）指向新位置，应用仍按原路径读写。
+//
+// 跨卷迁移先把源目录在同一卷上改名为临时名称，既能在复制前发现被占用的目录，
+// 也保证复制期间应用不会继续往原路径写入而丢失数据。复制和创建联接都成功后
+// 才删除改名后的原目录；任何一步失败都会删除已复制的部分并把原目录改回原名。
+// 系统目录、驱动器根目录和用户配置文件根目录一律拒绝，避免系统或登录失败。
+// ============================================================================
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::safety_constants::{is_reparse_point, PROTECTED_PATH_PREFIXES};
+
+/// 将目录移动到 `dest`，并在原位置创建指向新位置的目录联接
+pub fn relocate_folder_with_junction(src: &str, dest: &str) -> Result<(), String> {
+    let src_path = Path::new(src.trim_end_matches(['\\', '/']));
+    let dest_path = Path::new(dest.trim_end_matches(['\\', '/']));
+    validate_relocation(src_path, dest_path)?;
+
+    log::info!(
+        "迁移目录: {} -> {}",
+        src_path.display(),
+        dest_path.display()
+    );
+
+    // 同卷直接重命名，联接失败时再重命名回来即可
+    if fs::rename(src_path, dest_path).is_ok() {
+        if let Err(e) = create_junction(src_path, dest_path) {
+            log::warn!("创建目录联接失败，正在移回原位置: {}", e);
+            return match fs::rename(dest_path, src_path) {
+                Ok(()) => Err(format!("创建目录联接失败，目录已移回原位置: {}", e)),
+                Err(rollback) => Err(format!(
+                    "创建目录联接失败（{}），且移回原位置失败（{}），文件现位于 {}",
+                    e,
+                    rollback,
+                    dest_path.display()
+                )),
+            };
+        }
+    } else {
+        move_across_volumes(src_path, dest_path)?;
+    }
+    log::info!("目录联接已创建: {}", src_path.display());
+    Ok(())
+}
+
+/// 跨卷迁移：改名 → 复制 → 创建联接 → 删除改名后的原目录
+fn move_across_volumes(src: &Path, dest: &Path) -> Result<(), String> {
+    let staged = staging_path(src);
+    fs::rename(src, &staged).map_err(|e| {
+        format!(
+            "无法锁定源目录（可能有程序正在使用其中的文件）: {}: {}",
+            src.display(),
+            e
+        )
+    })?;
+
+    let result = copy_tree(&staged, dest)
+        .and_then(|()| create_junction(src, dest).map_err(|e| format!("创建目录联接失败: {}", e)));
+    if let Err(e) = result {
+        log::warn!("跨卷迁移失败，正在恢复原目录: {}", e);
+        let _ = fs::remove_dir_all(dest);
+        return match fs::rename(&staged, src) {
+            Ok(()) => Err(format!("{}，原目录已恢复", e)),
+            Err(rollback) => Err(format!(
+                "{}，且恢复原目录失败（{}），文件现位于 {}",
+                e,
+                rollback,
+                staged.display()
+            )),
+        };
+    }
+
+    // 联接已经生效，残留的原目录只占空间，不影响应用
+    if let Err(e) = fs::remove_dir_all(&staged) {
+        log::warn!(
+            "迁移已完成，但删除原目录 {} 失败（可手动删除）: {}",
+            staged.display(),
+            e
+        );
+    }
+    Ok(())
+}
+
+/// 与源目录同级的临时名称，改名不跨卷，总是瞬间完成
+fn staging_path(src: &Path) -> PathBuf {
+    let name = src
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    src.with_file_name(format!("{}.lightc-relocating-{}", name, std::process::id()))
+}
+
+fn validate_relocation(src: &Path, dest: &Path) -> Result<(), String> {
+    if !src.is_absolute() || !dest.is_absolute() {
+        return Err("源目录和目标目录都必须是绝对路径".to_string());
+    }
+    let metadata =
+        fs::symlink_metadata(src).map_err(|e| format!("无法读取目录 {}: {}", src.display(), e))?;
+    if !metadata.is_dir() {
+        return Err(format!("只支持迁移目录: {}", src.display()));
+    }
+    if is_reparse_point(&metadata) {
+        return Err(format!("目录已经是联接或符号链接: {}", src.display()));
+    }
+    if is_protected_relocation_source(src) {
+        return Err(format!("不允许迁移系统目录: {}", src.display()));
+    }
+
+    if dest.exists() {
+        return Err(format!("目标位置已存在: {}", dest.display()));
+    }
+    if !dest.parent().is_some_and(Path::is_dir) {
+        return Err(format!("目标目录的上级目录不存在: {}", dest.display()));
+    }
+    let src_lower = src.to_string_lossy().to_lowercase();
+    let dest_lower = dest.to_string_lossy().to_lowercase();
+    if dest_lower.starts_with(&format!("{}\\", src_lower))
+        || dest_lower.starts_with(&format!("{}/", src_lower))
+    {
+        return Err("目标目录不能位于源目录内部".to_string());
+    }
+    Ok(())
+}
+
+/// 驱动器根目录、一级目录（C:\Users、C:\ProgramData）、用户配置文件根目录和系统目录不可迁移
+fn is_protected_relocation_source(path: &Path) -> bool {
+    let named_components = path
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .count();
+    if named_components < 2 {
+        return true;
+    }
+
+    let path_lower = path.to_string_lossy().to_lowercase();
+    if PROTECTED_PATH_PREFIXES
+        .iter()
+        .any(|prefix| path_lower.starts_with(prefix))
+    {
+        return true;
+    }
+    if super::compress::is_system_file(path) {
+        return true;
+    }
+    std::env::var("USERPROFILE")
+        .is_ok_and(|profile| profile.trim_end_matches('\\').to_lowercase() == path_lower)
+}
+
+fn copy_tree(src: &Path, dest: &Path) -> Result<(), String> {
+    for entry in WalkDir::new(src).follow_links(false) {
+        let entry = entry.map_err(|e| format!("读取目录失败: {}", e))?;
+        let relative = entry
+            .path()
+            .strip_prefix(src)
+            .map_err(|e| format!("计算相对路径失败: {}", e))?;
+        let target = dest.join(relative);
+        let metadata = entry
+            .metadata()
+            .map_err(|e| format!("读取 {} 失败: {}", entry.path().display(), e))?;
+
+        // 内部的联接和符号链接复制后会变成真实目录或失效，直接放弃迁移
+        if is_reparse_point(&metadata) || entry.path_is_symlink() {
+            return Err(format!(
+                "目录中包含联接或符号链接，无法安全迁移: {}",
+                entry.path().display()
+            ));
+        }
+        if metadata.is_dir() {
+            fs::create_dir_all(&target)
+                .map_err(|e| format!("创建目录 {} 失败: {}", target.display(), e))?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .map_err(|e| format!("复制 {} 失败: {}", entry.path().display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// IO_REPARSE_TAG_MOUNT_POINT：目录联接使用的重解析点类型
+const IO_REPARSE_TAG_MOUNT_POINT: u32 = 0xA000_0003;
+
+/// 重解析数据的上限（MAXIMUM_REPARSE_DATA_BUFFER_SIZE）
+const MAXIMUM_REPARSE_DATA_BUFFER_SIZE: usize = 16 * 1024;
+
+/// 构造挂载点类型的 REPARSE_DATA_BUFFER
+///
+/// 替换名称为 NT 路径 `\??\D:\...`，显示名称为普通路径，两者都以 NUL 结尾。
+fn mount_point_reparse_buffer(target: &str) -> Result<Vec<u8>, String> {
+    let print_name: Vec<u16> = target.encode_utf16().collect();
+    let substitute_name: Vec<u16> = format!("\\??\\{}", target).encode_utf16().collect();
+    let substitute_bytes = substitute_name.len() * 2;
+    let print_bytes = print_name.len() * 2;
+    // 4 个 u16 偏移/长度字段 + 两个名称 + 两个 NUL
+    let data_length = 8 + substitute_bytes + 2 + print_bytes + 2;
+    if 8 + data_length > MAXIMUM_REPARSE_DATA_BUFFER_SIZE {
+        return Err(format!("目标路径过长，无法创建目录联接: {}", target));
+    }
+
+    let mut buffer = Vec::with_capacity(8 + data_length);
+    buffer.extend_from_slice(&IO_REPARSE_TAG_MOUNT_POINT.to_le_bytes());
+    buffer.extend_from_slice(&(data_length as u16).to_le_bytes());
+    buffer.extend_from_slice(&0u16.to_le_bytes()); // Reserved
+    buffer.extend_from_slice(&0u16.to_le_bytes()); // SubstituteNameOffset
+    buffer.extend_from_slice(&(substitute_bytes as u16).to_le_bytes());
+    buffer.extend_from_slice(&((substitute_bytes + 2) as u16).to_le_bytes()); // PrintNameOffset
+    buffer.extend_from_slice(&(print_bytes as u16).to_le_bytes());
+    for unit in substitute_name
+        .iter()
+        .chain(&[0])
+        .chain(&print_name)
+        .chain(&[0])
+    {
+        buffer.extend_from_slice(&unit.to_le_bytes());
+    }
+    Ok(buffer)
+}
+
+/// 在 `link` 处创建指向 `target` 的目录联接
+///
+/// 直接通过 FSCTL_SET_REPARSE_POINT 写入重解析点，不经过 `cmd /C mklink`，
+/// 路径中的空格、`&`、`^` 等字符不会被命令行解释。
+#[cfg(target_os = "windows")]
+fn create_junction(link: &Path, target: &Path) -> Result<(), String> {
+    use winapi::shared::minwindef::{DWORD, LPVOID};
+    use winapi::um::errhandlingapi::GetLastError;
+    use winapi::um::fileapi::{CreateFileW, OPEN_EXISTING};
+    use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+    use winapi::um::ioapiset::DeviceIoControl;
+    use winapi::um::winbase::{FILE_FLAG_BACKUP_SEMANTICS, FILE_FLAG_OPEN_REPARSE_POINT};
+    use winapi::um::winnt::GENERIC_WRITE;
+
+    const FSCTL_SET_REPARSE_POINT: DWORD = 0x0009_00A4;
+
+    let mut buffer = mount_point_reparse_buffer(&target.to_string_lossy())?;
+    fs::create_dir(link).map_err(|e| format!("创建联接目录 {} 失败: {}", link.display(), e))?;
+
+    let wide = super::enhanced_delete::windows_api::to_wide_string(&link.to_string_lossy());
+    let handle = unsafe {
+        CreateFileW(
+            wide.as_ptr(),
+            GENERIC_WRITE,
+            0,
+            std::ptr::null_mut(),
+            OPEN_EXISTING,
+            FILE_FLAG_OPEN_REPARSE_POINT | FILE_FLAG_BACKUP_SEMANTICS,
+            std::ptr::null_mut(),
+        )
+    };
+    if handle == INVALID_HANDLE_VALUE {
+        let err = unsafe { GetLastError() };
+        let _ = fs::remove_dir(link);
+        return Err(format!(
+            "无法打开联接目录 {} (错误码: {})",
+            link.display(),
+            err
+        ));
+    }
+
+    let mut returned: DWORD = 0;
+    let ok = unsafe {
+        DeviceIoControl(
+            handle,
+            FSCTL_SET_REPARSE_POINT,
+            buffer.as_mut_ptr() as LPVOID,
+            buffer.len() as DWORD,
+            std::ptr::null_mut(),
+            0,
+            &mut returned,
+            std::ptr::null_mut(),
+        )
+    };
+    let err = unsafe { GetLastError() };
+    unsafe { CloseHandle(handle) };
+
+    if ok == 0 {
+        let _ = fs::remove_dir(link);
+        return Err(format!(
+            "写入目录联接失败: {} (错误码: {})",
+            link.display(),
+            err
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn create_junction(_link: &Path, _target: &Path) -> Result<(), String> {
+    Err("此功能仅支持Windows系统".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relocation_rejects_roots_and_nested_targets() {
+        let root = std::env::temp_dir().join(format!("lightc-relocate-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let src = root.join("cache");
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::write(src.join("sub").join("a.bin"), b"abc").unwrap();
+
+        assert!(validate_relocation(&src, &root.join("moved")).is_ok());
+        assert!(validate_relocation(&src, &src.join("inner")).is_err());
+        assert!(validate_relocation(&src, &root.join("missing").join("moved")).is_err());
+        assert!(is_protected_relocation_source(Path::new("C:\\")));
+        assert!(is_protected_relocation_source(Path::new(
+            "C:\\Program Files\\App"
+        )));
+
+        let copied = root.join("copied");
+        copy_tree(&src, &copied).unwrap();
+        let content = fs::read(copied.join("sub").join("a.bin")).unwrap();
+        let _ = fs::remove_dir_all(&root);
+        assert_eq!(content, b"abc");
+    }
+
+    #[test]
+    fn test_mount_point_buffer_layout() {
+        let buffer = mount_point_reparse_buffer("D:\\A B").unwrap();
+        let u16_at = |offset: usize| u16::from_le_bytes([buffer[offset], buffer[offset + 1]]);
+        // `\??\D:\A B` 共 10 个字符，`D:\A B` 共 6 个字符
+        assert_eq!(&buffer[..4], &IO_REPARSE_TAG_MOUNT_POINT.to_le_bytes());
+        assert_eq!(u16_at(4) as usize, buffer.len() - 8);
+        assert_eq!(u16_at(8), 0);
+        assert_eq!(u16_at(10), 20);
+        assert_eq!(u16_at(12), 22);
+        assert_eq!(u16_at(14), 12);
+        assert_eq!(buffer.len(), 16 + 20 + 2 + 12 + 2);
+
+        let too_long = "D:\\".to_string() + &"a".repeat(MAXIMUM_REPARSE_DATA_BUFFER_SIZE);
+        assert!(mount_point_reparse_buffer(&too_long).is_err());
+    }
+
+    #[test]
+    fn test_staging_path_is_sibling() {
+        let src = std::env::temp_dir().join("cache");
+        let staged = staging_path(&src);
+        assert_eq!(staged.parent(), src.parent());
+        assert_ne!(staged, src);
+    }
+}
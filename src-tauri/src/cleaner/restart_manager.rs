@@ -0,0 +1,264 @@
+// ============================================================================
+// 重启管理器（Restart Manager）
+//
+// 文件被占用时只提示"文件正在使用"，用户无从下手。Windows 重启管理器
+// （RmStartSession / RmRegisterResources / RmGetList）可以查询哪些进程持有文件句柄，
+// 这里把它封装成按路径查询占用进程的接口，供删除诊断和删除结果展示使用。
+// ============================================================================
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::safety_constants::is_reparse_point_dir;
+
+/// 占用文件的进程
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockingProcess {
+    /// 进程 ID
+    pub pid: u32,
+    /// 应用名称（重启管理器提供的显示名称，如 "Google Chrome"）
+    pub app_name: String,
+    /// 服务短名称（占用者是服务时非空）
+    pub service_name: Option<String>,
+    /// 是否可以被重启管理器关闭后重新启动
+    pub restartable: bool,
+}
+
+/// 查询占用指定文件的进程
+///
+/// # 中文说明
+/// 重启管理器只接受文件路径，目录需要由调用方展开为其中的文件。
+/// 一次会话最多注册一批文件，返回的进程按 PID 去重。
+#[cfg(windows)]
+pub fn find_locking_processes(paths: &[&Path]) -> Result<Vec<LockingProcess>, String> {
+    let session = RmSession::start()?;
+    session.register(paths)?;
+    session.list()
+}
+
+#[cfg(not(windows))]
+pub fn find_locking_processes(_paths: &[&Path]) -> Result<Vec<LockingProcess>, String> {
+    Ok(Vec::new())
+}
+
+/// 请求占用指定文件的应用关闭，返回被关闭的进程
+///
+/// # 中文说明
+/// 只发送正常关闭请求（不强制结束），应用可以拒绝，未保存的数据由应用自行提示。
+/// 占用者包含系统服务时不处理，避免为清理缓存停掉服务。
+#[cfg(windows)]
+pub fn close_locking_processes(paths: &[&Path]) -> Result<Vec<LockingProcess>, String> {
+    let session = RmSession::start()?;
+    session.register(paths)?;
+    let processes = session.list()?;
+    if processes.is_empty() {
+        return Ok(processes);
+    }
+    if let Some(service) = processes.iter().find(|p| p.service_name.is_some()) {
+        return Err(format!(
+            "文件被系统服务 {} 占用，不自动关闭",
+            service.app_name
+        ));
+    }
+    session.shutdown()?;
+    Ok(processes)
+}
+
+#[cfg(not(windows))]
+pub fn close_locking_processes(_paths: &[&Path]) -> Result<Vec<LockingProcess>, String> {
+    Ok(Vec::new())
+}
+
+/// 目录最多注册的文件数，重启管理器一次会话注册过多文件时查询会明显变慢
+const MAX_REGISTERED_FILES: usize = 1000;
+
+/// 展开需要注册到重启管理器的文件：文件返回自身，目录返回其中的文件（不进入重解析点）
+pub(crate) fn lock_query_files(path: &Path) -> Vec<PathBuf> {
+    if !path.is_dir() {
+        return vec![path.to_path_buf()];
+    }
+    walkdir::WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !is_reparse_point_dir(e))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .take(MAX_REGISTERED_FILES)
+        .map(|e| e.into_path())
+        .collect()
+}
+
+/// 格式化占用进程列表，如 "chrome.exe (1234)、Code (5678)"
+pub fn describe_locking_processes(processes: &[LockingProcess]) -> String {
+    processes
+        .iter()
+        .map(|process| format!("{} ({})", process.app_name, process.pid))
+        .collect::<Vec<_>>()
+        .join("、")
+}
+
+/// 重启管理器会话，离开作用域时自动结束
+#[cfg(windows)]
+pub(crate) struct RmSession {
+    handle: u32,
+}
+
+#[cfg(windows)]
+impl RmSession {
+    pub(crate) fn start() -> Result<Self, String> {
+        use winapi::um::restartmanager::{RmStartSession, CCH_RM_SESSION_KEY};
+
+        let mut handle = 0u32;
+        let mut session_key = [0u16; CCH_RM_SESSION_KEY + 1];
+        let code = unsafe { RmStartSession(&mut handle, 0, session_key.as_mut_ptr()) };
+        if code != 0 {
+            return Err(format!("启动重启管理器会话失败 (错误码 {})", code));
+        }
+        Ok(Self { handle })
+    }
+
+    pub(crate) fn register(&self, paths: &[&Path]) -> Result<(), String> {
+        use std::os::windows::ffi::OsStrExt;
+        use winapi::um::restartmanager::RmRegisterResources;
+
+        let wide_paths: Vec<Vec<u16>> = paths
+            .iter()
+            .map(|path| {
+                path.as_os_str()
+                    .encode_wide()
+                    .chain(std::iter::once(0))
+                    .collect()
+            })
+            .collect();
+        let mut pointers: Vec<*const u16> = wide_paths.iter().map(|path| path.as_ptr()).collect();
+
+        let code = unsafe {
+            RmRegisterResources(
+                self.handle,
+                pointers.len() as u32,
+                pointers.as_mut_ptr(),
+                0,
+                std::ptr::null_mut(),
+                0,
+                std::ptr::null_mut(),
+            )
+        };
+        if code != 0 {
+            return Err(format!("注册文件到重启管理器失败 (错误码 {})", code));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn list(&self) -> Result<Vec<LockingProcess>, String> {
+        use winapi::um::restartmanager::{RmGetList, RM_PROCESS_INFO};
+
+        const ERROR_MORE_DATA: u32 = 234;
+
+        let mut infos: Vec<RM_PROCESS_INFO> = Vec::new();
+        // 两次调用之间可能有新进程打开文件，ERROR_MORE_DATA 时按新数量重试
+        for _ in 0..3 {
+            let mut needed = 0u32;
+            let mut count = infos.len() as u32;
+            let mut reasons = 0u32;
+            let code = unsafe {
+                RmGetList(
+                    self.handle,
+                    &mut needed,
+                    &mut count,
+                    if infos.is_empty() {
+                        std::ptr::null_mut()
+                    } else {
+                        infos.as_mut_ptr()
+                    },
+                    &mut reasons,
+                )
+            };
+            if code == ERROR_MORE_DATA {
+                infos = vec![unsafe { std::mem::zeroed() }; needed as usize];
+                continue;
+            }
+            if code != 0 {
+                return Err(format!("查询占用进程失败 (错误码 {})", code));
+            }
+
+            let mut processes: Vec<LockingProcess> = infos[..count as usize]
+                .iter()
+                .map(|info| LockingProcess {
+                    pid: info.Process.dwProcessId,
+                    app_name: wide_to_string(&info.strAppName),
+                    service_name: Some(wide_to_string(&info.strServiceShortName))
+                        .filter(|name| !name.is_empty()),
+                    restartable: info.bRestartable != 0,
+                })
+                .collect();
+            processes.sort_by_key(|process| process.pid);
+            processes.dedup_by_key(|process| process.pid);
+            return Ok(processes);
+        }
+        Err("查询占用进程失败：占用进程列表持续变化".to_string())
+    }
+
+    pub(crate) fn shutdown(&self) -> Result<(), String> {
+        use winapi::um::restartmanager::RmShutdown;
+
+        // lActionFlags 为 0：只请求关闭，不强制结束无响应的应用
+        let code = unsafe { RmShutdown(self.handle, 0, None) };
+        if code != 0 {
+            return Err(format!("关闭占用进程失败 (错误码 {})", code));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+impl Drop for RmSession {
+    fn drop(&mut self) {
+        unsafe {
+            winapi::um::restartmanager::RmEndSession(self.handle);
+        }
+    }
+}
+
+#[cfg(windows)]
+fn wide_to_string(buffer: &[u16]) -> String {
+    let length = buffer
+        .iter()
+        .position(|&ch| ch == 0)
+        .unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..length])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_query_files_expands_directories() {
+        let dir =
+            std::env::temp_dir().join(format!("lightc-restart-manager-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("a.tmp"), b"a").unwrap();
+        std::fs::write(dir.join("sub").join("b.tmp"), b"b").unwrap();
+
+        let mut files = lock_query_files(&dir);
+        files.sort();
+        assert_eq!(
+            files,
+            vec![dir.join("a.tmp"), dir.join("sub").join("b.tmp")]
+        );
+        assert_eq!(
+            lock_query_files(&dir.join("a.tmp")),
+            vec![dir.join("a.tmp")]
+        );
+
+        let processes = vec![LockingProcess {
+            pid: 1234,
+            app_name: "chrome.exe".to_string(),
+            service_name: None,
+            restartable: true,
+        }];
+        assert_eq!(describe_locking_processes(&processes), "chrome.exe (1234)");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
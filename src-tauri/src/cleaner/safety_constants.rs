@@ -1,8 +1,10 @@
 // ============================================================================
-// 删除安全保护常量（delete_engine 和 enhanced_delete 共享）
-// 避免两个引擎各自维护不一致的保护列表
+// 删除安全保护常量（delete_engine、enhanced_delete 和 permanent_delete 共享）
+// 避免各个引擎各自维护不一致的保护列表
 // ============================================================================
 
+use std::path::{Path, PathBuf};
+
 /// 绝对禁止删除的路径前缀（小写，starts_with 匹配）
 pub const PROTECTED_PATH_PREFIXES: &[&str] = &[
     "c:\\windows\\system32",
@@ -48,7 +50,14 @@ pub const PROTECTED_FILES: &[&str] = &[
     "desktop.ini",
     "ntuser.dat",
     "usrclass.dat",
-    // 社交软件配置文件（防止误删导致数据丢失）
+];
+
+/// 绝对禁止删除的用户数据文件/目录名（小写，按路径组件精确匹配）
+///
+/// 社交软件缓存扫描结果可能与聊天数据库同处一个目录树，
+/// 任何删除引擎遇到这些名称都必须拒绝，包括删除包含它们的目录。
+pub const PROTECTED_DATA_FILES: &[&str] = &[
+    // 社交软件配置文件和数据库（防止误删导致数据丢失）
     "config.data",
     "accinfo.dat",
     "msg.db",
@@ -57,16 +66,41 @@ pub const PROTECTED_FILES: &[&str] = &[
     "emotion.db",
     "favorite.db",
     "publicmsg.db",
+    "db_storage",
     "nt_db",
     "nt_config",
 ];
 
+/// 判断路径本身或其任一上级目录是否为受保护的用户数据文件
+pub fn is_protected_data_path(path: &Path) -> bool {
+    path.to_string_lossy()
+        .to_lowercase()
+        .split(['\\', '/'])
+        .any(|component| PROTECTED_DATA_FILES.contains(&component))
+}
+
+/// 在目录树中查找受保护的用户数据文件，返回第一个命中的路径
+///
+/// 目录整体删除（remove_dir_all）前调用；不进入重解析点目录，与删除范围保持一致。
+pub fn find_protected_data_file(dir: &Path) -> Option<PathBuf> {
+    walkdir::WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !is_reparse_point_dir(entry))
+        .filter_map(|entry| entry.ok())
+        .find(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            PROTECTED_DATA_FILES.contains(&name.as_str())
+        })
+        .map(|entry| entry.into_path())
+}
+
 /// 在 Windows 目录下禁止删除的扩展名（小写）
 pub const PROTECTED_EXTENSIONS_IN_WINDOWS: &[&str] = &[
     "sys", "dll", "exe", "drv", "ocx", "cpl", "msi", "msp", "msu", "cat", "mum", "manifest",
 ];
 
-/// 判断是否为 Windows 清理向导明确允许重建的系统缓存子目录。
+/// 判断是否为 Windows 清理向导明确允许重建的系统缓存子目录（以及 Steam 默认安装目录下的着色器缓存）。
 /// 这些目录位于受保护的系统根目录下，因此删除引擎必须只放行精确子路径，不能放宽整个父目录。
 pub fn is_rebuildable_system_cache_path(path: &str) -> bool {
     let normalized = path.replace('/', "\\").to_ascii_lowercase();
@@ -74,6 +108,11 @@ pub fn is_rebuildable_system_cache_path(path: &str) -> bool {
         "\\windows\\system32\\d3d_cache",
         "\\programdata\\microsoft\\windows defender\\localcopy",
         "\\programdata\\microsoft\\windows defender\\support",
+        "\\programdata\\microsoft\\windows\\wer\\reportqueue",
+        "\\programdata\\microsoft\\windows\\wer\\reportarchive",
+        "\\programdata\\microsoft\\windows\\wer\\temp",
+        "\\program files (x86)\\steam\\steamapps\\shadercache",
+        "\\program files\\steam\\steamapps\\shadercache",
     ]
     .iter()
     .any(|marker| {
@@ -87,9 +126,74 @@ pub fn is_rebuildable_system_cache_path(path: &str) -> bool {
     })
 }
 
+/// 判断路径是否位于任意驱动器的 `$Recycle.Bin` 下。
+/// 回收站条目只能通过 `SHEmptyRecycleBinW` 清空，逐文件删除需要 SYSTEM 权限且会留下 $I 元数据。
+pub fn is_recycle_bin_path(path: &str) -> bool {
+    let normalized = path.replace('/', "\\").to_ascii_lowercase();
+    let marker = ":\\$recycle.bin";
+    normalized.find(marker).is_some_and(|start| {
+        let suffix = &normalized[start + marker.len()..];
+        suffix.is_empty() || suffix.starts_with('\\')
+    })
+}
+
+/// 判断元数据是否表示重解析点（Junction、符号链接、卷挂载点等）
+///
+/// 必须传入不跟随链接的元数据（symlink_metadata / walkdir 在 follow_links(false) 下的元数据），
+/// 否则读到的是链接目标的属性。
+pub fn is_reparse_point(metadata: &std::fs::Metadata) -> bool {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
+        metadata.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT != 0
+    }
+    #[cfg(not(windows))]
+    {
+        metadata.file_type().is_symlink()
+    }
+}
+
+/// 判断元数据是否表示 OneDrive 等云同步的占位文件（仅云端、访问时才下载）
+///
+/// 读取这类文件的内容会触发下载，把正在清理的磁盘重新填满；扫描时应直接跳过，
+/// 它们也不占用本地空间。属性来自目录枚举，检查本身不会触发下载。
+pub fn is_cloud_placeholder(metadata: &std::fs::Metadata) -> bool {
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_OFFLINE: u32 = 0x1000;
+        const FILE_ATTRIBUTE_RECALL_ON_OPEN: u32 = 0x0004_0000;
+        const FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS: u32 = 0x0040_0000;
+        metadata.file_attributes()
+            & (FILE_ATTRIBUTE_OFFLINE
+                | FILE_ATTRIBUTE_RECALL_ON_OPEN
+                | FILE_ATTRIBUTE_RECALL_ON_DATA_ACCESS)
+            != 0
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = metadata;
+        false
+    }
+}
+
+/// 判断遍历条目是否为重解析点目录
+///
+/// AppData 中指向 C:\ 的 Junction 会让大小统计重复计数，甚至把删除带到系统目录，
+/// 统计和删除时都不能进入这类目录。
+pub fn is_reparse_point_dir(entry: &walkdir::DirEntry) -> bool {
+    (entry.file_type().is_dir() || entry.path_is_symlink())
+        && entry
+            .metadata()
+            .map(|metadata| is_reparse_point(&metadata))
+            .unwrap_or(false)
+}
+
 #[cfg(test)]
 mod tests {
-    use super::is_rebuildable_system_cache_path;
+    use super::{is_protected_data_path, is_rebuildable_system_cache_path};
+    use std::path::Path;
 
     #[test]
     fn matches_only_explicit_cache_directory_boundaries() {
@@ -106,5 +210,31 @@ mod tests {
         assert!(!is_rebuildable_system_cache_path(
             r"C:\Temp\ProgramData\Microsoft\Windows Defender\Support\entry.bin"
         ));
+        assert!(is_rebuildable_system_cache_path(
+            r"C:\ProgramData\Microsoft\Windows\WER\ReportQueue\AppCrash_x\Report.wer"
+        ));
+        assert!(!is_rebuildable_system_cache_path(
+            r"C:\ProgramData\Microsoft\Windows\WER\ERC\statecache.lock"
+        ));
+        assert!(is_rebuildable_system_cache_path(
+            r"C:\Program Files (x86)\Steam\steamapps\shadercache\730\fozpipelinesv6\a.foz"
+        ));
+        assert!(!is_rebuildable_system_cache_path(
+            r"C:\Program Files (x86)\Steam\steamapps\common\Game\game.exe"
+        ));
+    }
+
+    #[test]
+    fn protects_social_data_files_by_component() {
+        assert!(is_protected_data_path(Path::new(
+            r"C:\Users\Test\Documents\WeChat Files\wxid\Msg\MicroMsg.db"
+        )));
+        // QQ NT 的 nt_db 是目录，目录下的任何文件都受保护
+        assert!(is_protected_data_path(Path::new(
+            r"C:\Users\Test\Documents\Tencent Files\123\nt_qq\nt_db\files.db"
+        )));
+        assert!(!is_protected_data_path(Path::new(
+            r"C:\Users\Test\Documents\WeChat Files\wxid\FileStorage\MsgAttach\a.dat"
+        )));
     }
 }
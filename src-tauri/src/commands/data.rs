@@ -22,6 +22,27 @@ pub fn migrate_legacy_portable_data() -> Result<crate::data_dir::StorageLocation
     crate::data_dir::migrate_legacy_portable_data()
 }
 
+/// 在资源管理器中打开数据目录，便于用户查看或备份设置、白名单等数据文件
+#[tauri::command]
+pub fn open_app_data_folder() -> Result<(), String> {
+    let data_dir = crate::data_dir::get_data_dir();
+    std::fs::create_dir_all(&data_dir).map_err(|e| format!("创建数据目录失败: {}", e))?;
+
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("explorer")
+            .arg(&data_dir)
+            .spawn()
+            .map_err(|e| format!("打开文件夹失败: {}", e))?;
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("此功能仅支持 Windows 系统".to_string())
+    }
+}
+
 /// 设置数据目录并迁移数据
 #[tauri::command]
 pub fn set_data_directory(path: String) -> Result<String, String> {
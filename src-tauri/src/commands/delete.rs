@@ -2,13 +2,19 @@
 // 文件删除命令
 // ============================================================================
 
+use crate::cleaner::long_path::extended_path;
+use crate::cleaner::reboot_status::{self, RebootStatus};
 use crate::cleaner::{
-    DeleteEngine, EnhancedDeleteEngine, EnhancedDeleteProgress, EnhancedDeleteResult,
+    empty_recycle_bin_files, DeleteEngine, DeletePlan, DeleteProbe, EnhancedDeleteEngine,
+    EnhancedDeleteProgress, EnhancedDeleteResult, PathExplanation, PathStatus,
     PermanentDeleteEngine, PermanentDeleteResult, SafetyCheckResult,
 };
-use crate::scanner::{deep_junk, DeleteResult};
-use log::info;
-use serde::Deserialize;
+use crate::error::CleanError;
+use crate::logger::CleanupLogEntryInput;
+use crate::scanner::{deep_junk, CategoryScanResult, DeleteResult, JunkCategory, ScanEngine};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use tauri::{AppHandle, Emitter};
 
 /// 将删除进度发送给前端；事件失败不应中断实际删除任务。
@@ -26,6 +32,7 @@ fn emit_delete_preparing(app: &AppHandle, total_count: usize) {
             phase: "preparing".to_string(),
             processed_count: 0,
             total_count,
+            percent: 0,
             success_count: 0,
             failed_count: 0,
             reboot_pending_count: 0,
@@ -39,15 +46,31 @@ fn emit_delete_preparing(app: &AppHandle, total_count: usize) {
 #[derive(Debug, Deserialize)]
 pub struct DeleteRequest {
     pub paths: Vec<String>,
+    /// 删除后清理扫描根目录内留下的空目录，为空时使用设置中的 `prune_empty_dirs`
+    pub prune_empty_dirs: Option<bool>,
+}
+
+/// 将增强删除中标记为重启删除的文件计入重启提醒。
+pub(super) fn record_enhanced_reboot_pending(result: &EnhancedDeleteResult) {
+    let pending_size = result
+        .file_results
+        .iter()
+        .filter(|file| file.marked_for_reboot)
+        .map(|file| file.physical_size)
+        .sum();
+    reboot_status::record_reboot_pending(result.reboot_pending_count, pending_size);
 }
 
 /// 删除指定文件
 #[tauri::command]
-pub async fn delete_files(request: DeleteRequest) -> Result<DeleteResult, String> {
+pub async fn delete_files(request: DeleteRequest) -> Result<DeleteResult, CleanError> {
     info!("开始删除 {} 个文件", request.paths.len());
 
     let result = tokio::task::spawn_blocking(move || {
-        let engine = DeleteEngine::new();
+        let prune_empty_dirs = request
+            .prune_empty_dirs
+            .unwrap_or_else(|| crate::settings::get_settings().prune_empty_dirs);
+        let engine = DeleteEngine::new().with_prune_empty_dirs(prune_empty_dirs);
         engine.delete_paths(&request.paths)
     })
     .await
@@ -57,22 +80,549 @@ pub async fn delete_files(request: DeleteRequest) -> Result<DeleteResult, String
         "删除完成: 成功 {}, 失败 {}, 释放 {} 字节",
         result.success_count, result.failed_count, result.freed_size
     );
+    reboot_status::record_reboot_pending(result.reboot_pending_count, result.reboot_pending_size);
+
+    Ok(result)
+}
+
+/// 诊断单个路径为什么删不掉（不执行删除）
+#[tauri::command]
+pub async fn probe_delete(path: String) -> Result<DeleteProbe, CleanError> {
+    tokio::task::spawn_blocking(move || DeleteEngine::new().probe_delete(&path))
+        .await
+        .map_err(|e| CleanError::Other(format!("删除诊断任务异常: {}", e)))
+}
+
+/// 说明路径所属的垃圾分类、风险等级和建议的处理方式（不执行删除）
+#[tauri::command]
+pub async fn explain_path(path: String) -> Result<PathExplanation, CleanError> {
+    tokio::task::spawn_blocking(move || DeleteEngine::new().explain_path(&path))
+        .await
+        .map_err(|e| CleanError::Other(format!("路径说明任务异常: {}", e)))
+}
+
+/// 按目录树预览删除计划：每个目录汇总待删除的大小和数量，并标出受保护的路径（不执行删除）
+#[tauri::command]
+pub async fn build_delete_plan(paths: Vec<String>) -> Result<DeletePlan, CleanError> {
+    tokio::task::spawn_blocking(move || DeleteEngine::new().build_delete_plan(&paths))
+        .await
+        .map_err(|e| CleanError::Other(format!("删除计划任务异常: {}", e)))
+}
+
+/// 删除前校验已选路径是否仍存在并刷新大小（只读取元数据，不遍历目录）
+#[tauri::command]
+pub async fn validate_paths(paths: Vec<String>) -> Result<Vec<PathStatus>, CleanError> {
+    tokio::task::spawn_blocking(move || crate::cleaner::validate_paths(&paths))
+        .await
+        .map_err(|e| CleanError::Other(format!("路径校验任务异常: {}", e)))
+}
+
+/// 按分类在后端重新扫描并直接删除
+///
+/// 缓存类分类可能有十几万个文件，逐条路径在前端和后端之间往返会非常慢；
+/// 整类清理时只传分类名，路径不经过 IPC。需要挑选文件时仍使用 `delete_files`。
+#[tauri::command]
+pub async fn delete_scan_category(
+    category_name: String,
+    max_risk: Option<u8>,
+) -> Result<DeleteResult, CleanError> {
+    info!("按分类删除: {}", category_name);
+
+    let result = tokio::task::spawn_blocking(move || -> Result<DeleteResult, CleanError> {
+        let category = JunkCategory::all()
+            .into_iter()
+            .find(|c| c.display_name() == category_name)
+            .ok_or_else(|| CleanError::NotFound(format!("未知分类: {}", category_name)))?;
+        // 自定义路径分类没有固定扫描目录，只能按用户选择的路径删除
+        if category == JunkCategory::Custom {
+            return Err(CleanError::InvalidInput(
+                "自定义路径分类不支持整类删除，请选择具体文件".to_string(),
+            ));
+        }
+        if let Some(max_risk) = max_risk {
+            if category.risk_level() > max_risk {
+                return Err(CleanError::InvalidInput(format!(
+                    "分类「{}」风险等级为 {}，超过允许的 {}",
+                    category_name,
+                    category.risk_level(),
+                    max_risk
+                )));
+            }
+        }
+
+        let scan_result = ScanEngine::new().scan_category(&category);
+        info!(
+            "分类「{}」重新扫描到 {} 个文件",
+            category_name,
+            scan_result.files.len()
+        );
+        if category == JunkCategory::RecycleBin {
+            return Ok(empty_recycle_bin_files(&scan_result.files));
+        }
+        Ok(DeleteEngine::new().delete_files(&scan_result.files))
+    })
+    .await
+    .map_err(|e| format!("删除任务异常: {}", e))??;
+
+    info!(
+        "按分类删除完成: 成功 {}, 失败 {}, 释放 {} 字节",
+        result.success_count, result.failed_count, result.freed_size
+    );
+    reboot_status::record_reboot_pending(result.reboot_pending_count, result.reboot_pending_size);
+
+    Ok(result)
+}
+
+/// 清理指定 UWP 包的缓存目录内容（只删 LocalCache / TempState / AC\Temp 里的条目）
+#[tauri::command]
+pub async fn clean_uwp_caches(package_names: Vec<String>) -> Result<DeleteResult, CleanError> {
+    info!("清理 UWP 缓存: {:?}", package_names);
+
+    let result = tokio::task::spawn_blocking(move || -> Result<DeleteResult, CleanError> {
+        let paths =
+            crate::scanner::uwp_cache_paths(&package_names).map_err(CleanError::InvalidInput)?;
+        Ok(DeleteEngine::new().delete_paths(&paths))
+    })
+    .await
+    .map_err(|e| format!("删除任务异常: {}", e))??;
+
+    info!(
+        "UWP 缓存清理完成: 成功 {}, 失败 {}, 释放 {} 字节",
+        result.success_count, result.failed_count, result.freed_size
+    );
+    reboot_status::record_reboot_pending(result.reboot_pending_count, result.reboot_pending_size);
+
+    Ok(result)
+}
+
+/// 按历史清理会话重新删除同一批路径（问题目录反复产生垃圾时免去重新扫描）
+///
+/// 只处理仍然存在的路径，删除仍经过 `DeleteEngine` 的保护检查；
+/// 注册表残留等非文件记录在磁盘上不存在，自然被跳过。
+#[tauri::command]
+pub async fn reclean_session(filename: String) -> Result<DeleteResult, CleanError> {
+    info!("按清理会话重新清理: {}", filename);
+    let app_data_dir = crate::data_dir::get_data_dir();
+
+    let result = tokio::task::spawn_blocking(move || -> Result<DeleteResult, CleanError> {
+        let session = crate::logger::get_cleanup_session(&app_data_dir, &filename)?;
+        let mut seen = std::collections::HashSet::new();
+        let paths: Vec<String> = session
+            .entries
+            .into_iter()
+            .map(|entry| entry.path)
+            .filter(|path| seen.insert(path.to_lowercase()))
+            .filter(|path| {
+                std::fs::symlink_metadata(extended_path(std::path::Path::new(path))).is_ok()
+            })
+            .collect();
+        info!("会话中仍存在的路径: {} 个", paths.len());
+        Ok(DeleteEngine::new().delete_paths(&paths))
+    })
+    .await
+    .map_err(|e| format!("删除任务异常: {}", e))??;
+
+    info!(
+        "重新清理完成: 成功 {}, 失败 {}, 释放 {} 字节",
+        result.success_count, result.failed_count, result.freed_size
+    );
+    reboot_status::record_reboot_pending(result.reboot_pending_count, result.reboot_pending_size);
+
+    Ok(result)
+}
+
+/// 清理 Windows Defender 扫描历史缓存（需要管理员权限），隔离区和检测记录不受影响
+#[tauri::command]
+pub async fn clean_defender_cache() -> Result<DeleteResult, CleanError> {
+    info!("开始清理 Windows Defender 扫描历史缓存");
+    run_admin_system_clean(
+        "clean_defender_cache",
+        "Windows Defender 缓存",
+        crate::cleaner::clean_defender_cache,
+    )
+    .await
+}
+
+/// 清理 CBS 轮转归档日志（需要管理员权限），当前的 CBS.log 保留
+#[tauri::command]
+pub async fn clean_cbs_logs() -> Result<DeleteResult, CleanError> {
+    info!("开始清理 CBS 归档日志");
+    run_admin_system_clean("clean_cbs_logs", "CBS 日志", crate::cleaner::clean_cbs_logs).await
+}
+
+/// 需要管理员权限的系统组件清理：检查权限、在后台线程执行、记录审计日志和待重启删除量
+///
+/// `target` 用于权限不足时的提示，例如"CBS 日志"。
+async fn run_admin_system_clean<E>(
+    action: &str,
+    target: &str,
+    clean: fn() -> Result<DeleteResult, E>,
+) -> Result<DeleteResult, CleanError>
+where
+    E: Send + 'static,
+    CleanError: From<E>,
+{
+    if !crate::system_slim::check_admin() {
+        let denied: Result<DeleteResult, CleanError> = Err(CleanError::NeedsAdmin(format!(
+            "需要管理员权限才能清理 {}，请以管理员身份运行程序",
+            target
+        )));
+        crate::logger::record_audit(action, serde_json::json!({}), &denied);
+        return denied;
+    }
+
+    let result = tokio::task::spawn_blocking(clean)
+        .await
+        .map_err(|e| format!("删除任务异常: {}", e))?
+        .map_err(CleanError::from);
+    crate::logger::record_audit(action, serde_json::json!({}), &result);
+    let result = result?;
+    reboot_status::record_reboot_pending(result.reboot_pending_count, result.reboot_pending_size);
+
+    Ok(result)
+}
+
+/// 单个分类的一键清理结果
+#[derive(Debug, Serialize)]
+pub struct CategoryCleanSummary {
+    /// 分类显示名称
+    pub category: String,
+    /// 扫描到的文件数
+    pub file_count: usize,
+    /// 释放的空间（字节）；模拟运行时为可释放的空间
+    pub freed_size: u64,
+    /// 删除失败的文件数
+    pub failed_count: usize,
+}
+
+/// 一键清理结果
+#[derive(Debug, Serialize)]
+pub struct CleanSummary {
+    /// 是否只扫描不删除
+    pub dry_run: bool,
+    /// 各分类明细
+    pub categories: Vec<CategoryCleanSummary>,
+    /// 总释放空间（字节）；模拟运行时为可释放的空间
+    pub total_freed: u64,
+    /// 成功删除的文件数
+    pub success_count: usize,
+    /// 删除失败的文件数
+    pub failed_count: usize,
+    /// 标记为重启后删除的文件数
+    pub reboot_pending_count: usize,
+}
+
+impl CleanSummary {
+    pub(super) fn new(dry_run: bool) -> Self {
+        CleanSummary {
+            dry_run,
+            categories: Vec::new(),
+            total_freed: 0,
+            success_count: 0,
+            failed_count: 0,
+            reboot_pending_count: 0,
+        }
+    }
+}
+
+/// 扫描并清理指定分类（一次调用完成扫描、删除和记录日志）
+///
+/// 供脚本和定时清理使用，避免扫描、删除、写日志三次往返。每次调用都重新扫描，
+/// 重复调用只会删除新产生的文件。`dry_run` 为 true 时只扫描并返回可释放空间，不写日志。
+#[tauri::command]
+pub async fn clean_categories(
+    category_names: Vec<String>,
+    dry_run: bool,
+) -> Result<CleanSummary, CleanError> {
+    info!("一键清理: {:?} (模拟运行: {})", category_names, dry_run);
+    let categories = resolve_clean_categories(&category_names)?;
+    run_clean_categories(categories, dry_run).await
+}
+
+/// 最稳妥的一键清理：只清理风险等级 1 的分类并清空回收站
+///
+/// 面向不想逐项确认的用户，不接收也不返回路径列表。等级 1 的分类都是系统或程序
+/// 会自行重建的缓存，直接删除；移入回收站只会让刚清空的回收站重新变满。
+#[tauri::command]
+pub async fn safe_clean() -> Result<CleanSummary, CleanError> {
+    let categories = safe_clean_categories();
+    info!("安全清理: {:?}", categories);
+    run_clean_categories(categories, false).await
+}
+
+/// 安全清理包含的分类：风险等级 1 的分类加回收站
+fn safe_clean_categories() -> Vec<JunkCategory> {
+    JunkCategory::all()
+        .into_iter()
+        .filter(|category| category.risk_level() == 1 || *category == JunkCategory::RecycleBin)
+        .collect()
+}
+
+/// 紧急释放空间的清理顺序：先清空回收站和临时文件这类删除快、通常也最大的来源，
+/// 再处理更新下载和各类可重建缓存。都不需要用户逐项确认。
+const EMERGENCY_CLEAN_ORDER: &[JunkCategory] = &[
+    JunkCategory::RecycleBin,
+    JunkCategory::WindowsTemp,
+    JunkCategory::WindowsUpdate,
+    JunkCategory::DeliveryOptimization,
+    JunkCategory::ShaderCache,
+    JunkCategory::GameCache,
+    JunkCategory::ThumbnailCache,
+    JunkCategory::WindowsErrorReports,
+];
+
+/// 紧急释放空间结果
+#[derive(Debug, Serialize)]
+pub struct EmergencyFreeSpaceResult {
+    /// 目标释放量（字节）
+    pub target_bytes: u64,
+    /// 系统盘可用空间的实际增加量（字节）
+    pub freed_bytes: u64,
+    /// 结束时系统盘的可用空间（字节）
+    pub free_space: u64,
+    /// 是否达到目标
+    pub target_met: bool,
+    /// 实际执行的各分类清理明细
+    pub summary: CleanSummary,
+}
+
+/// 系统盘空间告急时的救急清理
+///
+/// 不做完整扫描（空间不足时扫描本身可能失败），按 `EMERGENCY_CLEAN_ORDER` 逐个分类清理，
+/// 每清理完一个分类就用 `GetDiskFreeSpaceExW` 重新查询可用空间，达到 `target_bytes` 后立即停止。
+/// 以实际可用空间为准，标记为重启后删除的文件不计入。
+#[tauri::command]
+pub async fn emergency_free_space(
+    target_bytes: u64,
+) -> Result<EmergencyFreeSpaceResult, CleanError> {
+    if target_bytes == 0 {
+        return Err(CleanError::InvalidInput(
+            "目标释放空间必须大于 0".to_string(),
+        ));
+    }
+    info!("紧急释放空间: 目标 {} 字节", target_bytes);
 
+    let (result, log_entries, reboot_pending_size) =
+        tokio::task::spawn_blocking(move || emergency_free_space_blocking(target_bytes))
+            .await
+            .map_err(|e| format!("清理任务异常: {}", e))??;
+    record_clean_results(&result.summary, log_entries, reboot_pending_size).await;
+
+    info!(
+        "紧急释放空间完成: 实际释放 {} 字节，当前可用 {} 字节，达到目标: {}",
+        result.freed_bytes, result.free_space, result.target_met
+    );
     Ok(result)
 }
 
+/// 按顺序逐个分类清理，直到可用空间增加量达到目标或分类用完
+fn emergency_free_space_blocking(
+    target_bytes: u64,
+) -> Result<(EmergencyFreeSpaceResult, Vec<CleanupLogEntryInput>, u64), String> {
+    let initial_free = super::system_drive_free_space()?;
+    let mut free_space = initial_free;
+    let mut summary = CleanSummary::new(false);
+    let mut log_entries = Vec::new();
+    let mut reboot_pending_size = 0;
+
+    for category in EMERGENCY_CLEAN_ORDER {
+        if free_space.saturating_sub(initial_free) >= target_bytes {
+            break;
+        }
+        let (step, entries, pending_size) =
+            clean_categories_blocking(std::slice::from_ref(category), false);
+        summary.total_freed += step.total_freed;
+        summary.success_count += step.success_count;
+        summary.failed_count += step.failed_count;
+        summary.reboot_pending_count += step.reboot_pending_count;
+        summary.categories.extend(step.categories);
+        log_entries.extend(entries);
+        reboot_pending_size += pending_size;
+
+        // 查询失败时按删除引擎报告的释放量估算，避免提前停止或无限继续
+        free_space = super::system_drive_free_space()
+            .unwrap_or_else(|_| initial_free.saturating_add(summary.total_freed));
+        info!(
+            "紧急释放空间: {} 完成，累计释放 {} 字节",
+            category.display_name(),
+            free_space.saturating_sub(initial_free)
+        );
+    }
+
+    let freed_bytes = free_space.saturating_sub(initial_free);
+    let result = EmergencyFreeSpaceResult {
+        target_bytes,
+        freed_bytes,
+        free_space,
+        target_met: freed_bytes >= target_bytes,
+        summary,
+    };
+    Ok((result, log_entries, reboot_pending_size))
+}
+
+/// 扫描并清理分类，删除后记录重启提醒和清理日志
+async fn run_clean_categories(
+    categories: Vec<JunkCategory>,
+    dry_run: bool,
+) -> Result<CleanSummary, CleanError> {
+    let (summary, log_entries, reboot_pending_size) =
+        tokio::task::spawn_blocking(move || clean_categories_blocking(&categories, dry_run))
+            .await
+            .map_err(|e| format!("清理任务异常: {}", e))?;
+
+    if !dry_run {
+        record_clean_results(&summary, log_entries, reboot_pending_size).await;
+    }
+
+    info!(
+        "一键清理完成: 成功 {}, 失败 {}, 释放 {} 字节",
+        summary.success_count, summary.failed_count, summary.total_freed
+    );
+    Ok(summary)
+}
+
+/// 记录重启后删除提醒和清理日志；日志写入失败不影响已完成的清理
+pub(super) async fn record_clean_results(
+    summary: &CleanSummary,
+    log_entries: Vec<CleanupLogEntryInput>,
+    reboot_pending_size: u64,
+) {
+    reboot_status::record_reboot_pending(summary.reboot_pending_count, reboot_pending_size);
+    if log_entries.is_empty() {
+        return;
+    }
+    let app_data_dir = crate::data_dir::get_data_dir();
+    if let Err(e) = crate::logger::record_cleanup_action(&app_data_dir, log_entries, None).await {
+        warn!("记录一键清理日志失败: {}", e);
+    }
+}
+
+/// 按显示名称解析要清理的分类（去重），未知分类和自定义路径分类返回错误
+pub(super) fn resolve_clean_categories(
+    category_names: &[String],
+) -> Result<Vec<JunkCategory>, CleanError> {
+    if category_names.is_empty() {
+        return Err(CleanError::InvalidInput("未指定要清理的分类".to_string()));
+    }
+    let mut categories: Vec<JunkCategory> = Vec::new();
+    for name in category_names {
+        let category = JunkCategory::all()
+            .into_iter()
+            .find(|c| c.display_name() == name)
+            .ok_or_else(|| CleanError::NotFound(format!("未知分类: {}", name)))?;
+        if category == JunkCategory::Custom {
+            return Err(CleanError::InvalidInput(
+                "自定义路径分类没有固定扫描目录，不支持一键清理".to_string(),
+            ));
+        }
+        if !categories.contains(&category) {
+            categories.push(category);
+        }
+    }
+    Ok(categories)
+}
+
+/// 逐个分类扫描并删除，返回 (汇总, 日志条目, 待重启删除的大小)
+fn clean_categories_blocking(
+    categories: &[JunkCategory],
+    dry_run: bool,
+) -> (CleanSummary, Vec<CleanupLogEntryInput>, u64) {
+    let engine = DeleteEngine::new();
+    let mut summary = CleanSummary::new(dry_run);
+    let mut log_entries = Vec::new();
+    let mut reboot_pending_size = 0;
+
+    for category in categories {
+        let scan_result = ScanEngine::new().scan_category(category);
+
+        if dry_run {
+            summary.total_freed += scan_result.total_size;
+            summary.categories.push(CategoryCleanSummary {
+                category: category.display_name().to_string(),
+                file_count: scan_result.file_count,
+                freed_size: scan_result.total_size,
+                failed_count: 0,
+            });
+            continue;
+        }
+
+        reboot_pending_size += delete_scanned_category(
+            &engine,
+            category,
+            &scan_result,
+            &mut summary,
+            &mut log_entries,
+        );
+    }
+
+    (summary, log_entries, reboot_pending_size)
+}
+
+/// 删除一个分类的扫描结果，累加到汇总和日志条目中，返回待重启删除的大小
+pub(super) fn delete_scanned_category(
+    engine: &DeleteEngine,
+    category: &JunkCategory,
+    scan_result: &CategoryScanResult,
+    summary: &mut CleanSummary,
+    log_entries: &mut Vec<CleanupLogEntryInput>,
+) -> u64 {
+    let display_name = category.display_name().to_string();
+    let result = if *category == JunkCategory::RecycleBin {
+        empty_recycle_bin_files(&scan_result.files)
+    } else {
+        engine.delete_files(&scan_result.files)
+    };
+    let failed: HashMap<&str, &str> = result
+        .failed_files
+        .iter()
+        .map(|f| (f.path.as_str(), f.reason.as_str()))
+        .collect();
+    for file in &scan_result.files {
+        let (success, error_message) = match failed.get(file.path.as_str()) {
+            Some(reason) => (false, Some(reason.to_string())),
+            // 仍然存在且未失败的文件已标记为重启后删除，不计入本次日志
+            None if std::fs::symlink_metadata(&file.path).is_ok() => continue,
+            None => (true, None),
+        };
+        log_entries.push(CleanupLogEntryInput {
+            category: display_name.clone(),
+            path: file.path.clone(),
+            size: file.size,
+            success,
+            error_message,
+        });
+    }
+
+    summary.total_freed += result.freed_size;
+    summary.success_count += result.success_count;
+    summary.failed_count += result.failed_count;
+    summary.reboot_pending_count += result.reboot_pending_count;
+    summary.categories.push(CategoryCleanSummary {
+        category: display_name,
+        file_count: scan_result.file_count,
+        freed_size: result.freed_size,
+        failed_count: result.failed_count,
+    });
+    result.reboot_pending_size
+}
+
 /// 增强删除文件
 #[tauri::command]
 pub async fn enhanced_delete_files(
     app: AppHandle,
     paths: Vec<String>,
-) -> Result<EnhancedDeleteResult, String> {
+    close_locking_apps: Option<bool>,
+    reboot_delete: Option<bool>,
+) -> Result<EnhancedDeleteResult, CleanError> {
     info!("增强删除: 开始删除 {} 个文件", paths.len());
     emit_delete_preparing(&app, paths.len());
 
     let progress_app = app.clone();
     let result = tokio::task::spawn_blocking(move || {
-        let engine = EnhancedDeleteEngine::new();
+        // reboot_delete 为 false 时被占用的文件直接作为失败返回，由用户自行处理
+        let engine = EnhancedDeleteEngine::new()
+            .with_reboot_delete(reboot_delete.unwrap_or(true))
+            .with_close_locking_apps(close_locking_apps.unwrap_or(false));
         engine.delete_files_with_progress(&paths, |progress| {
             emit_delete_progress(&progress_app, progress);
         })
@@ -87,6 +637,7 @@ pub async fn enhanced_delete_files(
         result.reboot_pending_count,
         result.freed_physical_size
     );
+    record_enhanced_reboot_pending(&result);
 
     Ok(result)
 }
@@ -99,13 +650,13 @@ pub async fn delete_deep_junk_files(
     scan_id: Option<String>,
     category_names: Option<Vec<String>>,
     excluded_paths: Option<Vec<String>>,
-) -> Result<EnhancedDeleteResult, String> {
+) -> Result<EnhancedDeleteResult, CleanError> {
     let category_names = category_names.unwrap_or_default();
     let excluded_paths = excluded_paths.unwrap_or_default();
     if !category_names.is_empty() {
-        let scan_id = scan_id
-            .as_deref()
-            .ok_or_else(|| "缺少深度扫描会话，无法展开完整分类".to_string())?;
+        let scan_id = scan_id.as_deref().ok_or_else(|| {
+            CleanError::InvalidInput("缺少深度扫描会话，无法展开完整分类".to_string())
+        })?;
         // 深度扫描结果按页返回，删除时从后端会话恢复完整分类，避免前端只传首屏文件。
         paths.extend(deep_junk::get_paths_for_categories(
             scan_id,
@@ -124,10 +675,10 @@ pub async fn delete_deep_junk_files(
         .iter()
         .find(|path| !deep_junk::is_deep_junk_path(path))
     {
-        return Err(format!(
+        return Err(CleanError::InvalidInput(format!(
             "深度清理安全校验失败，拒绝删除路径: {}",
             invalid_path
-        ));
+        )));
     }
 
     info!("深度垃圾清理: 开始删除 {} 个文件", paths.len());
@@ -149,19 +700,20 @@ pub async fn delete_deep_junk_files(
         result.reboot_pending_count,
         result.freed_physical_size
     );
+    record_enhanced_reboot_pending(&result);
     Ok(result)
 }
 
 /// 获取文件的物理大小（按簇对齐）
 #[tauri::command]
-pub async fn get_physical_size(logical_size: u64) -> Result<u64, String> {
+pub async fn get_physical_size(logical_size: u64) -> Result<u64, CleanError> {
     let engine = EnhancedDeleteEngine::new();
     Ok(engine.calculate_physical_size(logical_size))
 }
 
 /// 检查是否需要管理员权限
 #[tauri::command]
-pub async fn check_admin_for_path(path: String) -> Result<bool, String> {
+pub async fn check_admin_for_path(path: String) -> Result<bool, CleanError> {
     let path_lower = path.to_lowercase();
 
     let admin_required_paths = [
@@ -179,16 +731,58 @@ pub async fn check_admin_for_path(path: String) -> Result<bool, String> {
     Ok(false)
 }
 
-/// 永久删除卸载残留（深度清理）
+/// 查询重启后将被删除的文件（PendingFileRenameOperations 中的删除项）
+#[tauri::command]
+pub async fn get_pending_reboot_deletions() -> Result<Vec<String>, CleanError> {
+    let paths =
+        tokio::task::spawn_blocking(crate::cleaner::pending_reboot::get_pending_reboot_deletions)
+            .await
+            .map_err(|e| format!("读取重启删除队列任务异常: {}", e))??;
+    Ok(paths)
+}
+
+/// 查询是否有清理需要重启才能完成，前端启动时调用以提醒用户
+#[tauri::command]
+pub async fn get_reboot_pending_status() -> Result<RebootStatus, CleanError> {
+    tokio::task::spawn_blocking(reboot_status::get_reboot_pending_status)
+        .await
+        .map_err(|e| CleanError::Other(format!("读取重启提醒状态任务异常: {}", e)))
+}
+
+/// 撤销指定路径的重启删除标记
+#[tauri::command]
+pub async fn cancel_pending_reboot_deletion(path: String) -> Result<(), CleanError> {
+    info!("撤销重启删除: {}", path);
+    tokio::task::spawn_blocking(move || {
+        crate::cleaner::pending_reboot::cancel_pending_reboot_deletion(&path)
+    })
+    .await
+    .map_err(|e| format!("撤销重启删除任务异常: {}", e))??;
+    Ok(())
+}
+
+/// 永久删除卸载残留（深度清理），通过 `leftover-delete:progress` 推送按文件计的进度
 #[tauri::command]
 pub async fn delete_leftovers_permanent(
+    app: AppHandle,
     paths: Vec<String>,
-) -> Result<PermanentDeleteResult, String> {
+    max_concurrency: Option<usize>,
+    reboot_delete: Option<bool>,
+) -> Result<PermanentDeleteResult, CleanError> {
     info!("永久删除: 开始深度清理 {} 个卸载残留文件夹", paths.len());
 
     let result = tokio::task::spawn_blocking(move || {
-        let engine = PermanentDeleteEngine::new();
-        engine.delete_leftovers(paths)
+        let mut engine =
+            PermanentDeleteEngine::new().with_reboot_fallback(reboot_delete.unwrap_or(true));
+        // 前端可按磁盘类型选择并发数，机械硬盘传 1 顺序删除；未传时使用设置中的并发数
+        let max_concurrency =
+            max_concurrency.or_else(|| crate::settings::get_settings().delete_max_concurrency);
+        if let Some(max_concurrency) = max_concurrency {
+            engine = engine.with_max_concurrency(max_concurrency);
+        }
+        engine.delete_leftovers_with_progress(paths, &|progress| {
+            let _ = app.emit("leftover-delete:progress", progress);
+        })
     })
     .await
     .map_err(|e| format!("永久删除任务失败: {}", e))?;
@@ -201,13 +795,15 @@ pub async fn delete_leftovers_permanent(
         result.reboot_pending_count,
         result.freed_size
     );
+    // 标记重启删除的文件夹剩余大小未统计，提醒中只累加数量。
+    reboot_status::record_reboot_pending(result.reboot_pending_count, 0);
 
     Ok(result)
 }
 
 /// 执行单个路径的安全检查
 #[tauri::command]
-pub async fn check_leftover_safety(path: String) -> Result<SafetyCheckResult, String> {
+pub async fn check_leftover_safety(path: String) -> Result<SafetyCheckResult, CleanError> {
     let result = tokio::task::spawn_blocking(move || {
         let engine = PermanentDeleteEngine::new();
         let path = std::path::Path::new(&path);
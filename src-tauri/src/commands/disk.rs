@@ -54,6 +54,98 @@ pub fn get_disk_info() -> Result<DiskInfo, String> {
     }
 }
 
+/// 清理后可用空间核对结果
+#[derive(Debug, Serialize)]
+pub struct VerifyResult {
+    pub drive_letter: String,
+    /// 清理前的可用空间（字节）
+    pub before_free: u64,
+    /// 当前可用空间（字节）
+    pub after_free: u64,
+    /// 可用空间实际增加量，清理期间其他程序写入磁盘时可能为负
+    pub actual_freed: i64,
+    /// 清理报告的释放大小
+    pub reported_freed: Option<u64>,
+    /// 实际增加量明显少于报告值
+    pub shortfall: bool,
+    /// 差异说明，实际增加量与报告一致时为空
+    pub note: Option<String>,
+}
+
+/// 实际释放量允许比报告值少的误差：簇对齐、NTFS 元数据和日志等零散写入
+const FREED_SPACE_TOLERANCE: u64 = 4 * 1024 * 1024;
+
+/// 清理后重新查询可用空间，核对实际增加量与清理报告的释放大小
+///
+/// 前端应在清理前调用 `get_disk_info` 记下 `free_space`，清理完成后立即核对，
+/// 间隔越长，其他程序的写入对结果影响越大。
+#[tauri::command]
+pub fn verify_freed_space(
+    before_free: u64,
+    reported_freed: Option<u64>,
+    drive_letter: Option<String>,
+) -> Result<VerifyResult, String> {
+    #[cfg(target_os = "windows")]
+    {
+        let letter = drive_letter
+            .as_deref()
+            .and_then(|value| value.trim().chars().next())
+            .unwrap_or('C');
+        let drive = query_drive_info(letter)?;
+        let result = reconcile_freed_space(
+            drive.drive_letter,
+            before_free,
+            drive.free_space,
+            reported_freed,
+        );
+        info!(
+            "核对 {} 可用空间: 实际增加 {} 字节，报告 {:?} 字节",
+            result.drive_letter, result.actual_freed, result.reported_freed
+        );
+        Ok(result)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (before_free, reported_freed, drive_letter);
+        Err("此功能仅支持Windows系统".to_string())
+    }
+}
+
+pub(super) fn reconcile_freed_space(
+    drive_letter: String,
+    before_free: u64,
+    after_free: u64,
+    reported_freed: Option<u64>,
+) -> VerifyResult {
+    let actual_freed = after_free as i64 - before_free as i64;
+    let shortfall = reported_freed.is_some_and(|reported| {
+        (actual_freed.max(0) as u64).saturating_add(FREED_SPACE_TOLERANCE) < reported
+    });
+    let note = if shortfall {
+        Some(format!(
+            "可用空间实际增加 {} 字节，少于清理报告的 {} 字节。可能原因：部分文件需重启后才能删除、\
+             文件被移入回收站尚未清空、系统还原或卷影副本仍引用这些数据，或清理期间其他程序写入了磁盘",
+            actual_freed,
+            reported_freed.unwrap_or(0)
+        ))
+    } else if actual_freed < 0 {
+        Some("清理期间其他程序写入了磁盘，可用空间不增反减".to_string())
+    } else {
+        None
+    };
+
+    VerifyResult {
+        drive_letter,
+        before_free,
+        after_free,
+        actual_freed,
+        reported_freed,
+        shortfall,
+        note,
+    }
+}
+
 /// 获取本机固定磁盘分区列表。
 #[tauri::command]
 pub fn get_local_drives() -> Result<Vec<LocalDriveInfo>, String> {
@@ -102,6 +194,81 @@ pub fn get_local_drives() -> Result<Vec<LocalDriveInfo>, String> {
     }
 }
 
+/// 判断路径是否位于本机磁盘（非网络驱动器、可移动磁盘或光驱）
+///
+/// UNC 路径一律视为网络位置；无法识别类型的路径交给后续扫描按普通路径处理。
+pub(crate) fn is_local_fixed_drive(path: &std::path::Path) -> bool {
+    #[cfg(target_os = "windows")]
+    {
+        use winapi::um::fileapi::GetDriveTypeW;
+        use winapi::um::winbase::{DRIVE_CDROM, DRIVE_REMOTE, DRIVE_REMOVABLE};
+
+        let raw = path.to_string_lossy();
+        if raw.starts_with(r"\\") && !raw.starts_with(r"\\?\") {
+            return false;
+        }
+        let Some(letter) = raw
+            .trim_start_matches(r"\\?\")
+            .chars()
+            .next()
+            .filter(|ch| ch.is_ascii_alphabetic())
+        else {
+            return true;
+        };
+
+        let root = format!("{}:\\", letter.to_ascii_uppercase());
+        let wide_root = wide_null(&root);
+        let drive_type = unsafe { GetDriveTypeW(wide_root.as_ptr()) };
+        !matches!(drive_type, DRIVE_REMOTE | DRIVE_REMOVABLE | DRIVE_CDROM)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = path;
+        true
+    }
+}
+
+/// 扫描前确认目标位于本机磁盘，除非调用方或设置允许网络/可移动位置
+///
+/// 映射的网络驱动器和 U 盘遍历极慢，误选后扫描可能卡住数分钟。
+/// `allow_network` 为空时使用设置中的 `allow_network_scan`。
+pub(crate) fn ensure_local_scan_target(
+    path: &std::path::Path,
+    allow_network: Option<bool>,
+) -> Result<(), String> {
+    let allow_network =
+        allow_network.unwrap_or_else(|| crate::settings::get_settings().allow_network_scan);
+    if allow_network || is_local_fixed_drive(path) {
+        return Ok(());
+    }
+    Err(format!(
+        "{} 位于网络驱动器、可移动磁盘或光驱，扫描可能非常缓慢；如确需扫描请允许网络/可移动位置",
+        path.display()
+    ))
+}
+
+/// 系统盘可用空间低于该值时视为严重不足：完整扫描本身都可能因写不了临时文件而失败，
+/// 应先执行 `emergency_free_space` 腾出空间
+pub(crate) const CRITICAL_FREE_SPACE: u64 = 500 * 1024 * 1024;
+
+/// 查询系统盘当前可用空间（字节）
+pub(crate) fn system_drive_free_space() -> Result<u64, String> {
+    #[cfg(target_os = "windows")]
+    {
+        let letter = std::env::var("SystemDrive")
+            .ok()
+            .and_then(|drive| drive.chars().next())
+            .unwrap_or('C');
+        query_drive_info(letter).map(|drive| drive.free_space)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("此功能仅支持Windows系统".to_string())
+    }
+}
+
 #[cfg(target_os = "windows")]
 fn query_drive_info(letter: char) -> Result<LocalDriveInfo, String> {
     use winapi::um::fileapi::GetDiskFreeSpaceExW;
@@ -201,3 +368,26 @@ fn utf16_buffer_to_string(buffer: &[u16]) -> String {
 fn wide_null(value: &str) -> Vec<u16> {
     value.encode_utf16().chain(std::iter::once(0)).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconcile_freed_space_flags_shortfall() {
+        const GB: u64 = 1024 * 1024 * 1024;
+        let matched = reconcile_freed_space("C:".into(), 10 * GB, 12 * GB, Some(2 * GB));
+        assert_eq!(matched.actual_freed, 2 * GB as i64);
+        assert!(!matched.shortfall);
+        assert!(matched.note.is_none());
+
+        let short = reconcile_freed_space("C:".into(), 10 * GB, 10 * GB + GB / 2, Some(2 * GB));
+        assert!(short.shortfall);
+        assert!(short.note.unwrap().contains("重启"));
+
+        let grew = reconcile_freed_space("C:".into(), 10 * GB, 9 * GB, None);
+        assert_eq!(grew.actual_freed, -(GB as i64));
+        assert!(!grew.shortfall);
+        assert!(grew.note.is_some());
+    }
+}
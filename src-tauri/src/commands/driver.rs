@@ -17,17 +17,39 @@ pub async fn scan_old_drivers() -> Result<DriverScanResult, String> {
 pub async fn delete_old_drivers(
     published_names: Vec<String>,
 ) -> Result<DriverDeleteResult, String> {
-    tokio::task::spawn_blocking(move || crate::driver_cleanup::delete(published_names))
-        .await
-        .map_err(|error| format!("驱动清理任务失败: {}", error))?
+    let params = serde_json::json!({ "published_names": published_names.clone() });
+    let result = tokio::task::spawn_blocking(move || {
+        crate::system_slim::create_restore_point_if_enabled("清理旧驱动")?;
+        crate::driver_cleanup::delete(published_names)
+    })
+    .await
+    .map_err(|error| format!("驱动清理任务失败: {}", error))?;
+    crate::logger::record_audit("delete_old_drivers", params, &result);
+    result
+}
+
+/// 备份并删除单个驱动包（如 `oem42.inf`），与批量清理使用相同的安全校验。
+#[tauri::command]
+pub async fn remove_driver_package(oem_inf: String) -> Result<DriverDeleteResult, String> {
+    let params = serde_json::json!({ "oem_inf": oem_inf.clone() });
+    let result = tokio::task::spawn_blocking(move || {
+        crate::system_slim::create_restore_point_if_enabled("删除驱动包")?;
+        crate::driver_cleanup::delete(vec![oem_inf])
+    })
+    .await
+    .map_err(|error| format!("驱动清理任务失败: {}", error))?;
+    crate::logger::record_audit("remove_driver_package", params, &result);
+    result
 }
 
 /// 从当前数据目录的 driver_backups 中递归恢复全部驱动包。
 #[tauri::command]
 pub async fn restore_all_driver_backups() -> Result<DriverRestoreResult, String> {
-    tokio::task::spawn_blocking(crate::driver_cleanup::restore_all_backups)
+    let result = tokio::task::spawn_blocking(crate::driver_cleanup::restore_all_backups)
         .await
-        .map_err(|error| format!("驱动恢复任务失败: {}", error))?
+        .map_err(|error| format!("驱动恢复任务失败: {}", error))?;
+    crate::logger::record_audit("restore_all_driver_backups", serde_json::json!({}), &result);
+    result
 }
 
 /// 打开驱动备份目录，便于用户自行查看已导出的 INF 包。
@@ -22,6 +22,7 @@ pub fn cancel_hotspot_scan() {
 
 /// 扫描大目录
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn scan_hotspot(
     app: tauri::AppHandle,
     top_n: Option<usize>,
@@ -30,6 +31,7 @@ pub async fn scan_hotspot(
     size_threshold_mb: Option<u64>,
     ignore_system_dirs: Option<bool>,
     drive_letter: Option<String>,
+    allow_network: Option<bool>,
 ) -> Result<crate::scanner::HotspotScanResult, String> {
     use crate::scanner::HotspotScanner;
 
@@ -43,6 +45,10 @@ pub async fn scan_hotspot(
     let drive = normalize_hotspot_drive_letter(drive_letter.as_deref())?;
 
     if is_full_scan {
+        super::ensure_local_scan_target(
+            std::path::Path::new(&format!("{}:\\", drive)),
+            allow_network,
+        )?;
         info!(
             "开始 {} 盘深度扫描，Top {}，最大深度 {}，阈值 {}MB，忽略系统目录: {}",
             drive,
@@ -61,6 +67,10 @@ pub async fn scan_hotspot(
         );
     }
 
+    // AppData 扫描不支持取消，只登记全盘扫描
+    let _active_scan = is_full_scan.then(|| {
+        crate::scanner::register_active_scan("hotspot", crate::scanner::cancel_hotspot_scan)
+    });
     let result = tokio::task::spawn_blocking(move || {
         let scanner = HotspotScanner::new(is_full_scan, n)
             .with_display_depth(depth)
@@ -107,9 +117,14 @@ fn normalize_hotspot_drive_letter(value: Option<&str>) -> Result<char, String> {
 
 /// 单层路径钻取扫描
 #[tauri::command]
-pub async fn scan_path_direct(path: String) -> Result<crate::scanner::HotspotScanResult, String> {
+pub async fn scan_path_direct(
+    path: String,
+    allow_network: Option<bool>,
+) -> Result<crate::scanner::HotspotScanResult, String> {
     use crate::scanner::HotspotScanner;
 
+    super::ensure_local_scan_target(std::path::Path::new(&path), allow_network)?;
+
     info!("路径钻取扫描: {}", path);
 
     let result = tokio::task::spawn_blocking(move || HotspotScanner::scan_path_direct(&path))
@@ -162,7 +177,7 @@ pub async fn cleanup_directory_contents(path: String) -> Result<CleanupDirectory
             .filter(|e| e.path() != target_path)
             .collect();
 
-        entries.sort_by(|a, b| b.depth().cmp(&a.depth()));
+        entries.sort_by_key(|b| std::cmp::Reverse(b.depth()));
 
         for entry in entries {
             let entry_path = entry.path();
@@ -2,33 +2,76 @@
 // 卸载残留扫描与删除命令
 // ============================================================================
 
-use crate::scanner::{LeftoverScanResult, LeftoverScanner};
+use crate::error::CleanError;
+use crate::scanner::{
+    InstalledProgram, LeftoverScanResult, LeftoverScanner, OrphanTask, WhitelistEntry,
+    WhitelistKind,
+};
 use log::info;
+use tauri::{Emitter, Window};
 
-/// 扫描卸载残留
+/// 扫描卸载残留，并通过 `leftover-scan:progress` 推送当前分析的文件夹
 #[tauri::command]
 pub async fn scan_uninstall_leftovers(
+    window: Window,
     deep_scan: Option<bool>,
-) -> Result<LeftoverScanResult, String> {
+    aggressive: Option<bool>,
+) -> Result<LeftoverScanResult, CleanError> {
     let is_deep = deep_scan.unwrap_or(false);
-    info!("开始扫描卸载残留... 深度扫描: {}", is_deep);
+    let aggressive = aggressive.unwrap_or(false);
+    info!(
+        "开始扫描卸载残留... 深度扫描: {}, 激进模式: {}",
+        is_deep, aggressive
+    );
+    crate::scanner::reset_leftover_scan_cancelled();
+    let _active_scan =
+        crate::scanner::register_active_scan("leftover", crate::scanner::cancel_leftover_scan);
 
     let result = tokio::task::spawn_blocking(move || {
-        let scanner = LeftoverScanner::with_deep_scan(is_deep);
-        scanner.scan()
+        let scanner = LeftoverScanner::with_deep_scan(is_deep).with_aggressive(aggressive);
+        scanner.scan_with_progress(|progress| {
+            let _ = window.emit("leftover-scan:progress", progress);
+        })
     })
     .await
-    .map_err(|e| format!("扫描任务失败: {}", e))?;
+    .map_err(|e| CleanError::Other(format!("扫描任务失败: {}", e)))?;
 
     info!(
-        "卸载残留扫描完成: 发现 {} 个残留, 总大小 {} 字节",
+        "卸载残留扫描{}: 发现 {} 个残留, 总大小 {} 字节",
+        if result.cancelled {
+            "已取消"
+        } else {
+            "完成"
+        },
         result.leftovers.len(),
         result.total_size
     );
 
+    crate::scanner::remember_leftover_scan(&result);
     Ok(result)
 }
 
+/// 将最近一次扫描的发现导出为 JSON，供审计和回滚规划
+///
+/// `kind` 为 "leftovers"（卸载残留）或 "registry"（注册表残留）。
+/// 注册表导出记录每个条目的问题描述和关联路径，补充 .reg 备份缺少的信息。
+#[tauri::command]
+pub async fn export_findings(kind: String, output_path: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || {
+        let count = crate::scanner::export_findings(&kind, std::path::Path::new(&output_path))?;
+        info!("已导出 {} 条{}发现: {}", count, kind, output_path);
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("导出扫描发现任务异常: {}", e))?
+}
+
+/// 取消卸载残留扫描，已完成的部分结果仍会返回
+#[tauri::command]
+pub fn cancel_leftover_scan() {
+    crate::scanner::cancel_leftover_scan();
+}
+
 /// 删除卸载残留文件夹
 #[tauri::command]
 pub async fn delete_leftover_folders(
@@ -48,3 +91,53 @@ pub async fn delete_leftover_folders(
 
     Ok(result)
 }
+
+/// 获取已安装程序列表（含占用空间），用于卸载引导
+#[tauri::command]
+pub async fn get_installed_programs() -> Result<Vec<InstalledProgram>, String> {
+    let programs = tokio::task::spawn_blocking(crate::scanner::get_installed_programs)
+        .await
+        .map_err(|e| format!("读取已安装程序任务失败: {}", e))?;
+
+    info!("已读取 {} 个已安装程序", programs.len());
+
+    Ok(programs)
+}
+
+/// 扫描目标程序已不存在的第三方计划任务
+#[tauri::command]
+pub async fn scan_scheduled_tasks() -> Result<Vec<OrphanTask>, String> {
+    tokio::task::spawn_blocking(crate::scanner::scan_scheduled_tasks)
+        .await
+        .map_err(|e| format!("计划任务扫描任务失败: {}", e))?
+}
+
+/// 删除计划任务（schtasks /delete）
+#[tauri::command]
+pub async fn delete_scheduled_task(name: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || crate::scanner::delete_scheduled_task(&name))
+        .await
+        .map_err(|e| format!("删除计划任务任务失败: {}", e))?
+}
+
+// ============================================================================
+// 用户白名单（卸载残留 / 注册表残留扫描共用）
+// ============================================================================
+
+/// 获取白名单（内置条目 + 用户条目）
+#[tauri::command]
+pub fn get_whitelist() -> Vec<WhitelistEntry> {
+    crate::scanner::get_whitelist()
+}
+
+/// 添加用户白名单条目，下次扫描生效
+#[tauri::command]
+pub fn add_whitelist_entry(kind: WhitelistKind, pattern: String) -> Result<(), String> {
+    crate::scanner::add_whitelist_entry(kind, &pattern)
+}
+
+/// 移除用户白名单条目，内置条目不可移除
+#[tauri::command]
+pub fn remove_whitelist_entry(kind: WhitelistKind, pattern: String) -> Result<(), String> {
+    crate::scanner::remove_whitelist_entry(kind, &pattern)
+}
@@ -2,7 +2,7 @@
 // 清理日志命令
 // ============================================================================
 
-pub use crate::logger::{CleanupHistorySummary, CleanupLogEntryInput};
+pub use crate::logger::{AuditEntry, CleanupHistorySummary, CleanupLogEntryInput, CleanupSession};
 
 /// 记录清理操作到日志文件
 #[tauri::command]
@@ -21,9 +21,43 @@ pub async fn open_logs_folder() -> Result<(), String> {
     crate::logger::open_logs_folder(&app_data_dir)
 }
 
+/// 导出清理报告（HTML）
+///
+/// `filename` 为清理历史中的日志文件名，未指定时导出最近一次清理。
+#[tauri::command]
+pub async fn export_cleanup_report_html(
+    output_path: String,
+    filename: Option<String>,
+) -> Result<(), String> {
+    let app_data_dir = crate::data_dir::get_data_dir();
+    tokio::task::spawn_blocking(move || {
+        crate::logger::export_cleanup_report_html(
+            &app_data_dir,
+            std::path::Path::new(&output_path),
+            filename.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| format!("导出清理报告任务失败: {}", e))?
+}
+
 /// 获取清理历史记录列表
 #[tauri::command]
 pub async fn get_cleanup_history() -> Result<Vec<CleanupHistorySummary>, String> {
     let app_data_dir = crate::data_dir::get_data_dir();
     crate::logger::get_cleanup_history(&app_data_dir)
 }
+
+/// 读取一次清理会话的完整日志，`filename` 为清理历史中的日志文件名
+#[tauri::command]
+pub async fn get_cleanup_session(filename: String) -> Result<CleanupSession, String> {
+    let app_data_dir = crate::data_dir::get_data_dir();
+    crate::logger::get_cleanup_session(&app_data_dir, &filename)
+}
+
+/// 获取系统级操作审计日志（最新的在前），`limit` 默认 500 条
+#[tauri::command]
+pub async fn get_audit_log(limit: Option<usize>) -> Result<Vec<AuditEntry>, String> {
+    let app_data_dir = crate::data_dir::get_data_dir();
+    crate::logger::get_audit_log(&app_data_dir, limit)
+}
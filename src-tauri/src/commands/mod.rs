@@ -17,8 +17,10 @@ pub(crate) mod driver;
 mod hotspot;
 mod leftovers;
 mod logger_cmd;
+mod pipeline;
 mod registry;
 mod scan;
+mod settings;
 mod shell_icons;
 mod social;
 mod system;
@@ -37,8 +39,10 @@ pub use driver::*;
 pub use hotspot::*;
 pub use leftovers::*;
 pub use logger_cmd::*;
+pub use pipeline::*;
 pub use registry::*;
 pub use scan::*;
+pub use settings::*;
 pub use shell_icons::*;
 pub use social::*;
 pub use system::*;
@@ -0,0 +1,274 @@
+// ============================================================================
+// 清理流水线命令
+//
+// 界面按"扫描 → 删除 → 核对"依次执行时，每一步各自发送进度事件，没有统一的百分比。
+// 这里由后端按顺序执行全部步骤，并通过 `cleanup-pipeline:progress` 事件报告
+// 整条流水线的总进度：每个步骤占相同份额，步骤内按已处理的分类数推进。
+// ============================================================================
+
+use crate::cleaner::DeleteEngine;
+use crate::error::CleanError;
+use crate::logger::CleanupLogEntryInput;
+use crate::scanner::{CategoryScanResult, JunkCategory, ScanEngine};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+
+use super::{
+    delete_scanned_category, reconcile_freed_space, record_clean_results, resolve_clean_categories,
+    system_drive_free_space, CleanSummary, VerifyResult,
+};
+
+/// 流水线中的一个步骤
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CleanStep {
+    /// 扫描指定分类（显示名称），结果交给后面的删除步骤
+    Scan { categories: Vec<String> },
+    /// 删除此前所有扫描步骤找到的文件
+    Delete,
+    /// 重新查询系统盘可用空间，核对实际增加量与删除报告的释放大小
+    Verify,
+}
+
+impl CleanStep {
+    fn name(&self) -> &'static str {
+        match self {
+            CleanStep::Scan { .. } => "scan",
+            CleanStep::Delete => "delete",
+            CleanStep::Verify => "verify",
+        }
+    }
+}
+
+/// 流水线总进度事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationProgress {
+    /// 调用方传入的操作标识，用于区分同时进行的多条流水线
+    pub operation_id: Option<String>,
+    /// 当前步骤：scan / delete / verify
+    pub step: String,
+    /// 当前步骤序号（从 0 开始）
+    pub step_index: usize,
+    /// 步骤总数
+    pub step_count: usize,
+    /// 当前处理的分类显示名称
+    pub current_item: Option<String>,
+    /// 整条流水线的完成百分比（0-100）
+    pub overall_percent: f32,
+}
+
+/// 流水线执行结果
+#[derive(Debug, Serialize)]
+pub struct CleanupPipelineResult {
+    pub operation_id: Option<String>,
+    /// 扫描到的文件数
+    pub scanned_file_count: usize,
+    /// 扫描到的总大小（字节）
+    pub scanned_size: u64,
+    /// 删除汇总，没有删除步骤时为 None
+    pub clean: Option<CleanSummary>,
+    /// 可用空间核对结果，没有核对步骤或无法查询可用空间时为 None
+    pub verify: Option<VerifyResult>,
+}
+
+/// 按顺序执行扫描、删除、核对步骤，并推送 `cleanup-pipeline:progress` 总进度事件
+///
+/// 删除步骤必须在扫描步骤之后，核对步骤必须在删除步骤之后。
+/// 删除的文件和清理日志与 `clean_categories` 一致记录。
+#[tauri::command]
+pub async fn run_cleanup_pipeline(
+    app: AppHandle,
+    steps: Vec<CleanStep>,
+    operation_id: Option<String>,
+) -> Result<CleanupPipelineResult, CleanError> {
+    validate_steps(&steps)?;
+    info!("清理流水线 {:?}: {:?}", operation_id, steps);
+
+    let (result, log_entries, reboot_pending_size) = tokio::task::spawn_blocking(move || {
+        run_cleanup_pipeline_blocking(&app, &steps, operation_id)
+    })
+    .await
+    .map_err(|e| CleanError::Other(format!("清理流水线任务异常: {}", e)))??;
+
+    if let Some(summary) = &result.clean {
+        record_clean_results(summary, log_entries, reboot_pending_size).await;
+    }
+    info!(
+        "清理流水线完成: 扫描 {} 个文件, 释放 {} 字节",
+        result.scanned_file_count,
+        result
+            .clean
+            .as_ref()
+            .map_or(0, |summary| summary.total_freed)
+    );
+    Ok(result)
+}
+
+/// 检查步骤顺序，并提前解析所有扫描步骤的分类名称
+fn validate_steps(steps: &[CleanStep]) -> Result<(), CleanError> {
+    if steps.is_empty() {
+        return Err(CleanError::InvalidInput("未指定流水线步骤".to_string()));
+    }
+    let mut scanned = false;
+    let mut deleted = false;
+    for step in steps {
+        match step {
+            CleanStep::Scan { categories } => {
+                resolve_clean_categories(categories)?;
+                scanned = true;
+            }
+            CleanStep::Delete if !scanned => {
+                return Err(CleanError::InvalidInput(
+                    "删除步骤之前必须有扫描步骤".to_string(),
+                ));
+            }
+            CleanStep::Delete => deleted = true,
+            CleanStep::Verify if !deleted => {
+                return Err(CleanError::InvalidInput(
+                    "核对步骤之前必须有删除步骤".to_string(),
+                ));
+            }
+            CleanStep::Verify => {}
+        }
+    }
+    Ok(())
+}
+
+fn run_cleanup_pipeline_blocking(
+    app: &AppHandle,
+    steps: &[CleanStep],
+    operation_id: Option<String>,
+) -> Result<(CleanupPipelineResult, Vec<CleanupLogEntryInput>, u64), CleanError> {
+    let report = |step_index: usize, current_item: Option<String>, step_fraction: f32| {
+        let progress = OperationProgress {
+            operation_id: operation_id.clone(),
+            step: steps[step_index].name().to_string(),
+            step_index,
+            step_count: steps.len(),
+            current_item,
+            overall_percent: overall_percent(step_index, steps.len(), step_fraction),
+        };
+        if let Err(error) = app.emit("cleanup-pipeline:progress", progress) {
+            warn!("发送清理流水线进度失败: {}", error);
+        }
+    };
+
+    let engine = DeleteEngine::new();
+    // 已扫描、尚未删除的分类
+    let mut pending: Vec<(JunkCategory, CategoryScanResult)> = Vec::new();
+    let mut scanned_file_count = 0;
+    let mut scanned_size = 0;
+    let mut clean: Option<CleanSummary> = None;
+    let mut log_entries = Vec::new();
+    let mut reboot_pending_size = 0;
+    let mut free_before: Option<u64> = None;
+    let mut verify = None;
+
+    for (step_index, step) in steps.iter().enumerate() {
+        report(step_index, None, 0.0);
+        match step {
+            CleanStep::Scan { categories } => {
+                let categories = resolve_clean_categories(categories)?;
+                for (index, category) in categories.iter().enumerate() {
+                    let display_name = category.display_name().to_string();
+                    report(
+                        step_index,
+                        Some(display_name),
+                        index as f32 / categories.len() as f32,
+                    );
+                    let scan_result = ScanEngine::new().scan_category(category);
+                    scanned_file_count += scan_result.file_count;
+                    scanned_size += scan_result.total_size;
+                    pending.push((category.clone(), scan_result));
+                }
+            }
+            CleanStep::Delete => {
+                if free_before.is_none() {
+                    free_before = system_drive_free_space()
+                        .map_err(|e| warn!("清理流水线无法查询删除前可用空间: {}", e))
+                        .ok();
+                }
+                let summary = clean.get_or_insert_with(|| CleanSummary::new(false));
+                let total = pending.len();
+                for (index, (category, scan_result)) in pending.drain(..).enumerate() {
+                    report(
+                        step_index,
+                        Some(category.display_name().to_string()),
+                        index as f32 / total as f32,
+                    );
+                    reboot_pending_size += delete_scanned_category(
+                        &engine,
+                        &category,
+                        &scan_result,
+                        summary,
+                        &mut log_entries,
+                    );
+                }
+            }
+            CleanStep::Verify => {
+                let reported = clean.as_ref().map(|summary| summary.total_freed);
+                verify = match (free_before, system_drive_free_space()) {
+                    (Some(before), Ok(after)) => {
+                        let drive_letter =
+                            std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string());
+                        Some(reconcile_freed_space(drive_letter, before, after, reported))
+                    }
+                    (_, Err(e)) => {
+                        warn!("清理流水线无法核对可用空间: {}", e);
+                        None
+                    }
+                    (None, Ok(_)) => None,
+                };
+            }
+        }
+        report(step_index, None, 1.0);
+    }
+
+    let result = CleanupPipelineResult {
+        operation_id,
+        scanned_file_count,
+        scanned_size,
+        clean,
+        verify,
+    };
+    Ok((result, log_entries, reboot_pending_size))
+}
+
+/// 每个步骤占相同份额，`step_fraction` 为当前步骤内的完成比例
+fn overall_percent(step_index: usize, step_count: usize, step_fraction: f32) -> f32 {
+    if step_count == 0 {
+        return 100.0;
+    }
+    let done = step_index as f32 + step_fraction.clamp(0.0, 1.0);
+    (done / step_count as f32 * 100.0).min(100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(categories: &[&str]) -> CleanStep {
+        CleanStep::Scan {
+            categories: categories.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_overall_percent_splits_steps_evenly() {
+        assert_eq!(overall_percent(0, 3, 0.0), 0.0);
+        assert_eq!(overall_percent(1, 4, 0.5), 37.5);
+        assert_eq!(overall_percent(2, 3, 1.0), 100.0);
+        assert_eq!(overall_percent(0, 2, 2.0), 50.0);
+    }
+
+    #[test]
+    fn test_validate_steps_requires_scan_before_delete_and_delete_before_verify() {
+        let temp = JunkCategory::WindowsTemp.display_name();
+        assert!(validate_steps(&[]).is_err());
+        assert!(validate_steps(&[CleanStep::Delete]).is_err());
+        assert!(validate_steps(&[scan(&[temp]), CleanStep::Verify]).is_err());
+        assert!(validate_steps(&[scan(&["不存在的分类"]), CleanStep::Delete]).is_err());
+        assert!(validate_steps(&[scan(&[temp]), CleanStep::Delete, CleanStep::Verify]).is_ok());
+    }
+}
@@ -2,29 +2,47 @@
 // 注册表和右键菜单清理命令
 // ============================================================================
 
+use crate::error::CleanError;
 use crate::scanner::{
     RegistryBackup, RegistryDeleteResult, RegistryEntry, RegistryScanResult, RegistryScanner,
 };
 use log::info;
+use tauri::{Emitter, Window};
 
 // ============================================================================
 // 注册表冗余
 // ============================================================================
 
 /// 扫描注册表冗余
+///
+/// `software_key_depth` 非空时同时扫描 HKCU\Software 下的"厂商\产品"配置键。
+/// 扫描进度通过 `registry-scan:progress` 推送。
 #[tauri::command]
-pub async fn scan_registry_redundancy() -> Result<RegistryScanResult, String> {
+pub async fn scan_registry_redundancy(
+    window: Window,
+    software_key_depth: Option<usize>,
+) -> Result<RegistryScanResult, CleanError> {
     info!("开始扫描注册表冗余...");
+    crate::scanner::reset_registry_scan_cancelled();
+    let _active_scan =
+        crate::scanner::register_active_scan("registry", crate::scanner::cancel_registry_scan);
 
-    let result = tokio::task::spawn_blocking(|| {
-        let mut scanner = RegistryScanner::new();
-        scanner.scan()
+    let result = tokio::task::spawn_blocking(move || {
+        let mut scanner = RegistryScanner::new().with_software_key_depth(software_key_depth);
+        scanner.scan_with_progress(|progress| {
+            let _ = window.emit("registry-scan:progress", progress);
+        })
     })
     .await
-    .map_err(|e| format!("扫描任务失败: {}", e))?;
+    .map_err(|e| CleanError::Other(format!("扫描任务失败: {}", e)))?;
+    if crate::scanner::is_registry_scan_cancelled() {
+        info!("注册表扫描已取消");
+        return Err(CleanError::Cancelled);
+    }
 
     info!("注册表扫描完成: 发现 {} 个冗余条目", result.total_count);
 
+    crate::scanner::remember_registry_scan(&result);
     Ok(result)
 }
 
@@ -32,13 +50,35 @@ pub async fn scan_registry_redundancy() -> Result<RegistryScanResult, String> {
 #[tauri::command]
 pub async fn delete_registry_entries(
     entries: Vec<RegistryEntry>,
+) -> Result<RegistryDeleteResult, String> {
+    let params = serde_json::json!({
+        "paths": entries.iter().map(|entry| entry.path.as_str()).collect::<Vec<_>>(),
+    });
+    let result = delete_registry_entries_with_backup(entries).await;
+    crate::logger::record_audit("delete_registry_entries", params, &result);
+    result
+}
+
+/// 先导出并校验备份，再逐条删除；备份失败时不删除任何条目
+async fn delete_registry_entries_with_backup(
+    entries: Vec<RegistryEntry>,
 ) -> Result<RegistryDeleteResult, String> {
     info!("开始删除 {} 个注册表条目...", entries.len());
 
+    tokio::task::spawn_blocking(|| {
+        crate::system_slim::create_restore_point_if_enabled("删除注册表条目")
+    })
+    .await
+    .map_err(|e| format!("创建系统还原点任务异常: {}", e))??;
+
     let backup_dir = RegistryBackup::get_backup_dir();
     let backup_path = RegistryBackup::export_backup(&entries, &backup_dir)
         .map_err(|e| format!("创建备份失败: {}", e))?;
 
+    // 备份不完整时整批中止，宁可不删也不能删掉无法恢复的键
+    RegistryBackup::verify_backup(&backup_path, &entries)
+        .map_err(|e| format!("备份校验失败，已取消删除: {}", e))?;
+
     info!("注册表备份已保存到: {:?}", backup_path);
 
     let result = tokio::task::spawn_blocking(move || {
@@ -77,6 +117,24 @@ pub async fn delete_registry_entries(
     Ok(result)
 }
 
+/// 获取用户忽略的注册表条目路径
+#[tauri::command]
+pub fn get_ignored_registry_entries() -> Vec<String> {
+    crate::scanner::get_ignored_registry_entries()
+}
+
+/// 忽略注册表条目（完整键路径），之后的扫描不再列出
+#[tauri::command]
+pub fn ignore_registry_entry(path: String) -> Result<(), String> {
+    crate::scanner::ignore_registry_entry(&path)
+}
+
+/// 取消忽略注册表条目
+#[tauri::command]
+pub fn unignore_registry_entry(path: String) -> Result<(), String> {
+    crate::scanner::unignore_registry_entry(&path)
+}
+
 /// 打开注册表备份目录
 #[tauri::command]
 pub async fn open_registry_backup_dir() -> Result<(), String> {
@@ -132,9 +190,12 @@ pub async fn delete_context_menu_entries(
 
     info!("开始删除 {} 个右键菜单条目", entries.len());
 
+    let params = serde_json::to_value(&entries).unwrap_or_default();
     let result = tokio::task::spawn_blocking(move || do_delete(&entries))
         .await
-        .map_err(|e| format!("删除任务执行失败: {}", e))?;
+        .map_err(|e| format!("删除任务执行失败: {}", e));
+    crate::logger::record_audit("delete_context_menu_entries", params, &result);
+    let result = result?;
 
     info!(
         "右键菜单清理完成: 成功 {} 个，失败 {} 个",
@@ -2,10 +2,13 @@
 // 垃圾扫描与大文件扫描命令
 // ============================================================================
 
+use crate::error::CleanError;
 use crate::scanner::{
-    big_files, deep_junk, CategoryScanResult, JunkCategory, ScanEngine, ScanResult,
+    big_files, deep_junk, AppLogGroup, CategoryScanResult, FileInfo, JunkCategory, PathDiagnostic,
+    ReclaimEstimate, Recommendation, ScanEngine, ScanEstimate, ScanResult, ScopePermission,
+    UserProfileScanResult, UwpPackageCache, RESULT_SCHEMA_VERSION,
 };
-use log::info;
+use log::{info, warn};
 use serde::{Deserialize, Serialize};
 use tauri::Window;
 
@@ -13,22 +16,71 @@ use tauri::Window;
 #[derive(Debug, Deserialize)]
 pub struct ScanRequest {
     pub categories: Option<Vec<String>>,
+    /// 为 true 时只返回有发现的分类
+    pub skip_empty: Option<bool>,
+    /// 每个分类最多返回的文件数（按大小取最大的），其余通过 `get_category_files` 分页读取；
+    /// 为空时使用设置中的 `max_files_per_category`
+    pub max_files_per_category: Option<usize>,
+    /// 小于该大小（字节）的文件不列出，只计入分类总大小和小文件数量
+    pub min_file_size: Option<u64>,
+    /// 最大扫描深度，超出范围时限制在 1..=40；默认 10。
+    /// 浏览器缓存（BrowserCache）按站点和缓存类型多层嵌套，调高后能找到更多文件，代价是扫描变慢
+    pub max_depth: Option<usize>,
+    /// 单个分类最多统计的文件数，超出后该分类标记为 `truncated`
+    pub max_files: Option<usize>,
+    /// 单个分类最长扫描时间（秒），超出后该分类标记为 `truncated`
+    pub max_duration_secs: Option<u64>,
+    /// 低优先级扫描：降低线程 CPU/I/O 优先级并分批休眠，后台扫描时不拖慢前台程序
+    pub low_priority: Option<bool>,
 }
 
+/// 扫描深度允许的范围
+const MIN_SCAN_DEPTH: usize = 1;
+const MAX_SCAN_DEPTH: usize = 40;
+
 /// 分类信息（用于前端展示）
 #[derive(Debug, Serialize)]
 pub struct CategoryInfo {
     pub name: String,
     pub description: String,
     pub risk_level: u8,
+    /// 是否已收藏（前端加载时默认勾选）
+    pub is_favorite: bool,
 }
 
 /// 执行垃圾文件扫描
 #[tauri::command]
-pub async fn scan_junk_files(request: Option<ScanRequest>) -> Result<ScanResult, String> {
+pub async fn scan_junk_files(request: Option<ScanRequest>) -> Result<ScanResult, CleanError> {
     info!("开始扫描垃圾文件");
 
-    let result = tokio::task::spawn_blocking(move || {
+    let skip_empty = request
+        .as_ref()
+        .and_then(|req| req.skip_empty)
+        .unwrap_or(false);
+    let max_files_per_category = request
+        .as_ref()
+        .and_then(|req| req.max_files_per_category)
+        .or_else(|| crate::settings::get_settings().max_files_per_category);
+
+    let min_file_size = request
+        .as_ref()
+        .and_then(|req| req.min_file_size)
+        .unwrap_or(0);
+    let max_depth = request
+        .as_ref()
+        .and_then(|req| req.max_depth)
+        .map(|depth| depth.clamp(MIN_SCAN_DEPTH, MAX_SCAN_DEPTH));
+    let max_files = request.as_ref().and_then(|req| req.max_files);
+    let max_duration_secs = request.as_ref().and_then(|req| req.max_duration_secs);
+    let low_priority = request
+        .as_ref()
+        .and_then(|req| req.low_priority)
+        .unwrap_or(false);
+
+    crate::scanner::reset_junk_scan_cancelled();
+    let _active_scan =
+        crate::scanner::register_active_scan("junk", crate::scanner::cancel_junk_scan);
+    let mut result = tokio::task::spawn_blocking(move || {
         let engine = if let Some(req) = request {
             if let Some(category_names) = req.categories {
                 let categories: Vec<JunkCategory> = JunkCategory::all()
@@ -48,10 +100,37 @@ pub async fn scan_junk_files(request: Option<ScanRequest>) -> Result<ScanResult,
             ScanEngine::new()
         };
 
-        engine.scan()
+        let engine = match max_depth {
+            Some(depth) => engine.with_max_depth(depth),
+            None => engine,
+        };
+        let engine = match max_files {
+            Some(max_files) => engine.with_max_files(max_files),
+            None => engine,
+        };
+        let engine = match max_duration_secs {
+            Some(secs) => engine.with_max_duration(std::time::Duration::from_secs(secs)),
+            None => engine,
+        };
+        engine
+            .with_min_file_size(min_file_size)
+            .with_low_priority(low_priority)
+            .with_cancellable(true)
+            .scan()
     })
     .await
     .map_err(|e| format!("扫描任务异常: {}", e))?;
+    if crate::scanner::is_junk_scan_cancelled() {
+        info!("垃圾扫描已取消");
+        return Err(CleanError::Cancelled);
+    }
+
+    if skip_empty {
+        result.remove_empty_categories();
+    }
+    if let Some(max_files) = max_files_per_category {
+        crate::scanner::limit_category_files(&mut result, max_files);
+    }
 
     info!(
         "扫描完成: {} 个文件, {} 字节",
@@ -61,17 +140,207 @@ pub async fn scan_junk_files(request: Option<ScanRequest>) -> Result<ScanResult,
     Ok(result)
 }
 
+/// 分页读取最近一次限量扫描中某个分类的文件（按大小降序）
+#[tauri::command]
+pub fn get_category_files(
+    category_name: String,
+    offset: usize,
+    limit: Option<usize>,
+) -> Result<CategoryScanResult, CleanError> {
+    Ok(crate::scanner::get_category_files(
+        &category_name,
+        offset,
+        limit.unwrap_or(500),
+    )?)
+}
+
+/// 逐个扫描本机所有用户的临时文件、浏览器缓存和社交软件缓存（需要管理员权限）
+#[tauri::command]
+pub async fn scan_all_user_profiles() -> Result<Vec<UserProfileScanResult>, CleanError> {
+    if !crate::system_slim::check_admin() {
+        return Err(CleanError::NeedsAdmin(
+            "需要管理员权限才能扫描其他用户的文件，请以管理员身份运行程序".to_string(),
+        ));
+    }
+    info!("开始扫描所有用户配置文件");
+
+    let results = tokio::task::spawn_blocking(crate::scanner::scan_user_profiles)
+        .await
+        .map_err(|e| format!("扫描任务异常: {}", e))?;
+    info!("用户配置文件扫描完成: {} 个用户", results.len());
+    Ok(results)
+}
+
+/// 按包汇总 %LocalAppData%\Packages 下 UWP 应用的 LocalCache / TempState / AC\Temp 缓存
+#[tauri::command]
+pub async fn scan_uwp_caches() -> Result<Vec<UwpPackageCache>, CleanError> {
+    let packages = tokio::task::spawn_blocking(crate::scanner::scan_uwp_caches)
+        .await
+        .map_err(|e| format!("UWP 缓存扫描任务异常: {}", e))?;
+    info!("UWP 缓存扫描完成: {} 个包", packages.len());
+    Ok(packages)
+}
+
+/// 系统目录统计失败时的错误：未以管理员身份运行时的拒绝访问提示提权重启
+fn system_dir_error(error: std::io::Error) -> CleanError {
+    match CleanError::from(error) {
+        CleanError::PermissionDenied(message) if !crate::system_slim::check_admin() => {
+            CleanError::NeedsAdmin(message)
+        }
+        other => other,
+    }
+}
+
+/// 统计 Windows Defender 可清理的扫描历史缓存（不含隔离区）
+#[tauri::command]
+pub async fn get_defender_cache_info() -> Result<crate::cleaner::DefenderCacheInfo, CleanError> {
+    let info = tokio::task::spawn_blocking(crate::cleaner::get_defender_cache_info)
+        .await
+        .map_err(|e| format!("Defender 缓存统计任务异常: {}", e))?
+        .map_err(system_dir_error)?;
+    info!(
+        "Defender 扫描历史缓存: {} 个文件, {} 字节",
+        info.file_count, info.total_size
+    );
+    Ok(info)
+}
+
+/// 统计 C:\Windows\Logs\CBS 下可清理的 CbsPersist_* 轮转归档（不含当前的 CBS.log）
+#[tauri::command]
+pub async fn get_cbs_log_info() -> Result<crate::cleaner::CbsLogInfo, CleanError> {
+    let info = tokio::task::spawn_blocking(crate::cleaner::get_cbs_log_info)
+        .await
+        .map_err(|e| format!("CBS 日志统计任务异常: {}", e))?
+        .map_err(system_dir_error)?;
+    info!(
+        "CBS 归档日志: {} 个文件, {} 字节",
+        info.file_count, info.total_size
+    );
+    Ok(info)
+}
+
+/// 估算垃圾扫描范围，供前端在扫描前选择进度条样式
+///
+/// 同时检查系统盘可用空间，严重不足时前端应提示先执行 `emergency_free_space`。
+#[tauri::command]
+pub async fn estimate_scan_scope() -> Result<ScanEstimate, CleanError> {
+    let mut estimate = tokio::task::spawn_blocking(|| ScanEngine::new().estimate_scope())
+        .await
+        .map_err(|e| CleanError::Other(format!("估算任务异常: {}", e)))?;
+    estimate.system_drive_free_space = super::system_drive_free_space().ok();
+    estimate.critically_low_space = estimate
+        .system_drive_free_space
+        .is_some_and(|free| free < super::CRITICAL_FREE_SPACE);
+    if estimate.critically_low_space {
+        warn!(
+            "系统盘可用空间严重不足: {:?} 字节",
+            estimate.system_drive_free_space
+        );
+    }
+    Ok(estimate)
+}
+
+/// 快速估算可回收空间（首页标题数字），只浅层统计低风险来源，不删除任何文件
+#[tauri::command]
+pub async fn estimate_reclaimable() -> Result<ReclaimEstimate, CleanError> {
+    let estimate = tokio::task::spawn_blocking(crate::scanner::estimate_reclaimable)
+        .await
+        .map_err(|e| format!("估算任务异常: {}", e))?;
+    info!(
+        "可回收空间估算: {} 字节（另有 {} 字节需额外操作），耗时 {}ms",
+        estimate.total_size, estimate.optional_size, estimate.duration_ms
+    );
+    Ok(estimate)
+}
+
+/// 首页智能建议：比较休眠文件、Windows.old、回收站和临时文件，返回预计释放最多的一项操作
+///
+/// 各项都不足 100 MB 时返回 null。返回的 `command` 和 `args` 可直接交给 invoke。
+#[tauri::command]
+pub async fn get_top_recommendation() -> Result<Option<Recommendation>, CleanError> {
+    let recommendation = tokio::task::spawn_blocking(crate::scanner::get_top_recommendation)
+        .await
+        .map_err(|e| format!("智能建议任务异常: {}", e))?;
+    if let Some(recommendation) = &recommendation {
+        info!(
+            "智能建议: {}（预计释放 {} 字节）",
+            recommendation.title, recommendation.estimated_size
+        );
+    }
+    Ok(recommendation)
+}
+
+/// 扫描 AppData 下各应用超过阈值的 *.log，按应用目录汇总
+///
+/// 返回的文件路径可直接交给 `delete_files`，用户只清理某一个应用的日志。
+#[tauri::command]
+pub async fn scan_app_logs(min_size: Option<u64>) -> Result<Vec<AppLogGroup>, CleanError> {
+    let min_size = min_size.unwrap_or(crate::scanner::DEFAULT_MIN_LOG_SIZE);
+    let groups = tokio::task::spawn_blocking(move || crate::scanner::scan_app_logs(min_size))
+        .await
+        .map_err(|e| format!("应用日志扫描任务异常: {}", e))?;
+    info!(
+        "应用日志扫描完成: {} 个应用, {} 字节",
+        groups.len(),
+        groups.iter().map(|group| group.total_size).sum::<u64>()
+    );
+    Ok(groups)
+}
+
+/// 从垃圾扫描结果中取出体积最大的前 N 个文件
+///
+/// 只在已确认的垃圾文件范围内排序，删除风险低于全盘大文件视图。
+#[tauri::command]
+pub fn get_largest_junk_files(result: ScanResult, top_n: Option<usize>) -> Vec<FileInfo> {
+    // 与大文件扫描保持一致的数量上限，避免前端一次渲染过多条目
+    let top_n = top_n.unwrap_or(20).clamp(1, 500);
+    result.largest_files(top_n)
+}
+
+/// 将扫描结果导出为 JSON 文件，供外部脚本读取
+///
+/// 导出时写入当前 `RESULT_SCHEMA_VERSION`，解析方据此判断格式是否兼容。
+#[tauri::command]
+pub async fn export_scan_json(
+    mut result: ScanResult,
+    output_path: String,
+) -> Result<(), CleanError> {
+    result.schema_version = RESULT_SCHEMA_VERSION;
+
+    tokio::task::spawn_blocking(move || -> Result<(), CleanError> {
+        let json = serde_json::to_string_pretty(&result)
+            .map_err(|e| format!("序列化扫描结果失败: {}", e))?;
+        crate::logger::write_export_file(std::path::Path::new(&output_path), json)
+            .map_err(CleanError::Io)?;
+        info!("扫描结果已导出: {}", output_path);
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("导出扫描结果任务异常: {}", e))?
+}
+
 /// 执行所有固定分区的深度垃圾扫描，NTFS 优先使用 MFT。
 #[tauri::command]
-pub async fn scan_deep_junk_files(window: Window) -> Result<deep_junk::DeepJunkScanResult, String> {
+pub async fn scan_deep_junk_files(
+    window: Window,
+) -> Result<deep_junk::DeepJunkScanResult, CleanError> {
     info!("开始深度扫描垃圾文件");
     deep_junk::reset_cancelled();
+    let _active_scan = crate::scanner::register_active_scan("deep_junk", deep_junk::cancel);
 
     let result = tokio::task::spawn_blocking(move || deep_junk::scan_all(&window))
         .await
-        .map_err(|error| format!("深度扫描任务异常: {}", error))??;
+        .map_err(|error| format!("深度扫描任务异常: {}", error))?
+        .map_err(|error| {
+            // 取消时扫描线程返回的是提示文案，这里按取消标志归类，前端不再提示为失败
+            if deep_junk::is_cancelled() {
+                CleanError::Cancelled
+            } else {
+                CleanError::Other(error)
+            }
+        })?;
 
-    deep_junk::create_session(result)
+    Ok(deep_junk::create_session(result)?)
 }
 
 /// 获取深度扫描分类的分页文件。
@@ -81,8 +350,13 @@ pub fn get_deep_junk_category_page(
     category_name: String,
     offset: usize,
     limit: Option<usize>,
-) -> Result<CategoryScanResult, String> {
-    deep_junk::get_category_page(&scan_id, &category_name, offset, limit.unwrap_or(500))
+) -> Result<CategoryScanResult, CleanError> {
+    Ok(deep_junk::get_category_page(
+        &scan_id,
+        &category_name,
+        offset,
+        limit.unwrap_or(500),
+    )?)
 }
 
 /// 取消正在执行的深度垃圾扫描。
@@ -94,14 +368,14 @@ pub fn cancel_deep_junk_scan() {
 
 /// 扫描单个分类
 #[tauri::command]
-pub async fn scan_category(category_name: String) -> Result<CategoryScanResult, String> {
+pub async fn scan_category(category_name: String) -> Result<CategoryScanResult, CleanError> {
     info!("扫描分类: {}", category_name);
 
-    let result = tokio::task::spawn_blocking(move || -> Result<CategoryScanResult, String> {
+    let result = tokio::task::spawn_blocking(move || -> Result<CategoryScanResult, CleanError> {
         let category = JunkCategory::all()
             .into_iter()
             .find(|c| c.display_name() == category_name)
-            .ok_or_else(|| format!("未知分类: {}", category_name))?;
+            .ok_or_else(|| CleanError::NotFound(format!("未知分类: {}", category_name)))?;
 
         let engine = ScanEngine::new();
         Ok(engine.scan_category(&category))
@@ -112,34 +386,141 @@ pub async fn scan_category(category_name: String) -> Result<CategoryScanResult,
     Ok(result)
 }
 
+/// 扫描用户指定的路径列表，结果作为"自定义路径"分类返回，可直接交给删除命令
+#[tauri::command]
+pub async fn scan_paths(
+    paths: Vec<String>,
+    patterns: Option<Vec<String>>,
+    allow_network: Option<bool>,
+) -> Result<CategoryScanResult, CleanError> {
+    let scan_roots = validate_custom_scan_paths(&paths)?;
+    for root in &scan_roots {
+        super::ensure_local_scan_target(root, allow_network).map_err(CleanError::InvalidInput)?;
+    }
+    // 未指定模式时与普通分类一致，匹配全部文件
+    let patterns = patterns
+        .filter(|items| !items.is_empty())
+        .unwrap_or_else(|| vec!["*".to_string()]);
+    info!("扫描自定义路径: {} 个", scan_roots.len());
+
+    tokio::task::spawn_blocking(move || {
+        let pattern_refs: Vec<&str> = patterns.iter().map(String::as_str).collect();
+        ScanEngine::new().scan_custom_paths(&scan_roots, &pattern_refs)
+    })
+    .await
+    .map_err(|e| CleanError::Other(format!("扫描任务异常: {}", e)))
+}
+
+/// 校验自定义扫描路径：必须为绝对路径且不能是驱动器根目录
+fn validate_custom_scan_paths(paths: &[String]) -> Result<Vec<std::path::PathBuf>, CleanError> {
+    if paths.is_empty() {
+        return Err(CleanError::InvalidInput("未指定要扫描的路径".to_string()));
+    }
+
+    paths
+        .iter()
+        .map(|raw| {
+            let path = std::path::PathBuf::from(raw.trim());
+            if !path.is_absolute() {
+                return Err(CleanError::InvalidInput(format!("只支持绝对路径: {}", raw)));
+            }
+            // 整盘扫描会把用户数据全部列为可删除项，必须拒绝
+            if path.parent().is_none() {
+                return Err(CleanError::InvalidInput(format!(
+                    "不允许扫描驱动器根目录: {}",
+                    raw
+                )));
+            }
+            Ok(path)
+        })
+        .collect()
+}
+
+/// 诊断扫描依赖的 TEMP/TMP/USERPROFILE 等路径，排查"临时文件扫描为空"等问题
+#[tauri::command]
+pub async fn diagnose_paths() -> Result<Vec<PathDiagnostic>, CleanError> {
+    tokio::task::spawn_blocking(crate::scanner::diagnose_paths)
+        .await
+        .map_err(|e| CleanError::Other(format!("路径诊断任务异常: {}", e)))
+}
+
+/// 扫描前检查各分类扫描根目录的读写权限，列出需要管理员权限的目录
+#[tauri::command]
+pub async fn check_scope_permissions() -> Result<Vec<ScopePermission>, CleanError> {
+    tokio::task::spawn_blocking(|| ScanEngine::new().check_scope_permissions())
+        .await
+        .map_err(|e| CleanError::Other(format!("权限预检任务异常: {}", e)))
+}
+
 /// 获取所有可用的清理分类
 #[tauri::command]
 pub fn get_categories() -> Vec<CategoryInfo> {
+    let favorites = crate::settings::get_settings().favorite_categories;
     JunkCategory::all()
         .into_iter()
         .map(|c| CategoryInfo {
             name: c.display_name().to_string(),
             description: c.description().to_string(),
             risk_level: c.risk_level(),
+            is_favorite: favorites.iter().any(|name| name == c.display_name()),
         })
         .collect()
 }
 
 /// 扫描系统盘大文件，并实时推送进度
+///
+/// `exclude_dirs` 为额外跳过的目录（绝对路径），回收站、系统还原目录和 WinSxS 始终跳过。
 #[tauri::command]
 pub async fn scan_large_files(
     window: Window,
     top_n: Option<usize>,
     drive_letter: Option<String>,
-) -> Result<Vec<big_files::LargeFileEntry>, String> {
+    allow_network: Option<bool>,
+    low_priority: Option<bool>,
+    exclude_dirs: Option<Vec<String>>,
+) -> Result<Vec<big_files::LargeFileEntry>, CleanError> {
     big_files::reset_cancelled();
+    let _active_scan = crate::scanner::register_active_scan("large_file", big_files::cancel);
     let window = window.clone();
     // 大文件列表会直接渲染到前端，命令层收敛数量，避免异常配置造成界面和扫描压力失控。
     let top_n = top_n.unwrap_or(50).clamp(10, 500);
     let drive_letter = normalize_large_file_drive_letter(drive_letter.as_deref())?;
-    tokio::task::spawn_blocking(move || big_files::scan(&window, top_n, drive_letter))
-        .await
-        .map_err(|e| format!("扫描任务异常: {}", e))?
+    super::ensure_local_scan_target(
+        std::path::Path::new(&format!("{}:\\", drive_letter)),
+        allow_network,
+    )
+    .map_err(CleanError::InvalidInput)?;
+    let low_priority = low_priority.unwrap_or(false);
+    let exclude_dirs = exclude_dirs.unwrap_or_default();
+    let files = tokio::task::spawn_blocking(move || {
+        big_files::scan(&window, top_n, drive_letter, low_priority, &exclude_dirs)
+    })
+    .await
+    .map_err(|e| format!("扫描任务异常: {}", e))??;
+    Ok(files)
+}
+
+/// 扫描大文件并按修改时间分组汇总（30 天内 / 30-90 天 / 90-365 天 / 超过 1 年）
+#[tauri::command]
+pub async fn scan_large_files_by_age(
+    window: Window,
+    top_n: Option<usize>,
+    drive_letter: Option<String>,
+    allow_network: Option<bool>,
+    low_priority: Option<bool>,
+    exclude_dirs: Option<Vec<String>>,
+) -> Result<big_files::LargeFileAgeReport, CleanError> {
+    let files = scan_large_files(
+        window,
+        top_n,
+        drive_letter,
+        allow_network,
+        low_priority,
+        exclude_dirs,
+    )
+    .await?;
+    let buckets = big_files::summarize_by_age(&files, chrono::Local::now().timestamp());
+    Ok(big_files::LargeFileAgeReport { files, buckets })
 }
 
 /// 取消大文件扫描
@@ -148,7 +529,14 @@ pub fn cancel_large_file_scan() {
     big_files::cancel();
 }
 
-fn normalize_large_file_drive_letter(value: Option<&str>) -> Result<char, String> {
+/// 取消所有正在运行的扫描（大文件、垃圾、深度、社交软件、卸载残留、注册表、大目录），
+/// 返回被取消的扫描名称，没有扫描在运行时返回空列表
+#[tauri::command]
+pub fn cancel_all_scans() -> Vec<String> {
+    crate::scanner::cancel_all_scans()
+}
+
+fn normalize_large_file_drive_letter(value: Option<&str>) -> Result<char, CleanError> {
     // 前端只传盘符，但这里仍做兜底校验，避免手动调用命令时传入路径或特殊字符。
     let raw = value
         .and_then(|text| text.chars().find(|ch| ch.is_ascii_alphabetic()))
@@ -162,7 +550,10 @@ fn normalize_large_file_drive_letter(value: Option<&str>) -> Result<char, String
 
     let root = format!("{}:\\", raw);
     if !std::path::Path::new(&root).is_dir() {
-        return Err(format!("磁盘不存在或不可访问: {}", root));
+        return Err(CleanError::NotFound(format!(
+            "磁盘不存在或不可访问: {}",
+            root
+        )));
     }
 
     Ok(raw)
@@ -0,0 +1,36 @@
+// ============================================================================
+// 全局设置命令
+// ============================================================================
+
+use crate::scanner::JunkCategory;
+pub use crate::settings::AppSettings;
+
+/// 读取全局设置
+#[tauri::command]
+pub fn get_settings() -> AppSettings {
+    crate::settings::get_settings()
+}
+
+/// 更新部分设置项，返回保存后的完整设置
+#[tauri::command]
+pub fn update_settings(patch: serde_json::Value) -> Result<AppSettings, String> {
+    crate::settings::update_settings(patch)
+}
+
+/// 读取收藏的清理分类（显示名称）
+#[tauri::command]
+pub fn get_favorite_categories() -> Vec<String> {
+    crate::settings::get_settings().favorite_categories
+}
+
+/// 收藏或取消收藏一个清理分类，返回更新后的收藏列表
+#[tauri::command]
+pub fn toggle_favorite_category(category_name: String) -> Result<Vec<String>, String> {
+    if !JunkCategory::all()
+        .iter()
+        .any(|category| category.display_name() == category_name)
+    {
+        return Err(format!("未知的清理分类: {}", category_name));
+    }
+    crate::settings::toggle_favorite_category(&category_name)
+}
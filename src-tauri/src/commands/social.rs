@@ -2,25 +2,120 @@
 // 社交软件专清命令
 // ============================================================================
 
-use crate::scanner::{SocialScanResult, SocialScanner};
+use crate::cleaner::{EnhancedDeleteEngine, EnhancedDeleteResult};
+use crate::error::CleanError;
+use crate::scanner::{
+    forget_social_files, remember_social_scan, select_social_files_keep_recent,
+    SocialDuplicateReport, SocialScanResult, SocialScanner, SOCIAL_APP_IDS,
+};
 use log::info;
 
 /// 扫描社交软件缓存（带风险分级）
+///
+/// `apps` 为 `SOCIAL_APP_IDS` 中的 ID，只扫描列出的软件；不传时扫描全部。
+/// 实际找到的软件见结果中的 `detected_apps`。
 #[tauri::command]
-pub async fn scan_social_cache() -> Result<SocialScanResult, String> {
-    info!("开始扫描社交软件缓存（带风险分级）");
+pub async fn scan_social_cache(apps: Option<Vec<String>>) -> Result<SocialScanResult, CleanError> {
+    info!("开始扫描社交软件缓存（带风险分级）: {:?}", apps);
+    if let Some(unknown) = apps
+        .iter()
+        .flatten()
+        .find(|app| !SOCIAL_APP_IDS.iter().any(|id| id.eq_ignore_ascii_case(app)))
+    {
+        return Err(CleanError::InvalidInput(format!(
+            "未知的社交软件: {}",
+            unknown
+        )));
+    }
+    crate::scanner::reset_social_scan_cancelled();
+    let _active_scan =
+        crate::scanner::register_active_scan("social", crate::scanner::cancel_social_scan);
 
-    let result = tokio::task::spawn_blocking(|| {
-        let scanner = SocialScanner::new();
+    let result = tokio::task::spawn_blocking(move || {
+        let scanner = SocialScanner::new().with_apps(apps);
         scanner.scan()
     })
     .await
-    .map_err(|e| format!("扫描任务异常: {}", e))?;
+    .map_err(|e| CleanError::Other(format!("扫描任务异常: {}", e)))?;
+    if crate::scanner::is_social_scan_cancelled() {
+        info!("社交软件扫描已取消");
+        return Err(CleanError::Cancelled);
+    }
 
     info!(
         "社交软件扫描完成: {} 个文件, {} 字节, 可删除 {} 个文件 ({} 字节)",
         result.total_files, result.total_size, result.deletable_files, result.deletable_size
     );
+    remember_social_scan(&result);
 
     Ok(result)
 }
+
+/// 清理社交软件分类中的文件，只保留最新的 `keep_recent` 个
+///
+/// 分类来自最近一次 `scan_social_cache` 的结果，按修改时间保留最新文件，
+/// 聊天数据库等不可删除的文件不会被选中。
+#[tauri::command]
+pub async fn delete_social_files_keep_recent(
+    category_id: String,
+    keep_recent: usize,
+) -> Result<EnhancedDeleteResult, String> {
+    let result = tokio::task::spawn_blocking(move || -> Result<EnhancedDeleteResult, String> {
+        let paths = select_social_files_keep_recent(&category_id, keep_recent)?;
+        info!(
+            "社交软件清理: 分类 {} 保留最新 {} 个文件，删除 {} 个",
+            category_id,
+            keep_recent,
+            paths.len()
+        );
+        if paths.is_empty() {
+            return Ok(EnhancedDeleteResult::new());
+        }
+
+        let result = EnhancedDeleteEngine::new().delete_files(&paths);
+        let deleted: Vec<String> = result
+            .file_results
+            .iter()
+            .filter(|file| file.success)
+            .map(|file| file.path.clone())
+            .collect();
+        forget_social_files(&deleted);
+        Ok(result)
+    })
+    .await
+    .map_err(|e| format!("社交软件清理任务异常: {}", e))??;
+
+    super::delete::record_enhanced_reboot_pending(&result);
+    Ok(result)
+}
+
+/// 在最近一次社交软件扫描的图片视频中查找内容相同的文件
+///
+/// 每组建议保留最早的一份，其余副本交给前端确认后删除。
+/// 需要读取文件内容，可通过 `cancel_social_scan` / `cancel_all_scans` 取消。
+#[tauri::command]
+pub async fn find_duplicate_social_media() -> Result<SocialDuplicateReport, CleanError> {
+    crate::scanner::reset_social_scan_cancelled();
+    let _active_scan = crate::scanner::register_active_scan(
+        "social_duplicates",
+        crate::scanner::cancel_social_scan,
+    );
+
+    let report = tokio::task::spawn_blocking(crate::scanner::find_duplicate_social_media)
+        .await
+        .map_err(|e| CleanError::Other(format!("查找重复媒体任务异常: {}", e)))?;
+    if crate::scanner::is_social_scan_cancelled() {
+        info!("社交软件重复媒体查找已取消");
+        return Err(CleanError::Cancelled);
+    }
+    let report = report?;
+
+    info!(
+        "社交软件重复媒体: {} 组, 可删除 {} 个副本, 可释放 {} 字节（跨软件 {} 字节）",
+        report.groups.len(),
+        report.removable_count,
+        report.reclaimable_size,
+        report.cross_app_reclaimable_size
+    );
+    Ok(report)
+}
@@ -3,12 +3,12 @@
 // ============================================================================
 
 use log::info;
-use tauri::Window;
+use tauri::{AppHandle, Window};
 
 // 重新导出供前端使用
 pub use crate::health_score::HealthScoreResult;
 pub use crate::system_info::SystemInfo;
-pub use crate::system_slim::SystemSlimStatus;
+pub use crate::system_slim::{SystemSlimStatus, WindowsUpdateResetResult};
 
 // ============================================================================
 // 系统瘦身
@@ -20,6 +20,21 @@ pub fn check_admin_privilege() -> bool {
     crate::system_slim::check_admin()
 }
 
+/// 以管理员身份重新启动程序，新实例启动后退出当前实例
+#[tauri::command]
+pub fn relaunch_as_admin(app: AppHandle) -> Result<(), String> {
+    crate::system_slim::relaunch_as_admin()?;
+    info!("管理员实例已启动，退出当前实例");
+    app.exit(0);
+    Ok(())
+}
+
+/// 判断执行指定命令前是否需要提权（命令需要管理员且当前未以管理员运行）
+#[tauri::command]
+pub fn request_elevation_for(action: String) -> bool {
+    crate::system_slim::action_requires_admin(&action) && !crate::system_slim::check_admin()
+}
+
 /// 获取系统瘦身状态（异步：避免 DISM 阻塞主线程）
 #[tauri::command]
 pub async fn get_system_slim_status() -> SystemSlimStatus {
@@ -29,25 +44,61 @@ pub async fn get_system_slim_status() -> SystemSlimStatus {
 /// 关闭休眠功能
 #[tauri::command]
 pub fn disable_hibernation() -> Result<String, String> {
-    crate::system_slim::disable_hibernation()
+    let result = crate::system_slim::disable_hibernation();
+    crate::logger::record_audit("disable_hibernation", serde_json::json!({}), &result);
+    result
 }
 
 /// 开启休眠功能
 #[tauri::command]
 pub fn enable_hibernation() -> Result<String, String> {
-    crate::system_slim::enable_hibernation()
+    let result = crate::system_slim::enable_hibernation();
+    crate::logger::record_audit("enable_hibernation", serde_json::json!({}), &result);
+    result
 }
 
 /// 清理 WinSxS 组件存储
 #[tauri::command]
 pub async fn cleanup_winsxs(window: Window) -> Result<String, String> {
-    crate::system_slim::cleanup_winsxs(&window).await
+    let result = crate::system_slim::cleanup_winsxs(&window).await;
+    crate::logger::record_audit("cleanup_winsxs", serde_json::json!({}), &result);
+    result
 }
 
 /// 深度清理 WinSxS 组件基线
 #[tauri::command]
 pub async fn cleanup_winsxs_resetbase(window: Window) -> Result<String, String> {
-    crate::system_slim::cleanup_winsxs_resetbase(&window).await
+    let result = crate::system_slim::cleanup_winsxs_resetbase(&window).await;
+    crate::logger::record_audit("cleanup_winsxs_resetbase", serde_json::json!({}), &result);
+    result
+}
+
+/// 创建系统还原点（需要管理员权限，系统盘需已开启系统保护）
+#[tauri::command]
+pub async fn create_restore_point(description: String) -> Result<(), String> {
+    let params = serde_json::json!({ "description": description.clone() });
+    let result =
+        tokio::task::spawn_blocking(move || crate::system_slim::create_restore_point(&description))
+            .await
+            .map_err(|e| format!("创建系统还原点任务异常: {}", e))?;
+    crate::logger::record_audit("create_restore_point", params, &result);
+    result
+}
+
+/// 优化驱动器（SSD 执行 TRIM，HDD 执行碎片整理），通过 `drive-optimize-progress` 推送进度
+#[tauri::command]
+pub async fn optimize_drive(window: Window, drive: String) -> Result<String, String> {
+    crate::system_slim::optimize_drive(&window, drive).await
+}
+
+/// 停止更新服务后清空 Windows 更新下载缓存，并恢复服务
+#[tauri::command]
+pub async fn reset_windows_update_cache() -> Result<WindowsUpdateResetResult, String> {
+    let result = tokio::task::spawn_blocking(crate::system_slim::reset_windows_update_cache)
+        .await
+        .map_err(|e| format!("重置 Windows 更新缓存任务异常: {}", e))?;
+    crate::logger::record_audit("reset_windows_update_cache", serde_json::json!({}), &result);
+    result
 }
 
 /// 打开系统虚拟内存设置
@@ -76,3 +127,19 @@ pub async fn get_system_info() -> Result<SystemInfo, String> {
     info!("获取系统信息");
     crate::system_info::gather()
 }
+
+/// 查询磁盘介质类型（"SSD" / "HDD"），参数为盘符，如 "C:"
+#[tauri::command]
+pub async fn get_drive_media_type(drive: String) -> Result<String, String> {
+    tokio::task::spawn_blocking(move || crate::system_info::get_drive_media_type(&drive))
+        .await
+        .map_err(|e| format!("查询磁盘类型任务异常: {}", e))?
+}
+
+/// 清空待机内存列表，返回可用物理内存增加的字节数（需要管理员权限）
+#[tauri::command]
+pub async fn clear_standby_memory() -> Result<u64, String> {
+    tokio::task::spawn_blocking(crate::system_info::clear_standby_memory)
+        .await
+        .map_err(|e| format!("清理待机内存任务异常: {}", e))?
+}
@@ -3,11 +3,72 @@
 // ============================================================================
 
 use log::info;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// 移动文件进度事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct MoveFileProgress {
+    pub src: String,
+    /// 已复制字节
+    pub transferred: u64,
+    /// 文件总字节
+    pub total: u64,
+}
 
-/// 格式化文件大小
+/// 格式化文件大小，单位由设置中的 `size_units` 决定
 #[tauri::command]
 pub fn format_size(bytes: u64) -> String {
-    crate::scanner::format_size(bytes)
+    use crate::settings::SizeUnits;
+
+    match crate::settings::get_settings().size_units {
+        SizeUnits::Windows => crate::scanner::format_size(bytes),
+        SizeUnits::Binary => crate::scanner::format_size_with(bytes, true),
+        SizeUnits::Decimal => crate::scanner::format_size_with(bytes, false),
+    }
+}
+
+/// 对文件开启 NTFS 压缩，供大文件视图中想保留但希望缩小的旧文件使用
+#[tauri::command]
+pub async fn compress_file(path: String) -> Result<crate::cleaner::CompressResult, String> {
+    info!("NTFS 压缩: {}", path);
+    tokio::task::spawn_blocking(move || crate::cleaner::compress_file(&path))
+        .await
+        .map_err(|e| format!("压缩任务异常: {}", e))?
+}
+
+/// 将大文件移动到其他磁盘，返回新路径；跨卷复制时推送 `large-file:move-progress` 事件
+#[tauri::command]
+pub async fn move_file(app: AppHandle, src: String, dest_dir: String) -> Result<String, String> {
+    tokio::task::spawn_blocking(move || {
+        let mut last_percent = None;
+        crate::cleaner::move_file(&src, &dest_dir, |transferred, total| {
+            // 系统每复制一块就回调一次，按百分比变化节流，避免事件刷屏
+            let percent = (transferred * 100).checked_div(total).unwrap_or(100);
+            if last_percent == Some(percent) {
+                return;
+            }
+            last_percent = Some(percent);
+            let progress = MoveFileProgress {
+                src: src.clone(),
+                transferred,
+                total,
+            };
+            if let Err(e) = app.emit("large-file:move-progress", progress) {
+                log::warn!("发送移动文件进度失败: {}", e);
+            }
+        })
+    })
+    .await
+    .map_err(|e| format!("移动文件任务异常: {}", e))?
+}
+
+/// 把目录整体迁移到其他位置，并在原路径创建目录联接，应用仍按原路径访问
+#[tauri::command]
+pub async fn relocate_folder_with_junction(src: String, dest: String) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || crate::cleaner::relocate_folder_with_junction(&src, &dest))
+        .await
+        .map_err(|e| format!("目录迁移任务异常: {}", e))?
 }
 
 /// 打开Windows磁盘清理工具
@@ -111,6 +172,14 @@ pub fn open_recycle_bin() -> Result<(), String> {
     }
 }
 
+/// 按驱动器查询回收站的条目数和大小
+#[tauri::command]
+pub async fn get_recycle_bin_details() -> Result<Vec<crate::scanner::RecycleBinInfo>, String> {
+    tokio::task::spawn_blocking(crate::scanner::get_recycle_bin_details)
+        .await
+        .map_err(|e| format!("查询回收站任务异常: {}", e))
+}
+
 /// 打开任务管理器的启动项管理页面
 #[tauri::command]
 pub fn open_startup_manager() -> Result<(), String> {
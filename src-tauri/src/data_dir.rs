@@ -17,8 +17,6 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::sync::RwLock;
 
-use log;
-
 use crate::runtime::{
     current_application_root, current_executable_path, detect_distribution_channel,
     portable_webview_data_directory, DistributionChannel,
@@ -45,12 +43,15 @@ const PORTABLE_MIGRATION_DIR: &str = ".migration";
 const PORTABLE_MIGRATION_STATE_FILE: &str = "legacy_appdata_v1.json";
 
 /// 迁移数据目录时只复制 LightC 明确拥有的数据，避免用户误选磁盘根目录后把无关文件继续带到新位置。
-const MIGRATABLE_DATA_ENTRIES: [&str; 5] = [
+const MIGRATABLE_DATA_ENTRIES: [&str; 8] = [
     "install_history.json",
+    "settings.json",
     "logs",
     "reg_backups",
     "disk_growth_snapshots",
     "driver_backups",
+    "user_whitelist.json",
+    "reboot_pending.json",
 ];
 
 // ============================================================================
@@ -515,7 +516,7 @@ fn write_portable_migration_state(
 }
 
 /// 持久化配置到磁盘
-fn save_config_inner(path: &PathBuf) -> Result<(), String> {
+fn save_config_inner(path: &Path) -> Result<(), String> {
     let cfg_path = config_file_path().ok_or_else(|| "无法确定配置文件路径".to_string())?;
     let parent = cfg_path
         .parent()
@@ -1151,11 +1152,11 @@ fn clear_directory_contents(
         let entry = entry_res.map_err(|e| format!("读取目录条目失败 {}: {}", dir.display(), e))?;
         let path = entry.path();
         // 独立展示的虚拟磁盘数据由专属清理项处理，父级项目只统计其余内容。
-        if entry.file_name().to_str().is_some_and(|name| {
-            excluded_relative_paths
-                .iter()
-                .any(|excluded| *excluded == name)
-        }) {
+        if entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| excluded_relative_paths.contains(&name))
+        {
             continue;
         }
         if path.is_dir() {
@@ -1188,11 +1189,11 @@ fn directory_usage(dir: &Path, excluded_relative_paths: &[&str]) -> Result<(usiz
         let entry = entry_res.map_err(|e| format!("统计目录条目失败 {}: {}", dir.display(), e))?;
         let path = entry.path();
         // 这里只需要过滤当前目录的独立子项，嵌套目录由专属分类整体管理。
-        if entry.file_name().to_str().is_some_and(|name| {
-            excluded_relative_paths
-                .iter()
-                .any(|excluded| *excluded == name)
-        }) {
+        if entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| excluded_relative_paths.contains(&name))
+        {
             continue;
         }
         if path.is_dir() {
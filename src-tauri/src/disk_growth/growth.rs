@@ -8,11 +8,12 @@
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
-use super::mft_scan::{
-    normalize_path, scan_drive_with_progress, DirSizeEntry, DiskGrowthPhaseDuration,
-    DiskGrowthScanProgress, FileSnapshotEntry,
-};
+use super::mft_scan::scan_drive_with_progress;
 use super::snapshot::{build_snapshot, DiskSnapshot, DiskSnapshotEntry, DiskSnapshotManager};
+use super::types::{
+    normalize_path, DirSizeEntry, DiskGrowthPhaseDuration, DiskGrowthScanProgress,
+    FileSnapshotEntry,
+};
 
 const SIGNIFICANT_THRESHOLD: i64 = 1024 * 1024 * 1024;
 const FAST_THRESHOLD: i64 = 300 * 1024 * 1024;
@@ -760,7 +761,7 @@ fn build_analyze_entries(
 
 fn explain_path(path: &str, diff: i64, level: DiskGrowthLevel) -> (String, String) {
     let lower_path = path.to_lowercase();
-    let readable = format_size(diff.abs() as u64);
+    let readable = format_size(diff.unsigned_abs());
     let direction = if diff > 0 { "增加" } else { "减少" };
 
     if lower_path.contains("/windows/softwaredistribution") {
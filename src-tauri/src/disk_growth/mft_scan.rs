@@ -9,13 +9,17 @@
 
 use rayon::prelude::*;
 use rayon::ThreadPoolBuilder;
-use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
 use std::os::windows::fs::{FileExt, OpenOptionsExt};
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{Instant, UNIX_EPOCH};
 
+use super::types::{
+    ensure_not_cancelled, is_disk_growth_cancelled, normalize_path, DirSizeEntry,
+    DiskGrowthPhaseDuration, DiskGrowthScanProgress, FileSizeRecord, FullDiskScanResult,
+};
+
 use winapi::shared::minwindef::{DWORD, LPVOID};
 use winapi::shared::ntdef::HANDLE;
 use winapi::um::errhandlingapi::GetLastError;
@@ -37,29 +41,6 @@ const METADATA_PROGRESS_STEP: usize = 10_000;
 const MFT_SIZE_READ_CHUNK: usize = 16 * 1024 * 1024;
 const WINDOWS_TO_UNIX_SECONDS: i64 = 11_644_473_600;
 
-static DISK_GROWTH_SCAN_CANCELLED: AtomicBool = AtomicBool::new(false);
-
-pub fn reset_disk_growth_cancelled() {
-    DISK_GROWTH_SCAN_CANCELLED.store(false, Ordering::SeqCst);
-}
-
-pub fn cancel_disk_growth_scan() {
-    log::info!("收到取消全盘分析请求");
-    DISK_GROWTH_SCAN_CANCELLED.store(true, Ordering::SeqCst);
-}
-
-fn is_disk_growth_cancelled() -> bool {
-    DISK_GROWTH_SCAN_CANCELLED.load(Ordering::SeqCst)
-}
-
-fn ensure_not_cancelled() -> Result<(), String> {
-    if is_disk_growth_cancelled() {
-        Err("扫描已取消".to_string())
-    } else {
-        Ok(())
-    }
-}
-
 #[repr(C)]
 #[allow(non_snake_case)]
 struct MftEnumDataV0 {
@@ -83,14 +64,6 @@ struct FileRecord {
     parent_id: u64,
 }
 
-#[derive(Debug, Clone)]
-pub(crate) struct FileSizeRecord {
-    pub(crate) parent_id: u64,
-    pub(crate) path: String,
-    pub(crate) size: u64,
-    pub(crate) modified: i64,
-}
-
 struct FileSizeCollection {
     records: Vec<FileSizeRecord>,
     mft_size_count: usize,
@@ -124,52 +97,6 @@ struct NtfsFileSizeReader {
     mft_runs: Vec<DataRun>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DirSizeEntry {
-    pub path: String,
-    pub size: u64,
-    pub depth: u8,
-    pub modified: i64,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FileSnapshotEntry {
-    pub path: String,
-    pub size: u64,
-    #[serde(default)]
-    pub modified: i64,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DiskGrowthPhaseDuration {
-    pub stage: String,
-    pub duration_ms: u64,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DiskGrowthScanProgress {
-    pub stage: String,
-    pub message: String,
-    pub processed: usize,
-    pub total: Option<usize>,
-    pub elapsed_ms: u64,
-}
-
-#[derive(Debug, Clone)]
-pub struct FullDiskScanResult {
-    pub entries: Vec<DirSizeEntry>,
-    // 文件级明细只在后端写快照分片时使用，不进入前端响应，避免超大盘下复制出第二份路径列表。
-    pub(crate) file_records: Vec<FileSizeRecord>,
-    pub total_size: u64,
-    pub total_files_scanned: usize,
-    pub scan_duration_ms: u64,
-    pub root_path: String,
-    pub backend: String,
-    pub phase_durations: Vec<DiskGrowthPhaseDuration>,
-    pub mft_size_count: usize,
-    pub metadata_fallback_count: usize,
-}
-
 pub fn scan_drive_with_progress<F>(
     drive_letter: &str,
     progress: &F,
@@ -1068,10 +995,6 @@ fn read_signed_le(bytes: &[u8]) -> i64 {
     value
 }
 
-pub fn normalize_path(path: &str) -> String {
-    path.to_lowercase().replace('\\', "/")
-}
-
 fn metadata_thread_count() -> usize {
     // metadata 读取是随机 IO，线程过多会让 SSD、Defender 和文件系统过滤驱动互相抢资源。
     num_cpus::get().clamp(4, 8)
@@ -1125,11 +1048,6 @@ fn depth_from_root(path: &str, root: &str) -> usize {
 mod tests {
     use super::*;
 
-    #[test]
-    fn normalize_path_uses_forward_slash_and_lowercase() {
-        assert_eq!(normalize_path("C:\\Users\\Evan"), "c:/users/evan");
-    }
-
     #[test]
     fn depth_from_root_counts_segments() {
         assert_eq!(depth_from_root("C:\\", "C:\\"), 0);
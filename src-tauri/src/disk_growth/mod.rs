@@ -6,8 +6,26 @@
 // ============================================================================
 
 pub mod growth;
+#[cfg(target_os = "windows")]
 pub mod mft_scan;
 pub mod snapshot;
+pub mod types;
 
 pub use growth::*;
-pub use mft_scan::{cancel_disk_growth_scan, reset_disk_growth_cancelled};
+pub use types::{cancel_disk_growth_scan, reset_disk_growth_cancelled};
+
+/// 非 Windows 平台没有 MFT，全盘扫描直接返回不支持
+#[cfg(not(target_os = "windows"))]
+pub mod mft_scan {
+    use super::types::{DiskGrowthScanProgress, FullDiskScanResult};
+
+    pub fn scan_drive_with_progress<F>(
+        _drive_letter: &str,
+        _progress: &F,
+    ) -> Result<FullDiskScanResult, String>
+    where
+        F: Fn(DiskGrowthScanProgress) + Sync,
+    {
+        Err("此功能仅支持Windows系统".to_string())
+    }
+}
@@ -0,0 +1,100 @@
+// ============================================================================
+// 全盘扫描的数据结构和取消标记
+//
+// 快照、变化分析和前端响应都依赖这些类型，与 Windows 专用的 MFT 扫描器分开，
+// 使非 Windows 平台也能编译快照和对比逻辑。
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static DISK_GROWTH_SCAN_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+pub fn reset_disk_growth_cancelled() {
+    DISK_GROWTH_SCAN_CANCELLED.store(false, Ordering::SeqCst);
+}
+
+pub fn cancel_disk_growth_scan() {
+    log::info!("收到取消全盘分析请求");
+    DISK_GROWTH_SCAN_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+pub(crate) fn is_disk_growth_cancelled() -> bool {
+    DISK_GROWTH_SCAN_CANCELLED.load(Ordering::SeqCst)
+}
+
+pub(crate) fn ensure_not_cancelled() -> Result<(), String> {
+    if is_disk_growth_cancelled() {
+        Err("扫描已取消".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct FileSizeRecord {
+    pub(crate) parent_id: u64,
+    pub(crate) path: String,
+    pub(crate) size: u64,
+    pub(crate) modified: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirSizeEntry {
+    pub path: String,
+    pub size: u64,
+    pub depth: u8,
+    pub modified: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSnapshotEntry {
+    pub path: String,
+    pub size: u64,
+    #[serde(default)]
+    pub modified: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskGrowthPhaseDuration {
+    pub stage: String,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskGrowthScanProgress {
+    pub stage: String,
+    pub message: String,
+    pub processed: usize,
+    pub total: Option<usize>,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct FullDiskScanResult {
+    pub entries: Vec<DirSizeEntry>,
+    // 文件级明细只在后端写快照分片时使用，不进入前端响应，避免超大盘下复制出第二份路径列表。
+    pub(crate) file_records: Vec<FileSizeRecord>,
+    pub total_size: u64,
+    pub total_files_scanned: usize,
+    pub scan_duration_ms: u64,
+    pub root_path: String,
+    pub backend: String,
+    pub phase_durations: Vec<DiskGrowthPhaseDuration>,
+    pub mft_size_count: usize,
+    pub metadata_fallback_count: usize,
+}
+
+pub fn normalize_path(path: &str) -> String {
+    path.to_lowercase().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_path_uses_forward_slash_and_lowercase() {
+        assert_eq!(normalize_path("C:\\Users\\Evan"), "c:/users/evan");
+    }
+}
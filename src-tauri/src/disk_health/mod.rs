@@ -5,8 +5,10 @@
 // SMART 私有属性，避免在不同厂商和磁盘类型上产生误导性的“寿命百分比”。
 // ============================================================================
 
+#[cfg(target_os = "windows")]
 use base64::{engine::general_purpose::STANDARD as BASE64_STANDARD, Engine};
 use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+#[cfg(target_os = "windows")]
 use std::process::Command;
 
 const POWERSHELL_TIMEOUT_SECONDS: u64 = 12;
@@ -176,73 +178,54 @@ $partitions = @(Get-CimInstance -Namespace 'root/Microsoft/Windows/Storage' -Cla
 
     // 使用 PowerShell 官方的 UTF-16LE 编码参数，避免生产包中长脚本经过命令行转义后丢失输出。
     let encoded_script = encode_powershell_script(script);
-    let mut child = Command::new("powershell.exe")
-        .args([
-            "-NoLogo",
-            "-NoProfile",
-            "-NonInteractive",
-            "-WindowStyle",
-            "Hidden",
-            "-ExecutionPolicy",
-            "Bypass",
-            "-EncodedCommand",
-            encoded_script.as_str(),
-        ])
-        // PowerShell 需要依赖重定向管道返回 JSON；不使用 DETACHED_PROCESS，
-        // 避免部分 GUI/管理员环境下子进程 stdout 管道为空，同时保留无控制台标志。
-        .creation_flags(POWERSHELL_PROCESS_FLAGS)
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .spawn()
-        .map_err(|error| format!("无法启动 Windows 磁盘信息查询: {}", error))?;
-
-    let deadline = std::time::Instant::now()
-        .checked_add(std::time::Duration::from_secs(POWERSHELL_TIMEOUT_SECONDS))
-        .ok_or_else(|| "磁盘信息查询超时时间无效".to_string())?;
-    loop {
-        if let Some(status) = child
-            .try_wait()
-            .map_err(|error| format!("等待磁盘信息查询失败: {}", error))?
-        {
-            let output = child
-                .wait_with_output()
-                .map_err(|error| format!("读取磁盘信息查询结果失败: {}", error))?;
-            if !status.success() {
-                let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
-                return Err(if error.is_empty() {
-                    "Windows 磁盘信息查询失败".to_string()
-                } else {
-                    format!("Windows 磁盘信息查询失败: {}", error)
-                });
-            }
-            let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            if stdout.is_empty() {
-                let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-                let exit_code = status
-                    .code()
-                    .map_or_else(|| "未知".to_string(), |code| code.to_string());
-                return Err(if stderr.is_empty() {
-                    format!(
-                        "Windows 未返回磁盘信息（PowerShell 退出码 {}，没有 JSON 输出）",
-                        exit_code
-                    )
-                } else {
-                    format!(
-                        "Windows 未返回磁盘信息（PowerShell 退出码 {}）: {}",
-                        exit_code, stderr
-                    )
-                });
-            }
-            return Ok(stdout);
-        }
-
-        if std::time::Instant::now() >= deadline {
-            let _ = child.kill();
-            let _ = child.wait();
-            return Err("读取磁盘信息超时，请稍后重试".to_string());
-        }
-        std::thread::sleep(std::time::Duration::from_millis(40));
+    let output = crate::process_runner::run_with_timeout(
+        Command::new("powershell.exe")
+            .args([
+                "-NoLogo",
+                "-NoProfile",
+                "-NonInteractive",
+                "-WindowStyle",
+                "Hidden",
+                "-ExecutionPolicy",
+                "Bypass",
+                "-EncodedCommand",
+                encoded_script.as_str(),
+            ])
+            // PowerShell 需要依赖重定向管道返回 JSON；不使用 DETACHED_PROCESS，
+            // 避免部分 GUI/管理员环境下子进程 stdout 管道为空，同时保留无控制台标志。
+            .creation_flags(POWERSHELL_PROCESS_FLAGS),
+        std::time::Duration::from_secs(POWERSHELL_TIMEOUT_SECONDS),
+    )
+    .map_err(|error| format!("读取磁盘信息失败: {}", error))?;
+    let status = output.status;
+
+    if !status.success() {
+        let error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if error.is_empty() {
+            "Windows 磁盘信息查询失败".to_string()
+        } else {
+            format!("Windows 磁盘信息查询失败: {}", error)
+        });
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if stdout.is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let exit_code = status
+            .code()
+            .map_or_else(|| "未知".to_string(), |code| code.to_string());
+        return Err(if stderr.is_empty() {
+            format!(
+                "Windows 未返回磁盘信息（PowerShell 退出码 {}，没有 JSON 输出）",
+                exit_code
+            )
+        } else {
+            format!(
+                "Windows 未返回磁盘信息（PowerShell 退出码 {}）: {}",
+                exit_code, stderr
+            )
+        });
     }
+    Ok(stdout)
 }
 
 #[cfg(target_os = "windows")]
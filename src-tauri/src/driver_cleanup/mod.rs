@@ -13,6 +13,7 @@ use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+#[cfg(target_os = "windows")]
 use std::process::Command;
 use std::sync::{LazyLock, Mutex};
 use walkdir::WalkDir;
@@ -20,6 +21,9 @@ use walkdir::WalkDir;
 const DRIVER_BACKUP_DIR: &str = "driver_backups";
 #[cfg(target_os = "windows")]
 const HIDDEN_PROCESS_FLAGS: u32 = 0x08000000 | 0x00000008;
+// 导出驱动包需要复制整个 Driver Store 目录，给足时间但不允许无限等待。
+#[cfg(target_os = "windows")]
+const PNPUTIL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10 * 60);
 
 // 删除操作会修改系统 Driver Store，串行化后端请求可以避免两个清理任务交叉备份或删除。
 static DRIVER_DELETE_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
@@ -39,6 +43,8 @@ pub struct DriverPackageInfo {
     pub installed_device_count: usize,
     pub outranked_device_count: usize,
     pub file_count: usize,
+    /// Driver Store 中该驱动包目录占用的空间（字节）
+    pub package_size: u64,
     pub status: String,
     pub actionable: bool,
     pub reason: String,
@@ -51,6 +57,8 @@ pub struct DriverScanResult {
     pub total_count: usize,
     pub candidate_count: usize,
     pub high_confidence_count: usize,
+    /// 全部可处理候选驱动包占用的空间（字节）
+    pub candidate_size: u64,
     pub device_match_data_available: bool,
 }
 
@@ -116,12 +124,20 @@ pub fn scan() -> Result<DriverScanResult, String> {
             (Vec::new(), false)
         }
     };
-    let packages = classify_packages(raw_packages, &device_matches);
+    let mut packages = classify_packages(raw_packages, &device_matches);
+    for package in &mut packages {
+        package.package_size = driver_store_size(&package.driver_store_path);
+    }
     let candidate_count = packages.iter().filter(|package| package.actionable).count();
     let high_confidence_count = packages
         .iter()
         .filter(|package| package.status == "old_confirmed")
         .count();
+    let candidate_size = packages
+        .iter()
+        .filter(|package| package.actionable)
+        .map(|package| package.package_size)
+        .sum();
 
     Ok(DriverScanResult {
         is_admin: crate::system_slim::check_admin(),
@@ -129,6 +145,7 @@ pub fn scan() -> Result<DriverScanResult, String> {
         packages,
         candidate_count,
         high_confidence_count,
+        candidate_size,
         device_match_data_available,
     })
 }
@@ -211,8 +228,7 @@ pub fn delete(published_names: Vec<String>) -> Result<DriverDeleteResult, String
     for package in selected_packages {
         let published_name = package.published_name;
         // /uninstall 只解除该包与已断开设备的关联；正在运行的设备在扫描和后端复核中仍会被拦截。
-        let command_result =
-            run_pnputil(&["/delete-driver", published_name.as_str(), "/uninstall"]);
+        let command_result = run_pnputil(["/delete-driver", published_name.as_str(), "/uninstall"]);
         let (command_success, output_text) = match command_result {
             Ok(output) => (output.status_success, format_command_output(&output.output)),
             Err(error) => (false, error),
@@ -294,7 +310,7 @@ fn enumerate_driver_packages() -> Result<Vec<RawDriverPackage>, String> {
     let xml_path = temporary_xml_path();
     let xml_path_text = xml_path.to_string_lossy().to_string();
     let xml_result = (|| {
-        let output = run_pnputil(&[
+        let output = run_pnputil([
             "/enum-drivers",
             "/files",
             "/ids",
@@ -354,7 +370,7 @@ fn enumerate_device_matches() -> Result<Vec<RawDeviceMatch>, String> {
     let xml_path = temporary_xml_path();
     let xml_path_text = xml_path.to_string_lossy().to_string();
     let result = (|| {
-        let output = run_pnputil(&[
+        let output = run_pnputil([
             "/enum-devices",
             "/drivers",
             "/format",
@@ -487,6 +503,7 @@ fn classify_packages(
                 installed_device_count,
                 outranked_device_count,
                 file_count: package.file_count,
+                package_size: 0,
                 status: status.to_string(),
                 actionable,
                 reason,
@@ -572,7 +589,7 @@ fn backup_driver_package(package: &DriverPackageInfo, backup_root: &Path) -> Res
             error
         )
     })?;
-    let output = run_pnputil(&[
+    let output = run_pnputil([
         "/export-driver",
         package.published_name.as_str(),
         &backup_directory.to_string_lossy(),
@@ -691,14 +708,12 @@ fn parse_driver_xml(path: &Path) -> Result<Vec<RawDriverPackage>, String> {
                     }
                 }
             }
-            Ok(Event::Text(event)) => {
-                if current_text_element.is_some() {
-                    current_text.push_str(
-                        &event
-                            .decode()
-                            .map_err(|error| format!("解析 pnputil XML 文本失败: {}", error))?,
-                    );
-                }
+            Ok(Event::Text(event)) if current_text_element.is_some() => {
+                current_text.push_str(
+                    &event
+                        .decode()
+                        .map_err(|error| format!("解析 pnputil XML 文本失败: {}", error))?,
+                );
             }
             Ok(Event::End(event)) => {
                 let element_name = event.local_name();
@@ -771,14 +786,12 @@ fn parse_device_matches_xml(path: &Path) -> Result<Vec<RawDeviceMatch>, String>
                     current_text.clear();
                 }
             }
-            Ok(Event::Text(event)) => {
-                if current_text_element.is_some() {
-                    current_text.push_str(
-                        &event
-                            .decode()
-                            .map_err(|error| format!("解析设备匹配 XML 文本失败: {}", error))?,
-                    );
-                }
+            Ok(Event::Text(event)) if current_text_element.is_some() => {
+                current_text.push_str(
+                    &event
+                        .decode()
+                        .map_err(|error| format!("解析设备匹配 XML 文本失败: {}", error))?,
+                );
             }
             Ok(Event::End(event)) => {
                 let element_name = event.local_name();
@@ -996,7 +1009,7 @@ fn normalize_text_field_name(name: &str) -> String {
 }
 
 fn matches_any(value: &str, candidates: &[&str]) -> bool {
-    candidates.iter().any(|candidate| value == *candidate)
+    candidates.contains(&value)
 }
 
 fn build_driver_store_path(driver_package_id: &str) -> String {
@@ -1025,6 +1038,20 @@ fn build_driver_store_path(driver_package_id: &str) -> String {
     }
 }
 
+/// 统计驱动包在 Driver Store 中的目录大小，只读取元数据，不会修改任何文件。
+fn driver_store_size(driver_store_path: &str) -> u64 {
+    if driver_store_path.is_empty() {
+        return 0;
+    }
+    WalkDir::new(driver_store_path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
 fn read_attribute(
     event: &quick_xml::events::BytesStart<'_>,
     name: &[u8],
@@ -1084,13 +1111,15 @@ where
     {
         use std::os::windows::process::CommandExt;
 
-        let output = Command::new(pnputil_path())
-            .args(arguments)
-            // release 包是 Windows GUI 子系统；DETACHED_PROCESS 强制脱离父控制台，
-            // CREATE_NO_WINDOW 继续兜底，避免管理员环境下 pnputil 创建可见窗口。
-            .creation_flags(HIDDEN_PROCESS_FLAGS)
-            .output()
-            .map_err(|error| format!("启动 pnputil 失败: {}", error))?;
+        let output = crate::process_runner::run_with_timeout(
+            Command::new(pnputil_path())
+                .args(arguments)
+                // release 包是 Windows GUI 子系统；DETACHED_PROCESS 强制脱离父控制台，
+                // CREATE_NO_WINDOW 继续兜底，避免管理员环境下 pnputil 创建可见窗口。
+                .creation_flags(HIDDEN_PROCESS_FLAGS),
+            PNPUTIL_TIMEOUT,
+        )
+        .map_err(|error| format!("启动 pnputil 失败: {}", error))?;
         return Ok(CommandResult {
             status_success: output.status.success(),
             output: PnputilOutput {
@@ -1243,12 +1272,11 @@ fn decode_windows_output(raw: &[u8]) -> String {
 #[cfg(test)]
 mod tests {
     use super::{
-        compare_versions, format_command_output, normalize_published_names,
+        compare_versions, driver_store_size, format_command_output, normalize_published_names,
         parse_device_matches_xml, parse_driver_text, parse_driver_version, PnputilOutput,
     };
     use std::cmp::Ordering;
     use std::fs;
-    use std::path::PathBuf;
 
     #[test]
     fn parses_pnputil_version_with_date_prefix() {
@@ -1264,6 +1292,23 @@ mod tests {
         assert_eq!(compare_versions(&[1, 3], &[1, 2, 9]), Ordering::Greater);
     }
 
+    #[test]
+    fn sums_driver_store_directory_size() {
+        let directory = std::env::temp_dir().join(format!(
+            "lightc_driver_store_size_test_{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(directory.join("x64")).expect("create test directory");
+        fs::write(directory.join("example.inf"), vec![0u8; 100]).expect("write test INF");
+        fs::write(directory.join("x64").join("example.sys"), vec![0u8; 250])
+            .expect("write test SYS");
+        let size = driver_store_size(&directory.to_string_lossy());
+        fs::remove_dir_all(&directory).expect("remove test directory");
+
+        assert_eq!(size, 350);
+        assert_eq!(driver_store_size(""), 0);
+    }
+
     #[test]
     fn rejects_non_published_driver_names() {
         assert!(normalize_published_names(&["DriverStore\\oem1.inf".to_string()]).is_err());
@@ -1275,7 +1320,7 @@ mod tests {
 
     #[test]
     fn parses_current_and_outranked_device_drivers() {
-        let path = PathBuf::from(std::env::temp_dir()).join(format!(
+        let path = std::env::temp_dir().join(format!(
             "lightc_driver_match_test_{}_{}.xml",
             std::process::id(),
             1
@@ -0,0 +1,112 @@
+// ============================================================================
+// 命令错误类型
+//
+// 命令原先统一返回 `Result<T, String>`，前端只能拿到一句中文提示，无法区分
+// "需要管理员权限"和"文件不存在"，只能匹配文案来决定是否提示以管理员身份重启。
+// `CleanError` 序列化为 `{ code, message }`，前端按 `code` 分支，`message`
+// 仍是原来的中文提示，可直接展示。
+//
+// 内部模块仍返回 `String` 错误，经 `From<String>` 归为 `Other`；命令层在能判断
+// 出具体原因的位置显式构造对应变体。
+// ============================================================================
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// 扫描和删除命令的错误
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CleanError {
+    /// 需要以管理员身份运行
+    #[error("{0}")]
+    NeedsAdmin(String),
+    /// 路径、分类或扫描会话不存在
+    #[error("{0}")]
+    NotFound(String),
+    /// 系统拒绝访问
+    #[error("{0}")]
+    PermissionDenied(String),
+    /// 用户取消了操作
+    #[error("操作已取消")]
+    Cancelled,
+    /// 参数不合法（路径格式、风险等级等）
+    #[error("{0}")]
+    InvalidInput(String),
+    /// 文件读写失败
+    #[error("{0}")]
+    Io(String),
+    /// 其他错误
+    #[error("{0}")]
+    Other(String),
+}
+
+impl CleanError {
+    /// 前端用于分支判断的错误码
+    pub fn code(&self) -> &'static str {
+        match self {
+            CleanError::NeedsAdmin(_) => "needs_admin",
+            CleanError::NotFound(_) => "not_found",
+            CleanError::PermissionDenied(_) => "permission_denied",
+            CleanError::Cancelled => "cancelled",
+            CleanError::InvalidInput(_) => "invalid_input",
+            CleanError::Io(_) => "io",
+            CleanError::Other(_) => "other",
+        }
+    }
+}
+
+impl Serialize for CleanError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("CleanError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+impl From<String> for CleanError {
+    fn from(message: String) -> Self {
+        CleanError::Other(message)
+    }
+}
+
+impl From<&str> for CleanError {
+    fn from(message: &str) -> Self {
+        CleanError::Other(message.to_string())
+    }
+}
+
+impl From<std::io::Error> for CleanError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::NotFound => CleanError::NotFound(error.to_string()),
+            std::io::ErrorKind::PermissionDenied => CleanError::PermissionDenied(error.to_string()),
+            _ => CleanError::Io(error.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_error_serializes_code_and_message() {
+        let error = CleanError::NeedsAdmin("需要管理员权限".to_string());
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], "needs_admin");
+        assert_eq!(json["message"], "需要管理员权限");
+
+        let json = serde_json::to_value(CleanError::Cancelled).unwrap();
+        assert_eq!(json["code"], "cancelled");
+        assert_eq!(json["message"], "操作已取消");
+    }
+
+    #[test]
+    fn test_io_error_maps_by_kind() {
+        let missing = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        assert_eq!(CleanError::from(missing).code(), "not_found");
+        let denied = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        assert_eq!(CleanError::from(denied).code(), "permission_denied");
+        assert_eq!(CleanError::from("未知".to_string()).code(), "other");
+    }
+}
@@ -19,6 +19,12 @@ pub struct HealthScoreResult {
     pub junk_score: u32,
     /// C盘剩余百分比
     pub disk_free_percent: f64,
+    /// C盘总容量（字节，获取失败时为 0）
+    pub total_space: u64,
+    /// C盘可用空间（字节）
+    pub free_space: u64,
+    /// C盘已用空间（字节）
+    pub used_space: u64,
     /// 是否存在休眠文件
     pub has_hibernation: bool,
     /// 休眠文件大小
@@ -36,31 +42,44 @@ pub struct HealthScoreResult {
 pub fn calculate() -> HealthScoreResult {
     info!("计算系统健康评分...");
 
-    let (disk_free_percent, disk_score) = calculate_disk_score();
+    let disk = calculate_disk_score();
     let (has_hibernation, hibernation_size, hibernation_score) = calculate_hibernation_score();
     let (junk_size, junk_score) = calculate_junk_score();
 
-    let score = disk_score + hibernation_score + junk_score;
+    let score = disk.score + hibernation_score + junk_score;
 
     info!(
         "健康评分: {} (磁盘:{}, 休眠:{}, 垃圾:{})",
-        score, disk_score, hibernation_score, junk_score
+        score, disk.score, hibernation_score, junk_score
     );
 
     HealthScoreResult {
         score,
-        disk_score,
+        disk_score: disk.score,
         hibernation_score,
         junk_score,
-        disk_free_percent,
+        disk_free_percent: disk.free_percent,
+        total_space: disk.total_space,
+        free_space: disk.free_space,
+        used_space: disk.total_space.saturating_sub(disk.free_space),
         has_hibernation,
         hibernation_size,
         junk_size,
     }
 }
 
+/// 磁盘空间评分及评分所用的容量数据
+struct DiskScore {
+    free_percent: f64,
+    score: u32,
+    total_space: u64,
+    free_space: u64,
+}
+
 /// 计算磁盘空间评分 (满分40)
-fn calculate_disk_score() -> (f64, u32) {
+///
+/// 容量数据与评分来自同一次 GetDiskFreeSpaceExW 调用，避免两次查询结果不一致。
+fn calculate_disk_score() -> DiskScore {
     #[cfg(target_os = "windows")]
     {
         use std::ffi::OsStr;
@@ -107,11 +126,22 @@ fn calculate_disk_score() -> (f64, u32) {
             } else {
                 (free_percent / 5.0 * 10.0) as u32
             };
-            return (free_percent, score.min(40));
+            return DiskScore {
+                free_percent,
+                score: score.min(40),
+                total_space: total_bytes,
+                free_space: free_bytes,
+            };
         }
     }
 
-    (50.0, 20) // 非 Windows 或调用失败时的默认值
+    // 非 Windows 或调用失败时的默认值
+    DiskScore {
+        free_percent: 50.0,
+        score: 20,
+        total_space: 0,
+        free_space: 0,
+    }
 }
 
 /// 计算休眠文件评分 (满分30)
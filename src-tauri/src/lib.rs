@@ -3,6 +3,9 @@
 // Windows专属的智能磁盘清理工具
 // ============================================================================
 
+// 非 Windows 平台只编译占位实现，仅供 Windows 调用的辅助函数和数据结构在此处必然未被使用
+#![cfg_attr(not(windows), allow(dead_code))]
+
 // 模块声明
 mod ai_models;
 mod cleaner;
@@ -11,10 +14,13 @@ mod data_dir;
 mod disk_growth;
 mod disk_health;
 mod driver_cleanup;
+mod error;
 mod health_score;
 mod logger;
+mod process_runner;
 mod runtime;
 mod scanner;
+mod settings;
 mod system_info;
 mod system_slim;
 
@@ -48,6 +54,8 @@ async fn close_splashscreen(app: tauri::AppHandle) -> Result<(), String> {
 pub fn run() {
     // 初始化日志
     env_logger::init();
+    #[cfg(not(windows))]
+    log::warn!("当前不是 Windows 系统，注册表扫描、卸载残留、永久删除等功能将返回不支持");
 
     // 便携版必须在 Tauri 自动创建窗口前指定 WebView2 绝对数据目录，
     // 否则 localStorage 会继续落到 AppData，便携包移动后设置不会跟随。
@@ -81,62 +89,123 @@ pub fn run() {
             // 磁盘信息
             get_disk_info,
             get_local_drives,
+            verify_freed_space,
             get_disk_health,
             // 扫描相关
             scan_junk_files,
+            get_category_files,
+            scan_all_user_profiles,
+            scan_uwp_caches,
+            estimate_scan_scope,
+            estimate_reclaimable,
+            get_top_recommendation,
+            scan_app_logs,
+            get_largest_junk_files,
+            export_scan_json,
             scan_deep_junk_files,
             cancel_deep_junk_scan,
             get_deep_junk_category_page,
             scan_category,
+            scan_paths,
             scan_large_files,
+            scan_large_files_by_age,
             cancel_large_file_scan,
+            cancel_all_scans,
             scan_social_cache,
+            delete_social_files_keep_recent,
+            find_duplicate_social_media,
             get_categories,
+            diagnose_paths,
+            check_scope_permissions,
             // 删除相关
             delete_files,
+            probe_delete,
+            explain_path,
+            validate_paths,
+            build_delete_plan,
+            delete_scan_category,
+            clean_uwp_caches,
+            reclean_session,
+            get_defender_cache_info,
+            clean_defender_cache,
+            get_cbs_log_info,
+            clean_cbs_logs,
+            clean_categories,
+            safe_clean,
+            emergency_free_space,
+            run_cleanup_pipeline,
             // 工具函数
             format_size,
+            compress_file,
+            move_file,
+            relocate_folder_with_junction,
             open_disk_cleanup,
             open_in_folder,
             open_file,
             open_recycle_bin,
+            get_recycle_bin_details,
             // 系统瘦身
             check_admin_privilege,
+            relaunch_as_admin,
+            request_elevation_for,
             get_system_slim_status,
             disable_hibernation,
             enable_hibernation,
             cleanup_winsxs,
             cleanup_winsxs_resetbase,
+            create_restore_point,
+            optimize_drive,
+            reset_windows_update_cache,
             open_virtual_memory_settings,
             // 旧驱动清理
             scan_old_drivers,
             delete_old_drivers,
+            remove_driver_package,
             restore_all_driver_backups,
             open_driver_backup_dir,
             // 健康评分
             get_health_score,
             // 卸载残留和注册表清理
             scan_uninstall_leftovers,
+            cancel_leftover_scan,
+            export_findings,
             delete_leftover_folders,
+            get_installed_programs,
+            scan_scheduled_tasks,
+            delete_scheduled_task,
+            get_whitelist,
+            add_whitelist_entry,
+            remove_whitelist_entry,
             scan_registry_redundancy,
             delete_registry_entries,
+            get_ignored_registry_entries,
+            ignore_registry_entry,
+            unignore_registry_entry,
             open_registry_backup_dir,
             // 增强删除
             enhanced_delete_files,
             delete_deep_junk_files,
             get_physical_size,
             check_admin_for_path,
+            get_pending_reboot_deletions,
+            get_reboot_pending_status,
+            cancel_pending_reboot_deletion,
             // 永久删除（深度清理）
             delete_leftovers_permanent,
             check_leftover_safety,
             // 系统信息
             get_system_info,
+            get_drive_media_type,
+            clear_standby_memory,
             get_distribution_channel,
             verify_integrity,
             // 清理日志
             record_cleanup_action,
             open_logs_folder,
             get_cleanup_history,
+            get_cleanup_session,
+            get_audit_log,
+            export_cleanup_report_html,
             // C盘热点扫描
             scan_hotspot,
             cancel_hotspot_scan,
@@ -155,6 +224,7 @@ pub fn run() {
             get_disk_growth_directory_details,
             // 数据目录管理
             get_data_directory,
+            open_app_data_folder,
             get_storage_location_info,
             migrate_legacy_portable_data,
             set_data_directory,
@@ -162,6 +232,11 @@ pub fn run() {
             list_clearable_data_items,
             clear_selected_local_data,
             pick_folder_dialog,
+            // 全局设置
+            get_settings,
+            update_settings,
+            get_favorite_categories,
+            toggle_favorite_category,
             // AI 资产分析
             scan_ai_model_assets,
             delete_ai_model,
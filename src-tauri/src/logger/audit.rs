@@ -0,0 +1,183 @@
+// ============================================================================
+// 操作审计日志
+//
+// 清理日志只记录文件删除。关闭休眠、WinSxS 清理、删除注册表和驱动等需要管理员权限
+// 的系统级操作另外写入审计日志，受管电脑上需要据此追溯"谁在什么权限下做了什么"。
+//
+// 格式为 JSONL（每行一条记录），只追加不改写；当前文件超过 1 MB 时轮转为
+// audit.1.jsonl、audit.2.jsonl……，最多保留 5 份历史文件。
+//
+// 存储位置：<数据目录>/logs/audit/audit.jsonl
+// ============================================================================
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 当前审计日志文件名
+const AUDIT_LOG_FILE: &str = "audit.jsonl";
+
+/// 当前文件超过该大小后轮转（1 MB）
+const MAX_AUDIT_FILE_SIZE: u64 = 1024 * 1024;
+
+/// 保留的历史审计文件数量
+const MAX_AUDIT_ARCHIVES: usize = 5;
+
+/// `get_audit_log` 默认返回的记录数
+const DEFAULT_AUDIT_LIMIT: usize = 500;
+
+/// 串行化写入，避免并发命令的记录交错或轮转时丢行
+static AUDIT_WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 单条审计记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// 操作时间（本地时间，ISO 8601）
+    pub timestamp: String,
+    /// 命令名称，如 "disable_hibernation"
+    pub action: String,
+    /// 命令参数
+    pub params: serde_json::Value,
+    /// 执行时是否以管理员身份运行
+    pub elevated: bool,
+    /// 是否成功
+    pub success: bool,
+    /// 失败原因
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 记录一次系统级操作；写入失败只记录日志，不影响命令结果
+pub fn record_audit<T, E: std::fmt::Display>(
+    action: &str,
+    params: serde_json::Value,
+    result: &Result<T, E>,
+) {
+    let entry = AuditEntry {
+        timestamp: Local::now().to_rfc3339(),
+        action: action.to_string(),
+        params,
+        elevated: crate::system_slim::check_admin(),
+        success: result.is_ok(),
+        error: result.as_ref().err().map(ToString::to_string),
+    };
+    if let Err(e) = append_audit_entry(&audit_dir(&crate::data_dir::get_data_dir()), &entry) {
+        log::warn!("写入审计日志失败 ({}): {}", action, e);
+    }
+}
+
+/// 读取审计日志，最新的记录在前
+pub fn get_audit_log(app_data_dir: &Path, limit: Option<usize>) -> Result<Vec<AuditEntry>, String> {
+    let dir = audit_dir(app_data_dir);
+    let limit = limit.unwrap_or(DEFAULT_AUDIT_LIMIT);
+    let mut entries = Vec::new();
+
+    // 从当前文件到最旧的历史文件，每个文件内倒序读取
+    for index in 0..=MAX_AUDIT_ARCHIVES {
+        let Ok(content) = fs::read_to_string(audit_file(&dir, index)) else {
+            continue;
+        };
+        for line in content.lines().rev() {
+            if entries.len() >= limit {
+                return Ok(entries);
+            }
+            // 进程被结束时最后一行可能不完整，跳过即可
+            if let Ok(entry) = serde_json::from_str::<AuditEntry>(line) {
+                entries.push(entry);
+            }
+        }
+    }
+    Ok(entries)
+}
+
+fn audit_dir(app_data_dir: &Path) -> PathBuf {
+    app_data_dir.join("logs").join("audit")
+}
+
+/// 第 `index` 份审计文件：0 为当前文件，1.. 为历史文件（数字越大越旧）
+fn audit_file(dir: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        dir.join(AUDIT_LOG_FILE)
+    } else {
+        dir.join(format!("audit.{}.jsonl", index))
+    }
+}
+
+fn append_audit_entry(dir: &Path, entry: &AuditEntry) -> Result<(), String> {
+    let line = serde_json::to_string(entry).map_err(|e| format!("序列化审计记录失败: {}", e))?;
+    let _guard = AUDIT_WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    fs::create_dir_all(dir).map_err(|e| format!("创建审计日志目录失败: {}", e))?;
+    rotate_if_needed(dir, MAX_AUDIT_FILE_SIZE)?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(audit_file(dir, 0))
+        .map_err(|e| format!("打开审计日志失败: {}", e))?;
+    writeln!(file, "{}", line).map_err(|e| format!("写入审计日志失败: {}", e))
+}
+
+/// 当前文件达到 `max_size` 时依次后移历史文件，最旧的一份被覆盖
+fn rotate_if_needed(dir: &Path, max_size: u64) -> Result<(), String> {
+    let current = audit_file(dir, 0);
+    if fs::metadata(&current).map_or(true, |metadata| metadata.len() < max_size) {
+        return Ok(());
+    }
+    for index in (1..MAX_AUDIT_ARCHIVES).rev() {
+        let from = audit_file(dir, index);
+        if from.exists() {
+            fs::rename(&from, audit_file(dir, index + 1))
+                .map_err(|e| format!("轮转审计日志失败: {}", e))?;
+        }
+    }
+    fs::rename(&current, audit_file(dir, 1)).map_err(|e| format!("轮转审计日志失败: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(action: &str) -> AuditEntry {
+        AuditEntry {
+            timestamp: "2026-01-01T00:00:00+08:00".to_string(),
+            action: action.to_string(),
+            params: serde_json::json!({ "count": 1 }),
+            elevated: true,
+            success: true,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_audit_log_appends_rotates_and_reads_newest_first() {
+        let root = std::env::temp_dir().join(format!("lightc-audit-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let dir = audit_dir(&root);
+
+        append_audit_entry(&dir, &entry("disable_hibernation")).unwrap();
+        append_audit_entry(&dir, &entry("cleanup_winsxs")).unwrap();
+        rotate_if_needed(&dir, 1).unwrap();
+        append_audit_entry(&dir, &entry("delete_registry_entries")).unwrap();
+
+        let all = get_audit_log(&root, None).unwrap();
+        let limited = get_audit_log(&root, Some(2)).unwrap();
+        let archived = audit_file(&dir, 1).exists();
+        let _ = fs::remove_dir_all(&root);
+
+        let actions: Vec<&str> = all.iter().map(|e| e.action.as_str()).collect();
+        assert_eq!(
+            actions,
+            vec![
+                "delete_registry_entries",
+                "cleanup_winsxs",
+                "disable_hibernation"
+            ]
+        );
+        assert_eq!(limited.len(), 2);
+        assert!(archived);
+    }
+}
@@ -7,6 +7,8 @@
 // 2. 通过 std::fs::read_dir 统计文件数量，实现日志轮转（默认只保留10份）
 // 3. 异步写入日志，不阻塞主线程
 // 4. 即使日志写入失败，清理逻辑也能继续运行
+// 5. 先写临时文件再重命名，进程中途被结束也不会留下截断的日志；
+//    历史读取遇到无法解析的日志时移入 corrupt/ 子目录，不影响其他记录
 //
 // 日志存储位置：AppData/Roaming/LightC/logs/
 // 文件命名格式：cleanup_YYYYMMDD_HHMMSS.json
@@ -20,11 +22,23 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
+mod audit;
+mod report;
+
+pub use audit::*;
+pub use report::*;
+
 /// 默认最大保留的日志文件数量
 const DEFAULT_MAX_LOG_FILES: usize = 10;
 const MIN_LOG_FILES: usize = 1;
 const MAX_LOG_FILES_LIMIT: usize = 100;
 
+/// 写入中的临时日志后缀（不是 .json，不会被历史读取和日志轮转统计）
+const LOG_TEMP_EXTENSION: &str = "json.tmp";
+
+/// 无法解析的日志隔离目录名
+const CORRUPT_LOG_DIR: &str = "corrupt";
+
 fn normalize_log_retention(max_log_files: Option<usize>) -> usize {
     // 日志保留数来自前端本地设置，后端再次收敛边界，防止手动篡改 localStorage 导致无限保留或清空过多日志。
     max_log_files
@@ -208,7 +222,7 @@ impl CleanupLogger {
     ///
     /// 使用 serde_json 序列化日志数据：
     /// 1. 调用 serde_json::to_string_pretty() 生成格式化的 JSON
-    /// 2. 使用 std::fs::write() 写入文件
+    /// 2. 写入临时文件后重命名为正式日志（write_log_file）
     /// 3. 即使写入失败也不会影响程序运行
     pub async fn finish_session(&self) -> Result<PathBuf, String> {
         let mut session_guard = self.current_session.lock().await;
@@ -225,7 +239,7 @@ impl CleanupLogger {
             match serde_json::to_string_pretty(session) {
                 Ok(json_content) => {
                     // 写入文件，使用 Result 处理错误
-                    match fs::write(&log_path, json_content) {
+                    match write_log_file(&log_path, &json_content) {
                         Ok(_) => {
                             info!("清理日志已保存: {:?}", log_path);
 
@@ -286,7 +300,7 @@ impl CleanupLogger {
         let json_content =
             serde_json::to_string_pretty(&session).map_err(|e| format!("序列化失败: {}", e))?;
 
-        fs::write(&log_path, json_content).map_err(|e| format!("写入失败: {}", e))?;
+        write_log_file(&log_path, &json_content).map_err(|e| format!("写入失败: {}", e))?;
 
         info!("清理日志已保存: {:?}", log_path);
 
@@ -302,6 +316,55 @@ impl CleanupLogger {
     }
 }
 
+// ============================================================================
+// 日志文件读写
+// ============================================================================
+
+/// 先写入同目录的临时文件，再重命名为正式日志
+///
+/// 同一卷内重命名是原子操作，正式日志要么不存在，要么是完整的 JSON。
+fn write_log_file(log_path: &Path, content: &str) -> std::io::Result<()> {
+    let temp_path = log_path.with_extension(LOG_TEMP_EXTENSION);
+    fs::write(&temp_path, content)?;
+    fs::rename(&temp_path, log_path).inspect_err(|_| {
+        let _ = fs::remove_file(&temp_path);
+    })
+}
+
+/// 将无法解析的日志移入 corrupt/ 子目录，保留原文件供排查
+fn quarantine_corrupt_log(log_dir: &Path, path: &Path) {
+    let corrupt_dir = log_dir.join(CORRUPT_LOG_DIR);
+    let Some(file_name) = path.file_name() else {
+        return;
+    };
+    let result = fs::create_dir_all(&corrupt_dir)
+        .and_then(|_| fs::rename(path, corrupt_dir.join(file_name)));
+    match result {
+        Ok(_) => warn!("日志文件已损坏，已移至 corrupt 目录: {:?}", path),
+        Err(e) => warn!("日志文件已损坏，隔离失败: {:?}, 错误: {}", path, e),
+    }
+}
+
+/// 删除上次写入中断遗留的临时日志
+fn remove_stale_temp_logs(log_dir: &Path) {
+    let Ok(entries) = fs::read_dir(log_dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let is_temp = path
+            .file_name()
+            .map(|name| name.to_string_lossy().ends_with(LOG_TEMP_EXTENSION))
+            .unwrap_or(false);
+        if is_temp {
+            match fs::remove_file(&path) {
+                Ok(_) => debug!("已删除遗留的临时日志: {:?}", path),
+                Err(e) => warn!("删除临时日志失败: {:?}, 错误: {}", path, e),
+            }
+        }
+    }
+}
+
 // ============================================================================
 // 日志轮转逻辑
 // ============================================================================
@@ -350,7 +413,7 @@ async fn rotate_logs(log_dir: &Path, max_log_files: usize) -> Result<(), String>
 
     // 按创建时间排序（最旧的在前）
     let mut sorted_entries = entries;
-    sorted_entries.sort_by(|a, b| a.1.cmp(&b.1));
+    sorted_entries.sort_by_key(|a| a.1);
 
     // 计算需要删除的文件数量
     let files_to_delete = file_count - max_log_files;
@@ -375,6 +438,7 @@ async fn rotate_logs(log_dir: &Path, max_log_files: usize) -> Result<(), String>
 pub async fn cleanup_old_logs(app_data_dir: &Path) {
     let log_dir = app_data_dir.join("logs");
     if log_dir.exists() {
+        remove_stale_temp_logs(&log_dir);
         if let Err(e) = rotate_logs(&log_dir, DEFAULT_MAX_LOG_FILES).await {
             warn!("启动时日志轮转失败: {}", e);
         }
@@ -473,14 +537,13 @@ pub fn open_logs_folder(app_data_dir: &Path) -> Result<(), String> {
             .arg(&log_path)
             .spawn()
             .map_err(|e| format!("打开文件夹失败: {}", e))?;
+        Ok(())
     }
 
     #[cfg(not(target_os = "windows"))]
     {
-        return Err("此功能仅支持 Windows 系统".to_string());
+        Err("此功能仅支持 Windows 系统".to_string())
     }
-
-    Ok(())
 }
 
 /// 获取清理历史记录列表
@@ -501,9 +564,10 @@ pub fn get_cleanup_history(app_data_dir: &Path) -> Result<Vec<CleanupHistorySumm
         for entry in entries.filter_map(|e| e.ok()) {
             let path = entry.path();
             if path.extension().map(|ext| ext == "json").unwrap_or(false) {
+                // 读取失败可能是文件被占用，下次再试；内容无法解析才视为损坏
                 if let Ok(content) = std::fs::read_to_string(&path) {
-                    if let Ok(session) = serde_json::from_str::<CleanupSession>(&content) {
-                        history.push(CleanupHistorySummary {
+                    match serde_json::from_str::<CleanupSession>(&content) {
+                        Ok(session) => history.push(CleanupHistorySummary {
                             filename: path
                                 .file_name()
                                 .map(|n| n.to_string_lossy().to_string())
@@ -514,7 +578,8 @@ pub fn get_cleanup_history(app_data_dir: &Path) -> Result<Vec<CleanupHistorySumm
                             success_count: session.success_count,
                             failed_count: session.failed_count,
                             total_freed_bytes: session.total_freed_bytes,
-                        });
+                        }),
+                        Err(_) => quarantine_corrupt_log(&log_path, &path),
                     }
                 }
             }
@@ -526,6 +591,29 @@ pub fn get_cleanup_history(app_data_dir: &Path) -> Result<Vec<CleanupHistorySumm
     Ok(history)
 }
 
+/// 读取一次清理会话的完整日志（含逐条明细）
+pub fn get_cleanup_session(app_data_dir: &Path, filename: &str) -> Result<CleanupSession, String> {
+    let path = cleanup_log_path(app_data_dir, filename)?;
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("读取清理日志失败 {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("解析清理日志失败 {}: {}", filename, e))
+}
+
+/// 校验日志文件名并返回其完整路径
+///
+/// 文件名来自前端，只接受日志目录下的 `cleanup_*.json`：
+/// 拒绝路径分隔符、`..` 和盘符（`C:x.json` 拼接后会变成 C 盘当前目录下的文件）。
+fn cleanup_log_path(app_data_dir: &Path, filename: &str) -> Result<PathBuf, String> {
+    if filename.contains(['\\', '/', ':'])
+        || filename.contains("..")
+        || !filename.starts_with("cleanup_")
+        || !filename.ends_with(".json")
+    {
+        return Err(format!("日志文件名无效: {}", filename));
+    }
+    Ok(app_data_dir.join("logs").join(filename))
+}
+
 // ============================================================================
 // 全局日志管理器实例
 // ============================================================================
@@ -0,0 +1,282 @@
+// ============================================================================
+// 清理报告导出
+//
+// 为他人维护电脑后，需要一份可以直接交给对方查看的清理报告。
+// 这里把一次清理会话渲染成自包含的 HTML（内联样式、无外部资源），
+// 包含汇总数据、按分类统计和逐条清理明细。
+// ============================================================================
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use log::info;
+
+use super::{get_cleanup_history, get_cleanup_session, CleanupSession};
+use crate::scanner::format_size;
+
+/// 报告中的分类统计
+#[derive(Default)]
+struct CategoryTotals {
+    total: usize,
+    success: usize,
+    failed: usize,
+    freed_bytes: u64,
+}
+
+/// 读取清理会话日志；未指定文件名时读取最近一次会话
+pub fn load_cleanup_session(
+    app_data_dir: &Path,
+    filename: Option<&str>,
+) -> Result<CleanupSession, String> {
+    let filename = match filename {
+        Some(name) => name.to_string(),
+        None => get_cleanup_history(app_data_dir)?
+            .into_iter()
+            .next()
+            .map(|summary| summary.filename)
+            .ok_or_else(|| "没有清理记录，无法生成报告".to_string())?,
+    };
+    get_cleanup_session(app_data_dir, &filename)
+}
+
+/// 导出清理报告为 HTML 文件
+pub fn export_cleanup_report_html(
+    app_data_dir: &Path,
+    output_path: &Path,
+    filename: Option<&str>,
+) -> Result<(), String> {
+    let session = load_cleanup_session(app_data_dir, filename)?;
+    let html = render_cleanup_report_html(&session);
+    write_export_file(output_path, html)?;
+
+    info!("清理报告已导出: {}", output_path.display());
+    Ok(())
+}
+
+/// 写入用户选择的导出文件，父目录不存在时先创建
+///
+/// 清理报告、扫描结果 JSON 和扫描发现导出共用，错误信息保持一致。
+pub(crate) fn write_export_file(
+    output_path: &Path,
+    contents: impl AsRef<[u8]>,
+) -> Result<(), String> {
+    if let Some(parent) = output_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| format!("创建导出目录失败: {}", e))?;
+    }
+    fs::write(output_path, contents).map_err(|e| format!("写入导出文件失败: {}", e))
+}
+
+/// 渲染清理会话为自包含的 HTML 文档
+fn render_cleanup_report_html(session: &CleanupSession) -> String {
+    let mut html = String::with_capacity(4096 + session.entries.len() * 200);
+    push_report_header(&mut html, session);
+    push_category_table(&mut html, session);
+    push_entries_table(&mut html, session);
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// 文档头、内联样式、会话时间和汇总卡片
+fn push_report_header(html: &mut String, session: &CleanupSession) {
+    html.push_str(
+        r#"<!DOCTYPE html>
+<html lang="zh-CN">
+<head>
+<meta charset="utf-8">
+<title>LightC 清理报告</title>
+<style>
+body { font-family: "Microsoft YaHei", "Segoe UI", sans-serif; margin: 32px; color: #1f2937; }
+h1 { font-size: 24px; margin-bottom: 4px; }
+h2 { font-size: 18px; margin-top: 32px; }
+.meta { color: #6b7280; font-size: 13px; }
+.summary { display: flex; gap: 16px; margin-top: 20px; }
+.card { flex: 1; padding: 16px; border-radius: 8px; background: #f3f4f6; }
+.card .value { font-size: 22px; font-weight: 600; margin-top: 4px; }
+table { width: 100%; border-collapse: collapse; margin-top: 12px; font-size: 13px; }
+th, td { padding: 6px 10px; border-bottom: 1px solid #e5e7eb; text-align: left; }
+th { background: #f9fafb; }
+td.num { text-align: right; white-space: nowrap; }
+td.path { word-break: break-all; }
+.ok { color: #059669; }
+.fail { color: #dc2626; }
+.pending { color: #d97706; }
+</style>
+</head>
+<body>
+<h1>LightC 清理报告</h1>
+"#,
+    );
+
+    html.push_str(&format!(
+        "<div class=\"meta\">开始时间：{}　结束时间：{}</div>\n",
+        escape_html(&session.session_start),
+        escape_html(&session.session_end)
+    ));
+
+    html.push_str("<div class=\"summary\">\n");
+    for (label, value) in [
+        ("释放空间", format_size(session.total_freed_bytes)),
+        ("处理项目", session.total_files.to_string()),
+        ("成功", session.success_count.to_string()),
+        ("失败", session.failed_count.to_string()),
+    ] {
+        html.push_str(&format!(
+            "<div class=\"card\"><div>{}</div><div class=\"value\">{}</div></div>\n",
+            label, value
+        ));
+    }
+    html.push_str("</div>\n");
+}
+
+/// 按分类汇总的统计表，按释放空间降序
+fn push_category_table(html: &mut String, session: &CleanupSession) {
+    let mut categories: BTreeMap<&str, CategoryTotals> = BTreeMap::new();
+    for entry in &session.entries {
+        let totals = categories.entry(entry.category.as_str()).or_default();
+        totals.total += 1;
+        if entry.result == "Success" {
+            totals.success += 1;
+            totals.freed_bytes += entry.size;
+        } else {
+            totals.failed += 1;
+        }
+    }
+    let mut categories: Vec<(&str, CategoryTotals)> = categories.into_iter().collect();
+    categories.sort_by_key(|item| std::cmp::Reverse(item.1.freed_bytes));
+
+    html.push_str("<h2>分类统计</h2>\n<table>\n<tr><th>分类</th><th>项目数</th><th>成功</th><th>失败</th><th>释放空间</th></tr>\n");
+    for (category, totals) in &categories {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>\n",
+            escape_html(category),
+            totals.total,
+            totals.success,
+            totals.failed,
+            format_size(totals.freed_bytes)
+        ));
+    }
+    html.push_str("</table>\n");
+}
+
+/// 逐条清理明细，按大小降序
+fn push_entries_table(html: &mut String, session: &CleanupSession) {
+    let mut entries: Vec<_> = session.entries.iter().collect();
+    entries.sort_by_key(|item| std::cmp::Reverse(item.size));
+
+    html.push_str("<h2>清理明细</h2>\n<table>\n<tr><th>路径</th><th>分类</th><th>大小</th><th>结果</th></tr>\n");
+    for entry in entries {
+        let (class, label) = match entry.result.as_str() {
+            "Success" => ("ok", "已删除".to_string()),
+            "Locked_Pending_Reboot" => ("pending", "重启后删除".to_string()),
+            _ => (
+                "fail",
+                match &entry.error_message {
+                    Some(message) => format!("失败：{}", message),
+                    None => "失败".to_string(),
+                },
+            ),
+        };
+        html.push_str(&format!(
+            "<tr><td class=\"path\">{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"{}\">{}</td></tr>\n",
+            escape_html(&entry.path),
+            escape_html(&entry.category),
+            format_size(entry.size),
+            class,
+            escape_html(&label)
+        ));
+    }
+    html.push_str("</table>\n");
+}
+
+/// 转义 HTML 特殊字符（路径和错误信息来自文件系统，可能包含 & 等字符）
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logger::CleanupLogEntry;
+
+    fn entry(category: &str, path: &str, size: u64, result: &str) -> CleanupLogEntry {
+        CleanupLogEntry {
+            timestamp: "2026-01-01 10:00:00.000".to_string(),
+            category: category.to_string(),
+            path: path.to_string(),
+            size,
+            result: result.to_string(),
+            error_message: None,
+        }
+    }
+
+    #[test]
+    fn test_render_cleanup_report_html() {
+        let mut session = CleanupSession::new();
+        session.add_entry(entry("系统临时文件", r"C:\Temp\a&b.tmp", 2048, "Success"));
+        session.add_entry(entry("系统临时文件", r"C:\Temp\locked.tmp", 100, "Failed"));
+        session.add_entry(entry("浏览器缓存", r"C:\Cache\<x>", 1024, "Success"));
+        session.finish();
+
+        let html = render_cleanup_report_html(&session);
+        assert!(html.contains(r"C:\Temp\a&amp;b.tmp"));
+        assert!(html.contains(r"C:\Cache\&lt;x&gt;"));
+        assert!(html.contains("<td>系统临时文件</td><td class=\"num\">2</td>"));
+        assert!(html.contains(&format_size(3072)));
+        // 明细按大小降序
+        let first = html.find("a&amp;b.tmp").unwrap();
+        let second = html.find("&lt;x&gt;").unwrap();
+        assert!(first < second);
+    }
+
+    #[test]
+    fn test_write_export_file_creates_parent_dir() {
+        let root = std::env::temp_dir().join(format!("lightc-export-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let output = root.join("nested").join("report.html");
+
+        let written = write_export_file(&output, "<html></html>");
+        let contents = fs::read_to_string(&output);
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(written.is_ok());
+        assert_eq!(contents.unwrap(), "<html></html>");
+    }
+
+    #[test]
+    fn test_cleanup_session_rejects_paths_outside_logs_dir() {
+        let root = std::env::temp_dir().join(format!("lightc-session-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("logs")).unwrap();
+        let mut session = CleanupSession::new();
+        session.add_entry(entry("系统临时文件", r"C:\Temp\a.tmp", 10, "Success"));
+        session.finish();
+        fs::write(
+            root.join("logs").join("cleanup_20260101_100000.json"),
+            serde_json::to_string(&session).unwrap(),
+        )
+        .unwrap();
+
+        let loaded = get_cleanup_session(&root, "cleanup_20260101_100000.json");
+        let traversal = get_cleanup_session(&root, "..\\..\\cleanup_x.json");
+        let drive_relative = get_cleanup_session(&root, "C:cleanup_x.json");
+        let other_file = get_cleanup_session(&root, "settings.json");
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(loaded.unwrap().entries.len(), 1);
+        assert!(traversal.is_err());
+        assert!(drive_relative.is_err());
+        assert!(other_file.is_err());
+    }
+}
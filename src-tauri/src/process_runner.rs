@@ -0,0 +1,187 @@
+// ============================================================================
+// 外部命令执行
+//
+// icacls、reg、pnputil、DISM 等系统工具偶尔会卡死（DISM 尤其明显），
+// 直接 `.output()` 会让整个清理流程无限等待。这里统一提供带截止时间的执行，
+// 超时后结束进程树并返回错误，调用方可以照常走失败分支。
+// ============================================================================
+
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 短小的查询类命令（reg query、icacls 等）的默认超时
+pub const DEFAULT_COMMAND_TIMEOUT: Duration = Duration::from_secs(60);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(40);
+
+/// 执行外部命令并等待结束，超过 `timeout` 时结束进程并返回错误
+///
+/// # 中文说明
+/// stdout/stderr 在后台线程中持续读取，避免输出较大时子进程因管道写满而阻塞，
+/// 导致 try_wait 永远等不到退出。
+pub fn run_with_timeout(command: &mut Command, timeout: Duration) -> Result<Output, String> {
+    run_with_timeout_streaming(command, timeout, |_| {})
+}
+
+/// 与 [`run_with_timeout`] 相同，但会把 stdout 的每一行实时交给 `on_line`
+///
+/// DISM 等长任务需要边执行边解析进度，不能等进程结束后再读输出。
+pub fn run_with_timeout_streaming<F>(
+    command: &mut Command,
+    timeout: Duration,
+    mut on_line: F,
+) -> Result<Output, String>
+where
+    F: FnMut(&str),
+{
+    let program = command.get_program().to_string_lossy().into_owned();
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| format!("启动 {} 失败: {}", program, error))?;
+
+    let (line_sender, line_receiver) = mpsc::channel::<String>();
+    let stdout_reader = child.stdout.take().map(|stdout| {
+        thread::spawn(move || {
+            let mut reader = BufReader::new(stdout);
+            let mut collected = Vec::new();
+            let mut line = Vec::new();
+            loop {
+                line.clear();
+                match reader.read_until(b'\n', &mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {
+                        collected.extend_from_slice(&line);
+                        let _ = line_sender.send(String::from_utf8_lossy(&line).into_owned());
+                    }
+                }
+            }
+            collected
+        })
+    });
+    let stderr_reader = child.stderr.take().map(|mut stderr| {
+        thread::spawn(move || {
+            let mut collected = Vec::new();
+            let _ = stderr.read_to_end(&mut collected);
+            collected
+        })
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        while let Ok(line) = line_receiver.try_recv() {
+            on_line(line.trim_end_matches(['\r', '\n']));
+        }
+
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) => {}
+            Err(error) => {
+                kill_process_tree(&mut child);
+                return Err(format!("等待 {} 结束失败: {}", program, error));
+            }
+        }
+
+        if Instant::now() >= deadline {
+            kill_process_tree(&mut child);
+            // 子进程已结束，读取线程会随管道关闭退出；孙进程可能仍持有管道，因此不再 join。
+            return Err(format!(
+                "{} 执行超过 {} 秒未结束，已终止",
+                program,
+                timeout.as_secs()
+            ));
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let stdout = stdout_reader
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+    let stderr = stderr_reader
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+    while let Ok(line) = line_receiver.try_recv() {
+        on_line(line.trim_end_matches(['\r', '\n']));
+    }
+
+    Ok(Output {
+        status,
+        stdout,
+        stderr,
+    })
+}
+
+/// 结束子进程及其派生进程
+///
+/// 通过 `cmd /C` 启动的 DISM、powercfg 是孙进程，只结束 cmd 会让它们继续运行。
+fn kill_process_tree(child: &mut Child) {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+
+        let _ = Command::new("taskkill")
+            .args(["/PID", &child.id().to_string(), "/T", "/F"])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .creation_flags(0x08000000)
+            .status();
+    }
+
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(windows)]
+    fn shell(script: &str) -> Command {
+        let mut command = Command::new("cmd");
+        command.args(["/C", script]);
+        command
+    }
+
+    #[cfg(not(windows))]
+    fn shell(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    #[test]
+    fn test_collects_output_and_lines() {
+        let mut lines = Vec::new();
+        let output = run_with_timeout_streaming(
+            &mut shell("echo first&& echo second"),
+            Duration::from_secs(10),
+            |line| lines.push(line.trim().to_string()),
+        )
+        .expect("命令应正常结束");
+
+        assert!(output.status.success());
+        assert_eq!(lines, vec!["first", "second"]);
+        assert!(String::from_utf8_lossy(&output.stdout).contains("second"));
+    }
+
+    #[test]
+    fn test_kills_command_after_timeout() {
+        #[cfg(windows)]
+        let script = "ping -n 30 127.0.0.1 >nul";
+        #[cfg(not(windows))]
+        let script = "sleep 30";
+
+        let started = Instant::now();
+        let result = run_with_timeout(&mut shell(script), Duration::from_millis(300));
+
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+}
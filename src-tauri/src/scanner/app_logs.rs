@@ -0,0 +1,165 @@
+// ============================================================================
+// 应用日志扫描
+//
+// Slack、VS Code、Discord 等 Electron 应用在 %AppData% / %LocalAppData% 下写
+// main.log、renderer.log，失控时单个文件可达数 GB。日志文件分类只覆盖
+// C:\Windows\Logs 和 CrashDumps，这里逐个应用目录浅层查找超过阈值的 *.log，
+// 按应用目录汇总，方便用户只清理某一个应用的日志。
+// ============================================================================
+
+use rayon::prelude::*;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::{FileInfo, JunkCategory};
+use crate::cleaner::safety_constants::{is_reparse_point, is_reparse_point_dir};
+
+/// 默认只列出超过 10 MB 的日志
+pub const DEFAULT_MIN_LOG_SIZE: u64 = 10 * 1024 * 1024;
+
+/// 应用目录内的遍历深度：VS Code 的日志位于 Code\logs\<会话>\window1\renderer.log
+const APP_LOG_MAX_DEPTH: usize = 4;
+
+/// 不按应用处理的目录（小写）：Temp 已由临时文件分类覆盖，Packages 是 UWP 应用沙盒
+const SKIPPED_APP_DIRS: &[&str] = &["temp", "packages"];
+
+/// 单个应用的日志汇总
+#[derive(Debug, Clone, Serialize)]
+pub struct AppLogGroup {
+    /// 应用目录名，如 Slack、Code
+    pub app_name: String,
+    /// 应用目录完整路径
+    pub app_dir: String,
+    /// 日志总大小（字节）
+    pub total_size: u64,
+    /// 日志文件数
+    pub file_count: usize,
+    /// 超过阈值的日志文件（按大小降序）
+    pub files: Vec<FileInfo>,
+}
+
+/// 扫描当前用户 AppData 下各应用超过 `min_size` 的日志，按总大小降序返回
+pub fn scan_app_logs(min_size: u64) -> Vec<AppLogGroup> {
+    let roots: Vec<PathBuf> = ["APPDATA", "LOCALAPPDATA"]
+        .iter()
+        .filter_map(|name| std::env::var_os(name).map(PathBuf::from))
+        .collect();
+    scan_app_log_roots(&roots, min_size)
+}
+
+fn scan_app_log_roots(roots: &[PathBuf], min_size: u64) -> Vec<AppLogGroup> {
+    let app_dirs: Vec<PathBuf> = roots.iter().flat_map(|root| list_app_dirs(root)).collect();
+
+    let mut groups: Vec<AppLogGroup> = app_dirs
+        .par_iter()
+        .filter_map(|app_dir| scan_app_dir(app_dir, min_size))
+        .collect();
+    groups.sort_by_key(|b| std::cmp::Reverse(b.total_size));
+    groups
+}
+
+/// 列出根目录下的应用目录，跳过重解析点和 Temp 等非应用目录
+fn list_app_dirs(root: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(root) else {
+        log::warn!("无法读取应用数据目录: {}", root.display());
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            !SKIPPED_APP_DIRS.contains(&name.as_str())
+        })
+        .filter(|entry| {
+            fs::symlink_metadata(entry.path())
+                .is_ok_and(|metadata| metadata.is_dir() && !is_reparse_point(&metadata))
+        })
+        .map(|entry| entry.path())
+        .collect()
+}
+
+fn scan_app_dir(app_dir: &Path, min_size: u64) -> Option<AppLogGroup> {
+    let mut files: Vec<FileInfo> = WalkDir::new(app_dir)
+        .max_depth(APP_LOG_MAX_DEPTH)
+        .into_iter()
+        .filter_entry(|entry| !is_reparse_point_dir(entry))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file() && is_log_file(entry.path()))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            if metadata.len() < min_size {
+                return None;
+            }
+            let modified_time = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0);
+            Some(FileInfo::new(
+                entry.path().to_string_lossy().into_owned(),
+                entry.file_name().to_string_lossy().into_owned(),
+                metadata.len(),
+                modified_time,
+                false,
+                JunkCategory::LogFiles,
+            ))
+        })
+        .collect();
+    if files.is_empty() {
+        return None;
+    }
+    files.sort_by_key(|b| std::cmp::Reverse(b.size));
+
+    Some(AppLogGroup {
+        app_name: app_dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        app_dir: app_dir.to_string_lossy().into_owned(),
+        total_size: files.iter().map(|file| file.size).sum(),
+        file_count: files.len(),
+        files,
+    })
+}
+
+fn is_log_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("log"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_logs_grouped_by_app_and_filtered_by_size() {
+        let root = std::env::temp_dir().join(format!("lightc-app-logs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let slack_logs = root.join("Slack").join("logs");
+        let code_logs = root
+            .join("Code")
+            .join("logs")
+            .join("20260101")
+            .join("window1");
+        fs::create_dir_all(&slack_logs).unwrap();
+        fs::create_dir_all(&code_logs).unwrap();
+        fs::create_dir_all(root.join("Temp")).unwrap();
+        fs::write(slack_logs.join("main.log"), vec![0u8; 300]).unwrap();
+        fs::write(slack_logs.join("small.log"), vec![0u8; 10]).unwrap();
+        fs::write(slack_logs.join("data.bin"), vec![0u8; 500]).unwrap();
+        fs::write(code_logs.join("renderer.LOG"), vec![0u8; 200]).unwrap();
+        fs::write(root.join("Temp").join("setup.log"), vec![0u8; 900]).unwrap();
+
+        let groups = scan_app_log_roots(std::slice::from_ref(&root), 100);
+        let _ = fs::remove_dir_all(&root);
+
+        let names: Vec<&str> = groups.iter().map(|g| g.app_name.as_str()).collect();
+        assert_eq!(names, vec!["Slack", "Code"]);
+        assert_eq!(groups[0].total_size, 300);
+        assert_eq!(groups[0].file_count, 1);
+        assert_eq!(groups[1].files[0].name, "renderer.LOG");
+    }
+}
@@ -4,10 +4,16 @@
 // ============================================================================
 
 use serde::{Deserialize, Serialize};
-use std::cmp::{Ordering, Reverse};
+use std::cmp::Ordering;
+#[cfg(target_os = "windows")]
+use std::cmp::Reverse;
+#[cfg(target_os = "windows")]
 use std::collections::BinaryHeap;
 use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
-use tauri::{Emitter, Window};
+#[cfg(target_os = "windows")]
+use tauri::Emitter;
+use tauri::Window;
+#[cfg(target_os = "windows")]
 use walkdir::WalkDir;
 
 // 全局取消标志，跨线程共享
@@ -60,6 +66,43 @@ pub struct LargeFileScanProgress {
     pub elapsed_ms: u64,
 }
 
+/// 按修改时间划分的大文件分组
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LargeFileAgeBucket {
+    /// 分组名称（如"30-90 天"）
+    pub label: String,
+    /// 距今最少天数（含）
+    pub min_days: u64,
+    /// 距今最多天数（不含），最后一组为空
+    pub max_days: Option<u64>,
+    pub file_count: usize,
+    pub total_size: u64,
+}
+
+/// 大文件扫描结果及按修改时间的分组汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LargeFileAgeReport {
+    pub files: Vec<LargeFileEntry>,
+    pub buckets: Vec<LargeFileAgeBucket>,
+}
+
+/// 修改时间分组边界：(名称, 最少天数, 最多天数)
+const AGE_BUCKETS: &[(&str, u64, Option<u64>)] = &[
+    ("30 天内", 0, Some(30)),
+    ("30-90 天", 30, Some(90)),
+    ("90-365 天", 90, Some(365)),
+    ("超过 1 年", 365, None),
+];
+
+/// 默认跳过的目录（相对盘符根目录，小写）
+///
+/// 回收站和系统还原目录无权访问，WinSxS 组件存储里多是硬链接，遍历慢且大小重复统计。
+const DEFAULT_EXCLUDED_DIRS: &[&str] = &[
+    "$recycle.bin",
+    "system volume information",
+    r"windows\winsxs",
+];
+
 // ============================================================================
 // 命令入口
 // ============================================================================
@@ -80,19 +123,32 @@ pub(crate) fn is_cancelled() -> bool {
 }
 
 /// 执行大文件扫描（阻塞，应在 spawn_blocking 中调用）
+///
+/// `low_priority` 为 true 时扫描线程进入后台模式，WalkDir 遍历分批休眠。
+/// `exclude_dirs` 中的目录（绝对路径）与默认跳过目录一起整棵剪掉，不再进入遍历。
 pub fn scan(
     window: &Window,
     top_n: usize,
     drive_letter: char,
+    low_priority: bool,
+    exclude_dirs: &[String],
 ) -> Result<Vec<LargeFileEntry>, String> {
     #[cfg(target_os = "windows")]
     {
+        use super::low_priority::{BackgroundPriorityGuard, ScanThrottle};
+        use crate::cleaner::safety_constants::{
+            is_cloud_placeholder, is_reparse_point, is_reparse_point_dir,
+        };
         use std::time::Instant;
 
+        let _priority = BackgroundPriorityGuard::enter(low_priority);
+
         let drive_letter = drive_letter.to_ascii_uppercase();
         // 所有扫描引擎共享同一个盘符根路径，避免前端切盘后 MFT 与 WalkDir 扫描目标不一致。
         let root = format!("{}:\\", drive_letter);
 
+        let excluded_dirs = build_excluded_dirs(&root, exclude_dirs);
+
         log::info!("开始扫描大文件: {} (Top {})", root, top_n);
 
         // ========================================================================
@@ -147,6 +203,7 @@ pub fn scan(
                 match crate::scanner::big_files_engine::mft_bigfiles::scan_top_files_via_mft(
                     top_n,
                     drive_letter,
+                    &excluded_dirs,
                     |progress| {
                         let _ = window.emit(
                             "large-file-scan:progress",
@@ -206,32 +263,21 @@ pub fn scan(
         // ========================================================================
         // 降级：WalkDir 遍历（原有方案）
         // ========================================================================
-        let mut heap: BinaryHeap<Reverse<LargeFileEntry>> = BinaryHeap::new();
+        // 堆最多 top_n + 1 项，一次分配到位，扫描几十万文件时不再反复扩容
+        let mut heap: BinaryHeap<Reverse<LargeFileEntry>> = BinaryHeap::with_capacity(top_n + 1);
         let mut file_count: u64 = 0;
         let mut last_emit = Instant::now();
+        let mut throttle = ScanThrottle::new(low_priority);
 
         for entry in WalkDir::new(&root)
             .follow_links(false)
             .into_iter()
+            // 在 filter_entry 里剪枝，被排除的目录整棵不会被遍历；
+            // 联接和符号链接目录同样跳过，避免重复统计或绕进其他磁盘
             .filter_entry(|e| {
-                if e.file_type().is_dir() {
-                    let name = e.file_name().to_string_lossy().to_lowercase();
-                    if name == "$recycle.bin" || name == "system volume information" {
-                        return false;
-                    }
-                    if name == "winsxs" {
-                        if let Some(parent) = e.path().parent() {
-                            if parent
-                                .to_string_lossy()
-                                .to_lowercase()
-                                .ends_with("\\windows")
-                            {
-                                return false;
-                            }
-                        }
-                    }
-                }
-                true
+                !(e.file_type().is_dir()
+                    && (is_in_excluded_dir(&e.path().to_string_lossy(), &excluded_dirs)
+                        || is_reparse_point_dir(e)))
             })
             .filter_map(|e| e.ok())
             .filter(|e| e.file_type().is_file())
@@ -244,11 +290,14 @@ pub fn scan(
                 results.sort_by(|a, b| b.size.cmp(&a.size));
                 return Ok(results);
             }
+            throttle.tick();
 
-            let path = entry.path().to_path_buf();
-            let path_str = path.to_string_lossy().to_string();
-
+            // entry 与 metadata 只在本轮迭代内使用，不转存 PathBuf，迭代结束即释放
             if let Ok(metadata) = entry.metadata() {
+                // OneDrive 仅云端文件是重解析点并带有 RECALL 属性，后续读取可能触发下载，直接跳过
+                if is_reparse_point(&metadata) || is_cloud_placeholder(&metadata) {
+                    continue;
+                }
                 let size = metadata.len();
                 let modified = metadata
                     .modified()
@@ -261,7 +310,7 @@ pub fn scan(
 
                 if last_emit.elapsed().as_millis() >= 200 || file_count % 1000 == 0 {
                     let progress = LargeFileScanProgress {
-                        current_path: path_str.clone(),
+                        current_path: entry.path().to_string_lossy().into_owned(),
                         scanned_count: file_count,
                         found_count: heap.len(),
                         backend: "walkdir".into(),
@@ -273,6 +322,13 @@ pub fn scan(
                     last_emit = Instant::now();
                 }
 
+                // 堆已满且比堆中最小项还小的文件不可能进入 Top N，
+                // 跳过路径字符串、风险等级和来源标签的计算，绝大多数文件走这条路径
+                if heap.len() >= top_n && heap.peek().is_some_and(|min| size < min.0.size) {
+                    continue;
+                }
+
+                let path_str = entry.path().to_string_lossy().into_owned();
                 let risk_level = compute_file_risk_level(&path_str);
                 let source_label = compute_source_label(&path_str);
 
@@ -304,10 +360,66 @@ pub fn scan(
 
     #[cfg(not(target_os = "windows"))]
     {
+        let _ = (window, top_n, drive_letter, low_priority, exclude_dirs);
         Err("此功能仅支持Windows系统".to_string())
     }
 }
 
+/// 合并默认跳过目录和用户指定目录，统一为小写、反斜杠分隔、不含末尾反斜杠的绝对路径
+fn build_excluded_dirs(root: &str, exclude_dirs: &[String]) -> Vec<String> {
+    let normalize = |path: &str| {
+        path.trim()
+            .replace('/', "\\")
+            .trim_end_matches('\\')
+            .to_lowercase()
+    };
+    DEFAULT_EXCLUDED_DIRS
+        .iter()
+        .map(|dir| normalize(&format!("{}{}", root, dir)))
+        .chain(exclude_dirs.iter().map(|dir| normalize(dir)))
+        .filter(|dir| !dir.is_empty())
+        .collect()
+}
+
+/// 路径是否为某个跳过目录本身或位于其中（`excluded_dirs` 来自 `build_excluded_dirs`）
+pub(crate) fn is_in_excluded_dir(path: &str, excluded_dirs: &[String]) -> bool {
+    let path_lower = path.replace('/', "\\").to_lowercase();
+    excluded_dirs.iter().any(|dir| {
+        path_lower
+            .strip_prefix(dir.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('\\'))
+    })
+}
+
+/// 按修改时间汇总大文件，`now` 为当前 Unix 时间戳（秒）
+///
+/// 修改时间未知（为 0）的文件不计入任何分组；修改时间晚于当前时间的按 0 天计算。
+pub fn summarize_by_age(files: &[LargeFileEntry], now: i64) -> Vec<LargeFileAgeBucket> {
+    let mut buckets: Vec<LargeFileAgeBucket> = AGE_BUCKETS
+        .iter()
+        .map(|&(label, min_days, max_days)| LargeFileAgeBucket {
+            label: label.to_string(),
+            min_days,
+            max_days,
+            file_count: 0,
+            total_size: 0,
+        })
+        .collect();
+
+    for file in files.iter().filter(|file| file.modified > 0) {
+        let age_days = (now - file.modified).max(0) as u64 / 86_400;
+        if let Some(bucket) = buckets
+            .iter_mut()
+            .find(|bucket| !matches!(bucket.max_days, Some(max) if age_days >= max))
+        {
+            bucket.file_count += 1;
+            bucket.total_size += file.size;
+        }
+    }
+
+    buckets
+}
+
 // ============================================================================
 // 风险等级 & 来源标签
 // ============================================================================
@@ -481,3 +593,51 @@ pub(crate) fn compute_source_label(path: &str) -> String {
 
     "未知来源".to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(size: u64, modified: i64) -> LargeFileEntry {
+        LargeFileEntry {
+            path: format!("C:\\Data\\{}.bin", size),
+            size,
+            modified,
+            risk_level: 2,
+            source_label: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_summarize_by_age() {
+        let now = 1_000 * 86_400;
+        let files = vec![
+            entry(100, now - 10 * 86_400),
+            entry(200, now - 30 * 86_400),
+            entry(300, now - 200 * 86_400),
+            entry(400, now - 800 * 86_400),
+            entry(500, now + 86_400),
+            entry(600, 0),
+        ];
+        let buckets = summarize_by_age(&files, now);
+
+        let sizes: Vec<u64> = buckets.iter().map(|bucket| bucket.total_size).collect();
+        assert_eq!(sizes, vec![600, 200, 300, 400]);
+        assert_eq!(buckets[0].file_count, 2);
+        assert_eq!(buckets[3].max_days, None);
+    }
+
+    #[test]
+    fn test_excluded_dirs_prune_defaults_and_user_dirs() {
+        let excluded = build_excluded_dirs("C:\\", &["D:/Games/Steam/".to_string()]);
+
+        assert!(is_in_excluded_dir("C:\\Windows\\WinSxS", &excluded));
+        assert!(is_in_excluded_dir("C:\\$Recycle.Bin\\S-1-5-21", &excluded));
+        assert!(is_in_excluded_dir("D:\\Games\\Steam\\steamapps", &excluded));
+        assert!(!is_in_excluded_dir("C:\\Windows\\WinSxSBackup", &excluded));
+        assert!(!is_in_excluded_dir(
+            "C:\\Users\\me\\Windows\\WinSxS",
+            &excluded
+        ));
+    }
+}
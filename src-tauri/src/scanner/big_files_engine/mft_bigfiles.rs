@@ -12,7 +12,7 @@ use std::time::Instant;
 use log::info;
 
 use crate::scanner::big_files::{
-    compute_file_risk_level, compute_source_label, is_cancelled, LargeFileEntry,
+    compute_file_risk_level, compute_source_label, is_cancelled, is_in_excluded_dir, LargeFileEntry,
 };
 use crate::scanner::big_files_engine::mft_core;
 
@@ -37,9 +37,11 @@ fn is_system_path(path: &str) -> bool {
     SKIP_PATH_SEGMENTS.iter().any(|s| p.contains(s))
 }
 
+/// `excluded_dirs` 为 `big_files::build_excluded_dirs` 生成的跳过目录，在 TopN 阶段过滤
 pub fn scan_top_files_via_mft(
     top_n: usize,
     drive_letter: char,
+    excluded_dirs: &[String],
     progress_cb: impl Fn(MftBigFileProgress),
 ) -> Result<Vec<LargeFileEntry>, String> {
     // DEBUG: 需要文件日志时取消下面注释
@@ -124,6 +126,9 @@ pub fn scan_top_files_via_mft(
         .map(|candidate| candidate.mft_id)
         .collect();
     let paths = mft_core::rebuild_paths_for_ids(&entries, drive_letter, &candidate_ids);
+    // 全盘 USN 记录（C 盘常有数百万条）到这里已用完，立即释放，不拖到 TopN 结束
+    drop(entries);
+    drop(candidate_ids);
     let t3 = Instant::now();
     flog!(
         "[MFT-BigFiles] Step3 paths: {} 个, {:.1}s",
@@ -132,7 +137,8 @@ pub fn scan_top_files_via_mft(
     );
 
     // Step 4: BinaryHeap Top-N
-    let mut heap: BinaryHeap<Reverse<(u64, u64)>> = BinaryHeap::new();
+    // 修改时间随候选一起入堆，生成结果时不必再回到候选列表里逐个查找
+    let mut heap: BinaryHeap<Reverse<(u64, u64, i64)>> = BinaryHeap::with_capacity(top_n + 1);
     for candidate in &candidates {
         if is_cancelled() {
             return Err("扫描已取消".into());
@@ -140,11 +146,15 @@ pub fn scan_top_files_via_mft(
         let Some(path) = paths.get(&candidate.mft_id) else {
             continue;
         };
-        if is_system_path(path) {
+        if is_system_path(path) || is_in_excluded_dir(path, excluded_dirs) {
             continue;
         }
 
-        heap.push(Reverse((candidate.size, candidate.mft_id)));
+        heap.push(Reverse((
+            candidate.size,
+            candidate.mft_id,
+            candidate.modified,
+        )));
         if heap.len() > top_n {
             heap.pop();
         }
@@ -160,15 +170,12 @@ pub fn scan_top_files_via_mft(
     });
     let mut results: Vec<LargeFileEntry> = heap
         .into_iter()
-        .filter_map(|Reverse((_, mft_id))| {
+        .filter_map(|Reverse((size, mft_id, modified))| {
             let path = paths.get(&mft_id)?;
-            let candidate = candidates
-                .iter()
-                .find(|candidate| candidate.mft_id == mft_id)?;
             Some(LargeFileEntry {
                 path: path.clone(),
-                size: candidate.size,
-                modified: candidate.modified,
+                size,
+                modified,
                 risk_level: compute_file_risk_level(path),
                 source_label: compute_source_label(path),
             })
@@ -15,11 +15,9 @@ use winapi::um::errhandlingapi::GetLastError;
 use winapi::um::fileapi::{CreateFileW, OPEN_EXISTING};
 use winapi::um::handleapi::CloseHandle;
 use winapi::um::ioapiset::DeviceIoControl;
-use winapi::um::processthreadsapi::{GetCurrentProcess, OpenProcessToken};
-use winapi::um::securitybaseapi::GetTokenInformation;
 use winapi::um::winnt::{
-    TokenElevation, FILE_ATTRIBUTE_DIRECTORY, FILE_ATTRIBUTE_REPARSE_POINT, FILE_SHARE_READ,
-    FILE_SHARE_WRITE, GENERIC_READ, TOKEN_ELEVATION, TOKEN_QUERY,
+    FILE_ATTRIBUTE_DIRECTORY, FILE_ATTRIBUTE_REPARSE_POINT, FILE_SHARE_READ, FILE_SHARE_WRITE,
+    GENERIC_READ,
 };
 
 extern "system" {
@@ -272,23 +270,8 @@ fn resolve_path(
 }
 
 pub fn is_elevated() -> bool {
-    let mut t: HANDLE = std::ptr::null_mut();
-    if unsafe { OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut t) } == 0 {
-        return false;
-    }
-    let mut e = TOKEN_ELEVATION { TokenIsElevated: 0 };
-    let mut s: DWORD = 0;
-    let ok = unsafe {
-        GetTokenInformation(
-            t,
-            TokenElevation,
-            &mut e as *mut _ as LPVOID,
-            std::mem::size_of::<TOKEN_ELEVATION>() as DWORD,
-            &mut s,
-        )
-    };
-    unsafe { CloseHandle(t) };
-    ok != 0 && e.TokenIsElevated != 0
+    // 与系统瘦身共用同一个令牌提权检测，避免两处实现不一致。
+    crate::system_slim::check_admin()
 }
 
 pub fn is_ntfs(drive_letter: char) -> bool {
@@ -371,8 +354,13 @@ impl NtfsFileMetadataReader {
         top_limit: usize,
         progress_cb: &impl Fn(usize) -> bool,
     ) -> Result<Vec<MftTopFileCandidate>, String> {
-        let mut heap: BinaryHeap<Reverse<(u64, u64, i64)>> = BinaryHeap::new();
+        let mut heap: BinaryHeap<Reverse<(u64, u64, i64)>> =
+            BinaryHeap::with_capacity(top_limit + 1);
         let mut logical_record_id = 0u64;
+        // 读缓冲和单条记录的修正缓冲在所有 data run 间复用，
+        // 整个 $MFT 有数百万条记录，逐条 to_vec 会产生同样数量的小块分配
+        let mut buffer = vec![0u8; MFT_READ_CHUNK.max(self.file_record_size)];
+        let mut record: Vec<u8> = Vec::with_capacity(self.file_record_size);
 
         for run in &self.mft_runs {
             let run_start = (run.start_lcn as u64)
@@ -384,7 +372,6 @@ impl NtfsFileMetadataReader {
                 .ok_or_else(|| "$MFT data run 大小溢出".to_string())?;
             let records_in_run = run_bytes / self.file_record_size as u64;
             let mut bytes_read_in_run = 0u64;
-            let mut buffer = vec![0u8; MFT_READ_CHUNK.max(self.file_record_size)];
 
             while bytes_read_in_run < run_bytes {
                 let remaining = (run_bytes - bytes_read_in_run) as usize;
@@ -414,7 +401,8 @@ impl NtfsFileMetadataReader {
                         continue;
                     }
 
-                    let mut record = record_bytes.to_vec();
+                    record.clear();
+                    record.extend_from_slice(record_bytes);
                     if self.apply_fixup(&mut record).is_some() {
                         if let Some(metadata) = parse_active_file_record_metadata(&record) {
                             if metadata.size > 0 {
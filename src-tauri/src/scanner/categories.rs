@@ -38,13 +38,62 @@ pub enum JunkCategory {
     WindowsErrorReports,
     /// 安装程序临时文件
     InstallerTemp,
+    /// Windows Installer 中断安装遗留的回滚数据 (Config.Msi)
+    InstallerRollback,
+    /// Windows Installer 补丁缓存 ($PatchCache$)
+    InstallerPatchCache,
     /// 剪贴板缓存
     ClipboardCache,
     /// DirectX/GPU Shader 缓存
     ShaderCache,
+    /// Office 临时文档和自动恢复缓存
+    OfficeCache,
+    /// 开发工具的包管理和构建缓存（npm、pip、Cargo、Gradle、NuGet）
+    DeveloperCache,
+    /// 游戏着色器缓存和崩溃转储
+    GameCache,
+    /// 用户指定路径的临时分类（不参与常规扫描）
+    Custom,
 }
 
 impl JunkCategory {
+    /// 稳定标识（枚举变体名，与 JSON 序列化值一致），前端用作列表 key
+    pub fn key(&self) -> &'static str {
+        match self {
+            JunkCategory::WindowsTemp => "WindowsTemp",
+            JunkCategory::SystemCache => "SystemCache",
+            JunkCategory::BrowserCache => "BrowserCache",
+            JunkCategory::RecycleBin => "RecycleBin",
+            JunkCategory::WindowsUpdate => "WindowsUpdate",
+            JunkCategory::DeliveryOptimization => "DeliveryOptimization",
+            JunkCategory::WindowsDefenderCache => "WindowsDefenderCache",
+            JunkCategory::ThumbnailCache => "ThumbnailCache",
+            JunkCategory::LogFiles => "LogFiles",
+            JunkCategory::MemoryDump => "MemoryDump",
+            JunkCategory::OldWindowsInstallation => "OldWindowsInstallation",
+            JunkCategory::AppCache => "AppCache",
+            JunkCategory::FontCache => "FontCache",
+            JunkCategory::WindowsErrorReports => "WindowsErrorReports",
+            JunkCategory::InstallerTemp => "InstallerTemp",
+            JunkCategory::InstallerRollback => "InstallerRollback",
+            JunkCategory::InstallerPatchCache => "InstallerPatchCache",
+            JunkCategory::ClipboardCache => "ClipboardCache",
+            JunkCategory::ShaderCache => "ShaderCache",
+            JunkCategory::OfficeCache => "OfficeCache",
+            JunkCategory::DeveloperCache => "DeveloperCache",
+            JunkCategory::GameCache => "GameCache",
+            JunkCategory::Custom => "Custom",
+        }
+    }
+
+    /// 固定的展示顺序（`all()` 中的位置），Custom 排在最后
+    pub fn canonical_order(&self) -> usize {
+        JunkCategory::all()
+            .iter()
+            .position(|category| category == self)
+            .unwrap_or(usize::MAX)
+    }
+
     /// 获取分类的中文显示名称
     pub fn display_name(&self) -> &'static str {
         match self {
@@ -63,8 +112,14 @@ impl JunkCategory {
             JunkCategory::FontCache => "字体缓存",
             JunkCategory::WindowsErrorReports => "Windows错误报告",
             JunkCategory::InstallerTemp => "安装程序临时文件",
+            JunkCategory::InstallerRollback => "Windows Installer 回滚数据",
+            JunkCategory::InstallerPatchCache => "Windows Installer 补丁缓存",
             JunkCategory::ClipboardCache => "剪贴板缓存",
             JunkCategory::ShaderCache => "DirectX Shader 缓存",
+            JunkCategory::OfficeCache => "Office 临时文件",
+            JunkCategory::DeveloperCache => "开发工具缓存",
+            JunkCategory::GameCache => "游戏缓存",
+            JunkCategory::Custom => "自定义路径",
         }
     }
 
@@ -90,10 +145,28 @@ impl JunkCategory {
             JunkCategory::OldWindowsInstallation => "系统升级后保留的旧版Windows文件",
             JunkCategory::AppCache => "各类应用程序产生的缓存文件",
             JunkCategory::FontCache => "Windows字体渲染缓存，删除后会自动重建",
-            JunkCategory::WindowsErrorReports => "系统和应用崩溃时生成的错误报告文件",
+            JunkCategory::WindowsErrorReports => {
+                "系统和应用崩溃时生成的排队、已归档错误报告及临时文件，系统级报告需要管理员权限"
+            }
             JunkCategory::InstallerTemp => "软件安装过程中产生的临时文件",
+            JunkCategory::InstallerRollback => {
+                "中断的 MSI 安装在 C:\\Config.Msi 留下的回滚文件，没有安装正在进行时可安全删除"
+            }
+            JunkCategory::InstallerPatchCache => {
+                "MSI 补丁的基线文件缓存，删除后部分软件的修复、打补丁和卸载可能需要原始安装介质"
+            }
             JunkCategory::ClipboardCache => "剪贴板历史记录缓存文件",
             JunkCategory::ShaderCache => "GPU 着色器编译缓存，删除后游戏和应用首次运行时会重新生成",
+            JunkCategory::OfficeCache => {
+                "Office 未保存文件目录中的临时和恢复文件及上载中心缓存，关闭 Office 后可安全删除"
+            }
+            JunkCategory::DeveloperCache => {
+                "可重新生成的构建和包管理缓存（npm、pip、Cargo、Gradle、NuGet），删除后下次构建需重新下载依赖"
+            }
+            JunkCategory::GameCache => {
+                "Steam 和显卡驱动的游戏着色器缓存，删除后游戏首次启动时会自动重建"
+            }
+            JunkCategory::Custom => "用户指定文件夹中的文件，请确认内容后再删除",
         }
     }
 
@@ -105,6 +178,7 @@ impl JunkCategory {
             JunkCategory::FontCache => 1,
             JunkCategory::ClipboardCache => 1,
             JunkCategory::ShaderCache => 1,
+            JunkCategory::GameCache => 1,
             JunkCategory::BrowserCache => 2,
             JunkCategory::WindowsUpdate => 2,
             JunkCategory::DeliveryOptimization => 2,
@@ -112,14 +186,33 @@ impl JunkCategory {
             JunkCategory::LogFiles => 2,
             JunkCategory::WindowsErrorReports => 2,
             JunkCategory::InstallerTemp => 2,
+            JunkCategory::InstallerRollback => 2,
+            JunkCategory::OfficeCache => 2,
             JunkCategory::RecycleBin => 3,
             JunkCategory::SystemCache => 3,
             JunkCategory::AppCache => 3,
             JunkCategory::MemoryDump => 3,
             JunkCategory::OldWindowsInstallation => 3,
+            // 缓存可重建，但离线环境或私有源下重新下载可能失败
+            JunkCategory::DeveloperCache => 3,
+            // 补丁缓存不会自动重建，删除后只能靠原始安装包修复或卸载
+            JunkCategory::InstallerPatchCache => 4,
+            // 用户自选路径无法预判内容，按最高风险提示
+            JunkCategory::Custom => 5,
         }
     }
 
+    /// 是否为耗时难以预估的分类
+    ///
+    /// 浏览器缓存和开发工具缓存文件数量差异极大，回收站需要解析多用户元数据，
+    /// 浅层目录统计无法反映它们的真实耗时。
+    pub fn is_slow_to_scan(&self) -> bool {
+        matches!(
+            self,
+            JunkCategory::BrowserCache | JunkCategory::RecycleBin | JunkCategory::DeveloperCache
+        )
+    }
+
     /// 获取该分类需要扫描的路径列表
     pub fn get_scan_paths(&self) -> Vec<ScanPath> {
         match self {
@@ -190,6 +283,19 @@ impl JunkCategory {
                 // Opera 浏览器
                 ScanPath::env_path("APPDATA", Some("Opera Software\\Opera Stable\\Cache")),
             ],
+            // D3DSCache 和 NVIDIA/AMD DXCache 已由 ShaderCache 统计，这里不重复扫描；
+            // %LOCALAPPDATA%\CrashDumps 是所有应用共用的转储目录，已归 LogFiles
+            JunkCategory::GameCache => [
+                ScanPath::env_path("LOCALAPPDATA", Some("NVIDIA\\GLCache")),
+                ScanPath::env_path("LOCALAPPDATA", Some("AMD\\GLCache")),
+            ]
+            .into_iter()
+            .chain(
+                steam_shader_cache_dirs()
+                    .iter()
+                    .map(|dir| ScanPath::fixed_path(&dir.to_string_lossy())),
+            )
+            .collect(),
             JunkCategory::RecycleBin => get_all_drive_letters()
                 .into_iter()
                 .map(|letter| ScanPath::fixed_path(&format!("{}:\\$Recycle.Bin", letter)))
@@ -242,18 +348,29 @@ impl JunkCategory {
             JunkCategory::FontCache => vec![ScanPath::fixed_path(
                 "C:\\Windows\\ServiceProfiles\\LocalService\\AppData\\Local\\FontCache",
             )],
-            JunkCategory::WindowsErrorReports => vec![
-                ScanPath::env_path("LOCALAPPDATA", Some("Microsoft\\Windows\\WER")),
-                ScanPath::fixed_path("C:\\ProgramData\\Microsoft\\Windows\\WER"),
-            ],
+            // 只扫描排队、已归档的报告和临时文件；ERC（错误报告配置）等其余子目录不碰。
+            // 系统级目录没有管理员权限时无法读取，扫描结果的 `access_denied_paths` 会列出。
+            JunkCategory::WindowsErrorReports => WER_REPORT_SUBDIRS
+                .iter()
+                .flat_map(|subdir| {
+                    let sub_path = format!("Microsoft\\Windows\\WER\\{}", subdir);
+                    [
+                        ScanPath::env_path("LOCALAPPDATA", Some(&sub_path)),
+                        ScanPath::env_path("PROGRAMDATA", Some(&sub_path)),
+                    ]
+                })
+                .collect(),
             JunkCategory::InstallerTemp => vec![
-                // Windows Installer 补丁缓存
-                ScanPath::fixed_path("C:\\Windows\\Installer\\$PatchCache$"),
                 // 下载的安装程序
                 ScanPath::env_path("LOCALAPPDATA", Some("Downloaded Installations")),
                 // C:\NVIDIA、C:\AMD、C:\Intel 是用户主动保存的驱动安装包，
                 // 删除后可能导致用户无法回退驱动，不再纳入扫描范围
             ],
+            // 安装进行中时扫描引擎会跳过该分类（见 `is_msi_install_in_progress`）
+            JunkCategory::InstallerRollback => vec![ScanPath::fixed_path("C:\\Config.Msi")],
+            JunkCategory::InstallerPatchCache => vec![ScanPath::fixed_path(
+                "C:\\Windows\\Installer\\$PatchCache$",
+            )],
             JunkCategory::ClipboardCache => vec![ScanPath::env_path(
                 "LOCALAPPDATA",
                 Some("Microsoft\\Windows\\Clipboard"),
@@ -274,6 +391,22 @@ impl JunkCategory {
                 ScanPath::env_path("LOCALAPPDATA", Some("NVIDIA\\DXCache")),
                 ScanPath::env_path("LOCALAPPDATA", Some("Intel\\ShaderCache")),
             ],
+            JunkCategory::OfficeCache => vec![
+                ScanPath::env_path("APPDATA", Some("Microsoft\\Office\\UnsavedFiles")),
+                ScanPath::glob_path("LOCALAPPDATA", "Microsoft\\Office\\*\\OfficeFileCache"),
+                // 不扫描文档、桌面等用户目录：其中的 .wbk 备份和文档都是用户数据
+            ],
+            // 只扫描各工具明确的缓存子目录；Cargo 的 bin、Gradle 的 wrapper 等安装内容不在范围内
+            JunkCategory::DeveloperCache => vec![
+                ScanPath::env_path("LOCALAPPDATA", Some("npm-cache")),
+                ScanPath::env_path("APPDATA", Some("npm-cache")),
+                ScanPath::env_path("LOCALAPPDATA", Some("pip\\cache")),
+                ScanPath::env_path("USERPROFILE", Some(".cargo\\registry\\cache")),
+                ScanPath::env_path("USERPROFILE", Some(".gradle\\caches")),
+                ScanPath::env_path("USERPROFILE", Some(".nuget\\packages")),
+            ],
+            // 自定义分类的路径由调用方直接传入
+            JunkCategory::Custom => Vec::new(),
         }
     }
 
@@ -295,12 +428,23 @@ impl JunkCategory {
             JunkCategory::FontCache => vec!["*"],
             JunkCategory::WindowsErrorReports => vec!["*"],
             JunkCategory::InstallerTemp => vec!["*"],
+            JunkCategory::InstallerRollback => vec!["*"],
+            JunkCategory::InstallerPatchCache => vec!["*"],
             JunkCategory::ClipboardCache => vec!["*"],
             JunkCategory::ShaderCache => vec!["*"],
+            // UnsavedFiles 中以 .docx/.xlsb 等正常扩展名保存的未保存草稿可能是用户唯一的副本，
+            // 不能用 "*"，只清理临时和恢复文件。.wbk 是 Word 的备份副本，属于用户数据。
+            // ~$ 所有者文件只在对应文档就在旁边时才计入（见扫描引擎）。
+            JunkCategory::OfficeCache => {
+                vec!["~$*", "~wrl*.tmp", "*.asd", "*.xar", "*.fsd", "*.fsf"]
+            }
+            JunkCategory::DeveloperCache => vec!["*"],
+            JunkCategory::GameCache => vec!["*"],
+            JunkCategory::Custom => vec!["*"],
         }
     }
 
-    /// 获取所有分类
+    /// 获取所有分类（不含 Custom，自定义路径只在显式请求时扫描）
     pub fn all() -> Vec<JunkCategory> {
         vec![
             JunkCategory::WindowsTemp,
@@ -318,12 +462,20 @@ impl JunkCategory {
             JunkCategory::FontCache,
             JunkCategory::WindowsErrorReports,
             JunkCategory::InstallerTemp,
+            JunkCategory::InstallerRollback,
+            JunkCategory::InstallerPatchCache,
             JunkCategory::ClipboardCache,
             JunkCategory::ShaderCache,
+            JunkCategory::OfficeCache,
+            JunkCategory::DeveloperCache,
+            JunkCategory::GameCache,
         ]
     }
 }
 
+/// Windows 错误报告目录下可清理的子目录
+const WER_REPORT_SUBDIRS: &[&str] = &["ReportQueue", "ReportArchive", "Temp"];
+
 /// 获取当前系统中存在的驱动器盘符
 fn get_all_drive_letters() -> Vec<char> {
     ('A'..='Z')
@@ -417,39 +569,146 @@ impl ScanPath {
         match &self.path_type {
             PathType::Fixed | PathType::EnvBased => self.resolve().into_iter().collect(),
             PathType::GlobExpand => {
-                let Some(pattern) = &self.sub_path else {
-                    return Vec::new();
-                };
-
                 let Ok(base_path) = std::env::var(&self.base) else {
                     log::debug!("环境变量不存在，跳过 Glob 展开: {}", self.base);
                     return Vec::new();
                 };
+                self.expand_glob(std::path::Path::new(&base_path))
+            }
+        }
+    }
 
-                let mut full_pattern = std::path::PathBuf::from(base_path);
-                full_pattern.push(pattern);
-                let pattern_string = full_pattern.to_string_lossy().to_string();
-
-                let results: Vec<std::path::PathBuf> = match glob::glob(&pattern_string) {
-                    Ok(paths) => paths
-                        .filter_map(|entry| entry.ok())
-                        .filter(|path| path.exists())
-                        .collect(),
-                    Err(err) => {
-                        log::debug!("Glob 模式无效，跳过: {} ({})", pattern_string, err);
-                        Vec::new()
-                    }
-                };
+    /// 按指定用户配置文件目录解析路径列表
+    ///
+    /// 当前进程的 `%LOCALAPPDATA%` 等变量只指向运行程序的用户，扫描其他用户时
+    /// 需要从配置文件目录显式拼出对应目录。固定路径和系统级环境变量（SYSTEMROOT、
+    /// PROGRAMDATA 等）与用户无关，这里返回空，由常规扫描统计一次即可。
+    pub fn resolve_all_for_profile(
+        &self,
+        profile_dir: &std::path::Path,
+    ) -> Vec<std::path::PathBuf> {
+        let Some(base_path) = profile_env_dir(&self.base, profile_dir) else {
+            return Vec::new();
+        };
+        match &self.path_type {
+            PathType::Fixed => Vec::new(),
+            PathType::EnvBased => {
+                let mut path = base_path;
+                if let Some(sub) = &self.sub_path {
+                    path.push(sub);
+                }
+                if path.exists() {
+                    vec![path]
+                } else {
+                    Vec::new()
+                }
+            }
+            PathType::GlobExpand => self.expand_glob(&base_path),
+        }
+    }
+
+    /// 在基础目录下展开通配符子路径
+    fn expand_glob(&self, base_path: &std::path::Path) -> Vec<std::path::PathBuf> {
+        let Some(pattern) = &self.sub_path else {
+            return Vec::new();
+        };
+
+        let full_pattern = base_path.join(pattern);
+        let pattern_string = full_pattern.to_string_lossy().to_string();
 
-                log::debug!(
-                    "Glob 展开 '{}' 得到 {} 个路径",
-                    pattern_string,
-                    results.len()
-                );
-                results
+        let results: Vec<std::path::PathBuf> = match glob::glob(&pattern_string) {
+            Ok(paths) => paths
+                .filter_map(|entry| entry.ok())
+                .filter(|path| path.exists())
+                .collect(),
+            Err(err) => {
+                log::debug!("Glob 模式无效，跳过: {} ({})", pattern_string, err);
+                Vec::new()
+            }
+        };
+
+        log::debug!(
+            "Glob 展开 '{}' 得到 {} 个路径",
+            pattern_string,
+            results.len()
+        );
+        results
+    }
+}
+
+/// Steam 各游戏库的着色器缓存目录（`steamapps\shadercache`）
+///
+/// 安装目录从 `HKCU\Software\Valve\Steam\SteamPath` 读取，其他游戏库列在
+/// `steamapps\libraryfolders.vdf` 中。未安装 Steam 时返回空。
+fn steam_shader_cache_dirs() -> Vec<std::path::PathBuf> {
+    let Some(steam_dir) = read_steam_path() else {
+        return Vec::new();
+    };
+
+    let mut libraries = vec![steam_dir.clone()];
+    let vdf_path = steam_dir.join("steamapps").join("libraryfolders.vdf");
+    if let Ok(content) = std::fs::read_to_string(&vdf_path) {
+        for library in parse_steam_library_paths(&content) {
+            if !libraries.iter().any(|known| paths_equal(known, &library)) {
+                libraries.push(library);
             }
         }
     }
+
+    libraries
+        .into_iter()
+        .map(|library| library.join("steamapps").join("shadercache"))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+fn read_steam_path() -> Option<std::path::PathBuf> {
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+
+    let steam_key = RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey("Software\\Valve\\Steam")
+        .ok()?;
+    let steam_path: String = steam_key.get_value("SteamPath").ok()?;
+    // SteamPath 使用正斜杠（如 c:/program files (x86)/steam）
+    let steam_path = steam_path.trim().replace('/', "\\");
+    (!steam_path.is_empty()).then(|| std::path::PathBuf::from(steam_path))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn read_steam_path() -> Option<std::path::PathBuf> {
+    None
+}
+
+/// 从 libraryfolders.vdf 中提取游戏库路径（`"path"  "D:\\SteamLibrary"` 行）
+fn parse_steam_library_paths(vdf: &str) -> Vec<std::path::PathBuf> {
+    vdf.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('"').filter(|field| !field.trim().is_empty());
+            let key = fields.next()?;
+            let value = fields.next()?;
+            key.eq_ignore_ascii_case("path")
+                .then(|| std::path::PathBuf::from(value.replace("\\\\", "\\")))
+        })
+        .collect()
+}
+
+fn paths_equal(left: &std::path::Path, right: &std::path::Path) -> bool {
+    left.to_string_lossy()
+        .trim_end_matches('\\')
+        .eq_ignore_ascii_case(right.to_string_lossy().trim_end_matches('\\'))
+}
+
+/// 用户级环境变量在指定配置文件下对应的目录，系统级变量返回 None
+fn profile_env_dir(env_var: &str, profile_dir: &std::path::Path) -> Option<std::path::PathBuf> {
+    match env_var.to_ascii_uppercase().as_str() {
+        "USERPROFILE" => Some(profile_dir.to_path_buf()),
+        "LOCALAPPDATA" => Some(profile_dir.join("AppData\\Local")),
+        "APPDATA" => Some(profile_dir.join("AppData\\Roaming")),
+        "TEMP" | "TMP" => Some(profile_dir.join("AppData\\Local\\Temp")),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -458,15 +717,81 @@ mod tests {
 
     #[test]
     fn test_all_categories_covered() {
-        const JUNK_CATEGORY_VARIANT_COUNT: usize = 17;
+        const JUNK_CATEGORY_VARIANT_COUNT: usize = 22;
         assert_eq!(JunkCategory::all().len(), JUNK_CATEGORY_VARIANT_COUNT);
     }
 
+    #[test]
+    fn test_slow_categories() {
+        // 只有浏览器缓存、回收站和开发工具缓存无法通过浅层统计预估耗时
+        let slow: Vec<JunkCategory> = JunkCategory::all()
+            .into_iter()
+            .filter(|c| c.is_slow_to_scan())
+            .collect();
+        assert_eq!(
+            slow,
+            vec![
+                JunkCategory::BrowserCache,
+                JunkCategory::RecycleBin,
+                JunkCategory::DeveloperCache
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_steam_library_paths() {
+        let vdf = r#"
+"libraryfolders"
+{
+	"0"
+	{
+		"path"		"C:\\Program Files (x86)\\Steam"
+		"label"		""
+		"apps"
+		{
+			"228980"		"1234"
+		}
+	}
+	"1"
+	{
+		"path"		"D:\\SteamLibrary"
+	}
+}
+"#;
+        assert_eq!(
+            parse_steam_library_paths(vdf),
+            vec![
+                std::path::PathBuf::from(r"C:\Program Files (x86)\Steam"),
+                std::path::PathBuf::from(r"D:\SteamLibrary"),
+            ]
+        );
+    }
+
     #[test]
     fn test_recycle_bin_multi_drive() {
         assert!(get_all_drive_letters().contains(&'C'));
     }
 
+    #[test]
+    fn test_resolve_all_for_profile_builds_user_paths() {
+        let profile = std::env::temp_dir().join(format!("lightc-profile-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&profile);
+        let temp = profile.join("AppData\\Local\\Temp");
+        std::fs::create_dir_all(&temp).unwrap();
+
+        let roots: Vec<std::path::PathBuf> = JunkCategory::WindowsTemp
+            .get_scan_paths()
+            .iter()
+            .flat_map(|scan_path| scan_path.resolve_all_for_profile(&profile))
+            .collect();
+        // %TEMP% 与 %TMP% 都指向用户临时目录，C:\Windows\Temp 属于系统级路径不参与
+        assert_eq!(roots, vec![temp.clone(), temp.clone()]);
+        assert!(ScanPath::env_path("SYSTEMROOT", Some("Temp"))
+            .resolve_all_for_profile(&profile)
+            .is_empty());
+        let _ = std::fs::remove_dir_all(&profile);
+    }
+
     #[test]
     fn test_glob_path_resolve_all() {
         let fixed_path = ScanPath::fixed_path("C:\\");
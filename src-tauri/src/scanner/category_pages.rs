@@ -0,0 +1,91 @@
+// ============================================================================
+// 快速扫描分类分页
+//
+// 浏览器缓存等分类可能有十几万个文件，全部序列化给前端既占内存又拖慢渲染。
+// 扫描请求指定每类返回上限时，完整文件列表按大小降序保留在后端，
+// 首次只返回最大的 N 个文件，其余通过 `get_category_files` 按需加载。
+// ============================================================================
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use super::{CategoryScanResult, ScanResult};
+
+/// 单页最多返回的文件数，避免前端一次请求过多数据
+const MAX_CATEGORY_PAGE_SIZE: usize = 2000;
+
+/// 最近一次限量扫描的完整分类结果（按显示名称索引，文件已按大小降序）
+static CATEGORY_FILE_CACHE: LazyLock<Mutex<HashMap<String, CategoryScanResult>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 将每个分类的 `files` 限制为最大的 `max_files` 个，完整列表留在后端供分页读取
+///
+/// `total_size` / `file_count` 保持完整统计，被截断的分类 `has_more` 为 true。
+/// 新的扫描结果会替换之前缓存的同名分类。
+pub fn limit_category_files(result: &mut ScanResult, max_files: usize) {
+    let mut cache = CATEGORY_FILE_CACHE
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+
+    for category in &mut result.categories {
+        category
+            .files
+            .sort_by_key(|item| std::cmp::Reverse(item.size));
+        let limited = category.page(0, max_files);
+        let full = std::mem::replace(category, limited);
+        cache.insert(full.display_name.clone(), full);
+    }
+}
+
+/// 读取最近一次扫描中某个分类的一页文件
+pub fn get_category_files(
+    category_name: &str,
+    offset: usize,
+    limit: usize,
+) -> Result<CategoryScanResult, String> {
+    let cache = CATEGORY_FILE_CACHE
+        .lock()
+        .map_err(|_| "分类结果缓存锁异常，请重试".to_string())?;
+    let category = cache
+        .get(category_name)
+        .ok_or_else(|| format!("没有分类「{}」的扫描结果，请重新扫描", category_name))?;
+    Ok(category.page(offset, limit.clamp(1, MAX_CATEGORY_PAGE_SIZE)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{FileInfo, JunkCategory};
+
+    #[test]
+    fn test_limit_and_page_category_files() {
+        let mut category = CategoryScanResult::new(JunkCategory::ShaderCache);
+        for size in [10, 50, 30, 20, 40] {
+            category.add_file(FileInfo::new(
+                format!(r"C:\Cache\{}.bin", size),
+                format!("{}.bin", size),
+                size,
+                0,
+                false,
+                JunkCategory::ShaderCache,
+            ));
+        }
+        let name = category.display_name.clone();
+        let mut result = ScanResult::new();
+        result.add_category_result(category);
+
+        limit_category_files(&mut result, 2);
+
+        let first = &result.categories[0];
+        let sizes: Vec<u64> = first.files.iter().map(|file| file.size).collect();
+        assert_eq!(sizes, vec![50, 40]);
+        assert_eq!(first.file_count, 5);
+        assert_eq!(first.total_size, 150);
+        assert!(first.has_more);
+
+        let rest = get_category_files(&name, 2, 10).unwrap();
+        let sizes: Vec<u64> = rest.files.iter().map(|file| file.size).collect();
+        assert_eq!(sizes, vec![30, 20, 10]);
+        assert!(!rest.has_more);
+    }
+}
@@ -27,16 +27,22 @@
 // ============================================================================
 
 use serde::{Deserialize, Serialize};
+#[cfg(windows)]
 use std::ffi::OsString;
+#[cfg(windows)]
 use std::os::windows::ffi::OsStringExt;
+#[cfg(windows)]
 use std::path::Path;
+#[cfg(windows)]
 use winreg::enums::*;
+#[cfg(windows)]
 use winreg::RegKey;
 
 // ============================================================================
 // Windows API FFI（用于解析 MUIVerb 间接字符串 @path,-id）
 // ============================================================================
 
+#[cfg(windows)]
 #[link(name = "shlwapi")]
 extern "system" {
     /// https://learn.microsoft.com/en-us/windows/win32/api/shlwapi/nf-shlwapi-shloadindirectstring
@@ -50,6 +56,7 @@ extern "system" {
 
 /// 调用 SHLoadIndirectString 解析 @path,-id 格式的间接字符串
 /// 失败时返回原始字符串
+#[cfg(windows)]
 fn resolve_indirect_string(raw: &str) -> String {
     if !raw.starts_with('@') {
         return raw.to_string();
@@ -258,28 +265,9 @@ impl ContextMenuScanner {
     /// 执行完整扫描，返回所有找到的右键菜单条目
     pub fn scan(&self) -> Result<ContextMenuScanResult, String> {
         let start = std::time::Instant::now();
-        let mut entries: Vec<ContextMenuEntry> = Vec::new();
-
-        // 扫描 HKEY_LOCAL_MACHINE（需要管理员才能删除）
-        self.scan_hive(
-            RegKey::predef(HKEY_LOCAL_MACHINE),
-            "HKLM",
-            r"SOFTWARE\Classes",
-            true,
-            &mut entries,
-        );
-
-        // 扫描 HKEY_CURRENT_USER（当前用户级别，无需管理员）
-        self.scan_hive(
-            RegKey::predef(HKEY_CURRENT_USER),
-            "HKCU",
-            r"Software\Classes",
-            false,
-            &mut entries,
-        );
 
         // 对相同显示名称去重：优先保留 HKCU 条目（用户级优先）
-        entries = Self::deduplicate(entries);
+        let mut entries = Self::deduplicate(self.scan_hives());
 
         // 按状态排序：无效条目（exe 不存在）排在前面
         entries.sort_by(|a, b| {
@@ -307,7 +295,39 @@ impl ContextMenuScanner {
         })
     }
 
+    /// 依次扫描 HKLM 和 HKCU 两棵树
+    #[cfg(windows)]
+    fn scan_hives(&self) -> Vec<ContextMenuEntry> {
+        let mut entries: Vec<ContextMenuEntry> = Vec::new();
+
+        // 扫描 HKEY_LOCAL_MACHINE（需要管理员才能删除）
+        self.scan_hive(
+            RegKey::predef(HKEY_LOCAL_MACHINE),
+            "HKLM",
+            r"SOFTWARE\Classes",
+            true,
+            &mut entries,
+        );
+
+        // 扫描 HKEY_CURRENT_USER（当前用户级别，无需管理员）
+        self.scan_hive(
+            RegKey::predef(HKEY_CURRENT_USER),
+            "HKCU",
+            r"Software\Classes",
+            false,
+            &mut entries,
+        );
+        entries
+    }
+
+    /// 非 Windows 平台没有注册表，不会有右键菜单条目
+    #[cfg(not(windows))]
+    fn scan_hives(&self) -> Vec<ContextMenuEntry> {
+        Vec::new()
+    }
+
     /// 扫描指定注册表 Hive 下的所有右键菜单路径
+    #[cfg(windows)]
     fn scan_hive(
         &self,
         hive: RegKey,
@@ -352,6 +372,7 @@ impl ContextMenuScanner {
     }
 
     /// 扫描单个 shell 键下的所有子键（每个子键对应一个菜单项）
+    #[cfg(windows)]
     fn scan_shell_key(
         &self,
         shell_key: &RegKey,
@@ -394,6 +415,7 @@ impl ContextMenuScanner {
     }
 
     /// 从注册表键构建 ContextMenuEntry
+    #[cfg(windows)]
     fn build_entry(
         &self,
         entry_key: &RegKey,
@@ -487,6 +509,7 @@ impl ContextMenuScanner {
     /// - `C:\Windows\system32\notepad.exe %1`
     /// - `rundll32.exe shell32.dll,OpenAs_RunDLL %1`
     /// - `%SystemRoot%\system32\cmd.exe /c ...`
+    #[cfg_attr(not(windows), allow(dead_code))]
     fn extract_exe_path(cmd: &str) -> Option<String> {
         let cmd = cmd.trim();
         if cmd.is_empty() {
@@ -509,9 +532,9 @@ impl ContextMenuScanner {
         }
 
         // 处理带引号的路径：`"C:\path\to\app.exe" ...`
-        if cmd.starts_with('"') {
-            if let Some(end_quote) = cmd[1..].find('"') {
-                let path = &cmd[1..=end_quote];
+        if let Some(quoted) = cmd.strip_prefix('"') {
+            if let Some(end_quote) = quoted.find('"') {
+                let path = &quoted[..end_quote];
                 if !path.is_empty() {
                     return Some(expand_env_vars(path));
                 }
@@ -624,6 +647,7 @@ pub fn delete_context_menu_entries(
 
 /// 删除单个注册表条目（递归删除整个 shell 子键）
 /// 删除前自动导出 .reg 备份文件至数据目录
+#[cfg(windows)]
 fn delete_single_entry(req: &ContextMenuDeleteRequest) -> Result<(), String> {
     // 安全阀：拒绝删除系统保护的条目（shellex\ContextMenuHandlers）
     if req.reg_subpath.contains(r"shellex\ContextMenuHandlers") {
@@ -663,8 +687,14 @@ fn delete_single_entry(req: &ContextMenuDeleteRequest) -> Result<(), String> {
     Ok(())
 }
 
+#[cfg(not(windows))]
+fn delete_single_entry(_req: &ContextMenuDeleteRequest) -> Result<(), String> {
+    Err("此功能仅支持Windows系统".to_string())
+}
+
 /// 导出注册表键的 .reg 备份（用于误删恢复）
 /// 使用 reg.exe export 命令，备份文件存放在数据目录的 reg_backups/ 下
+#[cfg(windows)]
 fn export_reg_backup(reg_root: &str, subpath: &str) {
     let data_dir = crate::data_dir::get_data_dir();
     let backup_dir = data_dir.join("reg_backups");
@@ -684,12 +714,13 @@ fn export_reg_backup(reg_root: &str, subpath: &str) {
     let backup_path = backup_dir.join(&file_name);
 
     // 调用 reg.exe export 导出
-    match std::process::Command::new("reg.exe")
-        .args(["export", &format!("{}\\{}", reg_root, subpath)])
-        .arg(&backup_path)
-        .arg("/y") // 覆盖已有文件
-        .output()
-    {
+    match crate::process_runner::run_with_timeout(
+        std::process::Command::new("reg.exe")
+            .args(["export", &format!("{}\\{}", reg_root, subpath)])
+            .arg(&backup_path)
+            .arg("/y"), // 覆盖已有文件
+        crate::process_runner::DEFAULT_COMMAND_TIMEOUT,
+    ) {
         Ok(output) => {
             if output.status.success() {
                 log::info!("已导出注册表备份: {}", backup_path.display());
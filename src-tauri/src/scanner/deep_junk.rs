@@ -6,17 +6,24 @@
 // ============================================================================
 
 use std::collections::{HashMap, HashSet};
-use std::path::{Path, PathBuf};
+use std::path::Path;
+#[cfg(windows)]
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{LazyLock, Mutex};
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use log::{info, warn};
+#[cfg(windows)]
+use log::info;
+use log::warn;
 use serde::Serialize;
 use tauri::{Emitter, Window};
+#[cfg(windows)]
 use walkdir::WalkDir;
 
 use super::{CategoryScanResult, FileInfo, JunkCategory};
+#[cfg(windows)]
+use crate::cleaner::safety_constants::is_cloud_placeholder;
 
 const DEEP_JUNK_MIN_AGE_SECONDS: i64 = 24 * 60 * 60;
 const DEEP_JUNK_PAGE_SIZE: usize = 500;
@@ -84,11 +91,18 @@ pub fn cancel() {
     DEEP_JUNK_SCAN_CANCELLED.store(true, Ordering::SeqCst);
 }
 
-fn is_cancelled() -> bool {
+pub(crate) fn is_cancelled() -> bool {
     DEEP_JUNK_SCAN_CANCELLED.load(Ordering::SeqCst)
 }
 
 /// 执行所有固定分区的深度垃圾扫描。
+#[cfg(not(windows))]
+pub fn scan_all(_window: &Window) -> Result<DeepJunkScanResult, String> {
+    Err("深度垃圾扫描仅支持 Windows 系统".to_string())
+}
+
+/// 执行所有固定分区的深度垃圾扫描。
+#[cfg(windows)]
 pub fn scan_all(window: &Window) -> Result<DeepJunkScanResult, String> {
     let started_at = std::time::Instant::now();
     let mut categories = JunkCategory::all()
@@ -174,12 +188,6 @@ pub fn scan_all(window: &Window) -> Result<DeepJunkScanResult, String> {
         }
     }
 
-    #[cfg(not(windows))]
-    {
-        let _ = window;
-        return Err("深度垃圾扫描仅支持 Windows 系统".to_string());
-    }
-
     let mut result = DeepJunkScanResult {
         scan_mode: "deep".to_string(),
         scan_id: String::new(),
@@ -335,12 +343,7 @@ fn page_category(
     offset: usize,
     limit: usize,
 ) -> Result<CategoryScanResult, String> {
-    let mut page = category.clone();
-    let start = offset.min(category.files.len());
-    let end = start.saturating_add(limit).min(category.files.len());
-    page.files = category.files[start..end].to_vec();
-    page.has_more = end < category.file_count;
-    Ok(page)
+    Ok(category.page(offset, limit))
 }
 
 #[cfg(windows)]
@@ -583,6 +586,9 @@ fn scan_non_ntfs_drive(
             let Ok(metadata) = entry.metadata() else {
                 continue;
             };
+            if is_cloud_placeholder(&metadata) {
+                continue;
+            }
             let modified = metadata
                 .modified()
                 .ok()
@@ -780,13 +786,9 @@ fn match_deep_junk_category(path: &str) -> Option<JunkCategory> {
     }
     if contains_any(
         &normalized,
-        &[
-            "\\appdata\\local\\crashdumps\\",
-            "\\windows\\minidump\\",
-            "\\appdata\\local\\microsoft\\windows\\wer\\",
-            "\\programdata\\microsoft\\windows\\wer\\",
-        ],
-    ) {
+        &["\\appdata\\local\\crashdumps\\", "\\windows\\minidump\\"],
+    ) || is_wer_report_path(&normalized)
+    {
         return Some(JunkCategory::WindowsErrorReports);
     }
     if normalized.ends_with("\\windows\\memory.dmp") {
@@ -801,15 +803,12 @@ fn match_deep_junk_category(path: &str) -> Option<JunkCategory> {
     ) {
         return Some(JunkCategory::OldWindowsInstallation);
     }
-    if contains_any(
-        &normalized,
-        &[
-            "\\appdata\\local\\downloaded installations\\",
-            "\\windows\\installer\\$patchcache$\\",
-        ],
-    ) {
+    if normalized.contains("\\appdata\\local\\downloaded installations\\") {
         return Some(JunkCategory::InstallerTemp);
     }
+    if normalized.contains("\\windows\\installer\\$patchcache$\\") {
+        return Some(JunkCategory::InstallerPatchCache);
+    }
     if normalized.contains("\\appdata\\local\\microsoft\\windows\\webcache\\") {
         return Some(JunkCategory::AppCache);
     }
@@ -823,6 +822,20 @@ fn match_deep_junk_category(path: &str) -> Option<JunkCategory> {
     None
 }
 
+/// WER 目录下只有排队、已归档的报告和临时文件可清理，ERC 等配置目录不算垃圾
+fn is_wer_report_path(path: &str) -> bool {
+    [
+        "\\appdata\\local\\microsoft\\windows\\wer\\",
+        "\\programdata\\microsoft\\windows\\wer\\",
+    ]
+    .iter()
+    .any(|root| {
+        ["reportqueue\\", "reportarchive\\", "temp\\"]
+            .iter()
+            .any(|subdir| path.contains(&format!("{}{}", root, subdir)))
+    })
+}
+
 fn normalize_path(path: &str) -> String {
     path.replace('/', "\\").to_ascii_lowercase()
 }
@@ -61,6 +61,9 @@ impl FileInfo {
 /// 分类扫描结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CategoryScanResult {
+    /// 稳定标识（`JunkCategory::key()`），多次扫描之间不变，供前端 diff 使用
+    #[serde(default)]
+    pub key: String,
     /// 分类
     pub category: JunkCategory,
     /// 分类显示名称
@@ -75,14 +78,27 @@ pub struct CategoryScanResult {
     pub total_size: u64,
     /// 文件数量
     pub file_count: usize,
-    /// 深度扫描分页标记；快速扫描始终为 false。
+    /// 分页标记：`files` 只包含部分文件时为 true（深度扫描分页、快速扫描限制返回数量）。
     pub has_more: bool,
+    /// 低于最小文件大小、未列入 `files` 的文件数（其大小已计入 `total_size`）
+    #[serde(default)]
+    pub small_file_count: usize,
+    /// 未列入 `files` 的小文件总大小
+    #[serde(default)]
+    pub small_file_size: u64,
+    /// 达到扫描引擎的文件数或耗时上限后提前停止，结果不完整
+    #[serde(default)]
+    pub truncated: bool,
+    /// 因权限不足无法读取的扫描根目录（如未以管理员身份运行时的系统级错误报告目录）
+    #[serde(default)]
+    pub access_denied_paths: Vec<String>,
 }
 
 impl CategoryScanResult {
     /// 创建新的分类扫描结果
     pub fn new(category: JunkCategory) -> Self {
         CategoryScanResult {
+            key: category.key().to_string(),
             display_name: category.display_name().to_string(),
             description: category.description().to_string(),
             risk_level: category.risk_level(),
@@ -91,6 +107,10 @@ impl CategoryScanResult {
             total_size: 0,
             file_count: 0,
             has_more: false,
+            small_file_count: 0,
+            small_file_size: 0,
+            truncated: false,
+            access_denied_paths: Vec::new(),
         }
     }
 
@@ -101,15 +121,53 @@ impl CategoryScanResult {
         self.files.push(file);
     }
 
+    /// 记录未列出的小文件，只累加大小和小文件数量
+    pub fn add_small_file(&mut self, size: u64) {
+        self.total_size += size;
+        self.small_file_count += 1;
+        self.small_file_size += size;
+    }
+
+    /// 取出一页文件，总大小和文件数保持整个分类的统计值
+    pub fn page(&self, offset: usize, limit: usize) -> CategoryScanResult {
+        let start = offset.min(self.files.len());
+        let end = start.saturating_add(limit).min(self.files.len());
+        CategoryScanResult {
+            key: self.key.clone(),
+            category: self.category.clone(),
+            display_name: self.display_name.clone(),
+            description: self.description.clone(),
+            risk_level: self.risk_level,
+            files: self.files[start..end].to_vec(),
+            total_size: self.total_size,
+            file_count: self.file_count,
+            has_more: end < self.file_count,
+            small_file_count: self.small_file_count,
+            small_file_size: self.small_file_size,
+            truncated: self.truncated,
+            access_denied_paths: self.access_denied_paths.clone(),
+        }
+    }
+
     /// 获取人类可读的总大小
     pub fn human_readable_total_size(&self) -> String {
         format_size(self.total_size)
     }
 }
 
+/// 扫描结果 JSON 的格式版本
+///
+/// `ScanResult`、`SocialScanResult`、`LeftoverScanResult`、`RegistryScanResult` 共用。
+/// 字段改名、删除或含义变化时递增，外部脚本据此判断能否解析；只新增字段不需要递增。
+/// 没有该字段的旧数据反序列化为 0。
+pub const RESULT_SCHEMA_VERSION: u32 = 1;
+
 /// 完整扫描结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanResult {
+    /// JSON 格式版本（见 `RESULT_SCHEMA_VERSION`）
+    #[serde(default)]
+    pub schema_version: u32,
     /// 各分类的扫描结果
     pub categories: Vec<CategoryScanResult>,
     /// 总大小（字节）
@@ -126,6 +184,7 @@ impl ScanResult {
     /// 创建新的扫描结果
     pub fn new() -> Self {
         ScanResult {
+            schema_version: RESULT_SCHEMA_VERSION,
             categories: Vec::new(),
             total_size: 0,
             total_file_count: 0,
@@ -141,6 +200,34 @@ impl ScanResult {
         self.categories.push(result);
     }
 
+    /// 按 `JunkCategory::all()` 的固定顺序排列分类
+    ///
+    /// 各分类由并行线程扫描，完成顺序不固定；排序后连续两次扫描的列表顺序一致。
+    pub fn sort_categories(&mut self) {
+        self.categories
+            .sort_by_key(|category| category.category.canonical_order());
+    }
+
+    /// 移除没有发现文件的分类
+    ///
+    /// 空分类的大小和数量均为 0，移除后汇总统计保持不变。
+    pub fn remove_empty_categories(&mut self) {
+        self.categories
+            .retain(|category| category.file_count > 0 || category.small_file_count > 0);
+    }
+
+    /// 返回所有分类中体积最大的前 N 个文件（按大小降序）
+    pub fn largest_files(&self, top_n: usize) -> Vec<FileInfo> {
+        let mut files: Vec<&FileInfo> = self
+            .categories
+            .iter()
+            .flat_map(|category| category.files.iter())
+            .collect();
+        // 大小相同时按路径排序，保证多次调用结果稳定
+        files.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.path.cmp(&b.path)));
+        files.into_iter().take(top_n).cloned().collect()
+    }
+
     /// 设置扫描耗时
     pub fn set_duration(&mut self, duration_ms: u64) {
         self.scan_duration_ms = duration_ms;
@@ -158,6 +245,25 @@ impl Default for ScanResult {
     }
 }
 
+/// 扫描范围估算结果（仅浅层统计，不代表真实文件数量）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScanEstimate {
+    /// 参与估算的分类数量
+    pub category_count: usize,
+    /// 扫描根目录及其第一层子目录的数量
+    pub directory_count: usize,
+    /// 是否包含耗时难以预估的分类
+    pub has_slow_categories: bool,
+    /// 耗时难以预估的分类名称
+    pub slow_categories: Vec<String>,
+    /// 系统盘当前可用空间（字节），查询失败时为空
+    #[serde(default)]
+    pub system_drive_free_space: Option<u64>,
+    /// 系统盘可用空间严重不足，应先执行紧急释放再做完整扫描
+    #[serde(default)]
+    pub critically_low_space: bool,
+}
+
 /// 扫描进度信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanProgress {
@@ -210,17 +316,35 @@ impl ScanProgress {
 }
 
 /// 格式化文件大小为人类可读格式
+///
+/// 按 1024 进制计算、标注为 KB/MB/GB，与资源管理器显示一致。
 pub fn format_size(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-
-    if bytes >= GB {
-        format!("{:.2} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.2} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.2} KB", bytes as f64 / KB as f64)
+    format_size_in(bytes, 1024, ["KB", "MB", "GB"])
+}
+
+/// 按指定进制格式化文件大小，标注规范单位
+///
+/// `binary` 为 true 时按 1024 进制标注 KiB/MiB/GiB，否则按 1000 进制标注 KB/MB/GB
+/// （与硬盘厂商标称容量一致）。
+pub fn format_size_with(bytes: u64, binary: bool) -> String {
+    if binary {
+        format_size_in(bytes, 1024, ["KiB", "MiB", "GiB"])
+    } else {
+        format_size_in(bytes, 1000, ["KB", "MB", "GB"])
+    }
+}
+
+fn format_size_in(bytes: u64, base: u64, units: [&str; 3]) -> String {
+    let kb = base;
+    let mb = kb * base;
+    let gb = mb * base;
+
+    if bytes >= gb {
+        format!("{:.2} {}", bytes as f64 / gb as f64, units[2])
+    } else if bytes >= mb {
+        format!("{:.2} {}", bytes as f64 / mb as f64, units[1])
+    } else if bytes >= kb {
+        format!("{:.2} {}", bytes as f64 / kb as f64, units[0])
     } else {
         format!("{} B", bytes)
     }
@@ -235,6 +359,12 @@ pub struct DeleteResult {
     pub failed_count: usize,
     /// 标记为重启后删除的文件数
     pub reboot_pending_count: usize,
+    /// 标记为重启后删除的文件大小（已计入 freed_size）
+    #[serde(default)]
+    pub reboot_pending_size: u64,
+    /// 删除前已不存在而跳过的文件数（不计入失败）
+    #[serde(default)]
+    pub skipped_notfound: usize,
     /// 释放的空间大小（字节）
     pub freed_size: u64,
     /// 是否需要重启完成清理
@@ -250,6 +380,8 @@ impl DeleteResult {
             success_count: 0,
             failed_count: 0,
             reboot_pending_count: 0,
+            reboot_pending_size: 0,
+            skipped_notfound: 0,
             freed_size: 0,
             needs_reboot: false,
             failed_files: Vec::new(),
@@ -265,10 +397,16 @@ impl DeleteResult {
     /// 记录重启后删除
     pub fn add_reboot_pending(&mut self, size: u64) {
         self.reboot_pending_count += 1;
+        self.reboot_pending_size += size;
         self.needs_reboot = true;
         self.freed_size += size; // 文件将在重启后删除，计入释放空间
     }
 
+    /// 记录删除前已不存在的文件
+    pub fn add_skipped_notfound(&mut self) {
+        self.skipped_notfound += 1;
+    }
+
     /// 记录删除失败
     pub fn add_failure(&mut self, path: String, reason: String) {
         self.failed_count += 1;
@@ -290,3 +428,72 @@ pub struct DeleteError {
     /// 错误原因
     pub reason: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_file(path: &str, size: u64, category: JunkCategory) -> FileInfo {
+        let name = path.rsplit('\\').next().unwrap_or(path).to_string();
+        FileInfo::new(path.to_string(), name, size, 0, false, category)
+    }
+
+    #[test]
+    fn test_remove_empty_categories_keeps_totals() {
+        let mut result = ScanResult::new();
+        let mut temp = CategoryScanResult::new(JunkCategory::WindowsTemp);
+        temp.add_file(test_file(r"C:\Temp\a.tmp", 10, JunkCategory::WindowsTemp));
+        result.add_category_result(temp);
+        result.add_category_result(CategoryScanResult::new(JunkCategory::LogFiles));
+
+        result.remove_empty_categories();
+
+        assert_eq!(result.categories.len(), 1);
+        assert_eq!(result.categories[0].category, JunkCategory::WindowsTemp);
+        assert_eq!(result.total_size, 10);
+        assert_eq!(result.total_file_count, 1);
+    }
+
+    #[test]
+    fn test_sort_categories_uses_canonical_order() {
+        let mut result = ScanResult::new();
+        result.add_category_result(CategoryScanResult::new(JunkCategory::ShaderCache));
+        result.add_category_result(CategoryScanResult::new(JunkCategory::LogFiles));
+        result.add_category_result(CategoryScanResult::new(JunkCategory::WindowsTemp));
+
+        result.sort_categories();
+
+        let keys: Vec<&str> = result.categories.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(keys, vec!["WindowsTemp", "LogFiles", "ShaderCache"]);
+    }
+
+    #[test]
+    fn test_format_size_units() {
+        let bytes = 4_000_000_000;
+        assert_eq!(format_size(bytes), "3.73 GB");
+        assert_eq!(format_size_with(bytes, true), "3.73 GiB");
+        assert_eq!(format_size_with(bytes, false), "4.00 GB");
+        assert_eq!(format_size_with(512, false), "512 B");
+    }
+
+    #[test]
+    fn test_largest_files_across_categories() {
+        let mut result = ScanResult::new();
+        let mut temp = CategoryScanResult::new(JunkCategory::WindowsTemp);
+        temp.add_file(test_file(
+            r"C:\Temp\small.tmp",
+            5,
+            JunkCategory::WindowsTemp,
+        ));
+        temp.add_file(test_file(r"C:\Temp\big.tmp", 50, JunkCategory::WindowsTemp));
+        let mut logs = CategoryScanResult::new(JunkCategory::LogFiles);
+        logs.add_file(test_file(r"C:\Logs\mid.log", 20, JunkCategory::LogFiles));
+        result.add_category_result(temp);
+        result.add_category_result(logs);
+
+        let largest = result.largest_files(2);
+        let sizes: Vec<u64> = largest.iter().map(|file| file.size).collect();
+        assert_eq!(sizes, vec![50, 20]);
+        assert_eq!(result.largest_files(10).len(), 3);
+    }
+}
@@ -0,0 +1,94 @@
+// ============================================================================
+// 扫描发现导出
+//
+// 删除前把卸载残留 / 注册表残留的完整发现列表保存到磁盘，用于审计和回滚规划。
+// 注册表的 .reg 备份只能恢复键值，不记录"为什么被标记"；导出的 JSON
+// 保留每个条目的问题描述和关联路径。
+// ============================================================================
+
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+
+use super::{LeftoverScanResult, RegistryScanResult};
+
+/// 最近一次卸载残留扫描结果
+static LAST_LEFTOVER_SCAN: LazyLock<Mutex<Option<LeftoverScanResult>>> =
+    LazyLock::new(|| Mutex::new(None));
+
+/// 最近一次注册表残留扫描结果
+static LAST_REGISTRY_SCAN: LazyLock<Mutex<Option<RegistryScanResult>>> =
+    LazyLock::new(|| Mutex::new(None));
+
+/// 记录最近一次卸载残留扫描结果
+pub fn remember_leftover_scan(result: &LeftoverScanResult) {
+    *LAST_LEFTOVER_SCAN
+        .lock()
+        .unwrap_or_else(|error| error.into_inner()) = Some(result.clone());
+}
+
+/// 记录最近一次注册表残留扫描结果
+pub fn remember_registry_scan(result: &RegistryScanResult) {
+    *LAST_REGISTRY_SCAN
+        .lock()
+        .unwrap_or_else(|error| error.into_inner()) = Some(result.clone());
+}
+
+/// 将最近一次扫描的发现导出为格式化 JSON，返回导出的条目数
+///
+/// `kind` 为 "leftovers" 或 "registry"。
+pub fn export_findings(kind: &str, output_path: &Path) -> Result<usize, String> {
+    let (json, count) = match kind {
+        "leftovers" => {
+            let cache = LAST_LEFTOVER_SCAN
+                .lock()
+                .map_err(|_| "扫描结果缓存锁异常，请重试".to_string())?;
+            let result = cache
+                .as_ref()
+                .ok_or_else(|| "没有卸载残留扫描结果，请先扫描".to_string())?;
+            (serde_json::to_string_pretty(result), result.leftovers.len())
+        }
+        "registry" => {
+            let cache = LAST_REGISTRY_SCAN
+                .lock()
+                .map_err(|_| "扫描结果缓存锁异常，请重试".to_string())?;
+            let result = cache
+                .as_ref()
+                .ok_or_else(|| "没有注册表残留扫描结果，请先扫描".to_string())?;
+            (serde_json::to_string_pretty(result), result.entries.len())
+        }
+        other => return Err(format!("不支持的导出类型: {}", other)),
+    };
+    let json = json.map_err(|e| format!("序列化扫描结果失败: {}", e))?;
+
+    crate::logger::write_export_file(output_path, json)?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::RegistryEntry;
+
+    #[test]
+    fn test_export_registry_findings() {
+        assert!(export_findings("unknown", Path::new("unused.json")).is_err());
+
+        remember_registry_scan(&RegistryScanResult {
+            schema_version: crate::scanner::RESULT_SCHEMA_VERSION,
+            entries: vec![RegistryEntry::test_entry(
+                r"HKEY_CLASSES_ROOT\Applications\old.exe",
+            )],
+            total_count: 1,
+            scan_duration_ms: 0,
+        });
+
+        let output = std::env::temp_dir()
+            .join(format!("lightc-findings-export-{}", std::process::id()))
+            .join("registry.json");
+        assert_eq!(export_findings("registry", &output).unwrap(), 1);
+        let json = std::fs::read_to_string(&output).unwrap();
+        assert!(json.contains("old.exe"));
+        assert!(json.contains("associated_path"));
+        let _ = std::fs::remove_dir_all(output.parent().unwrap());
+    }
+}
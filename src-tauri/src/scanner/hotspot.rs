@@ -13,6 +13,8 @@ use std::time::{Instant, SystemTime};
 use tauri::Emitter;
 use walkdir::WalkDir;
 
+use crate::cleaner::safety_constants::is_cloud_placeholder;
+
 // ============================================================================
 // 数据结构定义
 // ============================================================================
@@ -98,6 +100,7 @@ pub struct HotspotScanProgress {
     pub stage_elapsed_ms: u64,
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_hotspot_progress(
     backend: &str,
     stage: &str,
@@ -183,7 +186,7 @@ const PROTECTED_DIRECTORIES: &[&str] = &[
 /// 收录为热点条目的最小目录大小（默认 50MB，用户可配置）
 const MIN_SIZE_THRESHOLD: u64 = 50 * 1024 * 1024;
 /// 绝对下限（1MB），防止返回过多细碎条目
-const SIZE_THRESHOLD_FLOOR: u64 = 1 * 1024 * 1024;
+const SIZE_THRESHOLD_FLOOR: u64 = 1024 * 1024;
 /// 触发下钻的最小目录大小（5GB）
 const DRILL_DOWN_SIZE_THRESHOLD: u64 = 5 * 1024 * 1024 * 1024;
 /// 触发下钻的最小文件数量
@@ -292,7 +295,7 @@ fn is_hidden_by_name(entry: &walkdir::DirEntry) -> bool {
     entry
         .file_name()
         .to_str()
-        .map(|n| is_hidden_name(n))
+        .map(is_hidden_name)
         .unwrap_or(false)
 }
 
@@ -300,7 +303,7 @@ fn is_hidden_by_name(entry: &walkdir::DirEntry) -> bool {
 pub(crate) fn is_hidden_by_path(path: &Path) -> bool {
     path.file_name()
         .and_then(|n| n.to_str())
-        .map(|n| is_hidden_name(n))
+        .map(is_hidden_name)
         .unwrap_or(false)
 }
 
@@ -513,7 +516,7 @@ impl HotspotScanner {
         }
 
         // 按大小降序排列
-        top_entries.sort_by(|a, b| b.total_size.cmp(&a.total_size));
+        top_entries.sort_by_key(|b| std::cmp::Reverse(b.total_size));
 
         let entries: Vec<HotspotEntry> = top_entries.into_iter().take(self.top_n).collect();
         let scan_duration_ms = start_time.elapsed().as_millis() as u64;
@@ -767,7 +770,7 @@ impl HotspotScanner {
             if let Some(app) = app_handle {
                 let _ = app.emit("hotspot-scan:cancelled", ());
             }
-            all_entries.sort_by(|a, b| b.total_size.cmp(&a.total_size));
+            all_entries.sort_by_key(|b| std::cmp::Reverse(b.total_size));
             let partial: Vec<HotspotEntry> = all_entries.into_iter().take(self.top_n).collect();
             return Ok(HotspotScanResult {
                 entries: partial,
@@ -927,7 +930,7 @@ impl HotspotScanner {
         }
 
         // 按大小降序排列，取前 N 个
-        sub_dirs.sort_by(|a, b| b.1.total_size.cmp(&a.1.total_size));
+        sub_dirs.sort_by_key(|b| std::cmp::Reverse(b.1.total_size));
 
         sub_dirs
             .into_iter()
@@ -995,7 +998,7 @@ impl HotspotScanner {
             }
         }
 
-        children.sort_by(|a, b| b.total_size.cmp(&a.total_size));
+        children.sort_by_key(|b| std::cmp::Reverse(b.total_size));
         children.into_iter().take(DRILL_DOWN_TOP_CHILDREN).collect()
     }
 
@@ -1176,7 +1179,12 @@ impl HotspotScanner {
                 Ok(e) => {
                     if e.file_type().is_file() {
                         // 单次 metadata() 调用，避免 Win11 + Defender 下重复 IO
-                        if let Ok(metadata) = e.metadata() {
+                        // 云端占位文件不占本地空间，不计入目录大小
+                        if let Some(metadata) = e
+                            .metadata()
+                            .ok()
+                            .filter(|metadata| !is_cloud_placeholder(metadata))
+                        {
                             total_size += metadata.len();
                             file_count += 1;
 
@@ -1213,7 +1221,7 @@ impl HotspotScanner {
         if entry
             .file_name()
             .to_str()
-            .map(|n| is_hidden_name(n))
+            .map(is_hidden_name)
             .unwrap_or(false)
         {
             return true;
@@ -1307,7 +1315,7 @@ impl HotspotScanner {
             })
             .collect();
 
-        children.sort_by(|a, b| b.total_size.cmp(&a.total_size));
+        children.sort_by_key(|b| std::cmp::Reverse(b.total_size));
         children.into_iter().take(DRILL_DOWN_TOP_CHILDREN).collect()
     }
 }
@@ -1363,7 +1371,7 @@ fn build_child_index(
     }
 
     for children in child_index.values_mut() {
-        children.sort_by(|a, b| b.stats.total_size.cmp(&a.stats.total_size));
+        children.sort_by_key(|b| std::cmp::Reverse(b.stats.total_size));
     }
 
     child_index
@@ -1581,7 +1589,7 @@ impl HotspotScanner {
             .collect();
 
         // 按大小降序排列
-        entries.sort_by(|a, b| b.total_size.cmp(&a.total_size));
+        entries.sort_by_key(|b| std::cmp::Reverse(b.total_size));
 
         let total_folders_scanned = entries.len();
         let scanned_total_size = entries.iter().map(|e| e.total_size).sum();
@@ -16,7 +16,13 @@ use log::{info, warn};
 
 use crate::scanner::hotspot::FolderStats;
 
-use super::mft_scanner::MftScanProgress;
+/// MFT 引擎的阶段进度（jwalk 降级时不推送）
+pub struct MftScanProgress {
+    pub stage: &'static str,
+    pub message: String,
+    pub processed: usize,
+    pub stage_elapsed_ms: u64,
+}
 
 /// 扫描后端类型（用于前端展示当前使用的引擎）
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -57,6 +63,8 @@ pub fn detect_backend(drive_letter: char) -> HotspotBackend {
             return HotspotBackend::Mft;
         }
     }
+    #[cfg(not(windows))]
+    let _ = drive_letter;
     HotspotBackend::Walkdir
 }
 
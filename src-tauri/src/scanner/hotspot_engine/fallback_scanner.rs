@@ -10,6 +10,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 
 use jwalk::WalkDir as JWalkDir;
 
+use crate::cleaner::safety_constants::is_cloud_placeholder;
 use crate::scanner::hotspot::{
     is_heavy_system_dir, is_hidden_by_path, FolderStats, HotspotScanner,
 };
@@ -42,6 +43,8 @@ pub fn aggregate_ancestor_stats(
     let walker = JWalkDir::new(root)
         .max_depth(max_depth as usize)
         .skip_hidden(false)
+        // 显式关闭链接跟随，避免 Junction 形成环路或重复统计目标目录
+        .follow_links(false)
         .process_read_dir(move |_depth, _path, _state, children| {
             // 预过滤：阻止 jwalk 进入巨型系统目录和隐藏目录
             // 当用户关闭系统目录过滤时，WinSxS/DriverStore 等也允许进入扫描
@@ -79,6 +82,10 @@ pub fn aggregate_ancestor_stats(
             Ok(m) => m,
             Err(_) => continue,
         };
+        // 云端占位文件不占本地空间，不计入目录大小
+        if is_cloud_placeholder(&metadata) {
+            continue;
+        }
         let file_size = metadata.len();
 
         // 仅在 Accurate 模式下收集修改时间
@@ -86,7 +93,7 @@ pub fn aggregate_ancestor_stats(
             metadata
                 .modified()
                 .ok()
-                .map(|t| HotspotScanner::system_time_to_millis(t))
+                .map(HotspotScanner::system_time_to_millis)
                 .unwrap_or(0)
         } else {
             0
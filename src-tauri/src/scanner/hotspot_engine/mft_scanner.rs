@@ -14,6 +14,8 @@ use log::info;
 use crate::scanner::big_files_engine::mft_core;
 use crate::scanner::hotspot::{is_hotspot_scan_cancelled, FolderStats};
 
+use super::engine_selector::MftScanProgress;
+
 #[derive(Clone)]
 struct DirectoryInfo {
     parent_id: u64,
@@ -28,13 +30,6 @@ struct DirectoryStats {
     last_modified: i64,
 }
 
-pub struct MftScanProgress {
-    pub stage: &'static str,
-    pub message: String,
-    pub processed: usize,
-    pub stage_elapsed_ms: u64,
-}
-
 struct DirectoryIndex {
     directories: HashMap<u64, DirectoryInfo>,
     deepest_depth: u16,
@@ -7,4 +7,22 @@
 
 pub mod engine_selector;
 pub mod fallback_scanner;
+#[cfg(windows)]
 pub mod mft_scanner;
+
+/// 非 Windows 平台没有 MFT，detect_backend 不会选中它；保留同名入口以便引擎选择器编译
+#[cfg(not(windows))]
+pub mod mft_scanner {
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    use super::engine_selector::MftScanProgress;
+    use crate::scanner::hotspot::FolderStats;
+
+    pub fn scan_via_mft(
+        _drive_letter: char,
+        _progress_cb: impl Fn(MftScanProgress),
+    ) -> Result<HashMap<PathBuf, FolderStats>, String> {
+        Err("此功能仅支持Windows系统".to_string())
+    }
+}
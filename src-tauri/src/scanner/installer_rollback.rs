@@ -0,0 +1,112 @@
+// ============================================================================
+// Windows Installer 回滚数据
+//
+// msiexec 安装过程中把被替换的文件和回滚脚本写入系统盘根目录的 Config.Msi，
+// 安装成功后本应删除，但安装被中断或崩溃时会一直残留。
+// 只有安装正在进行时这些文件才有用（安装失败时据此回滚），
+// 因此扫描和删除前都要确认当前没有 msiexec.exe 在运行。
+//
+// Windows Installer 服务在安装结束后还会保留 msiexec.exe 进程约 10 分钟，
+// 这段时间内同样按"安装进行中"处理，宁可晚一点清理也不删正在使用的回滚数据。
+// ============================================================================
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Windows Installer 安装进程名
+const MSIEXEC_PROCESS_NAME: &str = "msiexec.exe";
+
+/// 进程检测结果的缓存时间；删除时逐个文件检查，不能每次都枚举进程
+const INSTALL_CHECK_CACHE_DURATION: Duration = Duration::from_secs(5);
+
+/// 安装进行中拒绝删除回滚数据时的提示
+pub const MSI_INSTALL_IN_PROGRESS_MESSAGE: &str =
+    "Windows Installer 正在安装软件，回滚数据暂不能删除，请安装结束后重试";
+
+static INSTALL_CHECK_CACHE: Mutex<Option<(Instant, bool)>> = Mutex::new(None);
+
+/// 判断路径是否位于任意驱动器根目录的 Config.Msi 下（含目录本身）
+pub fn is_config_msi_path(path: &str) -> bool {
+    let normalized = path.replace('/', "\\").to_ascii_lowercase();
+    let marker = ":\\config.msi";
+    normalized.find(marker).is_some_and(|start| {
+        let suffix = &normalized[start + marker.len()..];
+        suffix.is_empty() || suffix.starts_with('\\')
+    })
+}
+
+/// 是否有 Windows Installer 安装正在进行（msiexec.exe 正在运行）
+///
+/// 无法枚举进程时按安装进行中处理。
+pub fn is_msi_install_in_progress() -> bool {
+    let mut cache = INSTALL_CHECK_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if let Some((checked_at, running)) = *cache {
+        if checked_at.elapsed() < INSTALL_CHECK_CACHE_DURATION {
+            return running;
+        }
+    }
+    let running = is_process_running(MSIEXEC_PROCESS_NAME).unwrap_or_else(|e| {
+        log::warn!("检测 Windows Installer 进程失败，按安装进行中处理: {}", e);
+        true
+    });
+    *cache = Some((Instant::now(), running));
+    running
+}
+
+#[cfg(windows)]
+fn is_process_running(exe_name: &str) -> Result<bool, String> {
+    use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+    use winapi::um::tlhelp32::{
+        CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W,
+        TH32CS_SNAPPROCESS,
+    };
+
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return Err(format!(
+                "CreateToolhelp32Snapshot 失败: {}",
+                std::io::Error::last_os_error()
+            ));
+        }
+
+        let mut entry: PROCESSENTRY32W = std::mem::zeroed();
+        entry.dwSize = std::mem::size_of::<PROCESSENTRY32W>() as u32;
+        let mut found = false;
+        let mut has_entry = Process32FirstW(snapshot, &mut entry) != 0;
+        while has_entry {
+            let len = entry
+                .szExeFile
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(entry.szExeFile.len());
+            if String::from_utf16_lossy(&entry.szExeFile[..len]).eq_ignore_ascii_case(exe_name) {
+                found = true;
+                break;
+            }
+            has_entry = Process32NextW(snapshot, &mut entry) != 0;
+        }
+        CloseHandle(snapshot);
+        Ok(found)
+    }
+}
+
+#[cfg(not(windows))]
+fn is_process_running(_exe_name: &str) -> Result<bool, String> {
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_msi_path_matches_drive_root_only() {
+        assert!(is_config_msi_path("C:\\Config.Msi"));
+        assert!(is_config_msi_path("D:/Config.Msi/1a2b3c.rbf"));
+        assert!(!is_config_msi_path("C:\\Config.Msi.bak"));
+        assert!(!is_config_msi_path("C:\\Users\\a\\Config.Msi\\x.rbf"));
+    }
+}
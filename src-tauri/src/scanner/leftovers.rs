@@ -25,6 +25,8 @@
 //     -0.15  包名格式目录（com.xxx.yyy）
 //     -0.15  纯版本号目录（1.2.3.4 / v2.0）
 //     -0.50  已知共享厂商目录（Adobe, Microsoft 等）
+//   激进模式（with_aggressive）：确认已卸载的应用目录（历史安装路径命中、且不匹配
+//   任何已安装应用）直接计入 +0.10，不扣"7 天内有修改"分，卸载后可立即清理。
 //
 // 【分类阈值】
 //   score >= 0.75 → HighConfidenceLeftover（前端默认勾选）
@@ -54,9 +56,14 @@ use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, SystemTime};
 use walkdir::WalkDir;
+
+use crate::cleaner::safety_constants::is_reparse_point_dir;
+#[cfg(windows)]
 use winreg::enums::*;
+#[cfg(windows)]
 use winreg::RegKey;
 
 // ============================================================================
@@ -107,6 +114,24 @@ static RE_PACKAGE_NAME: Lazy<Regex> =
 /// 纯版本号格式：1.2.3.4、v2.0 等
 static RE_VERSION_FOLDER: Lazy<Regex> = Lazy::new(|| Regex::new(r"^v?\d+(\.\d+){1,3}$").unwrap());
 
+/// 全局取消标志，跨线程共享（与 big_files.rs 模式一致）
+static LEFTOVER_SCAN_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// 重置取消标志（扫描开始前调用）
+pub fn reset_leftover_scan_cancelled() {
+    LEFTOVER_SCAN_CANCELLED.store(false, Ordering::SeqCst);
+}
+
+/// 设置取消标志（前端点击取消按钮时调用）
+pub fn cancel_leftover_scan() {
+    log::info!("收到取消卸载残留扫描请求");
+    LEFTOVER_SCAN_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+fn is_leftover_scan_cancelled() -> bool {
+    LEFTOVER_SCAN_CANCELLED.load(Ordering::SeqCst)
+}
+
 // ============================================================================
 // 数据模型
 // ============================================================================
@@ -114,12 +139,29 @@ static RE_VERSION_FOLDER: Lazy<Regex> = Lazy::new(|| Regex::new(r"^v?\d+(\.\d+){
 /// 卸载残留扫描结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LeftoverScanResult {
+    /// JSON 格式版本（见 `RESULT_SCHEMA_VERSION`）
+    #[serde(default)]
+    pub schema_version: u32,
     /// 发现的残留文件夹列表
     pub leftovers: Vec<LeftoverEntry>,
     /// 总大小（字节）
     pub total_size: u64,
     /// 扫描耗时（毫秒）
     pub scan_duration_ms: u64,
+    /// 扫描是否被取消（取消时 `leftovers` 只包含已完成的部分结果）
+    #[serde(default)]
+    pub cancelled: bool,
+}
+
+/// 卸载残留扫描进度事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct LeftoverScanProgress {
+    /// 当前正在分析的文件夹名称
+    pub current_folder: String,
+    /// 已分析的文件夹数量
+    pub scanned_count: usize,
+    /// 已发现的残留数量
+    pub found_count: usize,
 }
 
 /// 单个残留条目（前端兼容 + 新增置信度字段）
@@ -149,6 +191,16 @@ pub struct LeftoverEntry {
     pub detection_category: DetectionCategory,
     /// 评分理由列表（中文，供 UI 悬浮提示）
     pub reasons: Vec<String>,
+    /// 数据风险等级 (1-5)：1 = 配置/缓存，5 = 很可能包含用户数据
+    #[serde(default = "default_leftover_risk_level")]
+    pub risk_level: u8,
+    /// 删除前是否需要用户额外确认（risk_level >= 4）
+    #[serde(default)]
+    pub requires_confirmation: bool,
+}
+
+fn default_leftover_risk_level() -> u8 {
+    1
 }
 
 /// 残留类型
@@ -240,6 +292,31 @@ impl WhitelistRule {
     }
 }
 
+impl WhitelistRule {
+    /// 以用户白名单的模式语法展示规则（前缀规则追加 `*`）
+    fn display(&self) -> String {
+        match self {
+            WhitelistRule::Exact(s) | WhitelistRule::Pattern(s) => s.clone(),
+            WhitelistRule::Prefix(s) => format!("{}*", s),
+        }
+    }
+}
+
+/// 内置白名单规则的展示列表（供 `get_whitelist` 使用）
+pub(crate) fn builtin_leftover_whitelist() -> Vec<String> {
+    build_whitelist_rules()
+        .iter()
+        .map(WhitelistRule::display)
+        .collect()
+}
+
+/// 文件夹名（或模式）是否已被内置白名单覆盖
+pub(crate) fn is_builtin_leftover_whitelisted(name_lower: &str) -> bool {
+    build_whitelist_rules()
+        .iter()
+        .any(|rule| rule.matches(name_lower) || rule.display() == name_lower)
+}
+
 /// 构建结构化白名单规则列表
 fn build_whitelist_rules() -> Vec<WhitelistRule> {
     vec![
@@ -505,6 +582,61 @@ const FS_PROBE_MAX_DEPTH: usize = 4;
 /// 可执行文件扩展名（用于文件系统探测正向信号）
 const EXECUTABLE_EXTENSIONS: &[&str] = &["exe", "dll", "sys", "msi"];
 
+/// 通常存放用户数据的子目录名（命中时提高数据风险等级）
+const RISKY_SUBFOLDER_NAMES: &[&str] = &[
+    "backup",
+    "backups",
+    "database",
+    "databases",
+    "db",
+    "projects",
+    "saves",
+    "savegames",
+    "documents",
+    "archive",
+];
+
+/// 用户文档类扩展名（提高数据风险等级）
+const DOCUMENT_EXTENSIONS: &[&str] = &[
+    "doc", "docx", "xls", "xlsx", "ppt", "pptx", "pdf", "txt", "md", "psd", "ai", "sqlite", "db",
+    "mdb", "bak", "sav", "zip", "7z",
+];
+
+/// 日志/缓存类扩展名（以这类文件为主的目录风险较低）
+const DISPOSABLE_EXTENSIONS: &[&str] = &["log", "tmp", "etl", "dmp", "cache", "old"];
+
+/// 达到该数据风险等级的残留需要用户额外确认，且不默认勾选
+const CONFIRM_RISK_LEVEL: u8 = 4;
+
+/// 大于该大小的残留提高一级数据风险（1GB）
+const LARGE_LEFTOVER_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// 已知误报目录（小写路径后缀，按完整路径段匹配）
+///
+/// 这些目录看起来没有所属应用，但由系统组件或驱动服务使用。文件夹名白名单只看名称，
+/// 同名目录出现在别处时会误伤或漏掉；这里按位置精确排除用户反馈过的误报。
+const NEVER_FLAG_PATH_SUFFIXES: &[&str] = &[
+    // Windows 错误报告的崩溃转储目录
+    r"appdata\local\crashdumps",
+    // "连接的设备平台"服务（时间线、跨设备剪贴板）
+    r"appdata\local\connecteddevicesplatform",
+    // 邮件和日历、人脉应用的共享存储
+    r"appdata\local\comms",
+    r"appdata\local\elevateddiagnostics",
+    r"appdata\local\peerdistrepub",
+    // 显卡驱动服务写入的配置和遥测目录，驱动不在卸载列表中
+    r"appdata\local\nvidia corporation",
+    r"appdata\locallow\intel",
+    r"programdata\nvidia corporation",
+    r"programdata\intel",
+    // 更新编排器和 OpenSSH 服务
+    r"programdata\usoshared",
+    r"programdata\usoprivate",
+    r"programdata\ssh",
+    // Visual Studio / .NET 安装包缓存，删除后无法修复或卸载对应组件
+    r"programdata\package cache",
+];
+
 // ============================================================================
 // 已安装应用映射（路径所有权推断）
 // ============================================================================
@@ -534,11 +666,20 @@ struct InstalledAppMap {
     leftover_candidates: HashSet<String>,
 }
 
+/// 路径是否位于已知误报目录（按完整路径段匹配后缀，不区分大小写）
+fn is_known_false_positive(path: &Path) -> bool {
+    let path_lower = path.to_string_lossy().to_lowercase().replace('/', "\\");
+    let path_lower = path_lower.trim_end_matches('\\');
+    NEVER_FLAG_PATH_SUFFIXES
+        .iter()
+        .any(|suffix| path_lower == *suffix || path_lower.ends_with(&format!("\\{}", suffix)))
+}
+
 /// 规范化 DisplayName：转小写，去除版本号、括号内容、多余空格
 fn normalize_display_name(name: &str) -> String {
     let lower = name.to_lowercase();
     // 去除括号内容（如 "Foo App (x64)"  → "foo app"）
-    let no_parens = lower.replace(|c: char| c == '(' || c == ')', " ");
+    let no_parens = lower.replace(['(', ')'], " ");
     // 去除版本号模式（如 "v1.2.3"、"1.0.0"）
     let cleaned: String = no_parens
         .split_whitespace()
@@ -548,6 +689,23 @@ fn normalize_display_name(name: &str) -> String {
     cleaned.trim().to_string()
 }
 
+/// 已安装程序所在的卸载注册表位置（64 位、32 位和当前用户）
+#[cfg(windows)]
+const UNINSTALL_REG_PATHS: [(winreg::HKEY, &str); 3] = [
+    (
+        HKEY_LOCAL_MACHINE,
+        r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+    ),
+    (
+        HKEY_LOCAL_MACHINE,
+        r"SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall",
+    ),
+    (
+        HKEY_CURRENT_USER,
+        r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
+    ),
+];
+
 /// InstalledAppMap 中不允许作为 parent 目录名的公共父目录
 const EXCLUDED_PARENT_DIRS: &[&str] = &[
     "program files",
@@ -556,6 +714,40 @@ const EXCLUDED_PARENT_DIRS: &[&str] = &[
     "users",
 ];
 
+/// 读取卸载注册表中的 (DisplayName, InstallLocation)
+///
+/// 【安全说明】只读取注册表，不进行任何写入操作
+#[cfg(windows)]
+fn read_uninstall_entries() -> Vec<(String, Option<String>)> {
+    let mut entries = Vec::new();
+    for (hkey, path) in UNINSTALL_REG_PATHS {
+        let Ok(key) = RegKey::predef(hkey).open_subkey_with_flags(path, KEY_READ) else {
+            continue;
+        };
+        for subkey_name in key.enum_keys().filter_map(|k| k.ok()) {
+            let Ok(subkey) = key.open_subkey_with_flags(&subkey_name, KEY_READ) else {
+                continue;
+            };
+            let display_name: String = subkey.get_value("DisplayName").unwrap_or_default();
+            if display_name.is_empty() {
+                continue;
+            }
+            let install_location: Option<String> = subkey
+                .get_value::<String, _>("InstallLocation")
+                .ok()
+                .filter(|s| !s.is_empty());
+            entries.push((display_name, install_location));
+        }
+    }
+    entries
+}
+
+/// 非 Windows 平台没有卸载注册表，视为没有已安装程序
+#[cfg(not(windows))]
+fn read_uninstall_entries() -> Vec<(String, Option<String>)> {
+    Vec::new()
+}
+
 impl InstalledAppMap {
     /// 从注册表构建已安装应用映射
     ///
@@ -564,110 +756,86 @@ impl InstalledAppMap {
     ///   b. InstallLocation 路径的倒数第二级目录名，排除公共父目录
     /// 不再拆分 DisplayName token，避免短 token 碰撞导致误判
     fn build() -> Self {
+        let mut historical = load_install_history();
+        let map = Self::from_entries(read_uninstall_entries(), &historical);
+
+        // 将当前已知文件夹合并到历史记录中并持久化
+        historical.extend(map.known_folders.iter().cloned());
+        save_install_history(&historical);
+
+        log::info!(
+            "已安装应用映射构建完成: {} 个应用, {} 个已知文件夹名, {} 个 DisplayName, {} 个历史残留候选",
+            map.apps.len(),
+            map.known_folders.len(),
+            map.display_names.len(),
+            map.leftover_candidates.len()
+        );
+        map
+    }
+
+    /// 从 (DisplayName, InstallLocation) 列表构建映射，不读取注册表
+    ///
+    /// `historical` 为历史安装文件夹名，用于计算疑似残留候选：
+    /// 曾经在 InstallLocation 中出现过但当前已找不到的文件夹。
+    fn from_entries(
+        entries: impl IntoIterator<Item = (String, Option<String>)>,
+        historical: &HashSet<String>,
+    ) -> Self {
         let mut apps = Vec::new();
         let mut folder_to_app: HashMap<String, Vec<usize>> = HashMap::new();
         let mut known_folders = HashSet::new();
         let mut display_names = HashSet::new();
 
-        // 【安全说明】只读取注册表，不进行任何写入操作
-        let reg_paths = [
-            (
-                HKEY_LOCAL_MACHINE,
-                r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
-            ),
-            (
-                HKEY_LOCAL_MACHINE,
-                r"SOFTWARE\WOW6432Node\Microsoft\Windows\CurrentVersion\Uninstall",
-            ),
-            (
-                HKEY_CURRENT_USER,
-                r"SOFTWARE\Microsoft\Windows\CurrentVersion\Uninstall",
-            ),
-        ];
-
-        for (hkey, path) in reg_paths {
-            if let Ok(key) = RegKey::predef(hkey).open_subkey_with_flags(path, KEY_READ) {
-                for subkey_name in key.enum_keys().filter_map(|k| k.ok()) {
-                    if let Ok(subkey) = key.open_subkey_with_flags(&subkey_name, KEY_READ) {
-                        let display_name: String =
-                            subkey.get_value("DisplayName").unwrap_or_default();
-                        if display_name.is_empty() {
-                            continue;
-                        }
-
-                        let install_location: Option<String> = subkey
-                            .get_value::<String, _>("InstallLocation")
-                            .ok()
-                            .filter(|s| !s.is_empty());
-
-                        // 规范化 DisplayName 并加入集合
-                        let normalized = normalize_display_name(&display_name);
-                        if !normalized.is_empty() {
-                            display_names.insert(normalized);
-                        }
-
-                        // 仅从 InstallLocation 推断文件夹名，不拆分 DisplayName token
-                        let mut inferred = Vec::new();
-
-                        if let Some(ref loc) = install_location {
-                            let loc_path = Path::new(loc);
-                            // a. 末级目录名
-                            if let Some(folder) = loc_path.file_name() {
-                                let name = folder.to_string_lossy().to_lowercase();
-                                if !name.is_empty() {
-                                    inferred.push(name);
-                                }
-                            }
-                            // b. 倒数第二级目录名（排除公共父目录）
-                            if let Some(parent) = loc_path.parent() {
-                                if let Some(vendor) = parent.file_name() {
-                                    let v = vendor.to_string_lossy().to_lowercase();
-                                    if !v.is_empty() && !EXCLUDED_PARENT_DIRS.contains(&v.as_str())
-                                    {
-                                        inferred.push(v);
-                                    }
-                                }
-                            }
-                        }
+        for (display_name, install_location) in entries {
+            // 规范化 DisplayName 并加入集合
+            let normalized = normalize_display_name(&display_name);
+            if !normalized.is_empty() {
+                display_names.insert(normalized);
+            }
 
-                        // 去重
-                        inferred.sort();
-                        inferred.dedup();
+            // 仅从 InstallLocation 推断文件夹名，不拆分 DisplayName token
+            let mut inferred = Vec::new();
 
-                        let app_idx = apps.len();
-                        for name in &inferred {
-                            folder_to_app.entry(name.clone()).or_default().push(app_idx);
-                            known_folders.insert(name.clone());
+            if let Some(ref loc) = install_location {
+                let loc_path = Path::new(loc);
+                // a. 末级目录名
+                if let Some(folder) = loc_path.file_name() {
+                    let name = folder.to_string_lossy().to_lowercase();
+                    if !name.is_empty() {
+                        inferred.push(name);
+                    }
+                }
+                // b. 倒数第二级目录名（排除公共父目录）
+                if let Some(parent) = loc_path.parent() {
+                    if let Some(vendor) = parent.file_name() {
+                        let v = vendor.to_string_lossy().to_lowercase();
+                        if !v.is_empty() && !EXCLUDED_PARENT_DIRS.contains(&v.as_str()) {
+                            inferred.push(v);
                         }
-
-                        apps.push(InstalledAppInfo {
-                            display_name,
-                            install_location,
-                            inferred_folder_names: inferred,
-                        });
                     }
                 }
             }
+
+            // 去重
+            inferred.sort();
+            inferred.dedup();
+
+            let app_idx = apps.len();
+            for name in &inferred {
+                folder_to_app.entry(name.clone()).or_default().push(app_idx);
+                known_folders.insert(name.clone());
+            }
+
+            apps.push(InstalledAppInfo {
+                display_name,
+                install_location,
+                inferred_folder_names: inferred,
+            });
         }
 
-        // 加载历史安装文件夹并计算疑似残留候选
-        // leftover_candidates = 曾经在 InstallLocation 中出现过但当前注册表中已找不到的文件夹
-        let mut historical = load_install_history();
         let leftover_candidates: HashSet<String> =
             historical.difference(&known_folders).cloned().collect();
 
-        // 将当前已知文件夹合并到历史记录中并持久化
-        historical.extend(known_folders.iter().cloned());
-        save_install_history(&historical);
-
-        log::info!(
-            "已安装应用映射构建完成: {} 个应用, {} 个已知文件夹名, {} 个 DisplayName, {} 个历史残留候选",
-            apps.len(),
-            known_folders.len(),
-            display_names.len(),
-            leftover_candidates.len()
-        );
-
         InstalledAppMap {
             apps,
             folder_to_app,
@@ -708,6 +876,106 @@ impl InstalledAppMap {
     }
 }
 
+// ============================================================================
+// 已安装程序列表（卸载引导）
+// ============================================================================
+
+/// 已安装程序信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledProgram {
+    /// 显示名称
+    pub display_name: String,
+    /// 发布者
+    pub publisher: Option<String>,
+    /// 版本号
+    pub display_version: Option<String>,
+    /// 安装路径
+    pub install_location: Option<String>,
+    /// 安装程序登记的占用空间（字节，由 EstimatedSize 的 KB 值换算，未登记为 0）
+    pub estimated_size: u64,
+    /// 安装日期（YYYY-MM-DD，注册表未登记或格式不规范时为空）
+    pub install_date: Option<String>,
+}
+
+/// 读取已安装程序列表，按占用空间从大到小排序
+///
+/// # 中文说明
+/// 与"程序和功能"保持一致：跳过 SystemComponent=1 的系统组件和带 ParentKeyName 的补丁/更新项；
+/// 64 位与 32 位视图中重复登记的同一程序按名称和安装路径去重。
+#[cfg(windows)]
+pub fn get_installed_programs() -> Vec<InstalledProgram> {
+    let mut programs = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (hkey, path) in UNINSTALL_REG_PATHS {
+        let Ok(key) = RegKey::predef(hkey).open_subkey_with_flags(path, KEY_READ) else {
+            continue;
+        };
+        for subkey_name in key.enum_keys().filter_map(|k| k.ok()) {
+            let Ok(subkey) = key.open_subkey_with_flags(&subkey_name, KEY_READ) else {
+                continue;
+            };
+            let display_name: String = subkey.get_value("DisplayName").unwrap_or_default();
+            if display_name.trim().is_empty() {
+                continue;
+            }
+            let system_component: u32 = subkey.get_value("SystemComponent").unwrap_or(0);
+            if system_component == 1 || subkey.get_value::<String, _>("ParentKeyName").is_ok() {
+                continue;
+            }
+
+            let read_string = |name: &str| {
+                subkey
+                    .get_value::<String, _>(name)
+                    .ok()
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+            };
+            let install_location = read_string("InstallLocation");
+            let dedup_key = format!(
+                "{}|{}",
+                display_name.trim().to_lowercase(),
+                install_location.as_deref().unwrap_or("").to_lowercase()
+            );
+            if !seen.insert(dedup_key) {
+                continue;
+            }
+
+            let estimated_kb: u32 = subkey.get_value("EstimatedSize").unwrap_or(0);
+            programs.push(InstalledProgram {
+                display_name: display_name.trim().to_string(),
+                publisher: read_string("Publisher"),
+                display_version: read_string("DisplayVersion"),
+                install_location,
+                estimated_size: u64::from(estimated_kb) * 1024,
+                install_date: read_string("InstallDate")
+                    .as_deref()
+                    .and_then(format_install_date),
+            });
+        }
+    }
+
+    programs.sort_by(|a, b| {
+        b.estimated_size
+            .cmp(&a.estimated_size)
+            .then_with(|| a.display_name.cmp(&b.display_name))
+    });
+    programs
+}
+
+#[cfg(not(windows))]
+pub fn get_installed_programs() -> Vec<InstalledProgram> {
+    Vec::new()
+}
+
+/// 将注册表中的 InstallDate（YYYYMMDD）转换为 YYYY-MM-DD
+#[cfg_attr(not(windows), allow(dead_code))]
+fn format_install_date(raw: &str) -> Option<String> {
+    chrono::NaiveDate::parse_from_str(raw, "%Y%m%d")
+        .ok()
+        .map(|date| date.format("%Y-%m-%d").to_string())
+}
+
 // ============================================================================
 // 评分引擎
 // ============================================================================
@@ -763,6 +1031,12 @@ struct FsProbeResult {
     file_count: u32,
     /// 总大小（字节）
     total_size: u64,
+    /// 命中 RISKY_SUBFOLDER_NAMES 的子目录名（去重）
+    risky_subfolders: Vec<String>,
+    /// 文档类文件数量
+    document_count: u32,
+    /// 日志/缓存类文件数量
+    disposable_count: u32,
 }
 
 /// 对目录执行有限深度文件系统探测
@@ -772,13 +1046,29 @@ fn probe_directory(path: &Path, max_depth: usize) -> FsProbeResult {
         has_uninstaller: false,
         file_count: 0,
         total_size: 0,
+        risky_subfolders: Vec::new(),
+        document_count: 0,
+        disposable_count: 0,
     };
 
+    // 不进入重解析点目录，防止 Junction 把目标目录的大小算进残留
     for entry in WalkDir::new(path)
         .max_depth(max_depth)
+        .follow_links(false)
         .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !is_reparse_point_dir(e))
         .filter_map(|e| e.ok())
     {
+        if entry.file_type().is_dir() {
+            let dir_name = entry.file_name().to_string_lossy().to_lowercase();
+            if entry.depth() > 0
+                && RISKY_SUBFOLDER_NAMES.contains(&dir_name.as_str())
+                && !result.risky_subfolders.contains(&dir_name)
+            {
+                result.risky_subfolders.push(dir_name);
+            }
+            continue;
+        }
         if !entry.file_type().is_file() {
             continue;
         }
@@ -795,20 +1085,63 @@ fn probe_directory(path: &Path, max_depth: usize) -> FsProbeResult {
             let ext_lower = ext.to_string_lossy().to_lowercase();
             if EXECUTABLE_EXTENSIONS.contains(&ext_lower.as_str()) {
                 result.executable_count += 1;
+            } else if DOCUMENT_EXTENSIONS.contains(&ext_lower.as_str()) {
+                result.document_count += 1;
+            } else if DISPOSABLE_EXTENSIONS.contains(&ext_lower.as_str()) {
+                result.disposable_count += 1;
             }
         }
 
         // 检查卸载程序
-        if file_name.starts_with("uninstall") || file_name.starts_with("uninst") {
-            if file_name.ends_with(".exe") {
-                result.has_uninstaller = true;
-            }
+        if (file_name.starts_with("uninstall") || file_name.starts_with("uninst"))
+            && file_name.ends_with(".exe")
+        {
+            result.has_uninstaller = true;
         }
     }
 
     result
 }
 
+/// 根据探测结果评估残留的数据风险等级 (1-5) 及理由
+///
+/// 置信度回答"是不是残留"，风险等级回答"删错了损失多大"：
+/// 备份/数据库/项目子目录和文档类文件意味着可能有用户数据，
+/// 以日志/缓存为主的目录即使很大也只是配置残留。
+fn assess_leftover_risk(probe: &FsProbeResult) -> (u8, Vec<String>) {
+    let mut level: i32 = 1;
+    let mut reasons = Vec::new();
+
+    if !probe.risky_subfolders.is_empty() {
+        level += 2;
+        reasons.push(format!(
+            "风险：包含数据子目录 {}",
+            probe.risky_subfolders.join("、")
+        ));
+    }
+
+    if probe.document_count > 0 {
+        level += 1;
+        if probe.document_count * 3 >= probe.file_count {
+            level += 1;
+        }
+        reasons.push(format!("风险：包含 {} 个文档类文件", probe.document_count));
+    } else if probe.file_count > 0 && probe.disposable_count * 2 >= probe.file_count {
+        level -= 1;
+        reasons.push("风险低：以日志/缓存文件为主".into());
+    }
+
+    if probe.total_size >= LARGE_LEFTOVER_SIZE {
+        level += 1;
+        reasons.push(format!(
+            "风险：体积较大（{} MB）",
+            probe.total_size / 1024 / 1024
+        ));
+    }
+
+    (level.clamp(1, 5) as u8, reasons)
+}
+
 // ============================================================================
 // 卸载残留扫描器
 // ============================================================================
@@ -827,13 +1160,35 @@ pub struct LeftoverScanner {
     deep_scan: bool,
     /// 最低输出置信度阈值（低于此分数的条目不输出）
     min_confidence_threshold: f32,
+    /// 激进模式：已确认卸载的应用目录忽略修改时间信号
+    aggressive: bool,
 }
 
 impl LeftoverScanner {
     /// 创建新的扫描器实例（默认启用完整扫描，包括模拟器残留和虚拟磁盘检测）
     pub fn new() -> Self {
-        let app_map = InstalledAppMap::build();
-        let whitelist = build_whitelist_rules();
+        Self::with_app_map(InstalledAppMap::build())
+    }
+
+    /// 使用给定的已安装应用列表创建扫描器，不读取注册表也不更新安装历史
+    ///
+    /// 只使用 `display_name` 和 `install_location`，供测试注入已知数据。
+    pub fn with_installed_apps(programs: Vec<InstalledProgram>) -> Self {
+        let entries = programs
+            .into_iter()
+            .map(|program| (program.display_name, program.install_location));
+        Self::with_app_map(InstalledAppMap::from_entries(entries, &HashSet::new()))
+    }
+
+    fn with_app_map(app_map: InstalledAppMap) -> Self {
+        let mut whitelist = build_whitelist_rules();
+        // 用户白名单与内置规则合并，is_whitelisted 一并检查
+        whitelist.extend(
+            super::load_user_whitelist()
+                .leftover_folders
+                .into_iter()
+                .map(WhitelistRule::Pattern),
+        );
         log::info!(
             "置信度评分引擎初始化: {} 个已安装应用, {} 条白名单规则",
             app_map.apps.len(),
@@ -848,9 +1203,27 @@ impl LeftoverScanner {
             deep_scan: true,
             // 只输出 score >= 0.40 的条目（Suspicious 阈值）
             min_confidence_threshold: 0.40,
+            aggressive: false,
         }
     }
 
+    /// 启用激进模式：刚卸载的应用目录不必等修改时间变旧就能显示
+    ///
+    /// 只对确认已卸载的应用目录生效（历史安装路径中出现过、当前注册表中找不到，
+    /// 且不匹配任何已安装应用），其余目录仍按保守规则评分。
+    pub fn with_aggressive(mut self, enabled: bool) -> Self {
+        self.aggressive = enabled;
+        self
+    }
+
+    /// 激进模式下文件夹名能否跳过修改时间信号（应用确认已卸载，没有歧义）
+    fn bypasses_age_filter(&self, folder_lower: &str) -> bool {
+        self.aggressive
+            && self.app_map.is_leftover_candidate(folder_lower)
+            && !self.app_map.matches_display_name(folder_lower)
+            && self.app_map.find_owner(folder_lower).is_none()
+    }
+
     /// 兼容旧接口，参数已忽略，始终启用完整扫描
     pub fn with_deep_scan(_deep_scan: bool) -> Self {
         Self::new()
@@ -858,14 +1231,27 @@ impl LeftoverScanner {
 
     /// 执行卸载残留扫描
     pub fn scan(&self) -> LeftoverScanResult {
+        self.scan_with_progress(|_| {})
+    }
+
+    /// 执行卸载残留扫描，每分析一个候选文件夹回调一次进度
+    ///
+    /// 每个候选文件夹都要统计大小，耗时主要在这里；两个文件夹之间检查取消标志，
+    /// 取消后跳过剩余文件夹和虚拟磁盘扫描，返回已完成的部分结果。
+    pub fn scan_with_progress<F>(&self, mut on_progress: F) -> LeftoverScanResult
+    where
+        F: FnMut(&LeftoverScanProgress),
+    {
         let start_time = std::time::Instant::now();
         let mut leftovers = Vec::new();
         let mut total_size = 0u64;
+        let mut scanned_count = 0usize;
+        let mut cancelled = false;
 
         // 获取扫描路径
         let scan_paths = self.get_scan_paths();
 
-        for (base_path, source) in &scan_paths {
+        'scan: for (base_path, source) in &scan_paths {
             if !base_path.exists() {
                 continue;
             }
@@ -892,6 +1278,21 @@ impl LeftoverScanner {
                     if self.is_whitelisted(&folder_name) {
                         continue;
                     }
+                    // 已知误报目录（按完整路径后缀排除）
+                    if is_known_false_positive(&path) {
+                        continue;
+                    }
+
+                    if is_leftover_scan_cancelled() {
+                        cancelled = true;
+                        break 'scan;
+                    }
+                    scanned_count += 1;
+                    on_progress(&LeftoverScanProgress {
+                        current_folder: folder_name.clone(),
+                        scanned_count,
+                        found_count: leftovers.len(),
+                    });
 
                     let folder_lower = folder_name.to_lowercase();
 
@@ -958,9 +1359,13 @@ impl LeftoverScanner {
 
                         // 修改时间（只计算一次）
                         let days_old = Self::get_days_since_modified(&path);
+                        // 刚卸载的应用目录修改时间很新，激进模式下按已过期处理
+                        let bypass_age = self.bypasses_age_filter(&folder_lower);
 
                         // +0.10 超过 min_days_old 天未修改
-                        if days_old > self.min_days_old {
+                        if bypass_age {
+                            ctx.add(0.10, "激进模式：应用已确认卸载，忽略修改时间".into());
+                        } else if days_old > self.min_days_old {
                             ctx.add(0.10, format!("已 {} 天未修改", days_old));
                         }
 
@@ -991,7 +1396,7 @@ impl LeftoverScanner {
                         }
 
                         // -0.20 7 天内有修改记录
-                        if days_old < 7 {
+                        if days_old < 7 && !bypass_age {
                             ctx.add(-0.20, format!("最近 {} 天内有修改", days_old));
                         }
 
@@ -1028,6 +1433,18 @@ impl LeftoverScanner {
                         LeftoverType::Normal
                     };
 
+                    // 高风险残留即使置信度高也不默认勾选，由用户逐个确认
+                    let (risk_level, risk_reasons) = assess_leftover_risk(&probe);
+                    let requires_confirmation = risk_level >= CONFIRM_RISK_LEVEL;
+                    let detection_category = if requires_confirmation
+                        && detection_category == DetectionCategory::HighConfidenceLeftover
+                    {
+                        DetectionCategory::Suspicious
+                    } else {
+                        detection_category
+                    };
+                    ctx.reasons.extend(risk_reasons);
+
                     leftovers.push(LeftoverEntry {
                         path: path.to_string_lossy().to_string(),
                         size: probe.total_size,
@@ -1041,6 +1458,8 @@ impl LeftoverScanner {
                         confidence: ctx.score,
                         detection_category,
                         reasons: ctx.reasons,
+                        risk_level,
+                        requires_confirmation,
                     });
 
                     total_size += probe.total_size;
@@ -1049,7 +1468,7 @@ impl LeftoverScanner {
         }
 
         // 【深度扫描】扫描虚拟磁盘文件
-        if self.deep_scan {
+        if self.deep_scan && !cancelled {
             log::info!("执行深度扫描: 搜索孤立虚拟磁盘文件...");
             let virtual_disks = self.scan_virtual_disk_files();
             for entry in virtual_disks {
@@ -1058,11 +1477,16 @@ impl LeftoverScanner {
             }
         }
 
-        // 按置信度降序排列（同分则按大小降序）
+        // 需要确认的高风险条目排在最后，其余按置信度降序（同分则风险低的在前、再按大小降序）
         leftovers.sort_by(|a, b| {
-            b.confidence
-                .partial_cmp(&a.confidence)
-                .unwrap_or(std::cmp::Ordering::Equal)
+            a.requires_confirmation
+                .cmp(&b.requires_confirmation)
+                .then_with(|| {
+                    b.confidence
+                        .partial_cmp(&a.confidence)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .then_with(|| a.risk_level.cmp(&b.risk_level))
                 .then_with(|| b.size.cmp(&a.size))
         });
 
@@ -1083,9 +1507,11 @@ impl LeftoverScanner {
         );
 
         LeftoverScanResult {
+            schema_version: super::RESULT_SCHEMA_VERSION,
             leftovers,
             total_size,
             scan_duration_ms,
+            cancelled,
         }
     }
 
@@ -1093,7 +1519,7 @@ impl LeftoverScanner {
     // 私有方法
     // ========================================================================
 
-    /// 白名单检查（结构化规则匹配）
+    /// 白名单检查（结构化规则匹配，包含用户白名单）
     fn is_whitelisted(&self, folder_name: &str) -> bool {
         let name_lower = folder_name.to_lowercase();
         self.whitelist.iter().any(|rule| rule.matches(&name_lower))
@@ -1157,90 +1583,93 @@ impl LeftoverScanner {
             Some(PathBuf::from(r"C:\ProgramData")),
         ];
 
-        for dir_opt in scan_dirs.iter() {
-            if let Some(base_dir) = dir_opt {
-                if !base_dir.exists() {
+        for base_dir in scan_dirs.iter().flatten() {
+            if !base_dir.exists() {
+                continue;
+            }
+
+            // 递归搜索虚拟磁盘文件（限制深度为 5）
+            for entry in WalkDir::new(base_dir)
+                .max_depth(5)
+                .follow_links(false)
+                .into_iter()
+                .filter_entry(|e| e.depth() == 0 || !is_reparse_point_dir(e))
+                .filter_map(|e| e.ok())
+            {
+                let path = entry.path();
+
+                // 只处理文件
+                if !path.is_file() {
                     continue;
                 }
 
-                // 递归搜索虚拟磁盘文件（限制深度为 5）
-                for entry in WalkDir::new(base_dir)
-                    .max_depth(5)
-                    .into_iter()
-                    .filter_map(|e| e.ok())
-                {
-                    let path = entry.path();
-
-                    // 只处理文件
-                    if !path.is_file() {
-                        continue;
-                    }
-
-                    // 检查扩展名
-                    let ext = path
-                        .extension()
-                        .map(|e| format!(".{}", e.to_string_lossy().to_lowercase()))
-                        .unwrap_or_default();
-
-                    if !VIRTUAL_DISK_EXTENSIONS.contains(&ext.as_str()) {
-                        continue;
-                    }
-
-                    // 获取文件大小
-                    let size = path.metadata().map(|m| m.len()).unwrap_or(0);
-
-                    // 虚拟磁盘文件通常很大，忽略小于 100MB 的
-                    if size < 100 * 1024 * 1024 {
-                        continue;
-                    }
+                // 检查扩展名
+                let ext = path
+                    .extension()
+                    .map(|e| format!(".{}", e.to_string_lossy().to_lowercase()))
+                    .unwrap_or_default();
 
-                    // 检查路径中是否有上级目录在白名单内
-                    // WSL2 等场景：文件在 \wsl\<GUID>\ext4.vhdx，直接父目录是 GUID，
-                    // 但上级目录 "wsl" 在白名单中，应跳过
-                    if self.is_path_in_whitelist(path, base_dir) {
-                        log::info!("虚拟磁盘文件路径命中白名单，跳过: {}", path.display());
-                        continue;
-                    }
+                if !VIRTUAL_DISK_EXTENSIONS.contains(&ext.as_str()) {
+                    continue;
+                }
 
-                    // 检查父目录是否对应已安装应用
-                    let parent_folder = path
-                        .parent()
-                        .and_then(|p| p.file_name())
-                        .map(|n| n.to_string_lossy().to_lowercase())
-                        .unwrap_or_default();
+                // 获取文件大小
+                let size = path.metadata().map(|m| m.len()).unwrap_or(0);
 
-                    if self.app_map.has_inferred_ownership(&parent_folder) {
-                        continue;
-                    }
-
-                    let file_name = path
-                        .file_name()
-                        .map(|n| n.to_string_lossy().to_string())
-                        .unwrap_or_default();
+                // 虚拟磁盘文件通常很大，忽略小于 100MB 的
+                if size < 100 * 1024 * 1024 {
+                    continue;
+                }
 
-                    let last_modified = Self::get_last_modified(path);
+                // 检查路径中是否有上级目录在白名单内
+                // WSL2 等场景：文件在 \wsl\<GUID>\ext4.vhdx，直接父目录是 GUID，
+                // 但上级目录 "wsl" 在白名单中，应跳过
+                if self.is_path_in_whitelist(path, base_dir) {
+                    log::info!("虚拟磁盘文件路径命中白名单，跳过: {}", path.display());
+                    continue;
+                }
 
-                    log::info!(
-                        "发现孤立虚拟磁盘文件: {} ({} MB)",
-                        path.display(),
-                        size / 1024 / 1024
-                    );
+                // 检查父目录是否对应已安装应用
+                let parent_folder = path
+                    .parent()
+                    .and_then(|p| p.file_name())
+                    .map(|n| n.to_string_lossy().to_lowercase())
+                    .unwrap_or_default();
 
-                    results.push(LeftoverEntry {
-                        path: path.to_string_lossy().to_string(),
-                        size,
-                        app_name: file_name,
-                        source: LeftoverSource::VirtualDiskFile,
-                        last_modified,
-                        file_count: 1,
-                        is_emulator: false,
-                        is_virtual_disk: true,
-                        leftover_type: LeftoverType::VirtualDisk,
-                        confidence: 0.85,
-                        detection_category: DetectionCategory::HighConfidenceLeftover,
-                        reasons: vec!["孤立虚拟磁盘文件，未关联已安装应用".into()],
-                    });
+                if self.app_map.has_inferred_ownership(&parent_folder) {
+                    continue;
                 }
+
+                let file_name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default();
+
+                let last_modified = Self::get_last_modified(path);
+
+                log::info!(
+                    "发现孤立虚拟磁盘文件: {} ({} MB)",
+                    path.display(),
+                    size / 1024 / 1024
+                );
+
+                results.push(LeftoverEntry {
+                    path: path.to_string_lossy().to_string(),
+                    size,
+                    app_name: file_name,
+                    source: LeftoverSource::VirtualDiskFile,
+                    last_modified,
+                    file_count: 1,
+                    is_emulator: false,
+                    is_virtual_disk: true,
+                    leftover_type: LeftoverType::VirtualDisk,
+                    confidence: 0.85,
+                    detection_category: DetectionCategory::HighConfidenceLeftover,
+                    reasons: vec!["孤立虚拟磁盘文件，未关联已安装应用".into()],
+                    // 虚拟磁盘内可能是完整的系统或项目数据，始终需要确认
+                    risk_level: CONFIRM_RISK_LEVEL,
+                    requires_confirmation: true,
+                });
             }
         }
 
@@ -1344,6 +1773,7 @@ fn has_executables_shallow(path: &Path) -> Option<String> {
 
     for entry in WalkDir::new(path)
         .max_depth(max_depth)
+        .follow_links(false)
         .into_iter()
         .filter_map(|e| e.ok())
     {
@@ -1497,6 +1927,40 @@ fn is_safe_leftover_path(path: &std::path::Path) -> bool {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_aggressive_mode_only_bypasses_confirmed_uninstalls() {
+        let historical: HashSet<String> = ["oldtool", "editor"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        // editor 仍以 DisplayName 形式存在，属于有歧义的匹配
+        let app_map = InstalledAppMap::from_entries(
+            vec![(
+                "Editor".to_string(),
+                Some("D:\\Apps\\EditorPro".to_string()),
+            )],
+            &historical,
+        );
+        let scanner = LeftoverScanner::with_app_map(app_map).with_aggressive(true);
+
+        assert!(scanner.bypasses_age_filter("oldtool"));
+        assert!(!scanner.bypasses_age_filter("editor"));
+        assert!(!scanner.bypasses_age_filter("unknown"));
+        assert!(!scanner
+            .with_aggressive(false)
+            .bypasses_age_filter("oldtool"));
+    }
+
+    #[test]
+    fn test_format_install_date() {
+        assert_eq!(
+            format_install_date("20240115"),
+            Some("2024-01-15".to_string())
+        );
+        assert_eq!(format_install_date("2024/01/15"), None);
+        assert_eq!(format_install_date("20241345"), None);
+    }
+
     #[test]
     fn test_whitelist_exact() {
         let rules = build_whitelist_rules();
@@ -1510,6 +1974,29 @@ mod tests {
         assert!(!rules.iter().any(|r| r.matches("somerandomapp")));
     }
 
+    #[test]
+    fn test_known_false_positive_paths() {
+        for path in [
+            r"C:\Users\me\AppData\Local\CrashDumps",
+            r"C:\Users\me\AppData\Local\ConnectedDevicesPlatform",
+            r"C:\Users\me\AppData\Local\Comms\",
+            r"C:\ProgramData\NVIDIA Corporation",
+            r"C:\ProgramData\Package Cache",
+        ] {
+            assert!(is_known_false_positive(Path::new(path)), "{}", path);
+        }
+        // 同名目录出现在其他位置、或名称只是包含关键字时不排除
+        assert!(!is_known_false_positive(Path::new(
+            r"C:\Users\me\AppData\Roaming\CrashDumps"
+        )));
+        assert!(!is_known_false_positive(Path::new(
+            r"C:\Users\me\AppData\Local\MyComms"
+        )));
+        assert!(!is_known_false_positive(Path::new(
+            r"C:\Users\me\AppData\Local\OldApp"
+        )));
+    }
+
     #[test]
     fn test_whitelist_no_global_contains() {
         // 确保白名单不会因为 contains 而误匹配
@@ -1769,4 +2256,59 @@ mod tests {
             "真实残留路径不应命中白名单"
         );
     }
+
+    #[test]
+    fn test_assess_leftover_risk() {
+        let dir = std::env::temp_dir().join(format!("lightc-leftover-risk-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("logs")).unwrap();
+        fs::write(dir.join("logs").join("app.log"), b"log").unwrap();
+        fs::write(dir.join("config.json"), b"{}").unwrap();
+
+        let (level, _) = assess_leftover_risk(&probe_directory(&dir, FS_PROBE_MAX_DEPTH));
+        assert!(level < CONFIRM_RISK_LEVEL, "配置/日志残留不应要求确认");
+
+        fs::create_dir_all(dir.join("Backups")).unwrap();
+        fs::write(dir.join("Backups").join("notes.docx"), b"doc").unwrap();
+        let probe = probe_directory(&dir, FS_PROBE_MAX_DEPTH);
+        assert_eq!(probe.risky_subfolders, vec!["backups".to_string()]);
+        let (level, reasons) = assess_leftover_risk(&probe);
+        assert!(
+            level >= CONFIRM_RISK_LEVEL,
+            "含备份目录和文档的残留应要求确认"
+        );
+        assert!(reasons.iter().any(|r| r.contains("backups")));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    /// 测试用已安装程序，只填写名称和安装路径
+    fn program(name: &str, location: Option<&str>) -> InstalledProgram {
+        InstalledProgram {
+            display_name: name.to_string(),
+            publisher: None,
+            display_version: None,
+            install_location: location.map(str::to_string),
+            estimated_size: 0,
+            install_date: None,
+        }
+    }
+
+    #[test]
+    fn test_with_installed_apps_injects_app_map() {
+        let scanner = LeftoverScanner::with_installed_apps(vec![
+            program("Acme Editor 2.1", Some(r"C:\Program Files\Acme\Editor")),
+            program("NoLocation App", None),
+        ]);
+
+        assert_eq!(scanner.app_map.apps.len(), 2);
+        assert_eq!(
+            scanner.app_map.find_owner("editor"),
+            Some("Acme Editor 2.1")
+        );
+        assert!(scanner.app_map.has_exact_owner("acme"));
+        assert!(!scanner.app_map.has_exact_owner("program files"));
+        assert!(scanner.app_map.matches_display_name("nolocation app"));
+        assert!(scanner.app_map.leftover_candidates.is_empty());
+    }
 }
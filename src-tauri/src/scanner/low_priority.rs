@@ -0,0 +1,164 @@
+// ============================================================================
+// 低优先级扫描
+//
+// 全速扫描 C 盘会占满磁盘 I/O，前台程序明显卡顿。低优先级模式下扫描线程进入
+// 后台模式（THREAD_MODE_BACKGROUND_BEGIN 同时降低 CPU、I/O 和内存优先级），
+// 并开启 EcoQoS 执行速度节流；遍历循环每处理一批条目短暂休眠，把磁盘让给前台。
+// 扫描会变慢，换取扫描期间电脑仍然流畅。
+// ============================================================================
+
+use std::thread;
+use std::time::Duration;
+
+/// 每遍历多少个条目休眠一次
+const THROTTLE_BATCH: u32 = 256;
+/// 每批之间的休眠时间
+const THROTTLE_SLEEP: Duration = Duration::from_millis(10);
+
+/// 当前线程的后台优先级守卫，离开作用域时恢复正常优先级
+///
+/// 后台模式只能作用于当前线程，必须在实际执行遍历的线程里创建。
+pub struct BackgroundPriorityGuard {
+    active: bool,
+}
+
+impl BackgroundPriorityGuard {
+    /// 让当前线程进入后台模式；`enabled` 为 false 时什么也不做
+    pub fn enter(enabled: bool) -> Self {
+        let active = enabled && set_background_mode(true);
+        BackgroundPriorityGuard { active }
+    }
+}
+
+impl Drop for BackgroundPriorityGuard {
+    fn drop(&mut self) {
+        // 扫描线程可能来自线程池，必须恢复，否则后续任务也会一直低速运行
+        if self.active {
+            set_background_mode(false);
+        }
+    }
+}
+
+/// 遍历循环的节流器：启用时每处理一批条目休眠一次
+pub struct ScanThrottle {
+    enabled: bool,
+    seen: u32,
+}
+
+impl ScanThrottle {
+    pub fn new(enabled: bool) -> Self {
+        ScanThrottle { enabled, seen: 0 }
+    }
+
+    /// 每遍历一个条目调用一次
+    pub fn tick(&mut self) {
+        if !self.enabled {
+            return;
+        }
+        self.seen += 1;
+        if self.seen >= THROTTLE_BATCH {
+            self.seen = 0;
+            thread::sleep(THROTTLE_SLEEP);
+        }
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn set_background_mode(enable: bool) -> bool {
+    use winapi::um::errhandlingapi::GetLastError;
+    use winapi::um::processthreadsapi::{GetCurrentThread, SetThreadPriority};
+    use winapi::um::winbase::{THREAD_MODE_BACKGROUND_BEGIN, THREAD_MODE_BACKGROUND_END};
+
+    let mode = if enable {
+        THREAD_MODE_BACKGROUND_BEGIN
+    } else {
+        THREAD_MODE_BACKGROUND_END
+    };
+    let ok = unsafe { SetThreadPriority(GetCurrentThread(), mode as i32) } != 0;
+    if !ok {
+        log::warn!("切换扫描线程后台模式失败 (错误码 {})", unsafe {
+            GetLastError()
+        });
+        return false;
+    }
+    set_power_throttling(enable);
+    true
+}
+
+#[cfg(not(target_os = "windows"))]
+fn set_background_mode(_enable: bool) -> bool {
+    false
+}
+
+/// 开启或关闭当前线程的 EcoQoS 执行速度节流（Windows 10 1709 以下不支持，失败时忽略）
+#[cfg(target_os = "windows")]
+fn set_power_throttling(enable: bool) {
+    use winapi::um::processthreadsapi::GetCurrentThread;
+    use winapi::um::winnt::HANDLE;
+
+    const THREAD_POWER_THROTTLING: i32 = 3;
+    const THREAD_POWER_THROTTLING_CURRENT_VERSION: u32 = 1;
+    const THREAD_POWER_THROTTLING_EXECUTION_SPEED: u32 = 0x1;
+
+    #[repr(C)]
+    struct ThreadPowerThrottlingState {
+        version: u32,
+        control_mask: u32,
+        state_mask: u32,
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn SetThreadInformation(
+            thread: HANDLE,
+            thread_information_class: i32,
+            thread_information: *mut std::ffi::c_void,
+            thread_information_size: u32,
+        ) -> i32;
+    }
+
+    // 关闭时清空控制位，交还系统自行决定，而不是强制高性能
+    let mask = if enable {
+        THREAD_POWER_THROTTLING_EXECUTION_SPEED
+    } else {
+        0
+    };
+    let mut state = ThreadPowerThrottlingState {
+        version: THREAD_POWER_THROTTLING_CURRENT_VERSION,
+        control_mask: mask,
+        state_mask: mask,
+    };
+    let ok = unsafe {
+        SetThreadInformation(
+            GetCurrentThread(),
+            THREAD_POWER_THROTTLING,
+            &mut state as *mut ThreadPowerThrottlingState as *mut std::ffi::c_void,
+            std::mem::size_of::<ThreadPowerThrottlingState>() as u32,
+        )
+    } != 0;
+    if !ok {
+        log::debug!("当前系统不支持线程节流，仅使用后台优先级");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_throttle_sleeps_only_when_enabled() {
+        let mut disabled = ScanThrottle::new(false);
+        for _ in 0..THROTTLE_BATCH * 2 {
+            disabled.tick();
+        }
+        assert_eq!(disabled.seen, 0);
+
+        let started = Instant::now();
+        let mut enabled = ScanThrottle::new(true);
+        for _ in 0..THROTTLE_BATCH * 2 {
+            enabled.tick();
+        }
+        assert!(started.elapsed() >= THROTTLE_SLEEP * 2);
+    }
+}
@@ -2,28 +2,59 @@
 // 扫描器模块 - 负责扫描Windows系统中的垃圾文件
 // ============================================================================
 
+mod app_logs;
 pub(crate) mod big_files;
 pub(crate) mod big_files_engine;
 mod categories;
+mod category_pages;
 mod context_menu;
 pub(crate) mod deep_junk;
 mod file_info;
+mod findings_export;
 mod hotspot;
 pub(crate) mod hotspot_engine;
+mod installer_rollback;
 mod leftovers;
-mod recycle_bin;
+pub(crate) mod low_priority;
+mod path_diagnostics;
+mod reclaim_estimate;
+pub(crate) mod recycle_bin;
 mod registry;
 mod registry_scoring;
+mod registry_software;
+mod scan_cancel;
 mod scan_engine;
+mod scheduled_tasks;
+mod scope_permissions;
 pub(crate) mod shell_icons;
+mod social_duplicates;
+mod social_retention;
 mod social_scanner;
+mod user_profiles;
+mod user_whitelist;
+mod uwp_cache;
 
+pub use app_logs::*;
 pub use categories::*;
+pub use category_pages::*;
 pub use context_menu::*;
 pub use file_info::*;
+pub use findings_export::*;
 pub use hotspot::*;
+pub use installer_rollback::*;
 pub use leftovers::*;
+pub use path_diagnostics::*;
+pub use reclaim_estimate::*;
+pub use recycle_bin::{get_recycle_bin_details, RecycleBinInfo};
 pub use registry::*;
+pub use scan_cancel::*;
 pub use scan_engine::*;
+pub use scheduled_tasks::*;
+pub use scope_permissions::*;
 pub use shell_icons::*;
+pub use social_duplicates::*;
+pub use social_retention::*;
 pub use social_scanner::*;
+pub use user_profiles::*;
+pub use user_whitelist::*;
+pub use uwp_cache::*;
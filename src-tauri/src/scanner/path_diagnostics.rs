@@ -0,0 +1,167 @@
+// ============================================================================
+// 路径诊断
+//
+// 垃圾分类和健康评分依赖 TEMP/TMP/USERPROFILE 等环境变量解析扫描目录，
+// 环境变量缺失或指向不存在的目录时扫描只会静默返回空结果。
+// 这里列出关键变量的解析结果，便于用户反馈"临时文件没清掉"时定位原因。
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// 垃圾扫描依赖的环境变量
+const DIAGNOSED_ENV_VARS: &[&str] = &[
+    "TEMP",
+    "TMP",
+    "USERPROFILE",
+    "LOCALAPPDATA",
+    "APPDATA",
+    "PROGRAMDATA",
+    "SystemRoot",
+    "SystemDrive",
+];
+
+/// 单个路径的诊断结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathDiagnostic {
+    /// 环境变量名或来源说明
+    pub name: String,
+    /// 解析后的路径，未设置时为空
+    pub value: Option<String>,
+    /// 路径是否存在
+    pub exists: bool,
+    /// 是否为目录
+    pub is_dir: bool,
+    /// 当前进程能否在该目录中创建文件（系统目录和系统盘根目录不做写入探测，固定为 false）
+    pub writable: bool,
+    /// 发现的问题（中文，正常时为空）
+    pub issue: Option<String>,
+}
+
+/// 诊断垃圾扫描依赖的关键路径
+///
+/// 除环境变量外，还包含系统 `GetTempPath` 的实际结果（`std::env::temp_dir`），
+/// 它按 TMP → TEMP → USERPROFILE 顺序回退，可与上面的变量对照。
+pub fn diagnose_paths() -> Vec<PathDiagnostic> {
+    let mut diagnostics: Vec<PathDiagnostic> = DIAGNOSED_ENV_VARS
+        .iter()
+        .map(|name| diagnose_path(name, std::env::var(name).ok()))
+        .collect();
+
+    diagnostics.push(diagnose_path(
+        "GetTempPath",
+        Some(std::env::temp_dir().to_string_lossy().into_owned()),
+    ));
+
+    diagnostics
+}
+
+fn diagnose_path(name: &str, value: Option<String>) -> PathDiagnostic {
+    let value = value.map(|raw| raw.trim().to_string());
+    let Some(raw) = value.as_deref().filter(|raw| !raw.is_empty()) else {
+        return PathDiagnostic {
+            name: name.to_string(),
+            value: None,
+            exists: false,
+            is_dir: false,
+            writable: false,
+            issue: Some("环境变量未设置，依赖它的扫描目录会被跳过".to_string()),
+        };
+    };
+
+    // SystemDrive 的值是 "C:"，按驱动器根目录检查
+    let path_text = if raw.ends_with(':') {
+        format!("{}\\", raw)
+    } else {
+        raw.to_string()
+    };
+    let path = Path::new(&path_text);
+    let exists = path.exists();
+    let is_dir = path.is_dir();
+    // 以管理员身份运行时探测文件会真的写进 Windows 目录或 C:\ 根目录，这两处直接跳过
+    let system_location = is_system_location(name, &path_text);
+    let writable = is_dir && !system_location && is_writable_dir(path);
+
+    let issue = if raw.contains('%') {
+        Some("值中包含未展开的环境变量".to_string())
+    } else if !path.is_absolute() {
+        Some("不是绝对路径".to_string())
+    } else if !exists {
+        Some("路径不存在".to_string())
+    } else if !is_dir {
+        Some("路径不是目录".to_string())
+    } else if !writable && !system_location {
+        // 系统目录和系统盘根目录对普通用户本就只读，不视为异常
+        Some("当前用户无法写入该目录".to_string())
+    } else {
+        None
+    };
+
+    PathDiagnostic {
+        name: name.to_string(),
+        value,
+        exists,
+        is_dir,
+        writable,
+        issue,
+    }
+}
+
+/// 是否为系统目录或系统盘根目录（按变量名或与 SystemRoot/SystemDrive 的值比较）
+fn is_system_location(name: &str, path_text: &str) -> bool {
+    const SYSTEM_VARS: [&str; 2] = ["SystemRoot", "SystemDrive"];
+    if SYSTEM_VARS.contains(&name) {
+        return true;
+    }
+    let normalize = |text: &str| text.trim().trim_end_matches(['\\', '/']).to_lowercase();
+    let target = normalize(path_text);
+    SYSTEM_VARS
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .any(|value| !value.trim().is_empty() && normalize(&value) == target)
+}
+
+/// 尝试创建并删除一个探测文件判断目录是否可写
+fn is_writable_dir(dir: &Path) -> bool {
+    let probe = dir.join(format!(".lightc_write_probe_{}", std::process::id()));
+    match fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+    {
+        Ok(file) => {
+            drop(file);
+            let _ = fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnose_path() {
+        let temp = std::env::temp_dir();
+        let ok = diagnose_path("TEMP", Some(temp.to_string_lossy().into_owned()));
+        assert!(ok.exists && ok.is_dir && ok.writable);
+        assert_eq!(ok.issue, None);
+
+        let system_root = diagnose_path("SystemRoot", Some(temp.to_string_lossy().into_owned()));
+        assert!(system_root.is_dir && !system_root.writable);
+        assert_eq!(system_root.issue, None);
+
+        let missing = diagnose_path("TMP", None);
+        assert_eq!(missing.value, None);
+        assert!(missing.issue.is_some());
+
+        let unexpanded = diagnose_path("TEMP", Some("%USERPROFILE%\\AppData\\Local\\Temp".into()));
+        assert_eq!(
+            unexpanded.issue.as_deref(),
+            Some("值中包含未展开的环境变量")
+        );
+    }
+}
@@ -0,0 +1,397 @@
+// ============================================================================
+// 可回收空间快速估算
+//
+// 首页需要在用户操作前给出一个"大约能清理多少"的数字。完整的 scan_junk_files
+// 要遍历所有分类，耗时较长；这里只浅层统计低风险来源（临时文件、缩略图缓存、
+// 回收站），加上休眠文件和 WinSxS 的已知估算，限制深度和条目数保证两秒内返回。
+//
+// 估算刻意偏保守：临时文件只统计 24 小时前修改的文件，休眠文件和 WinSxS
+// 需要用户额外操作才能回收，单独计入 optional_size，不计入 total_size。
+//
+// `get_top_recommendation` 给首页"一键做最有用的事"：比较休眠文件、Windows.old、
+// 回收站和临时文件四个来源，只返回预计释放最多的一项及其对应命令。
+// ============================================================================
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+use walkdir::WalkDir;
+
+use super::JunkCategory;
+use crate::cleaner::safety_constants::is_reparse_point_dir;
+
+/// 临时目录的遍历深度
+const TEMP_MAX_DEPTH: usize = 3;
+
+/// 每个来源最多统计的文件数，超过后停止并标记为不完整
+const MAX_ENTRIES_PER_SOURCE: usize = 50_000;
+
+/// 修改时间在该时长内的临时文件可能仍在使用，不计入估算
+const TEMP_MIN_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// 预计释放低于该值（100 MB）时不值得打扰用户，不给出建议
+const MIN_RECOMMENDATION_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Windows.old 的遍历深度，升级备份的目录层级很深
+const WINDOWS_OLD_MAX_DEPTH: usize = 64;
+
+/// 单个来源的估算
+#[derive(Debug, Clone, Serialize)]
+pub struct ReclaimSource {
+    /// 来源标识（temp / thumbnails / recycle_bin / hibernation / winsxs）
+    pub id: String,
+    /// 显示名称
+    pub name: String,
+    /// 估算可回收大小（字节）
+    pub size: u64,
+    /// 是否计入保守总量（休眠文件和 WinSxS 需要额外操作，不计入）
+    pub included_in_total: bool,
+    /// 是否因达到条目上限提前停止（实际大小可能更大）
+    pub truncated: bool,
+}
+
+/// 可回收空间估算结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ReclaimEstimate {
+    /// 保守估算总量（只含可直接清理的低风险来源）
+    pub total_size: u64,
+    /// 需要额外操作才能回收的空间（关闭休眠、组件存储清理）
+    pub optional_size: u64,
+    /// 各来源明细
+    pub sources: Vec<ReclaimSource>,
+    /// 估算耗时（毫秒）
+    pub duration_ms: u64,
+}
+
+/// 首页建议的单项操作
+#[derive(Debug, Clone, Serialize)]
+pub struct Recommendation {
+    /// 来源标识（hibernation / windows_old / recycle_bin / temp）
+    pub id: String,
+    /// 操作名称
+    pub title: String,
+    /// 给用户看的理由
+    pub reason: String,
+    /// 预计释放的空间（字节）
+    pub estimated_size: u64,
+    /// 执行该操作要调用的命令
+    pub command: String,
+    /// 命令参数（与前端 invoke 的参数名一致）
+    pub args: serde_json::Value,
+    /// 命令是否需要管理员权限
+    pub requires_admin: bool,
+}
+
+/// 快速估算可回收空间，不删除任何文件
+pub fn estimate_reclaimable() -> ReclaimEstimate {
+    let start = Instant::now();
+    let mut sources = Vec::new();
+
+    let (size, truncated) = sum_old_files(&temp_dirs(), TEMP_MAX_DEPTH, TEMP_MIN_AGE);
+    sources.push(source("temp", "临时文件", size, true, truncated));
+
+    sources.push(source(
+        "thumbnails",
+        "缩略图缓存",
+        sum_thumbnail_cache(),
+        true,
+        false,
+    ));
+
+    sources.push(source(
+        "recycle_bin",
+        "回收站",
+        query_recycle_bin_size(),
+        true,
+        false,
+    ));
+
+    sources.push(source(
+        "hibernation",
+        "休眠文件",
+        hibernation_file_size(),
+        false,
+        false,
+    ));
+
+    // DISM 分析耗时数分钟，这里只使用系统瘦身页面已缓存的结果
+    let winsxs_size = crate::system_slim::cached_winsxs_reclaimable_size().unwrap_or(0);
+    sources.push(source("winsxs", "系统组件存储", winsxs_size, false, false));
+
+    let total_size = sources
+        .iter()
+        .filter(|s| s.included_in_total)
+        .map(|s| s.size)
+        .sum();
+    let optional_size = sources
+        .iter()
+        .filter(|s| !s.included_in_total)
+        .map(|s| s.size)
+        .sum();
+
+    ReclaimEstimate {
+        total_size,
+        optional_size,
+        sources,
+        duration_ms: start.elapsed().as_millis() as u64,
+    }
+}
+
+/// 找出预计释放空间最多的单项操作，都不足 100 MB 时返回 None
+pub fn get_top_recommendation() -> Option<Recommendation> {
+    let (temp_size, _) = sum_old_files(&temp_dirs(), TEMP_MAX_DEPTH, TEMP_MIN_AGE);
+    // 达到条目上限时按已统计的大小估算，实际只会更大
+    let (windows_old_size, _) = sum_old_files(
+        &[PathBuf::from(r"C:\Windows.old")],
+        WINDOWS_OLD_MAX_DEPTH,
+        Duration::ZERO,
+    );
+
+    pick_top_recommendation(vec![
+        hibernation_recommendation(hibernation_file_size()),
+        category_recommendation(
+            "windows_old",
+            JunkCategory::OldWindowsInstallation,
+            "系统升级后保留的旧版 Windows，确认不再回退后可删除",
+            windows_old_size,
+            true,
+        ),
+        category_recommendation(
+            "recycle_bin",
+            JunkCategory::RecycleBin,
+            "回收站中的文件仍占用磁盘空间",
+            query_recycle_bin_size(),
+            false,
+        ),
+        category_recommendation(
+            "temp",
+            JunkCategory::WindowsTemp,
+            "超过一天未修改的临时文件",
+            temp_size,
+            false,
+        ),
+    ])
+}
+
+/// 选出预计释放最多且达到最低阈值的一项
+fn pick_top_recommendation(candidates: Vec<Recommendation>) -> Option<Recommendation> {
+    candidates
+        .into_iter()
+        .filter(|candidate| candidate.estimated_size >= MIN_RECOMMENDATION_SIZE)
+        .max_by_key(|candidate| candidate.estimated_size)
+}
+
+fn hibernation_recommendation(size: u64) -> Recommendation {
+    Recommendation {
+        id: "hibernation".to_string(),
+        title: "关闭休眠".to_string(),
+        reason: "休眠文件 hiberfil.sys 按内存大小预留空间，不使用休眠时可关闭".to_string(),
+        estimated_size: size,
+        command: "disable_hibernation".to_string(),
+        args: serde_json::json!({}),
+        requires_admin: true,
+    }
+}
+
+/// 通过 `delete_scan_category` 整类清理的建议
+fn category_recommendation(
+    id: &str,
+    category: JunkCategory,
+    reason: &str,
+    size: u64,
+    requires_admin: bool,
+) -> Recommendation {
+    Recommendation {
+        id: id.to_string(),
+        title: format!("清理{}", category.display_name()),
+        reason: reason.to_string(),
+        estimated_size: size,
+        command: "delete_scan_category".to_string(),
+        args: serde_json::json!({ "categoryName": category.display_name() }),
+        requires_admin,
+    }
+}
+
+/// 休眠开启时 hiberfil.sys 的大小
+fn hibernation_file_size() -> u64 {
+    if crate::system_slim::check_hibernation_enabled() {
+        std::fs::metadata(r"C:\hiberfil.sys")
+            .map(|m| m.len())
+            .unwrap_or(0)
+    } else {
+        0
+    }
+}
+
+fn source(
+    id: &str,
+    name: &str,
+    size: u64,
+    included_in_total: bool,
+    truncated: bool,
+) -> ReclaimSource {
+    ReclaimSource {
+        id: id.to_string(),
+        name: name.to_string(),
+        size,
+        included_in_total,
+        truncated,
+    }
+}
+
+/// 用户和系统临时目录（去重，%TEMP% 与 %TMP% 通常相同）
+fn temp_dirs() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = ["TEMP", "TMP"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(PathBuf::from)
+        .collect();
+    dirs.push(PathBuf::from(r"C:\Windows\Temp"));
+    let mut seen = std::collections::HashSet::new();
+    dirs.retain(|dir| seen.insert(dir.to_string_lossy().to_lowercase()));
+    dirs
+}
+
+/// 统计目录中修改时间早于 `min_age` 的文件总大小，返回 (大小, 是否达到条目上限)
+fn sum_old_files(roots: &[PathBuf], max_depth: usize, min_age: Duration) -> (u64, bool) {
+    let now = SystemTime::now();
+    let mut total = 0u64;
+    let mut visited = 0usize;
+
+    for root in roots {
+        for entry in WalkDir::new(root)
+            .max_depth(max_depth)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !is_reparse_point_dir(e))
+            .filter_map(|e| e.ok())
+        {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            visited += 1;
+            if visited > MAX_ENTRIES_PER_SOURCE {
+                return (total, true);
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let old_enough = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .is_some_and(|age| age >= min_age);
+            if old_enough {
+                total += metadata.len();
+            }
+        }
+    }
+    (total, false)
+}
+
+/// 统计资源管理器缩略图缓存（thumbcache_*.db / iconcache_*.db）
+fn sum_thumbnail_cache() -> u64 {
+    dirs::data_local_dir()
+        .map(|dir| sum_cache_db_files(&dir.join(r"Microsoft\Windows\Explorer")))
+        .unwrap_or(0)
+}
+
+fn sum_cache_db_files(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            (name.starts_with("thumbcache_") || name.starts_with("iconcache_"))
+                && name.ends_with(".db")
+        })
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// 通过 SHQueryRecycleBinW 查询所有驱动器回收站的总大小
+#[cfg(windows)]
+fn query_recycle_bin_size() -> u64 {
+    use winapi::um::shellapi::{SHQueryRecycleBinW, SHQUERYRBINFO};
+
+    let mut info: SHQUERYRBINFO = unsafe { std::mem::zeroed() };
+    info.cbSize = std::mem::size_of::<SHQUERYRBINFO>() as u32;
+    // 根路径为空表示查询所有驱动器
+    let hr = unsafe { SHQueryRecycleBinW(std::ptr::null(), &mut info) };
+    if hr != 0 {
+        log::warn!("查询回收站大小失败 (HRESULT 0x{:08X})", hr);
+        return 0;
+    }
+    info.i64Size.max(0) as u64
+}
+
+#[cfg(not(windows))]
+fn query_recycle_bin_size() -> u64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum_old_files_respects_min_age_and_cache_names() {
+        let dir =
+            std::env::temp_dir().join(format!("lightc-reclaim-estimate-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("fresh.tmp"), vec![0u8; 64]).unwrap();
+        std::fs::write(dir.join("thumbcache_256.db"), vec![0u8; 32]).unwrap();
+        std::fs::write(dir.join("other.db"), vec![0u8; 16]).unwrap();
+
+        // 刚写入的文件不满足最小修改时长，不计入
+        let (size, truncated) = sum_old_files(
+            std::slice::from_ref(&dir),
+            TEMP_MAX_DEPTH,
+            Duration::from_secs(3600),
+        );
+        assert_eq!(size, 0);
+        assert!(!truncated);
+        let (size, _) = sum_old_files(std::slice::from_ref(&dir), TEMP_MAX_DEPTH, Duration::ZERO);
+        assert_eq!(size, 112);
+
+        assert_eq!(sum_cache_db_files(&dir), 32);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_top_recommendation_picks_largest_above_threshold() {
+        let gb = 1024 * 1024 * 1024;
+        let top = pick_top_recommendation(vec![
+            hibernation_recommendation(6 * gb),
+            category_recommendation(
+                "windows_old",
+                JunkCategory::OldWindowsInstallation,
+                "",
+                20 * gb,
+                true,
+            ),
+            category_recommendation("temp", JunkCategory::WindowsTemp, "", gb, false),
+        ])
+        .unwrap();
+        assert_eq!(top.id, "windows_old");
+        assert_eq!(top.command, "delete_scan_category");
+        assert_eq!(top.args["categoryName"], "旧版Windows安装");
+
+        let none = pick_top_recommendation(vec![
+            hibernation_recommendation(0),
+            category_recommendation(
+                "temp",
+                JunkCategory::WindowsTemp,
+                "",
+                MIN_RECOMMENDATION_SIZE - 1,
+                false,
+            ),
+        ]);
+        assert!(none.is_none());
+    }
+}
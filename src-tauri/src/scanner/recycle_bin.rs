@@ -3,9 +3,15 @@
 // 只返回当前用户能够在 Windows 回收站界面看到的有效条目
 // ============================================================================
 
-use super::{CategoryScanResult, FileInfo, JunkCategory};
+#[cfg(windows)]
+use super::FileInfo;
+use super::{CategoryScanResult, JunkCategory};
+#[cfg(windows)]
 use log::{debug, warn};
+use serde::Serialize;
+#[cfg(windows)]
 use std::fs;
+#[cfg(windows)]
 use std::path::{Path, PathBuf};
 #[cfg(windows)]
 use std::sync::OnceLock;
@@ -40,6 +46,60 @@ pub fn scan_current_user(category: &JunkCategory, result: &mut CategoryScanResul
     }
 }
 
+/// 单个驱动器的回收站统计
+#[derive(Debug, Clone, Serialize)]
+pub struct RecycleBinInfo {
+    /// 盘符，如 "C:"
+    pub drive: String,
+    /// 回收站中的条目数
+    pub item_count: u64,
+    /// 回收站总大小（字节）
+    pub total_size: u64,
+}
+
+/// 按驱动器查询回收站的条目数和大小。
+///
+/// 使用 `SHQueryRecycleBinW` 逐盘查询，结果与 Explorer 回收站属性页一致；
+/// 没有回收站的卷（如光驱）查询失败时跳过。
+pub fn get_recycle_bin_details() -> Vec<RecycleBinInfo> {
+    #[cfg(windows)]
+    {
+        get_drive_letters()
+            .into_iter()
+            .filter_map(query_drive_recycle_bin)
+            .collect()
+    }
+
+    #[cfg(not(windows))]
+    {
+        Vec::new()
+    }
+}
+
+#[cfg(windows)]
+fn query_drive_recycle_bin(drive_letter: char) -> Option<RecycleBinInfo> {
+    use std::os::windows::ffi::OsStrExt;
+    use winapi::um::shellapi::{SHQueryRecycleBinW, SHQUERYRBINFO};
+
+    let root: Vec<u16> = std::ffi::OsStr::new(&format!("{}:\\", drive_letter))
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut info: SHQUERYRBINFO = unsafe { std::mem::zeroed() };
+    info.cbSize = std::mem::size_of::<SHQUERYRBINFO>() as u32;
+    let hr = unsafe { SHQueryRecycleBinW(root.as_ptr(), &mut info) };
+    if hr != 0 {
+        debug!("查询 {}: 回收站失败 (HRESULT 0x{:08X})", drive_letter, hr);
+        return None;
+    }
+
+    Some(RecycleBinInfo {
+        drive: format!("{}:", drive_letter),
+        item_count: info.i64NumItems.max(0) as u64,
+        total_size: info.i64Size.max(0) as u64,
+    })
+}
+
 /// 校验路径是否属于当前用户可操作的回收站数据文件。
 pub fn is_current_user_entry_path(path: &str) -> bool {
     #[cfg(windows)]
@@ -197,7 +257,7 @@ mod tests {
 }
 
 #[cfg(windows)]
-fn current_user_sid() -> Option<String> {
+pub(crate) fn current_user_sid() -> Option<String> {
     static CURRENT_USER_SID: OnceLock<Option<String>> = OnceLock::new();
     CURRENT_USER_SID
         .get_or_init(current_user_sid_uncached)
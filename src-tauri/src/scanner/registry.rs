@@ -5,6 +5,7 @@
 //   1. 只读扫描：扫描阶段绝不修改任何注册表键值
 //   2. 铁证条件：关联 exe 不存在 + 非系统路径 + 非系统进程，三项全部满足才输出
 //   3. 范围收敛：只扫描 HKCR\Applications（文件关联残留），不碰系统关键区域
+//      HKCU\Software 软件配置键需显式开启（with_software_key_depth），只删当前用户配置
 //   4. 真实备份：删除前使用 reg.exe export 创建可恢复的 .reg 文件
 //   5. 用户确认：所有删除操作需用户明确选择
 //
@@ -14,17 +15,25 @@
 //   不会影响系统稳定性。
 // ============================================================================
 
-use chrono;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::fs::{self, File};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(windows)]
 use winreg::enums::*;
+#[cfg(windows)]
 use winreg::RegKey;
 
 use super::registry_scoring::{is_definitely_safe_to_delete, PathCache, PathResolver};
+use super::registry_software::{
+    find_orphan_software_keys, installed_app_names, normalize_app_name, read_software_tree,
+    SoftwareKeyNode,
+};
+use super::user_whitelist::matches_whitelist_pattern;
+use crate::process_runner::{run_with_timeout, DEFAULT_COMMAND_TIMEOUT};
 
 // ============================================================================
 // 数据类型
@@ -33,6 +42,9 @@ use super::registry_scoring::{is_definitely_safe_to_delete, PathCache, PathResol
 /// 扫描结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistryScanResult {
+    /// JSON 格式版本（见 `RESULT_SCHEMA_VERSION`）
+    #[serde(default)]
+    pub schema_version: u32,
     pub entries: Vec<RegistryEntry>,
     pub total_count: u32,
     pub scan_duration_ms: u64,
@@ -51,6 +63,19 @@ pub struct RegistryEntry {
     pub issue: String,
 }
 
+/// 注册表扫描进度事件负载
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistryScanProgress {
+    /// 当前阶段：applications（HKCR\Applications）或 software（HKCU\Software）
+    pub phase: String,
+    /// 当前正在读取的子键名，阶段开始时为空
+    pub current_key: String,
+    /// 当前阶段已读取的子键数量
+    pub scanned_count: usize,
+    /// 已发现的残留数量
+    pub found_count: usize,
+}
+
 /// 删除结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistryDeleteResult {
@@ -64,6 +89,31 @@ pub struct RegistryDeleteResult {
 // 扫描器
 // ============================================================================
 
+/// 软件配置键最多输出的条目数（与 HKCR\Applications 的上限一致）
+const MAX_SOFTWARE_KEY_ENTRIES: usize = 100;
+
+/// HKCR\Applications 每读取多少个子键上报一次进度（单个子键很快，逐个上报会刷屏）
+const APPLICATIONS_PROGRESS_INTERVAL: usize = 50;
+
+/// 全局取消标志，跨线程共享（与 big_files.rs 模式一致）
+static REGISTRY_SCAN_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// 重置取消标志（扫描开始前调用）
+pub fn reset_registry_scan_cancelled() {
+    REGISTRY_SCAN_CANCELLED.store(false, Ordering::SeqCst);
+}
+
+/// 设置取消标志（前端点击取消按钮时调用）
+pub fn cancel_registry_scan() {
+    log::info!("收到取消注册表扫描请求");
+    REGISTRY_SCAN_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+/// 查询取消标志，命令层据此区分取消和正常完成
+pub fn is_registry_scan_cancelled() -> bool {
+    REGISTRY_SCAN_CANCELLED.load(Ordering::SeqCst)
+}
+
 /// 注册表残留扫描器
 ///
 /// 扫描 HKCR\Applications 下所有子键，检查其 shell\open\command
@@ -71,56 +121,182 @@ pub struct RegistryDeleteResult {
 pub struct RegistryScanner {
     path_cache: PathCache,
     path_resolver: PathResolver,
+    /// 用户白名单（小写模式）
+    user_whitelist: Vec<String>,
+    /// HKCU\Software 软件配置键的读取深度，None 表示不扫描（默认只扫描 HKCR\Applications）
+    software_key_depth: Option<usize>,
+    /// 已安装应用的规范化名称，None 表示扫描时从注册表读取
+    installed_apps: Option<HashSet<String>>,
+    /// 用户忽略的条目完整路径（小写），扫描结果中不再出现
+    ignored_entries: HashSet<String>,
 }
 
 impl RegistryScanner {
     pub fn new() -> Self {
         log::info!("注册表残留扫描器已初始化");
+        let user_whitelist = super::load_user_whitelist();
         RegistryScanner {
             path_cache: PathCache::new(),
             path_resolver: PathResolver::new(),
+            user_whitelist: user_whitelist.registry_keys,
+            software_key_depth: None,
+            installed_apps: None,
+            ignored_entries: user_whitelist
+                .ignored_registry_entries
+                .into_iter()
+                .collect(),
         }
     }
 
+    /// 使用给定的已安装应用名称（DisplayName / 发布者 / 安装目录名），不再读取注册表
+    ///
+    /// 名称在这里统一规范化，调用方传原始名称即可。
+    pub fn with_installed_apps(mut self, apps: HashSet<String>) -> Self {
+        self.installed_apps = Some(
+            apps.iter()
+                .map(|name| normalize_app_name(name))
+                .filter(|name| !name.is_empty())
+                .collect(),
+        );
+        self
+    }
+
+    /// 同时扫描 HKCU\Software 下的"厂商\产品"配置键，`max_depth` 为键树读取深度（至少 2）
+    pub fn with_software_key_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.software_key_depth = max_depth;
+        self
+    }
+
+    /// 子键名是否在用户白名单中
+    fn is_key_whitelisted(&self, app_name: &str) -> bool {
+        let name_lower = app_name.to_lowercase();
+        self.user_whitelist
+            .iter()
+            .any(|pattern| matches_whitelist_pattern(pattern, &name_lower))
+    }
+
+    /// 完整键路径是否在用户忽略列表中
+    fn is_entry_ignored(&self, path: &str) -> bool {
+        self.ignored_entries
+            .contains(&path.trim_end_matches('\\').to_lowercase())
+    }
+
     /// 执行扫描
     pub fn scan(&mut self) -> RegistryScanResult {
+        self.scan_with_progress(|_| {})
+    }
+
+    /// 执行扫描，每个阶段开始时回调一次进度，阶段内按子键周期性回调
+    ///
+    /// 开启软件配置键扫描后，HKCU\Software 的键树读取是主要耗时，
+    /// 这一阶段每读完一个厂商键回调一次。
+    pub fn scan_with_progress<F>(&mut self, mut on_progress: F) -> RegistryScanResult
+    where
+        F: FnMut(&RegistryScanProgress),
+    {
         let start_time = std::time::Instant::now();
-        let mut entries = Vec::new();
-        let mut seen_paths: HashSet<String> = HashSet::new(); // 按关联路径去重
 
         log::info!("开始扫描注册表残留 (HKCR\\Applications)");
+        on_progress(&RegistryScanProgress {
+            phase: "applications".to_string(),
+            current_key: String::new(),
+            scanned_count: 0,
+            found_count: 0,
+        });
+
+        let Some(mut entries) = self.scan_applications(&mut on_progress) else {
+            return RegistryScanResult {
+                schema_version: super::RESULT_SCHEMA_VERSION,
+                entries: vec![],
+                total_count: 0,
+                scan_duration_ms: start_time.elapsed().as_millis() as u64,
+            };
+        };
 
-        let apps_key = match RegKey::predef(HKEY_CLASSES_ROOT)
-            .open_subkey_with_flags("Applications", KEY_READ)
+        if let Some(max_depth) = self
+            .software_key_depth
+            .filter(|_| !is_registry_scan_cancelled())
         {
-            Ok(k) => k,
-            Err(e) => {
-                log::warn!("无法打开 HKCR\\Applications: {}", e);
-                return RegistryScanResult {
-                    entries: vec![],
-                    total_count: 0,
-                    scan_duration_ms: start_time.elapsed().as_millis() as u64,
-                };
+            let found_before = entries.len();
+            on_progress(&RegistryScanProgress {
+                phase: "software".to_string(),
+                current_key: String::new(),
+                scanned_count: 0,
+                found_count: found_before,
+            });
+            entries.extend(self.scan_software_keys(max_depth, |vendor, scanned_count| {
+                on_progress(&RegistryScanProgress {
+                    phase: "software".to_string(),
+                    current_key: vendor.to_string(),
+                    scanned_count,
+                    found_count: found_before,
+                });
+            }));
+        }
+
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let total_count = entries.len() as u32;
+        let scan_duration_ms = start_time.elapsed().as_millis() as u64;
+
+        let (cache_hits, cache_misses) = self.path_cache.stats();
+        log::info!(
+            "扫描完成: {} 个残留条目, 耗时 {}ms, 缓存命中率 {:.1}%",
+            total_count,
+            scan_duration_ms,
+            if cache_hits + cache_misses > 0 {
+                (cache_hits as f64 / (cache_hits + cache_misses) as f64) * 100.0
+            } else {
+                0.0
             }
-        };
+        );
 
-        for app_name in apps_key.enum_keys().filter_map(|k| k.ok()) {
-            if entries.len() >= 100 {
+        RegistryScanResult {
+            schema_version: super::RESULT_SCHEMA_VERSION,
+            entries,
+            total_count,
+            scan_duration_ms,
+        }
+    }
+}
+
+impl RegistryScanner {
+    /// 扫描 HKCR\Applications 中关联程序已不存在的文件关联
+    ///
+    /// 无法读取 HKCR\Applications 时返回 None，整个扫描按无结果结束。
+    fn scan_applications<F>(&mut self, on_progress: &mut F) -> Option<Vec<RegistryEntry>>
+    where
+        F: FnMut(&RegistryScanProgress),
+    {
+        let mut entries = Vec::new();
+        let mut seen_paths: HashSet<String> = HashSet::new(); // 按关联路径去重
+
+        for (index, (app_name, command)) in read_application_commands()?.into_iter().enumerate() {
+            if entries.len() >= 100 || is_registry_scan_cancelled() {
                 break;
             }
+            if (index + 1) % APPLICATIONS_PROGRESS_INTERVAL == 0 {
+                on_progress(&RegistryScanProgress {
+                    phase: "applications".to_string(),
+                    current_key: app_name.clone(),
+                    scanned_count: index + 1,
+                    found_count: entries.len(),
+                });
+            }
 
-            // 读 shell\open\command 默认值（命令行字符串）
-            let shell_path = format!(r"{}\shell\open\command", app_name);
-            let exe_path = match apps_key.open_subkey_with_flags(&shell_path, KEY_READ) {
-                Ok(cmd_key) => match cmd_key.get_value::<String, _>("") {
-                    Ok(cmd) => self
-                        .path_resolver
-                        .extract_and_resolve(&cmd)
-                        .map(|(path, _)| path.to_string_lossy().to_string()),
-                    Err(_) => None,
-                },
-                Err(_) => None,
-            };
+            if self.is_key_whitelisted(&app_name) {
+                continue;
+            }
+            let entry_path = format!(r"HKEY_CLASSES_ROOT\Applications\{}", app_name);
+            if self.is_entry_ignored(&entry_path) {
+                continue;
+            }
+
+            let exe_path = command.and_then(|cmd| {
+                self.path_resolver
+                    .extract_and_resolve(&cmd)
+                    .map(|(path, _)| path.to_string_lossy().to_string())
+            });
 
             let exe_path = match exe_path {
                 Some(p) => p,
@@ -140,35 +316,50 @@ impl RegistryScanner {
             seen_paths.insert(path_lower);
 
             entries.push(RegistryEntry {
-                path: format!(r"HKEY_CLASSES_ROOT\Applications\{}", app_name),
+                path: entry_path,
                 name: app_name,
                 associated_path: exe_path.clone(),
                 issue: format!("关联的可执行文件不存在: {}", exe_path),
             });
         }
 
-        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        Some(entries)
+    }
 
-        let total_count = entries.len() as u32;
-        let scan_duration_ms = start_time.elapsed().as_millis() as u64;
+    /// 扫描 HKCU\Software 下厂商和产品均未匹配已安装应用的配置键
+    ///
+    /// `on_vendor(厂商键名, 已读取的厂商数)` 在每读完一个厂商键后调用。
+    fn scan_software_keys(
+        &self,
+        max_depth: usize,
+        on_vendor: impl FnMut(&str, usize),
+    ) -> Vec<RegistryEntry> {
+        log::info!("开始扫描 HKCU\\Software 软件配置键 (深度 {})", max_depth);
+        let vendors = read_software_tree(max_depth, on_vendor);
+        self.software_key_entries(&vendors)
+    }
 
-        let (cache_hits, cache_misses) = self.path_cache.stats();
-        log::info!(
-            "扫描完成: {} 个残留条目, 耗时 {}ms, 缓存命中率 {:.1}%",
-            total_count,
-            scan_duration_ms,
-            if cache_hits + cache_misses > 0 {
-                (cache_hits as f64 / (cache_hits + cache_misses) as f64) * 100.0
-            } else {
-                0.0
+    /// 根据键树生成软件配置键条目，未注入已安装应用时从注册表读取
+    fn software_key_entries(&self, vendors: &[SoftwareKeyNode]) -> Vec<RegistryEntry> {
+        let loaded;
+        let installed = match &self.installed_apps {
+            Some(apps) => apps,
+            None => {
+                loaded = installed_app_names();
+                &loaded
             }
-        );
-
-        RegistryScanResult {
-            entries,
-            total_count,
-            scan_duration_ms,
-        }
+        };
+        find_orphan_software_keys(vendors, installed, |name| self.is_key_whitelisted(name))
+            .into_iter()
+            .map(|orphan| RegistryEntry {
+                path: format!(r"HKEY_CURRENT_USER\Software\{}", orphan.relative_path),
+                name: orphan.relative_path,
+                associated_path: String::new(),
+                issue: orphan.issue,
+            })
+            .filter(|entry| !self.is_entry_ignored(&entry.path))
+            .take(MAX_SOFTWARE_KEY_ENTRIES)
+            .collect()
     }
 }
 
@@ -178,6 +369,39 @@ impl Default for RegistryScanner {
     }
 }
 
+/// 读取 HKCR\Applications 下每个应用名及其 shell\open\command 默认值（命令行字符串）
+#[cfg(windows)]
+fn read_application_commands() -> Option<Vec<(String, Option<String>)>> {
+    let apps_key =
+        match RegKey::predef(HKEY_CLASSES_ROOT).open_subkey_with_flags("Applications", KEY_READ) {
+            Ok(k) => k,
+            Err(e) => {
+                log::warn!("无法打开 HKCR\\Applications: {}", e);
+                return None;
+            }
+        };
+    Some(
+        apps_key
+            .enum_keys()
+            .filter_map(|k| k.ok())
+            .map(|app_name| {
+                let command = apps_key
+                    .open_subkey_with_flags(format!(r"{}\shell\open\command", app_name), KEY_READ)
+                    .and_then(|cmd_key| cmd_key.get_value::<String, _>(""))
+                    .ok();
+                (app_name, command)
+            })
+            .collect(),
+    )
+}
+
+/// 非 Windows 平台没有注册表
+#[cfg(not(windows))]
+fn read_application_commands() -> Option<Vec<(String, Option<String>)>> {
+    log::warn!("注册表扫描仅支持 Windows 系统");
+    None
+}
+
 // ============================================================================
 // 备份
 // ============================================================================
@@ -223,6 +447,25 @@ impl RegistryBackup {
         Ok(backup_file)
     }
 
+    /// 重新读取备份文件，确认每个待删除条目都导出了值数据
+    ///
+    /// reg export 失败时备份里只有回退注释和空的键头，这样的文件无法恢复任何数据，
+    /// 删除前必须发现并中止。
+    pub fn verify_backup(backup_file: &Path, entries: &[RegistryEntry]) -> Result<(), String> {
+        let raw = fs::read(backup_file).map_err(|e| format!("读取备份文件失败: {}", e))?;
+        let content = decode_reg_export(&raw)?;
+        let missing = entries_without_backup_values(&content, entries);
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "以下 {} 个条目未备份到值数据: {}",
+                missing.len(),
+                missing.join(", ")
+            ))
+        }
+    }
+
     /// 使用 reg.exe export 导出完整注册表键
     fn export_key_via_reg_exe(file: &mut File, entry: &RegistryEntry) -> Result<(), String> {
         let temp_dir = std::env::temp_dir();
@@ -230,10 +473,11 @@ impl RegistryBackup {
 
         let reg_subpath = Self::to_reg_exe_format(&entry.path)?;
 
-        let output = Command::new("reg")
-            .args(["export", &reg_subpath, &temp_file.to_string_lossy(), "/y"])
-            .output()
-            .map_err(|e| format!("执行 reg export 失败: {}", e))?;
+        let output = run_with_timeout(
+            Command::new("reg").args(["export", &reg_subpath, &temp_file.to_string_lossy(), "/y"]),
+            DEFAULT_COMMAND_TIMEOUT,
+        )
+        .map_err(|e| format!("执行 reg export 失败: {}", e))?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -304,6 +548,7 @@ impl RegistryBackup {
 /// 删除单个注册表条目（删除整个子键）
 ///
 /// 调用前必须通过 RegistryBackup::export_backup 创建备份。
+#[cfg(windows)]
 pub fn delete_registry_entry(entry: &RegistryEntry) -> Result<(), String> {
     let (root_key, subpath) = parse_registry_path_components(&entry.path)?;
     let (parent_path, child_name) = split_last_component(subpath)?;
@@ -320,6 +565,12 @@ pub fn delete_registry_entry(entry: &RegistryEntry) -> Result<(), String> {
     Ok(())
 }
 
+#[cfg(not(windows))]
+pub fn delete_registry_entry(_entry: &RegistryEntry) -> Result<(), String> {
+    Err("此功能仅支持Windows系统".to_string())
+}
+
+#[cfg(windows)]
 fn parse_registry_path_components(path: &str) -> Result<(RegKey, &str), String> {
     if let Some(subpath) = path.strip_prefix("HKEY_CURRENT_USER\\") {
         Ok((RegKey::predef(HKEY_CURRENT_USER), subpath))
@@ -332,17 +583,48 @@ fn parse_registry_path_components(path: &str) -> Result<(RegKey, &str), String>
     }
 }
 
+#[cfg(windows)]
 fn split_last_component(path: &str) -> Result<(&str, &str), String> {
     path.rsplit_once('\\')
         .ok_or_else(|| format!("无法分割路径: {}", path))
 }
 
+/// 返回在 .reg 内容中找不到值数据的条目路径
+///
+/// 条目键本身或其任一子键下出现值行（`"name"=` 或 `@=`）即视为已备份，
+/// 例如 HKCR\Applications\xxx.exe 的数据通常只在 shell\open\command 子键里。
+fn entries_without_backup_values(content: &str, entries: &[RegistryEntry]) -> Vec<String> {
+    let mut keys_with_values: Vec<String> = Vec::new();
+    let mut current_key: Option<String> = None;
+    for line in content.lines().map(str::trim) {
+        if let Some(key) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            current_key = Some(key.to_lowercase());
+        } else if line.starts_with('"') || line.starts_with("@=") {
+            if let Some(key) = current_key.take() {
+                keys_with_values.push(key);
+            }
+        }
+    }
+
+    entries
+        .iter()
+        .filter(|entry| {
+            let path = entry.path.trim_end_matches('\\').to_lowercase();
+            let subkey_prefix = format!("{}\\", path);
+            !keys_with_values
+                .iter()
+                .any(|key| *key == path || key.starts_with(&subkey_prefix))
+        })
+        .map(|entry| entry.path.clone())
+        .collect()
+}
+
 /// 解码 reg.exe 导出的 .reg 文件内容
 ///
 /// reg.exe 输出编码随系统语言变化：
 /// - 中日韩 Windows → UTF-16 LE + BOM (0xFF 0xFE)
 /// - 英文/其他 Windows → UTF-8 (有/无 BOM)
-/// 按前导 BOM 判断编码，无 BOM 则按 UTF-8/lossy 兜底
+///   按前导 BOM 判断编码，无 BOM 则按 UTF-8/lossy 兜底
 fn decode_reg_export(raw: &[u8]) -> Result<String, String> {
     if raw.len() >= 2 && raw[0] == 0xFF && raw[1] == 0xFE {
         // UTF-16 LE with BOM — 中文/日文/韩文 Windows 的默认输出
@@ -365,6 +647,19 @@ fn decode_reg_export(raw: &[u8]) -> Result<String, String> {
 // 测试
 // ============================================================================
 
+#[cfg(test)]
+impl RegistryEntry {
+    /// 测试用条目：子键名取路径最后一段，关联路径和问题描述为空
+    pub(crate) fn test_entry(path: &str) -> Self {
+        RegistryEntry {
+            path: path.to_string(),
+            name: path.rsplit('\\').next().unwrap_or_default().to_string(),
+            associated_path: String::new(),
+            issue: String::new(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -375,6 +670,27 @@ mod tests {
         assert!(scanner.path_cache.stats().0 == 0);
     }
 
+    #[test]
+    fn test_software_key_entries_with_injected_apps() {
+        let apps: HashSet<String> = ["Acme Editor".to_string()].into_iter().collect();
+        let scanner = RegistryScanner::new().with_installed_apps(apps);
+        let vendors = vec![SoftwareKeyNode::test_node(
+            "Contoso",
+            0,
+            vec![
+                SoftwareKeyNode::test_node("Acme Editor", 2, vec![]),
+                SoftwareKeyNode::test_node("OldPlugin", 1, vec![]),
+            ],
+        )];
+
+        let entries = scanner.software_key_entries(&vendors);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].path,
+            r"HKEY_CURRENT_USER\Software\Contoso\OldPlugin"
+        );
+    }
+
     #[test]
     fn test_to_reg_exe_format() {
         // HKCR\Applications\xxx → HKCR\Applications\xxx
@@ -383,6 +699,31 @@ mod tests {
         assert_eq!(result.unwrap(), r"HKCR\Applications\notepad.exe");
     }
 
+    #[test]
+    fn test_backup_verification_requires_value_data() {
+        let content = "Windows Registry Editor Version 5.00\n\n\
+            [HKEY_CLASSES_ROOT\\Applications\\old.exe]\n\n\
+            [HKEY_CLASSES_ROOT\\Applications\\old.exe\\shell\\open\\command]\n\
+            @=\"\\\"D:\\\\Old\\\\old.exe\\\" \\\"%1\\\"\"\n\n\
+            ; [回退] reg export 失败: 拒绝访问. 手动记录路径\n\
+            [HKEY_CURRENT_USER\\Software\\Gone]\n\
+            ; 此条目备份失败，请手动检查: 无匹配应用\n";
+        let entries = vec![
+            RegistryEntry::test_entry(r"HKEY_CLASSES_ROOT\Applications\Old.exe"),
+            RegistryEntry::test_entry(r"HKEY_CURRENT_USER\Software\Gone"),
+            RegistryEntry::test_entry(r"HKEY_CURRENT_USER\Software\Missing"),
+        ];
+
+        let missing = entries_without_backup_values(content, &entries);
+        assert_eq!(
+            missing,
+            vec![
+                r"HKEY_CURRENT_USER\Software\Gone",
+                r"HKEY_CURRENT_USER\Software\Missing"
+            ]
+        );
+    }
+
     #[test]
     fn test_is_definitely_safe_with_fake_path() {
         let mut cache = PathCache::new();
@@ -104,9 +104,9 @@ impl PathResolver {
         }
 
         // 处理带引号的路径: "C:\path\to\app.exe" args
-        if command.starts_with('"') {
-            if let Some(end) = command[1..].find('"') {
-                let path = &command[1..end + 1];
+        if let Some(quoted) = command.strip_prefix('"') {
+            if let Some(end) = quoted.find('"') {
+                let path = &quoted[..end];
                 if self.looks_like_exe_path(path) {
                     return Some(path.to_string());
                 }
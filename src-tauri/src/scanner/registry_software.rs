@@ -0,0 +1,301 @@
+// ============================================================================
+// HKCU\Software 软件配置键扫描
+//
+// 软件通常按 "厂商\产品" 两级结构写入 HKCU\Software（如 Tencent\WeChat）。
+// 只看第一级会漏掉厂商下的已卸载产品，又会把厂商下仍在使用的产品整体标记。
+// 这里按两级结构判断：厂商和产品都无法匹配已安装应用、且键内确实有数据时才标记；
+// 只有空子键的"空壳"不标记。
+//
+// 判断逻辑只依赖内存中的键树和已安装应用名称集合，测试不需要读写真实注册表。
+// ============================================================================
+
+use std::collections::HashSet;
+
+#[cfg(windows)]
+use winreg::enums::*;
+#[cfg(windows)]
+use winreg::RegKey;
+
+/// 不参与判断的 HKCU\Software 一级键（系统和通用注册位置）
+const SOFTWARE_KEY_SKIP: &[&str] = &[
+    "microsoft",
+    "classes",
+    "policies",
+    "wow6432node",
+    "clients",
+    "registeredapplications",
+    "appdatalow",
+    "windows",
+    "odbc",
+];
+
+/// 键名规范化后参与匹配的最短长度，避免 "qt"、"7z" 这类短名误匹配
+const MIN_MATCH_LEN: usize = 3;
+
+/// 内存中的注册表键树节点
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SoftwareKeyNode {
+    /// 键名（原始大小写）
+    pub name: String,
+    /// 键下的值数量
+    pub value_count: usize,
+    /// 键下的子键数量（超出读取深度时 children 为空，但该计数仍有效）
+    pub subkey_count: usize,
+    /// 已读取的子键
+    pub children: Vec<SoftwareKeyNode>,
+}
+
+impl SoftwareKeyNode {
+    /// 键或其子键中是否有数据
+    ///
+    /// 超出读取深度的子键无法确认，按有数据处理。
+    fn has_content(&self) -> bool {
+        self.value_count > 0
+            || (self.children.is_empty() && self.subkey_count > 0)
+            || self.children.iter().any(SoftwareKeyNode::has_content)
+    }
+}
+
+/// 被标记的软件配置键
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct OrphanSoftwareKey {
+    /// 相对 HKCU\Software 的路径，如 "Vendor\Product"
+    pub relative_path: String,
+    /// 标记原因
+    pub issue: String,
+}
+
+/// 规范化名称：转小写，只保留字母和数字
+pub(crate) fn normalize_app_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// 键名是否匹配任一已安装应用（名称集合已规范化）
+///
+/// 允许前缀匹配：厂商键 "Tencent" 能匹配发布者 "Tencent Technology (Shenzhen)"。
+fn matches_installed(key_name: &str, installed: &HashSet<String>) -> bool {
+    let key = normalize_app_name(key_name);
+    if key.chars().count() < MIN_MATCH_LEN {
+        return true;
+    }
+    installed.iter().any(|name| {
+        name == &key
+            || (name.chars().count() >= MIN_MATCH_LEN
+                && (name.starts_with(&key) || key.starts_with(name.as_str())))
+    })
+}
+
+/// 在 HKCU\Software 的一级键中查找无主的软件配置键
+///
+/// `is_whitelisted` 用于排除内置和用户白名单中的键名。
+pub(crate) fn find_orphan_software_keys(
+    vendors: &[SoftwareKeyNode],
+    installed: &HashSet<String>,
+    is_whitelisted: impl Fn(&str) -> bool,
+) -> Vec<OrphanSoftwareKey> {
+    let mut orphans = Vec::new();
+
+    for vendor in vendors {
+        let vendor_lower = vendor.name.to_lowercase();
+        if SOFTWARE_KEY_SKIP.contains(&vendor_lower.as_str())
+            || is_whitelisted(&vendor.name)
+            || matches_installed(&vendor.name, installed)
+        {
+            continue;
+        }
+
+        // 单级结构：厂商键下直接存放配置值
+        if vendor.children.is_empty() {
+            if vendor.has_content() {
+                orphans.push(OrphanSoftwareKey {
+                    relative_path: vendor.name.clone(),
+                    issue: format!("软件配置键 {} 未匹配任何已安装应用", vendor.name),
+                });
+            }
+            continue;
+        }
+
+        for product in &vendor.children {
+            if is_whitelisted(&product.name)
+                || matches_installed(&product.name, installed)
+                || !product.has_content()
+            {
+                continue;
+            }
+            orphans.push(OrphanSoftwareKey {
+                relative_path: format!(r"{}\{}", vendor.name, product.name),
+                issue: format!(
+                    "厂商 {} 和产品 {} 均未匹配任何已安装应用",
+                    vendor.name, product.name
+                ),
+            });
+        }
+    }
+
+    orphans
+}
+
+/// 已安装应用的规范化名称集合（DisplayName、发布者、安装目录名）
+pub(crate) fn installed_app_names() -> HashSet<String> {
+    let mut names = HashSet::new();
+    for program in super::get_installed_programs() {
+        names.insert(normalize_app_name(&program.display_name));
+        if let Some(publisher) = &program.publisher {
+            names.insert(normalize_app_name(publisher));
+        }
+        if let Some(folder) = program
+            .install_location
+            .as_deref()
+            .and_then(|location| std::path::Path::new(location.trim_matches('"')).file_name())
+        {
+            names.insert(normalize_app_name(&folder.to_string_lossy()));
+        }
+    }
+    names.remove("");
+    names
+}
+
+/// 读取 HKCU\Software 的键树（最多 `max_depth` 级，至少读取厂商和产品两级）
+///
+/// 每读完一个厂商键调用一次 `on_vendor(厂商键名, 已读取的厂商数)`。
+#[cfg(windows)]
+pub(crate) fn read_software_tree(
+    max_depth: usize,
+    mut on_vendor: impl FnMut(&str, usize),
+) -> Vec<SoftwareKeyNode> {
+    let max_depth = max_depth.max(2);
+    match RegKey::predef(HKEY_CURRENT_USER).open_subkey_with_flags("Software", KEY_READ) {
+        Ok(software) => software
+            .enum_keys()
+            .filter_map(|name| name.ok())
+            .take_while(|_| !super::is_registry_scan_cancelled())
+            .enumerate()
+            .filter_map(|(index, name)| {
+                let node = read_node(&software, name, max_depth);
+                if let Some(node) = &node {
+                    on_vendor(&node.name, index + 1);
+                }
+                node
+            })
+            .collect(),
+        Err(e) => {
+            log::warn!("无法打开 HKCU\\Software: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// 非 Windows 平台没有注册表
+#[cfg(not(windows))]
+pub(crate) fn read_software_tree(
+    _max_depth: usize,
+    _on_vendor: impl FnMut(&str, usize),
+) -> Vec<SoftwareKeyNode> {
+    Vec::new()
+}
+
+#[cfg(windows)]
+fn read_children(key: &RegKey, depth_left: usize) -> Vec<SoftwareKeyNode> {
+    key.enum_keys()
+        .filter_map(|name| name.ok())
+        .filter_map(|name| read_node(key, name, depth_left))
+        .collect()
+}
+
+#[cfg(windows)]
+fn read_node(parent: &RegKey, name: String, depth_left: usize) -> Option<SoftwareKeyNode> {
+    let subkey = parent.open_subkey_with_flags(&name, KEY_READ).ok()?;
+    let info = subkey.query_info().ok()?;
+    let children = if depth_left > 1 {
+        read_children(&subkey, depth_left - 1)
+    } else {
+        Vec::new()
+    };
+    Some(SoftwareKeyNode {
+        name,
+        value_count: info.values as usize,
+        subkey_count: info.sub_keys as usize,
+        children,
+    })
+}
+
+#[cfg(test)]
+impl SoftwareKeyNode {
+    /// 测试用节点，子键数量按已读取的子键计算
+    pub(crate) fn test_node(
+        name: &str,
+        value_count: usize,
+        children: Vec<SoftwareKeyNode>,
+    ) -> Self {
+        SoftwareKeyNode {
+            name: name.to_string(),
+            value_count,
+            subkey_count: children.len(),
+            children,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_orphan_software_keys_two_level() {
+        let installed: HashSet<String> = ["Tencent Technology (Shenzhen)", "Visual Studio Code"]
+            .iter()
+            .map(|name| normalize_app_name(name))
+            .collect();
+        let vendors = vec![
+            // 厂商匹配已安装应用 → 整个厂商跳过
+            SoftwareKeyNode::test_node(
+                "Tencent",
+                0,
+                vec![SoftwareKeyNode::test_node("OldTool", 3, vec![])],
+            ),
+            // 厂商未匹配，但产品匹配 → 只标记未匹配的产品
+            SoftwareKeyNode::test_node(
+                "SomeVendor",
+                0,
+                vec![
+                    SoftwareKeyNode::test_node("Visual Studio Code", 2, vec![]),
+                    SoftwareKeyNode::test_node("RemovedApp", 1, vec![]),
+                    // 只有空子键的空壳不标记
+                    SoftwareKeyNode::test_node(
+                        "EmptyShell",
+                        0,
+                        vec![SoftwareKeyNode::test_node("Settings", 0, vec![])],
+                    ),
+                ],
+            ),
+            // 单级结构：直接存放配置值
+            SoftwareKeyNode::test_node("UninstalledSingle", 4, vec![]),
+            SoftwareKeyNode::test_node(
+                "Microsoft",
+                10,
+                vec![SoftwareKeyNode::test_node("Office", 1, vec![])],
+            ),
+            SoftwareKeyNode::test_node("Protected", 1, vec![]),
+        ];
+
+        let orphans = find_orphan_software_keys(&vendors, &installed, |name| name == "Protected");
+        let paths: Vec<&str> = orphans.iter().map(|o| o.relative_path.as_str()).collect();
+        assert_eq!(paths, vec![r"SomeVendor\RemovedApp", "UninstalledSingle"]);
+    }
+
+    #[test]
+    fn test_has_content_beyond_read_depth() {
+        // 超出读取深度的子键按有数据处理
+        let truncated = SoftwareKeyNode {
+            name: "Deep".to_string(),
+            value_count: 0,
+            subkey_count: 2,
+            children: Vec::new(),
+        };
+        assert!(truncated.has_content());
+        assert!(!SoftwareKeyNode::test_node("Empty", 0, vec![]).has_content());
+    }
+}
@@ -0,0 +1,85 @@
+// ============================================================================
+// 统一取消扫描
+//
+// 各扫描模块有各自的取消标志（大文件、垃圾、深度扫描、社交软件、卸载残留、
+// 注册表、大目录）。界面只有一个"停止"按钮，不应关心当前跑的是哪一种扫描：
+// 扫描命令开始时登记自己的取消函数，结束时（守卫析构）注销，
+// `cancel_all_scans` 对所有登记中的扫描调用取消函数。
+// ============================================================================
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// 登记中的扫描
+struct ActiveScan {
+    id: u64,
+    name: &'static str,
+    cancel: fn(),
+}
+
+static ACTIVE_SCANS: Mutex<Vec<ActiveScan>> = Mutex::new(Vec::new());
+static NEXT_SCAN_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 扫描登记守卫，析构时注销
+pub struct ActiveScanGuard {
+    id: u64,
+}
+
+impl Drop for ActiveScanGuard {
+    fn drop(&mut self) {
+        let mut scans = ACTIVE_SCANS.lock().unwrap_or_else(|e| e.into_inner());
+        scans.retain(|scan| scan.id != self.id);
+    }
+}
+
+/// 登记正在运行的扫描，`cancel` 为该扫描模块的取消函数
+pub fn register_active_scan(name: &'static str, cancel: fn()) -> ActiveScanGuard {
+    let id = NEXT_SCAN_ID.fetch_add(1, Ordering::SeqCst);
+    let mut scans = ACTIVE_SCANS.lock().unwrap_or_else(|e| e.into_inner());
+    scans.push(ActiveScan { id, name, cancel });
+    ActiveScanGuard { id }
+}
+
+/// 取消所有登记中的扫描，返回被取消的扫描名称
+pub fn cancel_all_scans() -> Vec<String> {
+    let scans = ACTIVE_SCANS.lock().unwrap_or_else(|e| e.into_inner());
+    let mut cancelled: Vec<String> = Vec::new();
+    for scan in scans.iter() {
+        (scan.cancel)();
+        if !cancelled.iter().any(|name| name == scan.name) {
+            cancelled.push(scan.name.to_string());
+        }
+    }
+    log::info!("统一取消扫描: {:?}", cancelled);
+    cancelled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    static TEST_CANCEL_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn test_cancel() {
+        TEST_CANCEL_CALLS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn test_cancel_all_scans_only_reaches_registered_scans() {
+        let first = register_active_scan("test_scan", test_cancel);
+        let second = register_active_scan("test_scan", test_cancel);
+        let cancelled = cancel_all_scans();
+        assert_eq!(
+            cancelled.iter().filter(|name| *name == "test_scan").count(),
+            1
+        );
+        assert_eq!(TEST_CANCEL_CALLS.load(Ordering::SeqCst), 2);
+
+        drop(first);
+        drop(second);
+        let cancelled = cancel_all_scans();
+        assert!(!cancelled.iter().any(|name| name == "test_scan"));
+        assert_eq!(TEST_CANCEL_CALLS.load(Ordering::SeqCst), 2);
+    }
+}
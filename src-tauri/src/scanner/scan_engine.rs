@@ -3,24 +3,64 @@
 // 使用并行扫描优化性能
 // ============================================================================
 
-use log::{debug, info};
-use std::collections::HashSet;
+use log::{debug, info, warn};
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use walkdir::WalkDir;
 
-use super::{CategoryScanResult, FileInfo, JunkCategory, ScanResult};
-use crate::cleaner::safety_constants::is_rebuildable_system_cache_path;
+use super::low_priority::{BackgroundPriorityGuard, ScanThrottle};
+use super::{
+    is_msi_install_in_progress, CategoryScanResult, FileInfo, JunkCategory, ScanEstimate,
+    ScanResult,
+};
+use crate::cleaner::safety_constants::{
+    is_cloud_placeholder, is_rebuildable_system_cache_path, is_reparse_point_dir,
+};
+
+/// 垃圾扫描取消标志，只对启用 `with_cancellable` 的扫描生效
+static JUNK_SCAN_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// 重置取消标志（扫描开始前调用）
+pub fn reset_junk_scan_cancelled() {
+    JUNK_SCAN_CANCELLED.store(false, Ordering::SeqCst);
+}
+
+/// 设置取消标志（前端点击取消按钮时调用）
+pub fn cancel_junk_scan() {
+    info!("收到取消垃圾扫描请求");
+    JUNK_SCAN_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+/// 查询取消标志，命令层据此区分取消和正常完成
+pub fn is_junk_scan_cancelled() -> bool {
+    JUNK_SCAN_CANCELLED.load(Ordering::SeqCst)
+}
 
 /// 扫描引擎
 pub struct ScanEngine {
     /// 要扫描的分类列表
-    categories: Vec<JunkCategory>,
+    pub(super) categories: Vec<JunkCategory>,
     /// 最大扫描深度
     max_depth: usize,
+    /// 小于该大小的文件不列入结果，只计入分类的小文件统计（0 表示不过滤）
+    min_file_size: u64,
+    /// 按分类覆盖扫描根目录（用于测试，未覆盖的分类仍使用系统路径）
+    path_overrides: HashMap<JunkCategory, Vec<PathBuf>>,
+    /// 单个分类最多统计的文件数，超过后停止该分类并标记为截断
+    max_files: Option<usize>,
+    /// 单个分类最长扫描时间，超过后停止该分类并标记为截断
+    max_duration: Option<Duration>,
+    /// 扫描指定用户配置文件目录（为空时按当前进程的环境变量解析）
+    user_profile: Option<PathBuf>,
+    /// 低优先级模式：扫描线程进入后台模式，遍历时分批休眠
+    low_priority: bool,
+    /// 是否响应 `cancel_junk_scan`；删除前的重新扫描不可被残留的取消标志截断
+    cancellable: bool,
 }
 
 impl ScanEngine {
@@ -29,6 +69,13 @@ impl ScanEngine {
         ScanEngine {
             categories: JunkCategory::all(),
             max_depth: 10,
+            min_file_size: 0,
+            path_overrides: HashMap::new(),
+            max_files: None,
+            max_duration: None,
+            user_profile: None,
+            low_priority: false,
+            cancellable: false,
         }
     }
 
@@ -44,11 +91,71 @@ impl ScanEngine {
         self
     }
 
+    /// 设置列入结果的最小文件大小
+    ///
+    /// 浏览器等缓存中有大量不足 1KB 的小文件，全部列出会拖慢界面和 IPC。
+    /// 过滤掉的文件仍计入分类总大小，数量记在 `small_file_count`。
+    pub fn with_min_file_size(mut self, min_file_size: u64) -> Self {
+        self.min_file_size = min_file_size;
+        self
+    }
+
+    /// 覆盖指定分类的扫描根目录
+    ///
+    /// `get_scan_paths` 解析的是真实系统路径，测试无法控制其中的文件。
+    /// 覆盖后该分类只扫描给定目录，文件模式和保护规则不变。
+    pub fn with_path_overrides(mut self, overrides: HashMap<JunkCategory, Vec<PathBuf>>) -> Self {
+        self.path_overrides = overrides;
+        self
+    }
+
+    /// 设置单个分类最多统计的文件数
+    ///
+    /// 误配置的扫描根或重解析点循环可能让遍历停不下来；超出后该分类的
+    /// `CategoryScanResult.truncated` 为 true，界面据此提示结果不完整。
+    pub fn with_max_files(mut self, max_files: usize) -> Self {
+        self.max_files = Some(max_files);
+        self
+    }
+
+    /// 设置单个分类的最长扫描时间，超出后同样标记为截断
+    pub fn with_max_duration(mut self, max_duration: Duration) -> Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// 扫描指定用户的配置文件目录
+    ///
+    /// 只解析用户级路径（临时目录、AppData 下的缓存），系统级路径不扫描，
+    /// 供管理员在多用户电脑上逐个用户清理。
+    pub fn with_user_profile(mut self, profile_dir: PathBuf) -> Self {
+        self.user_profile = Some(profile_dir);
+        self
+    }
+
+    /// 响应 `cancel_junk_scan`，只用于用户可停止的扫描
+    pub fn with_cancellable(mut self, cancellable: bool) -> Self {
+        self.cancellable = cancellable;
+        self
+    }
+
+    /// 启用低优先级模式，后台扫描时不拖慢前台程序，代价是扫描变慢
+    pub fn with_low_priority(mut self, low_priority: bool) -> Self {
+        self.low_priority = low_priority;
+        self
+    }
+
     /// 执行完整扫描（并行扫描所有分类）
     pub fn scan(&self) -> ScanResult {
         let start_time = Instant::now();
         let categories = self.categories.clone();
         let max_depth = self.max_depth;
+        let min_file_size = self.min_file_size;
+        let max_files = self.max_files;
+        let max_duration = self.max_duration;
+        let user_profile = self.user_profile.clone();
+        let low_priority = self.low_priority;
+        let cancellable = self.cancellable;
 
         info!("开始并行扫描，共 {} 个分类", categories.len());
 
@@ -58,10 +165,23 @@ impl ScanEngine {
 
         for category in categories {
             let results_clone = Arc::clone(&results);
+            let user_profile = user_profile.clone();
+            let path_overrides: HashMap<JunkCategory, Vec<PathBuf>> = self
+                .path_overrides
+                .get(&category)
+                .map(|paths| HashMap::from([(category.clone(), paths.clone())]))
+                .unwrap_or_default();
             let handle = thread::spawn(move || {
                 let engine = ScanEngine {
                     categories: vec![category.clone()],
                     max_depth,
+                    min_file_size,
+                    path_overrides,
+                    max_files,
+                    max_duration,
+                    user_profile,
+                    low_priority,
+                    cancellable,
                 };
                 let category_result = engine.scan_category(&category);
 
@@ -89,6 +209,7 @@ impl ScanEngine {
         for category_result in category_results.iter() {
             result.add_category_result(category_result.clone());
         }
+        result.sort_categories();
 
         let duration = start_time.elapsed();
         result.set_duration(duration.as_millis() as u64);
@@ -106,6 +227,7 @@ impl ScanEngine {
     /// 扫描单个分类
     pub fn scan_category(&self, category: &JunkCategory) -> CategoryScanResult {
         let mut result = CategoryScanResult::new(category.clone());
+        let _priority = BackgroundPriorityGuard::enter(self.low_priority);
 
         // 回收站的物理目录包含多用户 SID 和 Shell 元数据，必须使用专用扫描器保持与 Explorer 一致。
         if matches!(category, JunkCategory::RecycleBin) {
@@ -113,35 +235,172 @@ impl ScanEngine {
             return result;
         }
 
-        let scan_paths = category.get_scan_paths();
+        // 安装进行中的 Config.Msi 是正在使用的回滚数据，列出来也不能删，安装结束后再扫描
+        if matches!(category, JunkCategory::InstallerRollback) && is_msi_install_in_progress() {
+            info!("Windows Installer 正在运行，跳过回滚数据扫描");
+            result.description =
+                "Windows Installer 正在安装软件，回滚数据仍在使用，请安装结束后重新扫描"
+                    .to_string();
+            return result;
+        }
+
         let patterns = category.get_file_patterns();
+        let resolved_list = self.resolve_category_paths(category);
+        let started = Instant::now();
 
+        for resolved_path in &resolved_list {
+            // 权限不足时 WalkDir 只会静默跳过根目录，这里单独记录，前端可提示以管理员身份重新扫描
+            if let Err(e) = fs::read_dir(resolved_path) {
+                if e.kind() == std::io::ErrorKind::PermissionDenied {
+                    warn!("没有权限读取扫描路径: {:?}", resolved_path);
+                    result
+                        .access_denied_paths
+                        .push(resolved_path.to_string_lossy().into_owned());
+                    continue;
+                }
+            }
+            debug!("扫描路径: {:?}", resolved_path);
+            self.scan_path(resolved_path, category, &patterns, started, &mut result);
+            if result.truncated || self.is_cancelled() {
+                break;
+            }
+        }
+
+        result
+    }
+
+    /// 扫描用户指定的路径列表，结果归入 Custom 分类
+    ///
+    /// 扫描根目录本身也要经过系统保护过滤：walkdir 的 filter_entry 不会拦截
+    /// 直接传入的单个文件，必须在这里先行排除。
+    pub fn scan_custom_paths(
+        &self,
+        paths: &[std::path::PathBuf],
+        patterns: &[&str],
+    ) -> CategoryScanResult {
+        let category = JunkCategory::Custom;
+        let mut result = CategoryScanResult::new(category.clone());
+        let mut unique_paths: HashSet<std::path::PathBuf> = HashSet::new();
+        let started = Instant::now();
+
+        for path in paths {
+            if result.truncated {
+                break;
+            }
+            if self.is_system_protected(path) || self.is_persistent_app_profile_path(path) {
+                debug!("自定义路径属于系统保护范围，跳过: {:?}", path);
+                continue;
+            }
+            let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+            if !unique_paths.insert(canonical) {
+                debug!("跳过重复路径: {:?}", path);
+                continue;
+            }
+            self.scan_path(path, &category, patterns, started, &mut result);
+        }
+
+        result
+    }
+
+    /// 查找路径所属的垃圾分类
+    ///
+    /// 与扫描使用同一套根目录、文件模式和保护规则：路径必须位于某个分类的扫描根目录之下、
+    /// 文件名匹配该分类的模式，且不属于系统保护或应用持久化数据。不检查扫描深度。
+    pub fn category_for_path(&self, path: &Path) -> Option<JunkCategory> {
+        if self.is_system_protected(path) || self.is_persistent_app_profile_path(path) {
+            return None;
+        }
+        let target = comparable_path(path);
+        self.categories
+            .iter()
+            .filter(|category| **category != JunkCategory::Custom)
+            .find(|category| {
+                let under_root = self.resolve_category_paths(category).iter().any(|root| {
+                    let root = comparable_path(root);
+                    target.len() > root.len()
+                        && target.starts_with(&root)
+                        && target.as_bytes()[root.len()] == b'\\'
+                });
+                under_root
+                    && self.matches_category_file(path, category, &category.get_file_patterns())
+            })
+            .cloned()
+    }
+
+    /// 解析分类的全部扫描根目录并去重
+    pub(super) fn resolve_category_paths(
+        &self,
+        category: &JunkCategory,
+    ) -> Vec<std::path::PathBuf> {
         // 收集所有解析后的路径，去重后再扫描
         // 例如 %TEMP% 和 %TMP% 可能指向同一个目录，避免重复扫描
         let mut unique_paths: HashSet<std::path::PathBuf> = HashSet::new();
         let mut resolved_list: Vec<std::path::PathBuf> = Vec::new();
 
-        for scan_path in scan_paths {
-            for resolved_path in scan_path.resolve_all() {
-                // 尝试规范化路径以消除符号链接、大小写等差异
-                let canonical = match std::fs::canonicalize(&resolved_path) {
-                    Ok(p) => p,
-                    Err(_) => resolved_path.clone(), // 规范化失败则使用原路径的克隆
-                };
-                if unique_paths.insert(canonical.clone()) {
-                    resolved_list.push(canonical);
-                } else {
-                    debug!("跳过重复路径: {:?}", resolved_path);
-                }
+        let roots: Vec<PathBuf> = match (self.path_overrides.get(category), &self.user_profile) {
+            (Some(paths), _) => paths.clone(),
+            (None, Some(profile_dir)) => category
+                .get_scan_paths()
+                .iter()
+                .flat_map(|scan_path| scan_path.resolve_all_for_profile(profile_dir))
+                .collect(),
+            (None, None) => category
+                .get_scan_paths()
+                .iter()
+                .flat_map(|scan_path| scan_path.resolve_all())
+                .collect(),
+        };
+
+        for resolved_path in roots {
+            // 尝试规范化路径以消除符号链接、大小写等差异
+            let canonical = match std::fs::canonicalize(&resolved_path) {
+                Ok(p) => p,
+                Err(_) => resolved_path.clone(), // 规范化失败则使用原路径的克隆
+            };
+            if unique_paths.insert(canonical.clone()) {
+                resolved_list.push(canonical);
+            } else {
+                debug!("跳过重复路径: {:?}", resolved_path);
             }
         }
 
-        for resolved_path in &resolved_list {
-            debug!("扫描路径: {:?}", resolved_path);
-            self.scan_path(resolved_path, category, &patterns, &mut result);
+        resolved_list
+    }
+
+    /// 估算扫描范围
+    ///
+    /// 只对每个扫描根目录做一层 read_dir，统计目录数量，不做递归遍历，
+    /// 供前端在正式扫描前决定使用确定或不确定进度条。
+    pub fn estimate_scope(&self) -> ScanEstimate {
+        let mut estimate = ScanEstimate {
+            category_count: self.categories.len(),
+            ..Default::default()
+        };
+
+        for category in &self.categories {
+            if category.is_slow_to_scan() {
+                estimate.has_slow_categories = true;
+                estimate
+                    .slow_categories
+                    .push(category.display_name().to_string());
+            }
+
+            for root in self.resolve_category_paths(category) {
+                if !root.is_dir() {
+                    continue;
+                }
+                // 根目录本身也计入，子目录只看第一层，保证估算足够廉价
+                estimate.directory_count += 1;
+                if let Ok(entries) = fs::read_dir(&root) {
+                    estimate.directory_count += entries
+                        .filter_map(|entry| entry.ok())
+                        .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                        .count();
+                }
+            }
         }
 
-        result
+        estimate
     }
 
     /// 扫描指定路径
@@ -150,6 +409,7 @@ impl ScanEngine {
         path: &Path,
         category: &JunkCategory,
         patterns: &[&str],
+        started: Instant,
         result: &mut CategoryScanResult,
     ) {
         // 检查路径是否存在
@@ -161,7 +421,7 @@ impl ScanEngine {
         // 如果是文件，直接处理
         if path.is_file() {
             if let Some(file_info) = self.get_file_info(path, category) {
-                result.add_file(file_info);
+                self.add_file_info(file_info, result);
             }
             return;
         }
@@ -172,11 +432,18 @@ impl ScanEngine {
             .follow_links(false)
             .into_iter()
             .filter_entry(|e| {
-                !self.is_system_protected(e.path())
+                // 扫描根以下的重解析点目录不进入，避免经 Junction 把系统目录列为可删除项
+                (e.depth() == 0 || !is_reparse_point_dir(e))
+                    && !self.is_system_protected(e.path())
                     && !self.is_persistent_app_profile_path(e.path())
             });
 
+        let mut throttle = ScanThrottle::new(self.low_priority);
         for entry in walker.filter_map(|e| e.ok()) {
+            if self.exceeds_limits(started, result) || self.is_cancelled() {
+                return;
+            }
+            throttle.tick();
             let entry_path = entry.path();
 
             // 跳过根目录本身
@@ -190,7 +457,7 @@ impl ScanEngine {
             }
 
             // 检查是否匹配模式
-            if !self.matches_patterns(entry_path, patterns) {
+            if !self.matches_category_file(entry_path, category, patterns) {
                 continue;
             }
 
@@ -201,11 +468,48 @@ impl ScanEngine {
 
             // 获取文件信息
             if let Some(file_info) = self.get_file_info(entry_path, category) {
-                result.add_file(file_info);
+                self.add_file_info(file_info, result);
             }
         }
     }
 
+    /// 检查分类是否超出文件数或耗时上限，超出时标记为截断
+    ///
+    /// 耗时在每个遍历条目上检查，目录中没有匹配文件时也能按时停止。
+    fn exceeds_limits(&self, started: Instant, result: &mut CategoryScanResult) -> bool {
+        if result.truncated {
+            return true;
+        }
+        let counted = result.file_count + result.small_file_count;
+        let too_many_files = self.max_files.is_some_and(|max| counted >= max);
+        let too_slow = self
+            .max_duration
+            .is_some_and(|max| started.elapsed() >= max);
+        if too_many_files || too_slow {
+            warn!(
+                "分类 {} 超出扫描上限（{} 个文件，{}ms），结果不完整",
+                result.display_name,
+                counted,
+                started.elapsed().as_millis()
+            );
+            result.truncated = true;
+        }
+        result.truncated
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancellable && is_junk_scan_cancelled()
+    }
+
+    /// 将文件加入结果，低于最小大小的文件只计入小文件统计
+    fn add_file_info(&self, file_info: FileInfo, result: &mut CategoryScanResult) {
+        if file_info.size < self.min_file_size {
+            result.add_small_file(file_info.size);
+        } else {
+            result.add_file(file_info);
+        }
+    }
+
     /// 获取文件信息（仅处理文件，目录已在 scan_path 中跳过）
     fn get_file_info(&self, path: &Path, category: &JunkCategory) -> Option<FileInfo> {
         let metadata = match fs::metadata(path) {
@@ -215,6 +519,11 @@ impl ScanEngine {
                 return None;
             }
         };
+        // 仅云端的占位文件不占本地空间，删除还会同步删掉云端副本
+        if is_cloud_placeholder(&metadata) {
+            debug!("跳过云端占位文件 {:?}", path);
+            return None;
+        }
 
         let name = path
             .file_name()
@@ -240,6 +549,23 @@ impl ScanEngine {
         ))
     }
 
+    /// 检查文件是否属于分类：文件名匹配模式，Office 的 ~$ 所有者文件还必须紧挨着对应文档
+    fn matches_category_file(
+        &self,
+        path: &Path,
+        category: &JunkCategory,
+        patterns: &[&str],
+    ) -> bool {
+        if !self.matches_patterns(path, patterns) {
+            return false;
+        }
+        let is_owner_file = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with("~$"));
+        !(*category == JunkCategory::OfficeCache && is_owner_file)
+            || has_office_owner_document(path)
+    }
+
     /// 检查文件名是否匹配模式
     fn matches_patterns(&self, path: &Path, patterns: &[&str]) -> bool {
         // 如果模式包含 "*"，匹配所有文件
@@ -384,6 +710,42 @@ impl ScanEngine {
 unsafe impl Send for ScanEngine {}
 unsafe impl Sync for ScanEngine {}
 
+/// 判断 Office 所有者文件（~$ 开头）旁边是否存在它所代表的文档
+///
+/// Office 打开文档时在同一目录创建所有者文件：文件名较长时用 "~$" 替换前两个字符，
+/// 较短时直接加上 "~$" 前缀。两种形式都按去掉 "~$" 后的后缀比对。
+fn has_office_owner_document(owner_path: &Path) -> bool {
+    let Some(owner_name) = owner_path.file_name() else {
+        return false;
+    };
+    let owner_name = owner_name.to_string_lossy().to_lowercase();
+    let Some(suffix) = owner_name.strip_prefix("~$") else {
+        return false;
+    };
+    let Some(dir) = owner_path.parent() else {
+        return false;
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    let suffix_len = suffix.chars().count();
+
+    entries.filter_map(|entry| entry.ok()).any(|entry| {
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+        let name_len = name.chars().count();
+        !name.starts_with("~$")
+            && name.ends_with(suffix)
+            && (name_len == suffix_len || name_len == suffix_len + 2)
+    })
+}
+
+/// 统一为小写、反斜杠、无扩展路径前缀和末尾分隔符的形式，用于前缀比较
+fn comparable_path(path: &Path) -> String {
+    let text = path.to_string_lossy().replace('/', "\\").to_lowercase();
+    let text = text.strip_prefix(r"\\?\").unwrap_or(&text);
+    text.trim_end_matches('\\').to_string()
+}
+
 impl Default for ScanEngine {
     fn default() -> Self {
         Self::new()
@@ -404,6 +766,177 @@ mod tests {
         assert!(engine.matches_glob("thumbcache_256.db", "thumbcache_*.db"));
     }
 
+    #[test]
+    fn test_scan_custom_paths_applies_patterns() {
+        let root = std::env::temp_dir().join(format!("lightc-custom-scan-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("a.log"), b"12").unwrap();
+        fs::write(root.join("sub").join("b.log"), b"123").unwrap();
+        fs::write(root.join("keep.txt"), b"1").unwrap();
+
+        let engine = ScanEngine::new();
+        // 同一路径重复传入只统计一次
+        let result = engine.scan_custom_paths(&[root.clone(), root.clone()], &["*.log"]);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(result.category, JunkCategory::Custom);
+        assert_eq!(result.file_count, 2);
+        assert_eq!(result.total_size, 5);
+    }
+
+    #[test]
+    fn test_scan_with_path_overrides_counts_files() {
+        let root =
+            std::env::temp_dir().join(format!("lightc-override-scan-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let temp_root = root.join("Temp");
+        let log_root = root.join("Logs");
+        fs::create_dir_all(temp_root.join("nested").join("deeper")).unwrap();
+        fs::create_dir_all(&log_root).unwrap();
+        fs::write(temp_root.join("a.tmp"), vec![0u8; 100]).unwrap();
+        fs::write(temp_root.join("nested").join("b.tmp"), vec![0u8; 20]).unwrap();
+        fs::write(
+            temp_root.join("nested").join("deeper").join("c.tmp"),
+            vec![0u8; 3],
+        )
+        .unwrap();
+        fs::write(log_root.join("app.log"), vec![0u8; 7]).unwrap();
+        fs::write(log_root.join("keep.txt"), vec![0u8; 50]).unwrap();
+
+        let engine = ScanEngine::new()
+            .with_categories(vec![JunkCategory::WindowsTemp, JunkCategory::LogFiles])
+            .with_path_overrides(HashMap::from([
+                (JunkCategory::WindowsTemp, vec![temp_root.clone()]),
+                (JunkCategory::LogFiles, vec![log_root.clone()]),
+            ]));
+        let result = engine.scan();
+        // 深度限制同样作用于覆盖后的根目录
+        let shallow = engine.with_max_depth(2).scan();
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(result.total_file_count, 4);
+        assert_eq!(result.total_size, 130);
+        let temp = result
+            .categories
+            .iter()
+            .find(|c| c.category == JunkCategory::WindowsTemp)
+            .unwrap();
+        assert_eq!(temp.file_count, 3);
+        assert_eq!(temp.total_size, 123);
+        let logs = result
+            .categories
+            .iter()
+            .find(|c| c.category == JunkCategory::LogFiles)
+            .unwrap();
+        assert_eq!(logs.file_count, 1);
+        assert_eq!(logs.total_size, 7);
+        assert_eq!(shallow.total_file_count, 3);
+        assert_eq!(shallow.total_size, 127);
+    }
+
+    #[test]
+    fn test_office_cache_keeps_real_documents() {
+        let root = std::env::temp_dir().join(format!("lightc-office-scan-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        for name in [
+            "~$port.docx",
+            "~$orphan.docx",
+            "~WRL0001.tmp",
+            "draft.asd",
+            "backup of report.wbk",
+            "report.docx",
+            "Book1.xlsb",
+        ] {
+            fs::write(root.join(name), vec![0u8; 10]).unwrap();
+        }
+
+        let result = ScanEngine::new()
+            .with_categories(vec![JunkCategory::OfficeCache])
+            .with_path_overrides(HashMap::from([(
+                JunkCategory::OfficeCache,
+                vec![root.clone()],
+            )]))
+            .scan();
+        let _ = fs::remove_dir_all(&root);
+
+        let mut names: Vec<String> = result.categories[0]
+            .files
+            .iter()
+            .map(|file| file.name.clone())
+            .collect();
+        names.sort();
+        // 没有对应文档的 ~$ 文件和 .wbk 备份副本都不算临时文件
+        assert_eq!(names, vec!["draft.asd", "~$port.docx", "~WRL0001.tmp"]);
+    }
+
+    #[test]
+    fn test_max_files_marks_category_truncated() {
+        let root =
+            std::env::temp_dir().join(format!("lightc-max-files-scan-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        for i in 0..5 {
+            fs::write(root.join(format!("{}.log", i)), b"1").unwrap();
+        }
+
+        let limited = ScanEngine::new()
+            .with_max_files(3)
+            .scan_custom_paths(std::slice::from_ref(&root), &["*.log"]);
+        let unlimited =
+            ScanEngine::new().scan_custom_paths(std::slice::from_ref(&root), &["*.log"]);
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(limited.truncated);
+        assert_eq!(limited.file_count, 3);
+        assert!(!unlimited.truncated);
+        assert_eq!(unlimited.file_count, 5);
+    }
+
+    #[test]
+    fn test_category_for_path_uses_roots_and_patterns() {
+        let root =
+            std::env::temp_dir().join(format!("lightc-category-path-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("sub")).unwrap();
+
+        let engine = ScanEngine::new()
+            .with_categories(vec![JunkCategory::LogFiles])
+            .with_path_overrides(HashMap::from([(
+                JunkCategory::LogFiles,
+                vec![root.clone()],
+            )]));
+        let log = engine.category_for_path(&root.join("sub").join("app.log"));
+        let text = engine.category_for_path(&root.join("notes.txt"));
+        let outside = engine.category_for_path(&std::env::temp_dir().join("other.log"));
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(log, Some(JunkCategory::LogFiles));
+        assert_eq!(text, None);
+        assert_eq!(outside, None);
+    }
+
+    #[test]
+    fn test_min_file_size_keeps_honest_total() {
+        let root =
+            std::env::temp_dir().join(format!("lightc-min-size-scan-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("small.log"), b"12").unwrap();
+        fs::write(root.join("large.log"), vec![0u8; 2048]).unwrap();
+
+        let engine = ScanEngine::new().with_min_file_size(1024);
+        let result = engine.scan_custom_paths(std::slice::from_ref(&root), &["*.log"]);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.file_count, 1);
+        assert_eq!(result.small_file_count, 1);
+        assert_eq!(result.small_file_size, 2);
+        assert_eq!(result.total_size, 2050);
+    }
+
     #[test]
     fn test_webview_profile_data_is_protected() {
         let engine = ScanEngine::new();
@@ -0,0 +1,340 @@
+// ============================================================================
+// 孤立计划任务检测
+//
+// 软件卸载后经常残留自动更新等计划任务，目标程序已不存在，
+// 每次开机或定时触发都会在事件日志里留下启动失败的记录。
+// 这里读取 C:\Windows\System32\Tasks 下的任务定义 XML，提取 <Exec><Command>，
+// 找出目标程序已不存在的第三方任务；Microsoft 自带任务一律不处理。
+// ============================================================================
+
+use std::path::{Path, PathBuf};
+
+use log::{debug, info, warn};
+use quick_xml::{events::Event, Reader};
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+/// 目标程序已不存在的计划任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanTask {
+    /// 任务名称（含文件夹，如 `\Vendor\UpdateTask`），用于 schtasks /tn
+    pub name: String,
+    /// 任务定义中的原始命令
+    pub command: String,
+    /// 展开环境变量后检查的程序路径
+    pub resolved_path: String,
+    /// 任务作者
+    pub author: Option<String>,
+    /// 任务定义文件路径
+    pub definition_path: String,
+}
+
+/// 从任务定义中提取的信息
+#[derive(Debug, Default, PartialEq)]
+struct TaskDefinition {
+    author: Option<String>,
+    commands: Vec<String>,
+}
+
+fn tasks_root() -> PathBuf {
+    let system_root = std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".to_string());
+    PathBuf::from(system_root).join("System32").join("Tasks")
+}
+
+/// 扫描目标程序已不存在的计划任务
+///
+/// # 中文说明
+/// 大部分任务定义需要管理员权限才能读取，读取失败的任务直接跳过。
+/// 只检查绝对路径命令：`cmd.exe`、`rundll32` 这类依赖 PATH 查找的命令无法可靠判断，不标记。
+pub fn scan_scheduled_tasks() -> Result<Vec<OrphanTask>, String> {
+    let root = tasks_root();
+    if !root.is_dir() {
+        return Err(format!("计划任务目录不存在: {}", root.display()));
+    }
+
+    let mut orphans = Vec::new();
+    for entry in WalkDir::new(&root)
+        .follow_links(false)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+    {
+        let path = entry.path();
+        let Some(name) = task_name(&root, path) else {
+            continue;
+        };
+        if is_microsoft_task_name(&name) {
+            continue;
+        }
+
+        let definition = match std::fs::read(path) {
+            Ok(raw) => parse_task_definition(&decode_task_xml(&raw)),
+            Err(e) => {
+                debug!("读取计划任务定义失败 {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        if is_microsoft_author(definition.author.as_deref()) {
+            continue;
+        }
+
+        // 任务有多个操作时，所有程序都能解析且都不存在才算孤立任务
+        let resolved: Option<Vec<PathBuf>> = definition
+            .commands
+            .iter()
+            .map(|command| resolve_command_path(command))
+            .collect();
+        let Some(resolved) = resolved.filter(|paths| !paths.is_empty()) else {
+            continue;
+        };
+        if resolved.iter().any(|target| target.exists()) {
+            continue;
+        }
+
+        orphans.push(OrphanTask {
+            name,
+            command: definition.commands[0].clone(),
+            resolved_path: resolved[0].to_string_lossy().into_owned(),
+            author: definition.author,
+            definition_path: path.to_string_lossy().into_owned(),
+        });
+    }
+
+    orphans.sort_by(|left, right| left.name.cmp(&right.name));
+    info!("计划任务扫描完成，发现 {} 个孤立任务", orphans.len());
+    Ok(orphans)
+}
+
+/// 使用 schtasks /delete 删除计划任务
+///
+/// 只允许删除任务目录中存在、且不是 Microsoft 的任务，避免前端传入任意名称。
+pub fn delete_scheduled_task(name: &str) -> Result<(), String> {
+    let name = name.trim();
+    let relative = name.trim_start_matches('\\');
+    if relative.is_empty()
+        || relative
+            .split('\\')
+            .any(|part| part == ".." || part.is_empty())
+    {
+        return Err(format!("计划任务名称无效: {}", name));
+    }
+    if is_microsoft_task_name(name) {
+        return Err("系统计划任务，禁止删除".to_string());
+    }
+
+    let definition_path = tasks_root().join(relative);
+    if !definition_path.is_file() {
+        return Err(format!("计划任务不存在: {}", name));
+    }
+    if let Ok(raw) = std::fs::read(&definition_path) {
+        let definition = parse_task_definition(&decode_task_xml(&raw));
+        if is_microsoft_author(definition.author.as_deref()) {
+            return Err("系统计划任务，禁止删除".to_string());
+        }
+    }
+
+    let task_name = format!("\\{}", relative);
+    let mut command = std::process::Command::new("schtasks");
+    command.args(["/delete", "/tn", &task_name, "/f"]);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    let output = crate::process_runner::run_with_timeout(
+        &mut command,
+        crate::process_runner::DEFAULT_COMMAND_TIMEOUT,
+    )?;
+    if output.status.success() {
+        info!("已删除计划任务: {}", task_name);
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        warn!("删除计划任务失败 {}: {}", task_name, stderr);
+        Err(format!("删除计划任务失败: {}", stderr))
+    }
+}
+
+/// 任务定义文件相对 Tasks 目录的路径即任务名称
+fn task_name(root: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(root).ok()?;
+    Some(format!(
+        "\\{}",
+        relative.to_string_lossy().replace('/', "\\")
+    ))
+}
+
+fn is_microsoft_task_name(name: &str) -> bool {
+    name.trim_start_matches('\\')
+        .to_ascii_lowercase()
+        .starts_with("microsoft\\")
+}
+
+/// Microsoft 任务的作者通常是 "Microsoft Corporation" 或引用系统 DLL 资源的 `$(@%SystemRoot%\...)`
+fn is_microsoft_author(author: Option<&str>) -> bool {
+    author
+        .map(|author| {
+            let author = author.to_ascii_lowercase();
+            author.contains("microsoft") || author.starts_with("$(@")
+        })
+        .unwrap_or(false)
+}
+
+/// 任务定义通常是带 BOM 的 UTF-16 LE，也兼容 UTF-8
+fn decode_task_xml(raw: &[u8]) -> String {
+    if let Some(body) = raw.strip_prefix(&[0xFF, 0xFE]) {
+        let units: Vec<u16> = body
+            .chunks_exact(2)
+            .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+    if let Some(body) = raw.strip_prefix(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = body
+            .chunks_exact(2)
+            .map(|chunk| u16::from_be_bytes([chunk[0], chunk[1]]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+    let body = raw.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(raw);
+    String::from_utf8_lossy(body).into_owned()
+}
+
+/// 提取 <RegistrationInfo><Author> 和所有 <Exec><Command>
+fn parse_task_definition(xml: &str) -> TaskDefinition {
+    let mut reader = Reader::from_str(xml);
+    let mut definition = TaskDefinition::default();
+    let mut current: Option<&'static str> = None;
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(event)) => {
+                current = match event.local_name().as_ref() {
+                    b"Author" => Some("Author"),
+                    b"Command" => Some("Command"),
+                    _ => None,
+                };
+                text.clear();
+            }
+            Ok(Event::Text(event)) if current.is_some() => {
+                if let Ok(decoded) = event.decode() {
+                    text.push_str(&decoded);
+                }
+            }
+            // 路径中的 &amp; 等实体引用会作为单独的事件出现
+            Ok(Event::GeneralRef(reference)) if current.is_some() => {
+                if let Ok(Some(ch)) = reference.resolve_char_ref() {
+                    text.push(ch);
+                } else if let Ok(name) = reference.decode() {
+                    if let Some(resolved) = quick_xml::escape::resolve_predefined_entity(&name) {
+                        text.push_str(resolved);
+                    }
+                }
+            }
+            Ok(Event::End(_)) => {
+                let value = text.trim().to_string();
+                match current.take() {
+                    Some("Author") if definition.author.is_none() && !value.is_empty() => {
+                        definition.author = Some(value)
+                    }
+                    Some("Command") if !value.is_empty() => definition.commands.push(value),
+                    _ => {}
+                }
+                text.clear();
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {}
+        }
+    }
+
+    definition
+}
+
+/// 展开命令中的环境变量并返回绝对路径；无法可靠判断的命令返回 None
+fn resolve_command_path(command: &str) -> Option<PathBuf> {
+    let expanded = expand_env_vars(command.trim().trim_matches('"'));
+    if expanded.contains('%') {
+        return None;
+    }
+    let path = PathBuf::from(expanded);
+    let is_absolute = path.is_absolute() || path.to_string_lossy().chars().nth(1) == Some(':');
+    is_absolute.then_some(path)
+}
+
+/// 展开 `%NAME%` 形式的环境变量，未定义的变量保持原样
+fn expand_env_vars(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find('%') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('%') else {
+            result.push_str(&rest[start..]);
+            return result;
+        };
+        let name = &after[..end];
+        match std::env::var(name) {
+            Ok(expanded) if !name.is_empty() => result.push_str(&expanded),
+            _ => {
+                result.push('%');
+                result.push_str(name);
+                result.push('%');
+            }
+        }
+        rest = &after[end + 1..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_task_definition() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-16"?>
+<Task version="1.2" xmlns="http://schemas.microsoft.com/windows/2004/02/mit/task">
+  <RegistrationInfo><Author>Vendor Inc.</Author></RegistrationInfo>
+  <Actions Context="Author">
+    <Exec>
+      <Command>"C:\Program Files\Vendor\updater.exe"</Command>
+      <Arguments>/silent</Arguments>
+    </Exec>
+  </Actions>
+</Task>"#;
+        let mut raw = vec![0xFF, 0xFE];
+        for unit in xml.encode_utf16() {
+            raw.extend_from_slice(&unit.to_le_bytes());
+        }
+
+        let definition = parse_task_definition(&decode_task_xml(&raw));
+        assert_eq!(definition.author.as_deref(), Some("Vendor Inc."));
+        assert_eq!(
+            definition.commands,
+            vec![r#""C:\Program Files\Vendor\updater.exe""#.to_string()]
+        );
+        assert_eq!(
+            resolve_command_path(&definition.commands[0]),
+            Some(PathBuf::from(r"C:\Program Files\Vendor\updater.exe"))
+        );
+        assert_eq!(resolve_command_path("rundll32.exe"), None);
+        assert_eq!(resolve_command_path("%LIGHTC_UNDEFINED_VAR%\\a.exe"), None);
+    }
+
+    #[test]
+    fn test_microsoft_tasks_are_excluded() {
+        assert!(is_microsoft_task_name(
+            r"\Microsoft\Windows\Defrag\ScheduledDefrag"
+        ));
+        assert!(!is_microsoft_task_name(r"\GoogleUpdateTaskMachineCore"));
+        assert!(is_microsoft_author(Some("Microsoft Corporation")));
+        assert!(is_microsoft_author(Some(
+            "$(@%SystemRoot%\\system32\\wininet.dll,-16001)"
+        )));
+        assert!(!is_microsoft_author(Some("Vendor Inc.")));
+        assert!(!is_microsoft_author(None));
+    }
+}
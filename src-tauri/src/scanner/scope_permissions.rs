@@ -0,0 +1,127 @@
+// ============================================================================
+// 扫描范围权限预检
+//
+// 大量删除失败的根源是当前进程对整棵目录树没有权限（其他用户的配置文件、
+// 系统级错误报告目录等）。扫描前逐个检查各分类扫描根目录的读取和写入权限，
+// 前端据此先提示以管理员身份运行，而不是清理后面对一长串失败。
+//
+// 只打开目录句柄检查访问权限，不创建探测文件，不修改任何内容。
+// ============================================================================
+
+use serde::Serialize;
+use std::path::Path;
+
+use super::{JunkCategory, ScanEngine};
+
+/// 单个扫描根目录的权限检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct ScopePermission {
+    /// 分类稳定标识（`JunkCategory::key()`）
+    pub category_key: String,
+    /// 分类显示名称
+    pub display_name: String,
+    /// 扫描根目录
+    pub path: String,
+    /// 能否列出目录内容
+    pub readable: bool,
+    /// 能否在目录中创建和删除文件
+    pub writable: bool,
+    /// 当前未以管理员身份运行且权限不足，提权后才能完整扫描和清理
+    pub needs_elevation: bool,
+}
+
+impl ScanEngine {
+    /// 检查各分类扫描根目录的访问权限，按分类顺序返回
+    ///
+    /// 回收站按盘符通过 Shell API 清空，不检查其物理目录。
+    pub fn check_scope_permissions(&self) -> Vec<ScopePermission> {
+        let elevated = crate::system_slim::check_admin();
+        let mut permissions = Vec::new();
+
+        for category in &self.categories {
+            if matches!(category, JunkCategory::RecycleBin | JunkCategory::Custom) {
+                continue;
+            }
+            for root in self.resolve_category_paths(category) {
+                if !root.is_dir() {
+                    continue;
+                }
+                let readable = can_open_dir(&root, false);
+                let writable = readable && can_open_dir(&root, true);
+                permissions.push(ScopePermission {
+                    category_key: category.key().to_string(),
+                    display_name: category.display_name().to_string(),
+                    path: root.to_string_lossy().into_owned(),
+                    readable,
+                    writable,
+                    needs_elevation: !elevated && !writable,
+                });
+            }
+        }
+
+        log::info!(
+            "扫描范围权限预检: {} 个目录，{} 个需要管理员权限",
+            permissions.len(),
+            permissions.iter().filter(|p| p.needs_elevation).count()
+        );
+        permissions
+    }
+}
+
+/// 以列目录（可选再加上添加文件）权限打开目录句柄，检查 ACL 是否允许
+#[cfg(windows)]
+fn can_open_dir(dir: &Path, write: bool) -> bool {
+    use std::os::windows::fs::OpenOptionsExt;
+
+    const FILE_LIST_DIRECTORY: u32 = 0x0001;
+    const FILE_ADD_FILE: u32 = 0x0002;
+    const FILE_FLAG_BACKUP_SEMANTICS: u32 = 0x0200_0000;
+    const FILE_SHARE_ALL: u32 = 0x1 | 0x2 | 0x4;
+
+    let access = if write {
+        FILE_LIST_DIRECTORY | FILE_ADD_FILE
+    } else {
+        FILE_LIST_DIRECTORY
+    };
+    std::fs::OpenOptions::new()
+        .access_mode(access)
+        .share_mode(FILE_SHARE_ALL)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS)
+        .open(dir)
+        .is_ok()
+}
+
+#[cfg(not(windows))]
+fn can_open_dir(dir: &Path, write: bool) -> bool {
+    if write {
+        std::fs::metadata(dir).is_ok_and(|metadata| !metadata.permissions().readonly())
+    } else {
+        std::fs::read_dir(dir).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_scope_permissions_reports_accessible_root() {
+        let root = std::env::temp_dir().join(format!("lightc-scope-{}", std::process::id()));
+        std::fs::create_dir_all(&root).unwrap();
+        let overrides: HashMap<JunkCategory, Vec<PathBuf>> =
+            HashMap::from([(JunkCategory::WindowsTemp, vec![root.clone()])]);
+
+        let permissions = ScanEngine::new()
+            .with_categories(vec![JunkCategory::WindowsTemp])
+            .with_path_overrides(overrides)
+            .check_scope_permissions();
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(permissions.len(), 1);
+        assert_eq!(permissions[0].category_key, "WindowsTemp");
+        assert!(permissions[0].readable && permissions[0].writable);
+        assert!(!permissions[0].needs_elevation);
+    }
+}
@@ -3,41 +3,62 @@
 //
 // 这里只处理 Explorer\MyComputer\NameSpace 下的 CLSID 节点。
 // 该范围比 DelegateFolders 更收敛，能降低把真实系统 Shell 扩展误判为“虚拟磁盘”的风险。
+//
+// 注册表和 ACL 操作只在 Windows 上编译，其他平台的公开函数直接返回不支持；
+// 备份文件名、CLSID 规范化、应用识别等纯字符串逻辑保留，便于在任意平台测试。
 // ============================================================================
 
+#![cfg_attr(not(windows), allow(dead_code))]
+
 use chrono::Local;
 use serde::{Deserialize, Serialize};
+#[cfg(windows)]
 use std::collections::HashMap;
+#[cfg(windows)]
 use std::ffi::{c_void, OsString};
 use std::fs;
 use std::io::Write;
+#[cfg(windows)]
 use std::os::windows::ffi::OsStringExt;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+#[cfg(windows)]
 use winapi::shared::minwindef::{FALSE, TRUE};
+#[cfg(windows)]
 use winapi::shared::sddl::{self, SDDL_REVISION_1};
+#[cfg(windows)]
 use winapi::um::accctrl::{
     ACCESS_MODE, DENY_ACCESS, EXPLICIT_ACCESS_W, NO_INHERITANCE, SE_REGISTRY_KEY, TRUSTEE_IS_SID,
     TRUSTEE_IS_WELL_KNOWN_GROUP,
 };
+#[cfg(windows)]
 use winapi::um::aclapi::{GetSecurityInfo, SetEntriesInAclW, SetSecurityInfo};
+#[cfg(windows)]
 use winapi::um::errhandlingapi::GetLastError;
+#[cfg(windows)]
 use winapi::um::securitybaseapi::{AllocateAndInitializeSid, FreeSid};
+#[cfg(windows)]
 use winapi::um::winbase::LocalFree;
+#[cfg(windows)]
 use winapi::um::winnt::{
     ACL, DACL_SECURITY_INFORMATION, DELETE, GROUP_SECURITY_INFORMATION, KEY_ALL_ACCESS,
     KEY_CREATE_SUB_KEY, KEY_READ, KEY_SET_VALUE, KEY_WOW64_32KEY, KEY_WOW64_64KEY,
     OWNER_SECURITY_INFORMATION, PSECURITY_DESCRIPTOR, PSID, SECURITY_WORLD_RID,
     SECURITY_WORLD_SID_AUTHORITY, SID_IDENTIFIER_AUTHORITY, WRITE_DAC, WRITE_OWNER,
 };
+#[cfg(windows)]
 use winreg::enums::{HKEY_CLASSES_ROOT, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+#[cfg(windows)]
 use winreg::{RegKey, HKEY};
 
 const NAMESPACE_PATH: &str =
     r"SOFTWARE\Microsoft\Windows\CurrentVersion\Explorer\MyComputer\NameSpace";
+#[cfg(windows)]
 const SHCNE_ASSOCCHANGED: u32 = 0x0800_0000;
+#[cfg(windows)]
 const SHCNF_IDLIST: u32 = 0x0000;
 
+#[cfg(windows)]
 #[link(name = "shell32")]
 extern "system" {
     fn SHChangeNotify(event_id: u32, flags: u32, item1: *const c_void, item2: *const c_void);
@@ -107,6 +128,7 @@ struct ShellIconBackup {
     created_at: String,
 }
 
+#[cfg(windows)]
 #[derive(Debug, Clone, Copy)]
 struct RegistryTargetContext {
     root: HKEY,
@@ -114,6 +136,7 @@ struct RegistryTargetContext {
 }
 
 /// 扫描所有受支持的 Hive / Registry View，避免只看当前进程视图而漏掉 32 位软件节点。
+#[cfg(windows)]
 pub fn scan_shell_icons() -> Result<Vec<ShellIconInfo>, String> {
     let scan_targets = [
         (
@@ -183,6 +206,12 @@ pub fn scan_shell_icons() -> Result<Vec<ShellIconInfo>, String> {
     Ok(entries)
 }
 
+#[cfg(not(windows))]
+pub fn scan_shell_icons() -> Result<Vec<ShellIconInfo>, String> {
+    Err("此功能仅支持Windows系统".to_string())
+}
+
+#[cfg(windows)]
 fn build_shell_icon_info(
     namespace_key: &RegKey,
     clsid: &str,
@@ -325,6 +354,7 @@ fn identify_known_application(
 }
 
 /// 普通删除或强力锁定前统一重新校验目标，不能信任前端传入任意注册表路径。
+#[cfg(windows)]
 pub fn remove_shell_icon(
     target: &ShellIconTarget,
     mode: u8,
@@ -479,7 +509,16 @@ pub fn remove_shell_icon(
     })
 }
 
+#[cfg(not(windows))]
+pub fn remove_shell_icon(
+    _target: &ShellIconTarget,
+    _mode: u8,
+) -> Result<ShellIconOperationResult, String> {
+    Err("此功能仅支持Windows系统".to_string())
+}
+
 /// 解锁只恢复原 ACL，不自动重新导入内容，避免用户只想允许软件重新注册时被意外恢复图标。
+#[cfg(windows)]
 pub fn unlock_shell_icon(target: &ShellIconTarget) -> Result<ShellIconOperationResult, String> {
     let target = normalize_target(target)?;
     let context = target_context(&target)?;
@@ -514,7 +553,13 @@ pub fn unlock_shell_icon(target: &ShellIconTarget) -> Result<ShellIconOperationR
     })
 }
 
+#[cfg(not(windows))]
+pub fn unlock_shell_icon(_target: &ShellIconTarget) -> Result<ShellIconOperationResult, String> {
+    Err("此功能仅支持Windows系统".to_string())
+}
+
 /// 从最近一次备份导入注册表内容，并恢复原始 ACL，覆盖普通删除和强力清理两种场景。
+#[cfg(windows)]
 pub fn restore_shell_icon(target: &ShellIconTarget) -> Result<ShellIconOperationResult, String> {
     let target = normalize_target(target)?;
     let context = target_context(&target)?;
@@ -574,6 +619,11 @@ pub fn restore_shell_icon(target: &ShellIconTarget) -> Result<ShellIconOperation
     })
 }
 
+#[cfg(not(windows))]
+pub fn restore_shell_icon(_target: &ShellIconTarget) -> Result<ShellIconOperationResult, String> {
+    Err("此功能仅支持Windows系统".to_string())
+}
+
 pub fn open_shell_icon_backup_dir() -> Result<(), String> {
     let path = backup_dir();
     fs::create_dir_all(&path).map_err(|error| format!("创建备份目录失败: {}", error))?;
@@ -652,6 +702,7 @@ fn shell_icon_log_path() -> PathBuf {
 }
 
 /// 将目标键写入 Regedit 的 LastKey 后打开注册表编辑器，避免用户手动复制长路径。
+#[cfg(windows)]
 pub fn open_shell_icon_registry(target: &ShellIconTarget) -> Result<(), String> {
     let target = normalize_target(target)?;
     let context = target_context(&target)?;
@@ -685,7 +736,13 @@ pub fn open_shell_icon_registry(target: &ShellIconTarget) -> Result<(), String>
     Ok(())
 }
 
+#[cfg(not(windows))]
+pub fn open_shell_icon_registry(_target: &ShellIconTarget) -> Result<(), String> {
+    Err("此功能仅支持Windows系统".to_string())
+}
+
 /// Regedit 的 LastKey 使用本地化的根节点名称，沿用已有值可兼容中英文系统。
+#[cfg(windows)]
 fn regedit_root_name(regedit_config: &RegKey) -> String {
     regedit_config
         .get_value::<String, _>("LastKey")
@@ -699,6 +756,7 @@ fn regedit_root_name(regedit_config: &RegKey) -> String {
         .unwrap_or_else(|| "计算机".to_string())
 }
 
+#[cfg(windows)]
 pub fn restart_explorer() -> Result<(), String> {
     // 只发送 Shell 变更通知，不结束 explorer.exe，避免 TranslucentTB 等任务栏扩展丢失注入状态。
     unsafe {
@@ -712,6 +770,12 @@ pub fn restart_explorer() -> Result<(), String> {
     Ok(())
 }
 
+#[cfg(not(windows))]
+pub fn restart_explorer() -> Result<(), String> {
+    Err("此功能仅支持Windows系统".to_string())
+}
+
+#[cfg(windows)]
 fn build_shell_icon_info_from_key(
     key: &RegKey,
     target: &ShellIconTarget,
@@ -778,6 +842,7 @@ fn normalize_target(target: &ShellIconTarget) -> Result<ShellIconTarget, String>
     })
 }
 
+#[cfg(windows)]
 fn target_context(target: &ShellIconTarget) -> Result<RegistryTargetContext, String> {
     let root = if target.hive == "HKCU" {
         HKEY_CURRENT_USER
@@ -793,6 +858,7 @@ fn target_context(target: &ShellIconTarget) -> Result<RegistryTargetContext, Str
     Ok(RegistryTargetContext { root, view_flags })
 }
 
+#[cfg(windows)]
 fn open_target_key(
     target: &ShellIconTarget,
     context: RegistryTargetContext,
@@ -803,12 +869,14 @@ fn open_target_key(
         .map_err(|error| format!("打开目标注册表节点失败: {}", error))
 }
 
+#[cfg(windows)]
 fn open_namespace_key(context: RegistryTargetContext, flags: u32) -> Result<RegKey, String> {
     RegKey::predef(context.root)
         .open_subkey_with_flags(NAMESPACE_PATH, flags | context.view_flags)
         .map_err(|error| format!("打开 Namespace 父键失败: {}", error))
 }
 
+#[cfg(windows)]
 fn delete_target_key_from_parent(
     parent: &RegKey,
     target: &ShellIconTarget,
@@ -820,6 +888,7 @@ fn delete_target_key_from_parent(
         .map_err(|error| format!("删除外壳图标节点失败: {}", error))
 }
 
+#[cfg(windows)]
 fn clear_key_contents(key: &RegKey) -> Result<(), String> {
     // 不能忽略枚举错误，否则部分子键清理失败时仍会返回“成功”，留下可复活内容。
     let child_names = key
@@ -842,6 +911,7 @@ fn clear_key_contents(key: &RegKey) -> Result<(), String> {
     Ok(())
 }
 
+#[cfg(windows)]
 fn verify_target_absent(
     target: &ShellIconTarget,
     context: RegistryTargetContext,
@@ -860,6 +930,7 @@ fn verify_target_absent(
 }
 
 /// 在父级 Namespace 上拒绝普通用户创建、修改和删除子键，避免目标被软件重新注册。
+#[cfg(windows)]
 fn add_namespace_lock_and_verify(namespace_key: &RegKey) -> Result<(), String> {
     add_lock_acl(namespace_key)?;
     let acl = read_acl_sddl(namespace_key)?;
@@ -872,10 +943,12 @@ fn add_namespace_lock_and_verify(namespace_key: &RegKey) -> Result<(), String> {
     Ok(())
 }
 
+#[cfg(windows)]
 fn is_lightc_namespace_lock_sddl_ref(sddl: &str) -> bool {
     is_lightc_lock_sddl_ref(sddl)
 }
 
+#[cfg(windows)]
 fn create_backup(
     target: &ShellIconTarget,
     key: &RegKey,
@@ -925,6 +998,7 @@ fn backup_stem(target: &ShellIconTarget) -> String {
     )
 }
 
+#[cfg(windows)]
 fn consolidate_backup(
     target: &ShellIconTarget,
     context: RegistryTargetContext,
@@ -963,6 +1037,7 @@ fn consolidate_backup(
     Ok(canonical)
 }
 
+#[cfg(windows)]
 fn export_registry_key(target: &ShellIconTarget, backup_path: &Path) -> Result<(), String> {
     let mut args = vec![
         "export".to_string(),
@@ -985,6 +1060,7 @@ fn export_registry_key(target: &ShellIconTarget, backup_path: &Path) -> Result<(
     }
 }
 
+#[cfg(windows)]
 fn import_registry_backup(path: &str, target: &ShellIconTarget) -> Result<(), String> {
     let mut args = vec!["import".to_string(), path.to_string()];
     append_view_arg(&mut args, target.registry_view.as_str());
@@ -1049,6 +1125,7 @@ fn find_backups(target: &ShellIconTarget) -> Result<Vec<ShellIconBackup>, String
     Ok(candidates)
 }
 
+#[cfg(windows)]
 fn find_source_path(clsid_key: &RegKey) -> Option<String> {
     for subkey_name in ["InProcServer32", "LocalServer32"] {
         let Ok(key) = clsid_key.open_subkey_with_flags(subkey_name, KEY_READ) else {
@@ -1146,6 +1223,7 @@ fn registry_path(target: &ShellIconTarget) -> String {
     format!(r"{}\{}\{}", target.hive, NAMESPACE_PATH, target.clsid)
 }
 
+#[cfg(windows)]
 fn read_acl_sddl(key: &RegKey) -> Result<Option<String>, String> {
     unsafe {
         let mut owner: PSID = std::ptr::null_mut();
@@ -1190,6 +1268,7 @@ fn read_acl_sddl(key: &RegKey) -> Result<Option<String>, String> {
     }
 }
 
+#[cfg(windows)]
 fn add_lock_acl(key: &RegKey) -> Result<(), String> {
     unsafe {
         // 重复点击彻底删除时不重复追加相同 ACE，避免 ACL 和备份一样持续膨胀。
@@ -1272,6 +1351,7 @@ fn add_lock_acl(key: &RegKey) -> Result<(), String> {
     }
 }
 
+#[cfg(windows)]
 fn restore_acl(key: &RegKey, sddl: &str) -> Result<(), String> {
     unsafe {
         let wide: Vec<u16> = sddl.encode_utf16().chain(std::iter::once(0)).collect();
@@ -1339,10 +1419,12 @@ fn restore_acl(key: &RegKey, sddl: &str) -> Result<(), String> {
     }
 }
 
+#[cfg(windows)]
 fn is_lightc_lock_sddl(sddl: String) -> bool {
     is_lightc_lock_sddl_ref(&sddl)
 }
 
+#[cfg(windows)]
 fn is_lightc_lock_sddl_ref(sddl: &str) -> bool {
     sddl.split('(')
         .skip(1)
@@ -1356,6 +1438,7 @@ fn is_lightc_lock_sddl_ref(sddl: &str) -> bool {
         })
 }
 
+#[cfg(windows)]
 fn is_lightc_lock_mask(mask: &str) -> bool {
     let normalized = mask.trim().to_ascii_uppercase();
     if normalized == "DCLCSD" {
@@ -1371,6 +1454,7 @@ fn is_lightc_lock_mask(mask: &str) -> bool {
 }
 
 /// 从当前安全描述符中移除 LightC 自己添加的 Everyone 拒绝 ACE，保留其他用户 ACL 不变。
+#[cfg(windows)]
 fn remove_lightc_lock_acl(key: &RegKey) -> Result<(), String> {
     let current_sddl = read_acl_sddl(key)?.ok_or_else(|| "读取目标注册表 ACL 失败".to_string())?;
     let unlocked_sddl = remove_lightc_lock_aces_from_sddl(&current_sddl)
@@ -1378,6 +1462,7 @@ fn remove_lightc_lock_acl(key: &RegKey) -> Result<(), String> {
     restore_acl(key, &unlocked_sddl)
 }
 
+#[cfg(windows)]
 fn remove_lightc_lock_aces_from_sddl(sddl: &str) -> Option<String> {
     let mut result = String::with_capacity(sddl.len());
     let mut cursor = 0;
@@ -1399,11 +1484,13 @@ fn remove_lightc_lock_aces_from_sddl(sddl: &str) -> Option<String> {
     removed.then_some(result)
 }
 
+#[cfg(windows)]
 fn is_lightc_lock_ace(ace: &str) -> bool {
     let fields: Vec<&str> = ace.split(';').collect();
     fields.len() >= 6 && fields[0] == "D" && fields[5] == "WD" && is_lightc_lock_mask(fields[2])
 }
 
+#[cfg(windows)]
 fn resolve_indirect_string(raw: &str) -> String {
     if !raw.starts_with('@') {
         return raw.to_string();
@@ -1437,22 +1524,24 @@ fn resolve_indirect_string(raw: &str) -> String {
     raw.to_string()
 }
 
+#[cfg(windows)]
 fn hidden_command(program: &str, args: &[&str]) -> Result<std::process::Output, String> {
     use std::os::windows::process::CommandExt;
-    Command::new(program)
-        .args(args)
-        .creation_flags(0x08000000)
-        .output()
-        .map_err(|error| format!("执行 {} 失败: {}", program, error))
+    crate::process_runner::run_with_timeout(
+        Command::new(program).args(args).creation_flags(0x08000000),
+        crate::process_runner::DEFAULT_COMMAND_TIMEOUT,
+    )
+    .map_err(|error| format!("执行 {} 失败: {}", program, error))
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        identify_application, identify_known_application, is_lightc_lock_sddl,
-        is_system_component_path, normalize_clsid, registry_path,
-        remove_lightc_lock_aces_from_sddl, ShellIconTarget,
+        identify_application, identify_known_application, is_system_component_path,
+        normalize_clsid, registry_path, ShellIconTarget,
     };
+    #[cfg(windows)]
+    use super::{is_lightc_lock_sddl, remove_lightc_lock_aces_from_sddl};
 
     #[test]
     fn normalizes_only_guid_clsid() {
@@ -1475,6 +1564,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(windows)]
     fn detects_lightc_deny_ace() {
         assert!(is_lightc_lock_sddl("D:(D;;0x00010006;;;WD)".to_string()));
         assert!(is_lightc_lock_sddl("D:(D;;DCLCSD;;;WD)".to_string()));
@@ -1482,6 +1572,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(windows)]
     fn removes_only_lightc_lock_ace_from_sddl() {
         let sddl = "O:USG:WDD:(D;;DCLCSD;;;WD)(A;;KA;;;BA)";
         let unlocked = remove_lightc_lock_aces_from_sddl(sddl).expect("lock ACE should be found");
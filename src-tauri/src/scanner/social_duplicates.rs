@@ -0,0 +1,257 @@
+// ============================================================================
+// 社交软件重复媒体
+//
+// 同一张图片、同一段视频转发后会在微信、QQ 等多个软件的缓存里各存一份。
+// 这里在最近一次社交软件扫描的图片视频文件中查找内容完全相同的文件，
+// 每组建议保留最早的一份，其余可以删除。
+//
+// 分三步缩小范围，避免对每个文件做完整读取：
+// 1. 按大小分组，大小唯一的文件不可能重复；
+// 2. 对同大小的文件只哈希开头和结尾各 64 KB；
+// 3. 部分哈希仍相同的文件再做完整内容哈希。
+//
+// 微信图片缓存是加密的 .dat，与其他软件里的原图字节不同，跨软件重复主要来自视频和文件。
+// ============================================================================
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fs::File;
+use std::hash::Hasher;
+use std::io::{Read, Seek, SeekFrom};
+
+use rayon::prelude::*;
+use serde::Serialize;
+
+use super::{
+    cached_deletable_social_files, is_social_scan_cancelled, FileCategory, SocialFileEntry,
+};
+
+/// 小于该大小的文件不参与查重（缩略图等，节省的空间不值得逐个确认）
+const MIN_DUPLICATE_SIZE: u64 = 16 * 1024;
+
+/// 部分哈希读取的头尾长度
+const PARTIAL_HASH_BYTES: u64 = 64 * 1024;
+
+/// 完整哈希的读缓冲大小
+const FULL_HASH_BUFFER: usize = 1024 * 1024;
+
+/// 重复组中的一份文件
+#[derive(Debug, Clone, Serialize)]
+pub struct SocialDuplicateFile {
+    /// 文件路径
+    pub path: String,
+    /// 所属应用名称
+    pub app_name: String,
+    /// 修改时间（Unix 时间戳，秒）
+    pub modified: u64,
+    /// 建议保留的副本（每组一份）
+    pub keep: bool,
+}
+
+/// 一组内容相同的文件
+#[derive(Debug, Clone, Serialize)]
+pub struct SocialDuplicateGroup {
+    /// 单个文件大小（字节）
+    pub size: u64,
+    /// 组内文件，建议保留的副本在最前
+    pub files: Vec<SocialDuplicateFile>,
+    /// 删除其余副本可释放的空间
+    pub reclaimable_size: u64,
+    /// 副本是否分布在多个软件中
+    pub cross_app: bool,
+}
+
+/// 重复媒体查找结果
+#[derive(Debug, Clone, Serialize)]
+pub struct SocialDuplicateReport {
+    /// 重复组（按可释放空间降序）
+    pub groups: Vec<SocialDuplicateGroup>,
+    /// 建议删除的副本数
+    pub removable_count: usize,
+    /// 删除全部建议副本可释放的空间
+    pub reclaimable_size: u64,
+    /// 其中跨软件重复可释放的空间
+    pub cross_app_reclaimable_size: u64,
+}
+
+/// 在最近一次社交软件扫描的图片视频中查找重复文件
+pub fn find_duplicate_social_media() -> Result<SocialDuplicateReport, String> {
+    let files = cached_deletable_social_files(FileCategory::ImageVideo)?;
+    log::info!("社交软件重复媒体: 检查 {} 个图片视频文件", files.len());
+    let groups = group_duplicates(files);
+    if is_social_scan_cancelled() {
+        return Err("扫描已取消".to_string());
+    }
+    Ok(build_report(groups))
+}
+
+/// 按大小、部分哈希、完整哈希逐步分组，返回内容相同的文件组
+fn group_duplicates(files: Vec<SocialFileEntry>) -> Vec<Vec<SocialFileEntry>> {
+    let mut by_size: HashMap<u64, Vec<SocialFileEntry>> = HashMap::new();
+    for file in files {
+        if file.size >= MIN_DUPLICATE_SIZE {
+            by_size.entry(file.size).or_default().push(file);
+        }
+    }
+    let candidates: Vec<Vec<SocialFileEntry>> = by_size
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .collect();
+
+    let candidates = split_by_hash(candidates, partial_hash);
+    split_by_hash(candidates, |path, _| full_hash(path))
+}
+
+/// 对每组内的文件并行计算哈希，按哈希拆分，只保留仍有多个文件的子组
+///
+/// 读取失败的文件（已被删除或被占用）直接剔除，不会被当作重复。
+fn split_by_hash<F>(groups: Vec<Vec<SocialFileEntry>>, hash: F) -> Vec<Vec<SocialFileEntry>>
+where
+    F: Fn(&str, u64) -> Option<u64> + Sync,
+{
+    groups
+        .into_par_iter()
+        .flat_map_iter(|group| {
+            let mut by_hash: HashMap<u64, Vec<SocialFileEntry>> = HashMap::new();
+            for file in group {
+                if is_social_scan_cancelled() {
+                    break;
+                }
+                if let Some(digest) = hash(&file.path, file.size) {
+                    by_hash.entry(digest).or_default().push(file);
+                }
+            }
+            by_hash.into_values().filter(|group| group.len() > 1)
+        })
+        .collect()
+}
+
+/// 哈希文件开头和结尾各 `PARTIAL_HASH_BYTES`，小文件只读一次
+fn partial_hash(path: &str, size: u64) -> Option<u64> {
+    let mut file = File::open(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    let mut buffer = vec![0u8; PARTIAL_HASH_BYTES as usize];
+
+    let head_len = size.min(PARTIAL_HASH_BYTES) as usize;
+    file.read_exact(&mut buffer[..head_len]).ok()?;
+    hasher.write(&buffer[..head_len]);
+
+    if size > PARTIAL_HASH_BYTES * 2 {
+        file.seek(SeekFrom::End(-(PARTIAL_HASH_BYTES as i64)))
+            .ok()?;
+        file.read_exact(&mut buffer).ok()?;
+        hasher.write(&buffer);
+    }
+    Some(hasher.finish())
+}
+
+/// 哈希完整文件内容
+fn full_hash(path: &str) -> Option<u64> {
+    let mut file = File::open(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    let mut buffer = vec![0u8; FULL_HASH_BUFFER];
+    loop {
+        let read = file.read(&mut buffer).ok()?;
+        if read == 0 {
+            break;
+        }
+        hasher.write(&buffer[..read]);
+    }
+    Some(hasher.finish())
+}
+
+/// 每组保留修改时间最早的一份（通常是最初收到的那份），其余建议删除
+fn build_report(groups: Vec<Vec<SocialFileEntry>>) -> SocialDuplicateReport {
+    let mut groups: Vec<SocialDuplicateGroup> = groups
+        .into_iter()
+        .map(|mut files| {
+            files.sort_by(|a, b| {
+                a.modified
+                    .cmp(&b.modified)
+                    .then_with(|| a.path.cmp(&b.path))
+            });
+            let size = files[0].size;
+            let cross_app = files.iter().any(|file| file.app_name != files[0].app_name);
+            SocialDuplicateGroup {
+                size,
+                reclaimable_size: size * (files.len() as u64 - 1),
+                cross_app,
+                files: files
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, file)| SocialDuplicateFile {
+                        path: file.path,
+                        app_name: file.app_name,
+                        modified: file.modified,
+                        keep: index == 0,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.reclaimable_size
+            .cmp(&a.reclaimable_size)
+            .then_with(|| a.files[0].path.cmp(&b.files[0].path))
+    });
+
+    SocialDuplicateReport {
+        removable_count: groups.iter().map(|group| group.files.len() - 1).sum(),
+        reclaimable_size: groups.iter().map(|group| group.reclaimable_size).sum(),
+        cross_app_reclaimable_size: groups
+            .iter()
+            .filter(|group| group.cross_app)
+            .map(|group| group.reclaimable_size)
+            .sum(),
+        groups,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_duplicates_grouped_by_content_across_apps() {
+        let root = std::env::temp_dir().join(format!("lightc-social-dup-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+
+        let video = vec![7u8; 200 * 1024];
+        // 与 video 大小相同、头尾相同，只有中间一个字节不同，必须由完整哈希区分
+        let mut near_copy = video.clone();
+        near_copy[100 * 1024] = 8;
+        let entries = [
+            ("wechat.mp4", &video, "微信", 200),
+            ("qq.mp4", &video, "QQ", 100),
+            ("qq_other.mp4", &near_copy, "QQ", 300),
+        ];
+        let files: Vec<SocialFileEntry> = entries
+            .iter()
+            .map(|(name, content, app, modified)| {
+                let path = root.join(name);
+                fs::write(&path, content).unwrap();
+                SocialFileEntry {
+                    app_name: app.to_string(),
+                    modified: *modified,
+                    ..SocialFileEntry::test_entry(&path.to_string_lossy(), content.len() as u64)
+                }
+            })
+            .collect();
+
+        let report = build_report(group_duplicates(files));
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(report.groups.len(), 1);
+        let group = &report.groups[0];
+        assert!(group.cross_app);
+        assert_eq!(group.files.len(), 2);
+        // 保留最早的一份
+        assert!(group.files[0].keep && group.files[0].path.ends_with("qq.mp4"));
+        assert!(!group.files[1].keep);
+        assert_eq!(report.removable_count, 1);
+        assert_eq!(report.reclaimable_size, 200 * 1024);
+        assert_eq!(report.cross_app_reclaimable_size, 200 * 1024);
+    }
+}
@@ -0,0 +1,139 @@
+// ============================================================================
+// 社交软件媒体保留策略
+//
+// 清理微信图片时用户通常希望保留最近的截图和照片。
+// 最近一次社交软件扫描的分类结果保留在后端，按修改时间保留最新的 N 个文件，
+// 其余可删除的文件交给删除引擎处理，路径不需要经过前端往返。
+// ============================================================================
+
+use std::sync::{LazyLock, Mutex};
+
+use super::{FileCategory, SocialCategoryStats, SocialFileEntry, SocialScanResult};
+
+/// 最近一次社交软件扫描的分类结果
+static LAST_SOCIAL_SCAN: LazyLock<Mutex<Vec<SocialCategoryStats>>> =
+    LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// 记录最近一次社交软件扫描结果，新的扫描会替换之前的结果
+pub fn remember_social_scan(result: &SocialScanResult) {
+    let mut cache = LAST_SOCIAL_SCAN
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+    *cache = result.categories.clone();
+}
+
+/// 选出分类中除最新 `keep_recent` 个文件以外的可删除文件
+///
+/// # 中文说明
+/// 只在可删除的文件中排序保留，聊天数据库等不可删除的文件不占用保留名额，也不会被选中。
+/// 修改时间相同时按路径排序，保证多次调用结果一致。
+pub fn select_social_files_keep_recent(
+    category_id: &str,
+    keep_recent: usize,
+) -> Result<Vec<String>, String> {
+    let cache = LAST_SOCIAL_SCAN
+        .lock()
+        .map_err(|_| "社交软件扫描结果缓存锁异常，请重试".to_string())?;
+    let category = cache
+        .iter()
+        .find(|category| category.id == category_id)
+        .ok_or_else(|| format!("没有分类「{}」的扫描结果，请重新扫描", category_id))?;
+    Ok(files_beyond_recent(category, keep_recent))
+}
+
+/// 取出最近一次扫描中指定分类的可删除文件
+pub(crate) fn cached_deletable_social_files(
+    category: FileCategory,
+) -> Result<Vec<SocialFileEntry>, String> {
+    let cache = LAST_SOCIAL_SCAN
+        .lock()
+        .map_err(|_| "社交软件扫描结果缓存锁异常，请重试".to_string())?;
+    if cache.is_empty() {
+        return Err("没有社交软件扫描结果，请先扫描".to_string());
+    }
+    Ok(cache
+        .iter()
+        .flat_map(|stats| stats.files.iter())
+        .filter(|file| file.category == category && file.deletable)
+        .cloned()
+        .collect())
+}
+
+/// 从缓存中移除已删除的文件，并同步分类统计
+pub fn forget_social_files(deleted_paths: &[String]) {
+    if deleted_paths.is_empty() {
+        return;
+    }
+    let deleted: std::collections::HashSet<&str> =
+        deleted_paths.iter().map(String::as_str).collect();
+    let mut cache = LAST_SOCIAL_SCAN
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+    for category in cache.iter_mut() {
+        category
+            .files
+            .retain(|file| !deleted.contains(file.path.as_str()));
+        category.file_count = category.files.len();
+        category.total_size = category.files.iter().map(|file| file.size).sum();
+        category.deletable_count = category.files.iter().filter(|file| file.deletable).count();
+        category.deletable_size = category
+            .files
+            .iter()
+            .filter(|file| file.deletable)
+            .map(|file| file.size)
+            .sum();
+    }
+}
+
+fn files_beyond_recent(category: &SocialCategoryStats, keep_recent: usize) -> Vec<String> {
+    let mut files: Vec<_> = category
+        .files
+        .iter()
+        .filter(|file| file.deletable)
+        .collect();
+    files.sort_by(|left, right| {
+        right
+            .modified
+            .cmp(&left.modified)
+            .then_with(|| left.path.cmp(&right.path))
+    });
+    files
+        .into_iter()
+        .skip(keep_recent)
+        .map(|file| file.path.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::SocialFileEntry;
+
+    fn image(name: &str, modified: u64, deletable: bool) -> SocialFileEntry {
+        SocialFileEntry {
+            modified,
+            deletable,
+            ..SocialFileEntry::test_entry(&format!(r"C:\WeChat\Image\{}.dat", name), 10)
+        }
+    }
+
+    #[test]
+    fn test_files_beyond_recent_keeps_newest() {
+        let category = SocialCategoryStats::test_category(vec![
+            image("old", 100, true),
+            image("newest", 400, true),
+            image("locked", 500, false),
+            image("middle", 200, true),
+        ]);
+
+        assert_eq!(
+            files_beyond_recent(&category, 1),
+            vec![
+                r"C:\WeChat\Image\middle.dat".to_string(),
+                r"C:\WeChat\Image\old.dat".to_string()
+            ]
+        );
+        assert!(files_beyond_recent(&category, 3).is_empty());
+        assert_eq!(files_beyond_recent(&category, 0).len(), 3);
+    }
+}
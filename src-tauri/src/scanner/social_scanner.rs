@@ -4,6 +4,7 @@
 //
 // 支持的社交软件：
 // - 微信 (WeChat): 通过注册表读取自定义路径，识别聊天记录数据库
+//   微信 4.0 (xwechat_files): 识别 db_storage 数据库和 msg 下的附件/视频/文件
 // - QQ/NTQQ: 定位 nt_data 目录，识别消息数据库
 // - 钉钉 (DingTalk): 定位 storage 和 cache 目录
 // - 飞书 (Lark/Feishu): 扫描 LarkShell，定位 sdk_storage 和 file_storage
@@ -16,7 +17,7 @@
 //
 // CRITICAL (危险) - 聊天记录数据库，禁止删除
 //   识别规则：
-//   1. 路径包含: Msg/Database, Msg/Multi, nt_msg, nt_db, tdata
+//   1. 路径包含: Msg/Database, Msg/Multi, db_storage, nt_msg, nt_db, tdata
 //   2. 文件后缀: .db, .db-wal, .db-shm, .sqlite, .sqlite-wal, .sqlite-shm
 //   3. 效果: is_deletable = false，前端禁用勾选
 //
@@ -39,9 +40,11 @@
 // ============================================================================
 
 use log::{debug, info};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use walkdir::WalkDir;
 
 // ============================================================================
@@ -182,6 +185,9 @@ pub struct SocialFileEntry {
     pub size: u64,
     /// 所属应用名称
     pub app_name: String,
+    /// 修改时间（Unix 时间戳，秒），读取失败时为 0
+    #[serde(default)]
+    pub modified: u64,
     /// 文件分类
     pub category: FileCategory,
     /// 风险等级
@@ -211,9 +217,23 @@ pub struct SocialCategoryStats {
     pub files: Vec<SocialFileEntry>,
 }
 
+/// 单个社交软件的占用汇总
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppSummary {
+    /// 应用名称
+    pub app_name: String,
+    /// 文件数量
+    pub file_count: usize,
+    /// 总大小（字节）
+    pub total_size: u64,
+}
+
 /// 社交软件扫描结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SocialScanResult {
+    /// JSON 格式版本（见 `RESULT_SCHEMA_VERSION`）
+    #[serde(default)]
+    pub schema_version: u32,
     /// 按分类统计
     pub categories: Vec<SocialCategoryStats>,
     /// 总文件数
@@ -226,6 +246,9 @@ pub struct SocialScanResult {
     pub deletable_size: u64,
     /// 检测到的社交软件列表
     pub detected_apps: Vec<String>,
+    /// 按应用统计（按总大小降序）
+    #[serde(default)]
+    pub by_app: Vec<AppSummary>,
 }
 
 /// 社交软件路径信息
@@ -256,6 +279,28 @@ enum RegistryPathResult {
 // 社交软件扫描器
 // ============================================================================
 
+/// 可单独扫描的社交软件 ID（`qq` 同时包含 QQ 和 NTQQ）
+pub const SOCIAL_APP_IDS: &[&str] = &["wechat", "qq", "dingtalk", "feishu", "wxwork", "telegram"];
+
+/// 全局取消标志，跨线程共享（与 big_files.rs 模式一致）
+static SOCIAL_SCAN_CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// 重置取消标志（扫描开始前调用）
+pub fn reset_social_scan_cancelled() {
+    SOCIAL_SCAN_CANCELLED.store(false, Ordering::SeqCst);
+}
+
+/// 设置取消标志（前端点击取消按钮时调用）
+pub fn cancel_social_scan() {
+    info!("收到取消社交软件扫描请求");
+    SOCIAL_SCAN_CANCELLED.store(true, Ordering::SeqCst);
+}
+
+/// 查询取消标志，命令层据此区分取消和正常完成
+pub fn is_social_scan_cancelled() -> bool {
+    SOCIAL_SCAN_CANCELLED.load(Ordering::SeqCst)
+}
+
 /// 社交软件扫描器
 /// 负责智能路径溯源和文件类型深度分类
 pub struct SocialScanner {
@@ -271,6 +316,10 @@ pub struct SocialScanner {
     default_documents: String,
     /// 所有可用盘符（用于全盘搜索）
     available_drives: Vec<String>,
+    /// 是否扫描运行程序的用户（HKCU 中的自定义路径只对该用户有效）
+    current_user: bool,
+    /// 只扫描这些社交软件（`SOCIAL_APP_IDS` 中的 ID），None 表示全部
+    apps: Option<Vec<String>>,
 }
 
 impl SocialScanner {
@@ -306,9 +355,45 @@ impl SocialScanner {
             documents_dir,
             default_documents,
             available_drives,
+            current_user: true,
+            apps: None,
+        }
+    }
+
+    /// 为指定用户配置文件目录创建扫描器
+    ///
+    /// 其他用户的目录只能从配置文件路径拼出：不读取 HKCU（那是运行程序的用户的注册表），
+    /// 也不做全盘搜索（搜到的目录无法确定属于哪个用户），只检查配置文件下的默认位置。
+    pub fn for_profile(profile_dir: &Path) -> Self {
+        let user_profile = profile_dir.to_string_lossy().to_string();
+        let default_documents = format!("{}\\Documents", user_profile);
+        Self {
+            local_appdata: format!("{}\\AppData\\Local", user_profile),
+            appdata: format!("{}\\AppData\\Roaming", user_profile),
+            documents_dir: default_documents.clone(),
+            default_documents,
+            available_drives: Vec::new(),
+            current_user: false,
+            apps: None,
+            user_profile,
         }
     }
 
+    /// 只扫描指定的社交软件，未列出的软件连路径检测都跳过
+    ///
+    /// 微信的全盘搜索等检测步骤本身就很慢，只用 QQ 的用户不应为此等待。
+    pub fn with_apps(mut self, apps: Option<Vec<String>>) -> Self {
+        self.apps = apps.map(|apps| apps.iter().map(|app| app.to_lowercase()).collect());
+        self
+    }
+
+    /// 是否需要扫描该社交软件
+    fn wants_app(&self, app_id: &str) -> bool {
+        self.apps
+            .as_ref()
+            .is_none_or(|apps| apps.iter().any(|app| app == app_id))
+    }
+
     // ========================================================================
     // 系统工具方法
     // ========================================================================
@@ -338,59 +423,71 @@ impl SocialScanner {
         let mut detected_apps = Vec::new();
 
         // 1. 微信路径检测（包括注册表溯源）
-        if let Some(paths) = self.detect_wechat_paths() {
-            if !paths.is_empty() {
-                detected_apps.push("微信".to_string());
-                all_paths.extend(paths);
+        if self.wants_app("wechat") {
+            if let Some(paths) = self.detect_wechat_paths() {
+                if !paths.is_empty() {
+                    detected_apps.push("微信".to_string());
+                    all_paths.extend(paths);
+                }
             }
         }
 
         // 2. QQ/NTQQ 路径检测
-        if let Some(paths) = self.detect_qq_paths() {
-            if !paths.is_empty() {
-                detected_apps.push("QQ".to_string());
-                all_paths.extend(paths);
+        if self.wants_app("qq") {
+            if let Some(paths) = self.detect_qq_paths() {
+                if !paths.is_empty() {
+                    detected_apps.push("QQ".to_string());
+                    all_paths.extend(paths);
+                }
             }
-        }
 
-        if let Some(paths) = self.detect_ntqq_paths() {
-            if !paths.is_empty() {
-                if !detected_apps.contains(&"NTQQ".to_string()) {
-                    detected_apps.push("NTQQ".to_string());
+            if let Some(paths) = self.detect_ntqq_paths() {
+                if !paths.is_empty() {
+                    if !detected_apps.contains(&"NTQQ".to_string()) {
+                        detected_apps.push("NTQQ".to_string());
+                    }
+                    all_paths.extend(paths);
                 }
-                all_paths.extend(paths);
             }
         }
 
         // 3. 钉钉路径检测
-        if let Some(paths) = self.detect_dingtalk_paths() {
-            if !paths.is_empty() {
-                detected_apps.push("钉钉".to_string());
-                all_paths.extend(paths);
+        if self.wants_app("dingtalk") {
+            if let Some(paths) = self.detect_dingtalk_paths() {
+                if !paths.is_empty() {
+                    detected_apps.push("钉钉".to_string());
+                    all_paths.extend(paths);
+                }
             }
         }
 
         // 4. 飞书路径检测
-        if let Some(paths) = self.detect_feishu_paths() {
-            if !paths.is_empty() {
-                detected_apps.push("飞书".to_string());
-                all_paths.extend(paths);
+        if self.wants_app("feishu") {
+            if let Some(paths) = self.detect_feishu_paths() {
+                if !paths.is_empty() {
+                    detected_apps.push("飞书".to_string());
+                    all_paths.extend(paths);
+                }
             }
         }
 
         // 5. 企业微信路径检测
-        if let Some(paths) = self.detect_wxwork_paths() {
-            if !paths.is_empty() {
-                detected_apps.push("企业微信".to_string());
-                all_paths.extend(paths);
+        if self.wants_app("wxwork") {
+            if let Some(paths) = self.detect_wxwork_paths() {
+                if !paths.is_empty() {
+                    detected_apps.push("企业微信".to_string());
+                    all_paths.extend(paths);
+                }
             }
         }
 
         // 6. Telegram 路径检测
-        if let Some(paths) = self.detect_telegram_paths() {
-            if !paths.is_empty() {
-                detected_apps.push("Telegram".to_string());
-                all_paths.extend(paths);
+        if self.wants_app("telegram") {
+            if let Some(paths) = self.detect_telegram_paths() {
+                if !paths.is_empty() {
+                    detected_apps.push("Telegram".to_string());
+                    all_paths.extend(paths);
+                }
             }
         }
 
@@ -408,14 +505,17 @@ impl SocialScanner {
         let total_size: u64 = categories.iter().map(|c| c.total_size).sum();
         let deletable_files: usize = categories.iter().map(|c| c.deletable_count).sum();
         let deletable_size: u64 = categories.iter().map(|c| c.deletable_size).sum();
+        let by_app = summarize_by_app(&categories);
 
         SocialScanResult {
+            schema_version: super::RESULT_SCHEMA_VERSION,
             categories,
             total_files,
             total_size,
             deletable_files,
             deletable_size,
             detected_apps,
+            by_app,
         }
     }
 
@@ -430,7 +530,8 @@ impl SocialScanner {
     ///    - 如果值为 "MyDocument:"，则使用系统文档目录
     ///    - 如果是绝对路径（如 "E:\data\xwechat_files"），则直接使用
     /// 2. 默认文档目录下的 "WeChat Files"
-    /// 3. 全盘搜索 "WeChat Files" 文件夹（保底方案）
+    /// 3. 微信 4.0 的 "xwechat_files"（文档目录或客户端配置中的存储位置）
+    /// 4. 全盘搜索 "WeChat Files" 文件夹（保底方案）
     fn detect_wechat_paths(&self) -> Option<Vec<SocialAppPath>> {
         let mut paths = Vec::new();
         let mut found_base_paths: Vec<PathBuf> = Vec::new();
@@ -483,7 +584,16 @@ impl SocialScanner {
         }
 
         // ----------------------------------------------------------------
-        // 步骤 3: 全盘搜索备选（如果上述路径都不存在）
+        // 步骤 3: 微信 4.0 数据目录（与旧版可以同时存在）
+        // ----------------------------------------------------------------
+        for xwechat_path in self.find_xwechat_base_paths() {
+            if !found_base_paths.contains(&xwechat_path) {
+                found_base_paths.push(xwechat_path);
+            }
+        }
+
+        // ----------------------------------------------------------------
+        // 步骤 4: 全盘搜索备选（如果上述路径都不存在）
         // ----------------------------------------------------------------
         if found_base_paths.is_empty() {
             info!("微信默认路径不存在，启动全盘搜索...");
@@ -493,7 +603,7 @@ impl SocialScanner {
         }
 
         // ----------------------------------------------------------------
-        // 步骤 4: 扫描找到的所有基础路径
+        // 步骤 5: 扫描找到的所有基础路径
         // ----------------------------------------------------------------
         let is_custom = registry_result.is_some();
 
@@ -502,6 +612,17 @@ impl SocialScanner {
             self.scan_wechat_base_directory(&base_path, is_custom, &mut paths);
         }
 
+        // 微信 4.0 客户端日志不在账号目录下
+        let xwechat_log = PathBuf::from(format!("{}\\Tencent\\xwechat\\log", self.appdata));
+        if xwechat_log.exists() {
+            paths.push(SocialAppPath {
+                app_name: "微信".to_string(),
+                path: xwechat_log,
+                category: FileCategory::TempCache,
+                is_custom_path: false,
+            });
+        }
+
         if paths.is_empty() {
             None
         } else {
@@ -532,8 +653,20 @@ impl SocialScanner {
                 .to_string_lossy()
                 .to_string();
 
-            // 跳过系统目录
-            if user_name == "All Users" || user_name == "Applet" || user_name.starts_with(".") {
+            // 跳过系统目录（微信 4.0 对应 all_users）
+            if user_name == "All Users"
+                || user_name == "all_users"
+                || user_name == "Applet"
+                || user_name.starts_with(".")
+            {
+                continue;
+            }
+
+            // 微信 4.0 账号目录用小写 msg 存放附件，Windows 下 join("Msg") 同样命中，
+            // 按旧版结构扫描会把整个 msg 当成聊天记录，必须先按 db_storage 区分
+            if user_dir.join("db_storage").is_dir() {
+                info!("  微信 4.0 用户: {}", user_name);
+                self.scan_xwechat_account(&user_dir, is_custom, paths);
                 continue;
             }
 
@@ -667,6 +800,93 @@ impl SocialScanner {
         }
     }
 
+    /// 查找微信 4.0 的 xwechat_files 目录
+    ///
+    /// 默认位于文档目录；用户在客户端修改存储位置后，
+    /// `%AppData%\Tencent\xwechat\config\*.ini` 中记录的是 xwechat_files 的上级目录。
+    fn find_xwechat_base_paths(&self) -> Vec<PathBuf> {
+        let mut candidates = vec![
+            PathBuf::from(format!("{}\\xwechat_files", self.documents_dir)),
+            PathBuf::from(format!("{}\\xwechat_files", self.default_documents)),
+        ];
+
+        let config_dir = PathBuf::from(format!("{}\\Tencent\\xwechat\\config", self.appdata));
+        if let Ok(entries) = std::fs::read_dir(&config_dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                let is_ini = path
+                    .extension()
+                    .map(|ext| ext.eq_ignore_ascii_case("ini"))
+                    .unwrap_or(false);
+                if !is_ini {
+                    continue;
+                }
+                let Ok(content) = std::fs::read_to_string(&path) else {
+                    continue;
+                };
+                let root = content.trim().trim_matches('\0');
+                if root.is_empty() {
+                    continue;
+                }
+                debug!("微信 4.0 存储位置配置 {}: {}", path.display(), root);
+                let root = PathBuf::from(root);
+                if root
+                    .file_name()
+                    .map(|name| name.eq_ignore_ascii_case("xwechat_files"))
+                    .unwrap_or(false)
+                {
+                    candidates.push(root);
+                } else {
+                    candidates.push(root.join("xwechat_files"));
+                }
+            }
+        }
+
+        let mut found = Vec::new();
+        for candidate in candidates {
+            if candidate.is_dir() && !found.contains(&candidate) {
+                found.push(candidate);
+            }
+        }
+        found
+    }
+
+    /// 扫描微信 4.0 账号目录
+    ///
+    /// 目录结构：
+    ///   db_storage/        - 聊天记录数据库 (CRITICAL)
+    ///   msg/attach/        - 加密图片 .dat (LOW)
+    ///   msg/video/         - 视频 (LOW)
+    ///   msg/file/          - 接收的文件 (MEDIUM)
+    ///   cache/, temp/      - 运行缓存 (NONE)
+    fn scan_xwechat_account(
+        &self,
+        account_dir: &Path,
+        is_custom: bool,
+        paths: &mut Vec<SocialAppPath>,
+    ) {
+        let layout: [(&str, FileCategory); 6] = [
+            ("db_storage", FileCategory::ChatDatabase),
+            ("msg\\attach", FileCategory::ImageVideo),
+            ("msg\\video", FileCategory::ImageVideo),
+            ("msg\\file", FileCategory::FileTransfer),
+            ("cache", FileCategory::TempCache),
+            ("temp", FileCategory::TempCache),
+        ];
+
+        for (relative, category) in layout {
+            let dir = account_dir.join(relative);
+            if dir.exists() {
+                paths.push(SocialAppPath {
+                    app_name: "微信".to_string(),
+                    path: dir,
+                    category,
+                    is_custom_path: is_custom,
+                });
+            }
+        }
+    }
+
     /// 全盘搜索 WeChat Files 文件夹
     /// 当注册表和默认路径都失败时，作为保底方案
     fn search_wechat_files_on_all_drives(&self) -> Option<Vec<PathBuf>> {
@@ -736,6 +956,10 @@ impl SocialScanner {
         use winreg::enums::*;
         use winreg::RegKey;
 
+        if !self.current_user {
+            return None;
+        }
+
         let hkcu = RegKey::predef(HKEY_CURRENT_USER);
 
         // 尝试读取微信注册表路径
@@ -896,6 +1120,10 @@ impl SocialScanner {
         use winreg::enums::*;
         use winreg::RegKey;
 
+        if !self.current_user {
+            return None;
+        }
+
         let hkcu = RegKey::predef(HKEY_CURRENT_USER);
 
         // 尝试读取 QQ 注册表路径
@@ -1111,6 +1339,10 @@ impl SocialScanner {
         use winreg::enums::*;
         use winreg::RegKey;
 
+        if !self.current_user {
+            return None;
+        }
+
         let hkcu = RegKey::predef(HKEY_CURRENT_USER);
 
         // 尝试读取 NTQQ 注册表路径
@@ -1499,43 +1731,19 @@ impl SocialScanner {
 
     /// 扫描并分类文件
     fn scan_and_classify(&self, app_paths: &[SocialAppPath]) -> Vec<SocialCategoryStats> {
-        // 初始化分类统计
-        let mut category_map: HashMap<FileCategory, SocialCategoryStats> = HashMap::new();
-
-        for cat in &[
-            FileCategory::ChatDatabase,
-            FileCategory::ImageVideo,
-            FileCategory::FileTransfer,
-            FileCategory::TempCache,
-            FileCategory::MomentsCache,
-        ] {
-            category_map.insert(
-                *cat,
-                SocialCategoryStats {
-                    id: cat.id().to_string(),
-                    name: cat.display_name().to_string(),
-                    description: cat.description().to_string(),
-                    file_count: 0,
-                    total_size: 0,
-                    deletable_count: 0,
-                    deletable_size: 0,
-                    files: Vec::new(),
-                },
-            );
-        }
-
-        // 扫描每个路径
-        for app_path in app_paths {
-            if !app_path.path.exists() {
-                continue;
-            }
+        // 微信、QQ、飞书的目录树都可能很大，各路径并行遍历，各自返回文件列表，
+        // 再按原顺序合并到分类统计中，避免多个线程同时修改同一个分类
+        let partials: Vec<Vec<SocialFileEntry>> = app_paths
+            .par_iter()
+            .filter(|app_path| app_path.path.exists())
+            .map(|app_path| {
+                self.scan_directory(&app_path.path, &app_path.app_name, app_path.category)
+            })
+            .collect();
 
-            self.scan_directory(
-                &app_path.path,
-                &app_path.app_name,
-                app_path.category,
-                &mut category_map,
-            );
+        let mut category_map = empty_category_map();
+        for entries in partials {
+            merge_social_entries(&mut category_map, entries);
         }
 
         // 转换为 Vec 并排序
@@ -1551,14 +1759,14 @@ impl SocialScanner {
         categories
     }
 
-    /// 扫描目录并添加到分类
+    /// 扫描目录，返回分类后的文件列表
     fn scan_directory(
         &self,
         path: &Path,
         app_name: &str,
         base_category: FileCategory,
-        category_map: &mut HashMap<FileCategory, SocialCategoryStats>,
-    ) {
+    ) -> Vec<SocialFileEntry> {
+        let mut entries = Vec::new();
         for entry in WalkDir::new(path)
             .follow_links(false)
             .max_depth(10)
@@ -1566,6 +1774,9 @@ impl SocialScanner {
             .filter_map(|e| e.ok())
             .filter(|e| e.file_type().is_file())
         {
+            if is_social_scan_cancelled() {
+                break;
+            }
             if let Ok(metadata) = entry.metadata() {
                 let file_path = entry.path();
                 let size = metadata.len();
@@ -1574,28 +1785,25 @@ impl SocialScanner {
                 let (category, risk_level) = self.classify_file(file_path, base_category);
                 let deletable = risk_level.is_deletable();
 
-                let file_entry = SocialFileEntry {
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|duration| duration.as_secs())
+                    .unwrap_or(0);
+
+                entries.push(SocialFileEntry {
                     path: file_path.to_string_lossy().to_string(),
                     size,
                     app_name: app_name.to_string(),
+                    modified,
                     category,
                     risk_level,
                     deletable,
-                };
-
-                if let Some(stats) = category_map.get_mut(&category) {
-                    stats.file_count += 1;
-                    stats.total_size += size;
-
-                    if deletable {
-                        stats.deletable_count += 1;
-                        stats.deletable_size += size;
-                    }
-
-                    stats.files.push(file_entry);
-                }
+                });
             }
         }
+        entries
     }
 
     /// 根据文件特征分类并确定风险等级
@@ -1672,6 +1880,8 @@ impl SocialScanner {
             "\\msg\\multi",    // 微信: Msg/Multi
             "/msg/database",   // 兼容正斜杠
             "/msg/multi",
+            "\\db_storage\\", // 微信 4.0: db_storage
+            "/db_storage/",
             "\\nt_msg", // NTQQ: nt_msg
             "\\nt_db",  // NTQQ: nt_db
             "/nt_msg",
@@ -1805,7 +2015,11 @@ impl SocialScanner {
         let is_wechat_encrypted_image = ext == "dat"
             && (path_str.contains("filestorage\\image") || path_str.contains("filestorage/image"));
 
-        if is_wechat_encrypted_image {
+        // 微信 4.0 的加密图片在 msg/attach/<会话>/<月份>/Img 下
+        let is_xwechat_encrypted_image = ext == "dat"
+            && (path_str.contains("\\msg\\attach\\") || path_str.contains("/msg/attach/"));
+
+        if is_wechat_encrypted_image || is_xwechat_encrypted_image {
             return (FileCategory::ImageVideo, RiskLevel::Low);
         }
 
@@ -1828,6 +2042,8 @@ impl SocialScanner {
             "/filerecv/",
             "\\msgattach\\", // 微信: MsgAttach
             "/msgattach/",
+            "\\msg\\file\\", // 微信 4.0: msg/file
+            "/msg/file/",
             "\\file_storage\\", // 飞书: file_storage
             "/file_storage/",
             "\\file\\", // 通用 File 目录
@@ -1870,6 +2086,79 @@ impl SocialScanner {
     }
 }
 
+/// 创建各分类的空统计，合并扫描结果时作为累加的起点
+fn empty_category_map() -> HashMap<FileCategory, SocialCategoryStats> {
+    [
+        FileCategory::ChatDatabase,
+        FileCategory::ImageVideo,
+        FileCategory::FileTransfer,
+        FileCategory::TempCache,
+        FileCategory::MomentsCache,
+    ]
+    .into_iter()
+    .map(|cat| {
+        (
+            cat,
+            SocialCategoryStats {
+                id: cat.id().to_string(),
+                name: cat.display_name().to_string(),
+                description: cat.description().to_string(),
+                file_count: 0,
+                total_size: 0,
+                deletable_count: 0,
+                deletable_size: 0,
+                files: Vec::new(),
+            },
+        )
+    })
+    .collect()
+}
+
+/// 把单个路径的扫描结果累加到分类统计
+fn merge_social_entries(
+    category_map: &mut HashMap<FileCategory, SocialCategoryStats>,
+    entries: Vec<SocialFileEntry>,
+) {
+    for entry in entries {
+        let Some(stats) = category_map.get_mut(&entry.category) else {
+            continue;
+        };
+        stats.file_count += 1;
+        stats.total_size += entry.size;
+        if entry.deletable {
+            stats.deletable_count += 1;
+            stats.deletable_size += entry.size;
+        }
+        stats.files.push(entry);
+    }
+}
+
+/// 按应用名称汇总各分类中的文件
+fn summarize_by_app(categories: &[SocialCategoryStats]) -> Vec<AppSummary> {
+    let mut totals: HashMap<&str, (usize, u64)> = HashMap::new();
+    for file in categories.iter().flat_map(|category| &category.files) {
+        let entry = totals.entry(file.app_name.as_str()).or_insert((0, 0));
+        entry.0 += 1;
+        entry.1 += file.size;
+    }
+
+    let mut by_app: Vec<AppSummary> = totals
+        .into_iter()
+        .map(|(app_name, (file_count, total_size))| AppSummary {
+            app_name: app_name.to_string(),
+            file_count,
+            total_size,
+        })
+        .collect();
+    by_app.sort_by(|left, right| {
+        right
+            .total_size
+            .cmp(&left.total_size)
+            .then_with(|| left.app_name.cmp(&right.app_name))
+    });
+    by_app
+}
+
 impl Default for SocialScanner {
     fn default() -> Self {
         Self::new()
@@ -1880,6 +2169,40 @@ impl Default for SocialScanner {
 // 单元测试
 // ============================================================================
 
+#[cfg(test)]
+impl SocialFileEntry {
+    /// 测试用条目：微信的可删除低风险图片，修改时间为 0
+    pub(crate) fn test_entry(path: &str, size: u64) -> Self {
+        SocialFileEntry {
+            path: path.to_string(),
+            size,
+            app_name: "微信".to_string(),
+            modified: 0,
+            category: FileCategory::ImageVideo,
+            risk_level: RiskLevel::Low,
+            deletable: true,
+        }
+    }
+}
+
+#[cfg(test)]
+impl SocialCategoryStats {
+    /// 测试用图片分类，数量和大小按文件列表汇总
+    pub(crate) fn test_category(files: Vec<SocialFileEntry>) -> Self {
+        let deletable: Vec<&SocialFileEntry> = files.iter().filter(|f| f.deletable).collect();
+        SocialCategoryStats {
+            id: "images".to_string(),
+            name: String::new(),
+            description: String::new(),
+            file_count: files.len(),
+            total_size: files.iter().map(|f| f.size).sum(),
+            deletable_count: deletable.len(),
+            deletable_size: deletable.iter().map(|f| f.size).sum(),
+            files,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1892,6 +2215,17 @@ mod tests {
         assert!(RiskLevel::None.is_deletable());
     }
 
+    #[test]
+    fn test_with_apps_limits_detection() {
+        let profile = Path::new("C:\\Users\\test");
+        let all = SocialScanner::for_profile(profile);
+        let qq_only = SocialScanner::for_profile(profile).with_apps(Some(vec!["QQ".to_string()]));
+
+        assert!(all.wants_app("wechat") && all.wants_app("telegram"));
+        assert!(qq_only.wants_app("qq"));
+        assert!(!qq_only.wants_app("wechat"));
+    }
+
     #[test]
     fn test_file_category_risk() {
         assert_eq!(
@@ -1911,4 +2245,91 @@ mod tests {
             RiskLevel::None
         );
     }
+
+    #[test]
+    fn test_classify_xwechat_layout() {
+        let scanner = SocialScanner::new();
+        let account = r"D:\WeChat\xwechat_files\wxid_test_1a2b";
+
+        let db = PathBuf::from(format!(r"{}\db_storage\message\message_0.db", account));
+        assert_eq!(
+            scanner.classify_file(&db, FileCategory::ChatDatabase),
+            (FileCategory::ChatDatabase, RiskLevel::Critical)
+        );
+
+        let image = PathBuf::from(format!(
+            r"{}\msg\attach\9e20f478\2024-05\Img\a1b2.dat",
+            account
+        ));
+        assert_eq!(
+            scanner.classify_file(&image, FileCategory::ImageVideo),
+            (FileCategory::ImageVideo, RiskLevel::Low)
+        );
+
+        let file = PathBuf::from(format!(r"{}\msg\file\2024-05\report.pdf", account));
+        assert_eq!(
+            scanner.classify_file(&file, FileCategory::FileTransfer),
+            (FileCategory::FileTransfer, RiskLevel::Medium)
+        );
+    }
+
+    #[test]
+    fn test_summarize_by_app() {
+        let qq_file = SocialFileEntry {
+            app_name: "QQ".to_string(),
+            ..SocialFileEntry::test_entry(r"C:\Data\QQ\50.dat", 50)
+        };
+        let categories = vec![
+            SocialCategoryStats::test_category(vec![
+                SocialFileEntry::test_entry(r"C:\Data\微信\100.dat", 100),
+                qq_file,
+            ]),
+            SocialCategoryStats::test_category(vec![SocialFileEntry::test_entry(
+                r"C:\Data\微信\300.dat",
+                300,
+            )]),
+        ];
+
+        let by_app = summarize_by_app(&categories);
+        assert_eq!(by_app.len(), 2);
+        assert_eq!(by_app[0].app_name, "微信");
+        assert_eq!(by_app[0].file_count, 2);
+        assert_eq!(by_app[0].total_size, 400);
+        assert_eq!(by_app[1].app_name, "QQ");
+        assert_eq!(by_app[1].total_size, 50);
+    }
+
+    #[test]
+    fn test_merge_social_entries_accumulates_partials_in_order() {
+        let database = SocialFileEntry {
+            category: FileCategory::ChatDatabase,
+            risk_level: RiskLevel::Critical,
+            deletable: false,
+            ..SocialFileEntry::test_entry(r"C:\Data\msg.db", 500)
+        };
+
+        let mut category_map = empty_category_map();
+        merge_social_entries(
+            &mut category_map,
+            vec![SocialFileEntry::test_entry(r"C:\Data\a.dat", 100), database],
+        );
+        merge_social_entries(
+            &mut category_map,
+            vec![SocialFileEntry::test_entry(r"C:\Data\b.dat", 30)],
+        );
+
+        let images = &category_map[&FileCategory::ImageVideo];
+        assert_eq!(images.file_count, 2);
+        assert_eq!(images.total_size, 130);
+        assert_eq!(images.deletable_size, 130);
+        assert_eq!(images.files[0].path, r"C:\Data\a.dat");
+        assert_eq!(images.files[1].path, r"C:\Data\b.dat");
+
+        let database = &category_map[&FileCategory::ChatDatabase];
+        assert_eq!(database.file_count, 1);
+        assert_eq!(database.total_size, 500);
+        assert_eq!(database.deletable_count, 0);
+        assert_eq!(database.deletable_size, 0);
+        assert_eq!(category_map[&FileCategory::TempCache].file_count, 0);
+    }
 }
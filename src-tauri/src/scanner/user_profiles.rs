@@ -0,0 +1,143 @@
+// ============================================================================
+// 多用户扫描
+//
+// 垃圾分类和社交软件扫描默认按当前进程的 %USERPROFILE% / %LOCALAPPDATA% 解析路径，
+// 只能清理运行程序的用户。家庭电脑上管理员需要一次清理所有用户的临时文件和缓存，
+// 这里枚举 C:\Users 下的配置文件，按配置文件目录显式拼出路径，逐个用户扫描。
+// 读取其他用户的目录需要管理员权限，由命令层检查。
+// ============================================================================
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{JunkCategory, ScanEngine, ScanResult, SocialScanResult, SocialScanner};
+use crate::cleaner::safety_constants::is_reparse_point;
+
+/// 不是真实用户的配置文件目录（小写）
+const SKIPPED_PROFILE_NAMES: &[&str] = &["default", "default user", "public", "all users"];
+
+/// 按用户扫描的垃圾分类（只包含用户级路径的分类）
+const PER_USER_CATEGORIES: &[JunkCategory] =
+    &[JunkCategory::WindowsTemp, JunkCategory::BrowserCache];
+
+/// 用户配置文件
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UserProfile {
+    /// 用户名（配置文件目录名）
+    pub user_name: String,
+    /// 配置文件目录，如 C:\Users\Alice
+    pub profile_dir: PathBuf,
+    /// 是否为运行程序的用户
+    pub is_current_user: bool,
+}
+
+/// 单个用户的扫描结果
+#[derive(Debug, Clone, Serialize)]
+pub struct UserProfileScanResult {
+    /// 用户名
+    pub user_name: String,
+    /// 配置文件目录
+    pub profile_dir: String,
+    /// 是否为运行程序的用户
+    pub is_current_user: bool,
+    /// 临时文件和浏览器缓存扫描结果
+    pub junk: ScanResult,
+    /// 社交软件缓存扫描结果
+    pub social: SocialScanResult,
+}
+
+/// 枚举系统盘 Users 目录下的用户配置文件
+pub fn enumerate_user_profiles() -> Vec<UserProfile> {
+    let system_drive = std::env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string());
+    let users_dir = PathBuf::from(format!("{}\\Users", system_drive));
+    list_profiles_in(&users_dir, current_profile_dir().as_deref())
+}
+
+fn current_profile_dir() -> Option<PathBuf> {
+    std::env::var_os("USERPROFILE").map(PathBuf::from)
+}
+
+/// 列出目录下的用户配置文件：跳过系统内置目录、重解析点和没有 AppData 的目录
+fn list_profiles_in(users_dir: &Path, current_profile: Option<&Path>) -> Vec<UserProfile> {
+    let Ok(entries) = fs::read_dir(users_dir) else {
+        log::warn!("无法读取用户目录: {}", users_dir.display());
+        return Vec::new();
+    };
+    let current = current_profile.map(|p| p.to_string_lossy().to_lowercase());
+
+    let mut profiles: Vec<UserProfile> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_lowercase();
+            !SKIPPED_PROFILE_NAMES.contains(&name.as_str())
+        })
+        .filter(|entry| {
+            fs::symlink_metadata(entry.path())
+                .is_ok_and(|metadata| metadata.is_dir() && !is_reparse_point(&metadata))
+        })
+        .filter(|entry| entry.path().join("AppData").is_dir())
+        .map(|entry| {
+            let profile_dir = entry.path();
+            let is_current_user =
+                current.as_deref() == Some(profile_dir.to_string_lossy().to_lowercase().as_str());
+            UserProfile {
+                user_name: entry.file_name().to_string_lossy().into_owned(),
+                profile_dir,
+                is_current_user,
+            }
+        })
+        .collect();
+    profiles.sort_by_key(|a| a.user_name.to_lowercase());
+    profiles
+}
+
+/// 逐个用户扫描临时文件、浏览器缓存和社交软件缓存
+pub fn scan_user_profiles() -> Vec<UserProfileScanResult> {
+    enumerate_user_profiles()
+        .into_iter()
+        .map(|profile| {
+            log::info!("扫描用户 {} 的配置文件", profile.user_name);
+            let junk = ScanEngine::new()
+                .with_categories(PER_USER_CATEGORIES.to_vec())
+                .with_user_profile(profile.profile_dir.clone())
+                .scan();
+            // 运行程序的用户仍使用完整扫描器，保留注册表自定义路径和全盘搜索
+            let social = if profile.is_current_user {
+                SocialScanner::new().scan()
+            } else {
+                SocialScanner::for_profile(&profile.profile_dir).scan()
+            };
+            UserProfileScanResult {
+                user_name: profile.user_name,
+                profile_dir: profile.profile_dir.to_string_lossy().into_owned(),
+                is_current_user: profile.is_current_user,
+                junk,
+                social,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_profiles_skips_builtin_and_empty_dirs() {
+        let users = std::env::temp_dir().join(format!("lightc-users-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&users);
+        for name in ["Bob", "alice", "Public", "Default"] {
+            fs::create_dir_all(users.join(name).join("AppData")).unwrap();
+        }
+        fs::create_dir_all(users.join("NoAppData")).unwrap();
+
+        let profiles = list_profiles_in(&users, Some(&users.join("Bob")));
+        let _ = fs::remove_dir_all(&users);
+
+        let names: Vec<&str> = profiles.iter().map(|p| p.user_name.as_str()).collect();
+        assert_eq!(names, vec!["alice", "Bob"]);
+        assert!(!profiles[0].is_current_user);
+        assert!(profiles[1].is_current_user);
+    }
+}
@@ -0,0 +1,260 @@
+// ============================================================================
+// 用户白名单
+//
+// 卸载残留的内置白名单（build_whitelist_rules）是编译期常量，注册表扫描没有内置白名单，
+// 用户无法保护自己的应用不被标记。这里把用户追加的条目持久化到数据目录，
+// 扫描器初始化时与内置规则合并；内置条目只读，不可移除。
+//
+// 条目语法与内置 Pattern 规则一致：不区分大小写，"name" 精确匹配，
+// "name*" 前缀匹配，"*name" 后缀匹配。
+//
+// 注册表忽略列表按完整键路径记录用户明确要保留的单个条目，不做模式匹配，
+// 与白名单存放在同一个文件里。
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::Mutex;
+
+use super::{builtin_leftover_whitelist, is_builtin_leftover_whitelisted};
+
+/// 用户白名单文件名
+const USER_WHITELIST_FILE: &str = "user_whitelist.json";
+
+/// 串行化读改写，避免并发的添加/移除互相覆盖
+static USER_WHITELIST_LOCK: Mutex<()> = Mutex::new(());
+
+/// 白名单作用的扫描器
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WhitelistKind {
+    /// 卸载残留扫描的文件夹名
+    LeftoverFolder,
+    /// 注册表残留扫描的 HKCR\Applications 子键名
+    RegistryKey,
+}
+
+/// 白名单条目（`get_whitelist` 返回内置和用户条目）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhitelistEntry {
+    pub kind: WhitelistKind,
+    /// 匹配模式（小写）
+    pub pattern: String,
+    /// 是否为内置条目（内置条目不可移除）
+    pub builtin: bool,
+}
+
+/// 持久化的用户白名单
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UserWhitelist {
+    #[serde(default)]
+    pub leftover_folders: Vec<String>,
+    #[serde(default)]
+    pub registry_keys: Vec<String>,
+    /// 忽略的注册表条目完整路径（小写，不含末尾反斜杠）
+    #[serde(default)]
+    pub ignored_registry_entries: Vec<String>,
+}
+
+impl UserWhitelist {
+    fn patterns_mut(&mut self, kind: WhitelistKind) -> &mut Vec<String> {
+        match kind {
+            WhitelistKind::LeftoverFolder => &mut self.leftover_folders,
+            WhitelistKind::RegistryKey => &mut self.registry_keys,
+        }
+    }
+}
+
+/// 检查名称是否命中白名单模式（`pattern` 与 `name_lower` 均为小写）
+pub(crate) fn matches_whitelist_pattern(pattern: &str, name_lower: &str) -> bool {
+    if let Some(prefix) = pattern.strip_suffix('*') {
+        name_lower.starts_with(prefix)
+    } else if let Some(suffix) = pattern.strip_prefix('*') {
+        name_lower.ends_with(suffix)
+    } else {
+        name_lower == pattern
+    }
+}
+
+/// 从数据目录加载用户白名单，文件不存在或损坏时返回空白名单
+pub fn load_user_whitelist() -> UserWhitelist {
+    let path = crate::data_dir::get_data_dir().join(USER_WHITELIST_FILE);
+    match fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+            log::warn!("用户白名单文件损坏，已忽略 {}: {}", path.display(), e);
+            UserWhitelist::default()
+        }),
+        Err(_) => UserWhitelist::default(),
+    }
+}
+
+/// 先写临时文件再重命名，写入中途失败不会留下截断的白名单文件
+fn save_user_whitelist(whitelist: &UserWhitelist) -> Result<(), String> {
+    let dir = crate::data_dir::get_data_dir();
+    fs::create_dir_all(&dir).map_err(|e| format!("无法创建数据目录: {}", e))?;
+    let json = serde_json::to_string_pretty(whitelist)
+        .map_err(|e| format!("序列化用户白名单失败: {}", e))?;
+    let path = dir.join(USER_WHITELIST_FILE);
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, json).map_err(|e| format!("写入用户白名单失败: {}", e))?;
+    fs::rename(&temp_path, &path).map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        format!("保存用户白名单失败: {}", e)
+    })
+}
+
+/// 返回内置条目和用户条目，内置条目在前
+pub fn get_whitelist() -> Vec<WhitelistEntry> {
+    let user = load_user_whitelist();
+    let entry = |kind, pattern: String, builtin| WhitelistEntry {
+        kind,
+        pattern,
+        builtin,
+    };
+
+    let mut entries: Vec<WhitelistEntry> = builtin_leftover_whitelist()
+        .into_iter()
+        .map(|pattern| entry(WhitelistKind::LeftoverFolder, pattern, true))
+        .collect();
+    entries.extend(
+        user.leftover_folders
+            .into_iter()
+            .map(|pattern| entry(WhitelistKind::LeftoverFolder, pattern, false)),
+    );
+    entries.extend(
+        user.registry_keys
+            .into_iter()
+            .map(|pattern| entry(WhitelistKind::RegistryKey, pattern, false)),
+    );
+    entries
+}
+
+/// 添加用户白名单条目，已被内置或用户条目覆盖时不重复添加
+pub fn add_whitelist_entry(kind: WhitelistKind, pattern: &str) -> Result<(), String> {
+    let pattern = normalize_pattern(pattern)?;
+    if is_builtin(kind, &pattern) {
+        return Ok(());
+    }
+
+    let _guard = USER_WHITELIST_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut whitelist = load_user_whitelist();
+    let patterns = whitelist.patterns_mut(kind);
+    if patterns.contains(&pattern) {
+        return Ok(());
+    }
+    log::info!("添加用户白名单条目 {:?}: {}", kind, pattern);
+    patterns.push(pattern);
+    save_user_whitelist(&whitelist)
+}
+
+/// 移除用户白名单条目，内置条目不可移除
+pub fn remove_whitelist_entry(kind: WhitelistKind, pattern: &str) -> Result<(), String> {
+    let pattern = normalize_pattern(pattern)?;
+    if is_builtin(kind, &pattern) {
+        return Err(format!("「{}」是内置白名单条目，不能移除", pattern));
+    }
+
+    let _guard = USER_WHITELIST_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut whitelist = load_user_whitelist();
+    let patterns = whitelist.patterns_mut(kind);
+    let before = patterns.len();
+    patterns.retain(|existing| existing != &pattern);
+    if patterns.len() == before {
+        return Err(format!("白名单中没有「{}」", pattern));
+    }
+    log::info!("移除用户白名单条目 {:?}: {}", kind, pattern);
+    save_user_whitelist(&whitelist)
+}
+
+/// 返回忽略的注册表条目路径
+pub fn get_ignored_registry_entries() -> Vec<String> {
+    load_user_whitelist().ignored_registry_entries
+}
+
+/// 忽略注册表条目，之后的扫描结果不再包含该键
+pub fn ignore_registry_entry(path: &str) -> Result<(), String> {
+    let path = normalize_registry_path(path)?;
+    let _guard = USER_WHITELIST_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut whitelist = load_user_whitelist();
+    if whitelist.ignored_registry_entries.contains(&path) {
+        return Ok(());
+    }
+    log::info!("忽略注册表条目: {}", path);
+    whitelist.ignored_registry_entries.push(path);
+    save_user_whitelist(&whitelist)
+}
+
+/// 取消忽略注册表条目
+pub fn unignore_registry_entry(path: &str) -> Result<(), String> {
+    let path = normalize_registry_path(path)?;
+    let _guard = USER_WHITELIST_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    let mut whitelist = load_user_whitelist();
+    let before = whitelist.ignored_registry_entries.len();
+    whitelist
+        .ignored_registry_entries
+        .retain(|existing| existing != &path);
+    if whitelist.ignored_registry_entries.len() == before {
+        return Err(format!("忽略列表中没有「{}」", path));
+    }
+    log::info!("取消忽略注册表条目: {}", path);
+    save_user_whitelist(&whitelist)
+}
+
+/// 规范化注册表键路径：去除首尾空白和末尾反斜杠并转小写，只接受完整路径
+pub(crate) fn normalize_registry_path(path: &str) -> Result<String, String> {
+    let path = path.trim().trim_end_matches('\\').to_lowercase();
+    if !path.starts_with("hkey_") || !path.contains('\\') {
+        return Err(format!("无效的注册表键路径: {}", path));
+    }
+    Ok(path)
+}
+
+/// 规范化用户输入：去除首尾空白并转小写，拒绝空模式和单独的通配符
+fn normalize_pattern(pattern: &str) -> Result<String, String> {
+    let pattern = pattern.trim().to_lowercase();
+    if pattern.trim_matches('*').is_empty() {
+        return Err("白名单条目不能为空或只包含通配符".to_string());
+    }
+    Ok(pattern)
+}
+
+fn is_builtin(kind: WhitelistKind, pattern: &str) -> bool {
+    match kind {
+        WhitelistKind::LeftoverFolder => is_builtin_leftover_whitelisted(pattern),
+        WhitelistKind::RegistryKey => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whitelist_pattern_and_builtin_entries() {
+        assert!(matches_whitelist_pattern("myapp", "myapp"));
+        assert!(!matches_whitelist_pattern("myapp", "myapp2"));
+        assert!(matches_whitelist_pattern("myapp*", "myapp2"));
+        assert!(matches_whitelist_pattern("*studio", "my studio"));
+
+        assert!(is_builtin(WhitelistKind::LeftoverFolder, "microsoft"));
+        assert!(!is_builtin(WhitelistKind::RegistryKey, "myapp.exe"));
+
+        assert!(remove_whitelist_entry(WhitelistKind::LeftoverFolder, "Microsoft").is_err());
+        assert!(normalize_pattern(" * ").is_err());
+        assert_eq!(normalize_pattern(" MyApp* ").unwrap(), "myapp*");
+
+        assert_eq!(
+            normalize_registry_path(" HKEY_CLASSES_ROOT\\Applications\\Old.exe\\ ").unwrap(),
+            "hkey_classes_root\\applications\\old.exe"
+        );
+        assert!(normalize_registry_path("old.exe").is_err());
+    }
+}
@@ -0,0 +1,176 @@
+// ============================================================================
+// UWP 应用缓存
+//
+// 商店应用把缓存放在 %LocalAppData%\Packages\<包名>\ 下的 LocalCache、TempState、
+// AC\Temp 中，普通分类不扫描 Packages，资源管理器默认也看不到，常常累积数 GB。
+// 这里按包汇总这三个缓存目录的大小；清理时只删除缓存目录里的内容，
+// LocalState、Settings 等保存应用数据和设置的目录永远不碰。
+// ============================================================================
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::cleaner::safety_constants::{is_reparse_point, is_reparse_point_dir};
+
+/// 包目录下允许清理的缓存子目录
+const UWP_CACHE_SUBDIRS: &[&str] = &["LocalCache", "TempState", "AC\\Temp"];
+
+/// 单个 UWP 包的缓存汇总
+#[derive(Debug, Clone, Serialize)]
+pub struct UwpPackageCache {
+    /// 包名，如 Microsoft.WindowsStore_8wekyb3d8bbwe
+    pub package_name: String,
+    /// 包目录
+    pub package_dir: String,
+    /// 缓存总大小（字节）
+    pub cache_size: u64,
+    /// 缓存文件数
+    pub file_count: usize,
+    /// 存在的缓存子目录
+    pub cache_dirs: Vec<String>,
+}
+
+/// 枚举当前用户的 UWP 包，返回有缓存的包（按缓存大小降序）
+pub fn scan_uwp_caches() -> Vec<UwpPackageCache> {
+    match packages_dir() {
+        Some(dir) => scan_packages_in(&dir),
+        None => Vec::new(),
+    }
+}
+
+/// 列出指定包缓存目录里的直接子项，交给删除引擎删除；缓存目录本身保留
+pub fn uwp_cache_paths(package_names: &[String]) -> Result<Vec<String>, String> {
+    let packages_dir = packages_dir().ok_or_else(|| "无法定位 Packages 目录".to_string())?;
+    cache_entries_for(&packages_dir, package_names)
+}
+
+fn packages_dir() -> Option<PathBuf> {
+    std::env::var_os("LOCALAPPDATA").map(|dir| PathBuf::from(dir).join("Packages"))
+}
+
+fn scan_packages_in(packages_dir: &Path) -> Vec<UwpPackageCache> {
+    let Ok(entries) = fs::read_dir(packages_dir) else {
+        log::warn!("无法读取 Packages 目录: {}", packages_dir.display());
+        return Vec::new();
+    };
+
+    let mut packages: Vec<UwpPackageCache> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+        .filter_map(|entry| summarize_package(&entry.path()))
+        .collect();
+    packages.sort_by_key(|b| std::cmp::Reverse(b.cache_size));
+    packages
+}
+
+fn summarize_package(package_dir: &Path) -> Option<UwpPackageCache> {
+    let mut cache_size = 0;
+    let mut file_count = 0;
+    let mut cache_dirs = Vec::new();
+
+    for cache_dir in existing_cache_dirs(package_dir) {
+        for entry in WalkDir::new(&cache_dir)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|entry| !is_reparse_point_dir(entry))
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+        {
+            if let Ok(metadata) = entry.metadata() {
+                cache_size += metadata.len();
+                file_count += 1;
+            }
+        }
+        cache_dirs.push(cache_dir.to_string_lossy().into_owned());
+    }
+
+    if file_count == 0 {
+        return None;
+    }
+    Some(UwpPackageCache {
+        package_name: package_dir.file_name()?.to_string_lossy().into_owned(),
+        package_dir: package_dir.to_string_lossy().into_owned(),
+        cache_size,
+        file_count,
+        cache_dirs,
+    })
+}
+
+/// 包目录下实际存在、且不是重解析点的缓存子目录
+fn existing_cache_dirs(package_dir: &Path) -> Vec<PathBuf> {
+    UWP_CACHE_SUBDIRS
+        .iter()
+        .map(|subdir| {
+            subdir
+                .split('\\')
+                .fold(package_dir.to_path_buf(), |dir, part| dir.join(part))
+        })
+        .filter(|dir| {
+            fs::symlink_metadata(dir)
+                .is_ok_and(|metadata| metadata.is_dir() && !is_reparse_point(&metadata))
+        })
+        .collect()
+}
+
+/// 展开指定包的缓存目录内容，包名必须是 Packages 下的单级目录名
+fn cache_entries_for(packages_dir: &Path, package_names: &[String]) -> Result<Vec<String>, String> {
+    let mut paths = Vec::new();
+    for name in package_names {
+        // 包名来自前端，拒绝路径分隔符和 ..，确保只能落在 Packages 的直接子目录
+        if name.is_empty() || name.contains(['\\', '/']) || name == "." || name == ".." {
+            return Err(format!("无效的包名: {}", name));
+        }
+        let package_dir = packages_dir.join(name);
+        if !package_dir.is_dir() {
+            return Err(format!("UWP 包不存在: {}", name));
+        }
+        for cache_dir in existing_cache_dirs(&package_dir) {
+            let Ok(entries) = fs::read_dir(&cache_dir) else {
+                continue;
+            };
+            paths.extend(
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path().to_string_lossy().into_owned()),
+            );
+        }
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uwp_cache_only_counts_and_cleans_cache_subdirs() {
+        let root = std::env::temp_dir().join(format!("lightc-uwp-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let package = root.join("Contoso.App_abc123");
+        fs::create_dir_all(package.join("LocalCache").join("img")).unwrap();
+        fs::create_dir_all(package.join("AC").join("Temp")).unwrap();
+        fs::create_dir_all(package.join("LocalState")).unwrap();
+        fs::write(
+            package.join("LocalCache").join("img").join("a.bin"),
+            vec![0u8; 100],
+        )
+        .unwrap();
+        fs::write(package.join("AC").join("Temp").join("b.tmp"), vec![0u8; 50]).unwrap();
+        fs::write(package.join("LocalState").join("data.db"), vec![0u8; 999]).unwrap();
+        fs::create_dir_all(root.join("Empty.App_xyz")).unwrap();
+
+        let packages = scan_packages_in(&root);
+        let entries = cache_entries_for(&root, &["Contoso.App_abc123".to_string()]).unwrap();
+        let rejected = cache_entries_for(&root, &["..\\Windows".to_string()]);
+        let _ = fs::remove_dir_all(&root);
+
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].cache_size, 150);
+        assert_eq!(packages[0].file_count, 2);
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().all(|path| !path.contains("LocalState")));
+        assert!(rejected.is_err());
+    }
+}
@@ -0,0 +1,269 @@
+// ============================================================================
+// 全局设置存储
+//
+// 清理保留策略、并发数、扫描范围等选项统一保存在数据目录的 settings.json，
+// 避免每个功能各自维护一个小 JSON 文件，也避免多个命令并发读改写互相覆盖。
+//
+// - 写入先落到临时文件再重命名，异常退出时不会留下半截 JSON
+// - `schema_version` 记录文件格式版本，读取旧版本时在 `migrate` 中补齐
+// - 前端只提交需要修改的字段，未提交的字段保持原值
+// ============================================================================
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+/// 设置文件名
+const SETTINGS_FILE: &str = "settings.json";
+
+/// 当前设置格式版本
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
+
+/// 串行化设置的读改写
+static SETTINGS_LOCK: Mutex<()> = Mutex::new(());
+
+/// 应用设置
+///
+/// 新增字段必须带 `#[serde(default)]`，保证旧版本写出的文件仍能读取。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// 设置文件格式版本，文件中缺失时视为 0（引入版本号之前写出的文件）
+    #[serde(default)]
+    pub schema_version: u32,
+    /// 删除后是否清理扫描根目录内留下的空目录
+    pub prune_empty_dirs: bool,
+    /// 永久删除的最大并发线程数，为空时使用 CPU 核心数
+    pub delete_max_concurrency: Option<usize>,
+    /// 是否允许扫描网络驱动器和可移动磁盘
+    pub allow_network_scan: bool,
+    /// 快速扫描每个分类首次返回的最大文件数，为空时不限制
+    pub max_files_per_category: Option<usize>,
+    /// 收藏的清理分类（显示名称），前端加载时默认勾选
+    pub favorite_categories: Vec<String>,
+    /// `format_size` 命令使用的大小单位
+    pub size_units: SizeUnits,
+    /// 注册表删除、旧驱动清理、WinSxS 清理前自动创建系统还原点，创建失败时取消操作
+    pub auto_restore_point_before_risky_ops: bool,
+}
+
+/// 文件大小显示单位
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SizeUnits {
+    /// 1024 进制，标注 KB/MB/GB（与资源管理器一致，默认）
+    #[default]
+    Windows,
+    /// 1024 进制，标注 KiB/MiB/GiB
+    Binary,
+    /// 1000 进制，标注 KB/MB/GB（与硬盘标称容量一致）
+    Decimal,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
+            prune_empty_dirs: false,
+            delete_max_concurrency: None,
+            allow_network_scan: false,
+            max_files_per_category: None,
+            favorite_categories: Vec::new(),
+            size_units: SizeUnits::Windows,
+            auto_restore_point_before_risky_ops: false,
+        }
+    }
+}
+
+impl AppSettings {
+    /// 校验设置取值
+    fn validate(&self) -> Result<(), String> {
+        if self.delete_max_concurrency == Some(0) {
+            return Err("删除并发数必须大于 0".to_string());
+        }
+        if self.max_files_per_category == Some(0) {
+            return Err("每个分类返回的文件数必须大于 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// 读取设置，文件不存在或损坏时返回默认值
+pub fn get_settings() -> AppSettings {
+    let _guard = SETTINGS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    load_settings(&settings_path())
+}
+
+/// 合并部分字段并保存，返回保存后的完整设置
+///
+/// # 中文说明
+/// `patch` 必须是 JSON 对象，只包含需要修改的字段；`schema_version` 由后端维护，提交时忽略。
+/// 字段类型不匹配或取值非法时拒绝保存，原设置保持不变。
+pub fn update_settings(patch: serde_json::Value) -> Result<AppSettings, String> {
+    let _guard = SETTINGS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = settings_path();
+    let updated = merge_settings(&load_settings(&path), patch)?;
+    save_settings(&path, &updated)?;
+    Ok(updated)
+}
+
+/// 收藏或取消收藏一个分类，返回更新后的收藏列表
+///
+/// 分类名称是否有效由调用方检查，这里只负责在同一把锁内完成读改写。
+pub fn toggle_favorite_category(category_name: &str) -> Result<Vec<String>, String> {
+    let _guard = SETTINGS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let path = settings_path();
+    let mut settings = load_settings(&path);
+    toggle_favorite(&mut settings.favorite_categories, category_name);
+    save_settings(&path, &settings)?;
+    Ok(settings.favorite_categories)
+}
+
+fn toggle_favorite(favorites: &mut Vec<String>, category_name: &str) {
+    if let Some(index) = favorites.iter().position(|name| name == category_name) {
+        favorites.remove(index);
+    } else {
+        favorites.push(category_name.to_string());
+    }
+}
+
+fn settings_path() -> PathBuf {
+    crate::data_dir::get_data_dir().join(SETTINGS_FILE)
+}
+
+fn merge_settings(current: &AppSettings, patch: serde_json::Value) -> Result<AppSettings, String> {
+    let serde_json::Value::Object(patch) = patch else {
+        return Err("设置格式无效，应为 JSON 对象".to_string());
+    };
+
+    let mut merged = serde_json::to_value(current).map_err(|e| format!("序列化设置失败: {}", e))?;
+    if let Some(fields) = merged.as_object_mut() {
+        for (key, value) in patch {
+            if key == "schema_version" {
+                continue;
+            }
+            if !fields.contains_key(&key) {
+                return Err(format!("未知的设置项: {}", key));
+            }
+            fields.insert(key, value);
+        }
+    }
+
+    let settings: AppSettings =
+        serde_json::from_value(merged).map_err(|e| format!("设置取值无效: {}", e))?;
+    settings.validate()?;
+    Ok(settings)
+}
+
+fn load_settings(path: &Path) -> AppSettings {
+    let json = match fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(_) => return AppSettings::default(),
+    };
+    match serde_json::from_str::<AppSettings>(&json) {
+        Ok(settings) => migrate(settings),
+        Err(e) => {
+            log::warn!("设置文件损坏，使用默认设置 {}: {}", path.display(), e);
+            AppSettings::default()
+        }
+    }
+}
+
+/// 将旧版本设置升级到当前格式
+fn migrate(mut settings: AppSettings) -> AppSettings {
+    if settings.schema_version == 0 {
+        // v0 → v1：字段相同，缺失的字段已由 serde 补为默认值
+        log::info!("设置文件从版本 0 升级到 {}", SETTINGS_SCHEMA_VERSION);
+    } else if settings.schema_version > SETTINGS_SCHEMA_VERSION {
+        // 新版本写出的文件：保留能识别的字段，保存时会降为当前版本
+        log::warn!(
+            "设置文件版本 {} 高于当前支持的版本 {}",
+            settings.schema_version,
+            SETTINGS_SCHEMA_VERSION
+        );
+    }
+    settings.schema_version = SETTINGS_SCHEMA_VERSION;
+    settings
+}
+
+/// 先写临时文件再重命名，保证设置文件始终是完整的 JSON
+fn save_settings(path: &Path, settings: &AppSettings) -> Result<(), String> {
+    let Some(parent) = path.parent() else {
+        return Err(format!("设置文件路径无效: {}", path.display()));
+    };
+    fs::create_dir_all(parent)
+        .map_err(|e| format!("创建数据目录失败 {}: {}", parent.display(), e))?;
+
+    let json =
+        serde_json::to_string_pretty(settings).map_err(|e| format!("序列化设置失败: {}", e))?;
+    let temp_path = path.with_extension("json.tmp");
+    fs::write(&temp_path, json)
+        .map_err(|e| format!("写入设置文件失败 {}: {}", temp_path.display(), e))?;
+    fs::rename(&temp_path, path).map_err(|e| {
+        let _ = fs::remove_file(&temp_path);
+        format!("保存设置文件失败 {}: {}", path.display(), e)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_settings_and_atomic_save() {
+        let current = AppSettings::default();
+        let updated = merge_settings(
+            &current,
+            serde_json::json!({ "prune_empty_dirs": true, "schema_version": 99 }),
+        )
+        .unwrap();
+        assert!(updated.prune_empty_dirs);
+        assert_eq!(updated.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert_eq!(updated.delete_max_concurrency, None);
+
+        assert!(merge_settings(&current, serde_json::json!({ "unknown": 1 })).is_err());
+        assert!(
+            merge_settings(&current, serde_json::json!({ "delete_max_concurrency": 0 })).is_err()
+        );
+        assert!(merge_settings(&current, serde_json::json!([])).is_err());
+
+        let dir = std::env::temp_dir().join(format!("lightc-settings-{}", std::process::id()));
+        let path = dir.join(SETTINGS_FILE);
+        save_settings(&path, &updated).unwrap();
+        assert_eq!(load_settings(&path), updated);
+        assert!(!path.with_extension("json.tmp").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_toggle_favorite_adds_then_removes() {
+        let mut favorites = vec!["回收站".to_string()];
+        toggle_favorite(&mut favorites, "Windows临时文件");
+        assert_eq!(favorites, vec!["回收站", "Windows临时文件"]);
+        toggle_favorite(&mut favorites, "回收站");
+        assert_eq!(favorites, vec!["Windows临时文件"]);
+    }
+
+    #[test]
+    fn test_load_settings_fills_missing_fields() {
+        let legacy: AppSettings = serde_json::from_str("{}").unwrap();
+        assert_eq!(legacy.schema_version, 0);
+
+        let dir =
+            std::env::temp_dir().join(format!("lightc-settings-legacy-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(SETTINGS_FILE);
+        fs::write(&path, r#"{ "allow_network_scan": true }"#).unwrap();
+
+        let settings = load_settings(&path);
+        assert!(settings.allow_network_scan);
+        assert!(!settings.prune_empty_dirs);
+        assert!(settings.favorite_categories.is_empty());
+        assert_eq!(settings.size_units, SizeUnits::Windows);
+        assert!(!settings.auto_restore_point_before_risky_ops);
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
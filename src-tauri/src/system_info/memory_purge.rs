@@ -0,0 +1,149 @@
+// ============================================================================
+// 待机内存清理
+//
+// 待机列表（Standby List）是系统缓存的已释放页面，任务管理器里算作"已缓存"。
+// 通过 NtSetSystemInformation(SystemMemoryListInformation, MemoryPurgeStandbyList)
+// 清空待机列表，与 RAMMap "Empty Standby List" 相同。需要管理员权限，
+// 并在当前进程令牌中启用 SeProfileSingleProcessPrivilege。
+// ============================================================================
+
+/// 清空待机内存列表，返回前后可用物理内存的差值（字节）
+///
+/// # 中文说明
+/// 返回值由 GlobalMemoryStatusEx 前后两次采样计算，其他进程同时申请内存时
+/// 可能小于实际释放量，结果为负时按 0 返回。
+pub fn clear_standby_memory() -> Result<u64, String> {
+    if !crate::system_slim::check_admin() {
+        return Err("需要管理员权限才能清理待机内存，请以管理员身份运行程序".to_string());
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        const SYSTEM_MEMORY_LIST_INFORMATION: u32 = 80;
+        const MEMORY_PURGE_STANDBY_LIST: i32 = 4;
+        const STATUS_PRIVILEGE_NOT_HELD: i32 = 0xC000_0061_u32 as i32;
+
+        #[link(name = "ntdll")]
+        extern "system" {
+            fn NtSetSystemInformation(
+                system_information_class: u32,
+                system_information: *mut std::ffi::c_void,
+                system_information_length: u32,
+            ) -> i32;
+        }
+
+        enable_privilege("SeProfileSingleProcessPrivilege")?;
+
+        let before = available_physical_memory()?;
+        let mut command = MEMORY_PURGE_STANDBY_LIST;
+        let status = unsafe {
+            NtSetSystemInformation(
+                SYSTEM_MEMORY_LIST_INFORMATION,
+                &mut command as *mut i32 as *mut std::ffi::c_void,
+                std::mem::size_of::<i32>() as u32,
+            )
+        };
+        if status == STATUS_PRIVILEGE_NOT_HELD {
+            return Err("缺少 SeProfileSingleProcessPrivilege 权限，无法清理待机内存".to_string());
+        }
+        if status < 0 {
+            return Err(format!(
+                "清理待机内存失败 (NTSTATUS 0x{:08X})",
+                status as u32
+            ));
+        }
+        let after = available_physical_memory()?;
+
+        let reclaimed = after.saturating_sub(before);
+        log::info!("待机内存已清理，可用内存增加 {} 字节", reclaimed);
+        Ok(reclaimed)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("此功能仅支持Windows系统".to_string())
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn available_physical_memory() -> Result<u64, String> {
+    use winapi::um::sysinfoapi::{GlobalMemoryStatusEx, MEMORYSTATUSEX};
+
+    let mut status: MEMORYSTATUSEX = unsafe { std::mem::zeroed() };
+    status.dwLength = std::mem::size_of::<MEMORYSTATUSEX>() as u32;
+    if unsafe { GlobalMemoryStatusEx(&mut status) } == 0 {
+        return Err("查询内存状态失败".to_string());
+    }
+    Ok(status.ullAvailPhys)
+}
+
+/// 在当前进程令牌中启用指定特权（管理员令牌默认持有但未启用）
+#[cfg(target_os = "windows")]
+fn enable_privilege(name: &str) -> Result<(), String> {
+    use std::os::windows::ffi::OsStrExt;
+    use winapi::um::errhandlingapi::GetLastError;
+    use winapi::um::handleapi::CloseHandle;
+    use winapi::um::processthreadsapi::{GetCurrentProcess, OpenProcessToken};
+    use winapi::um::securitybaseapi::AdjustTokenPrivileges;
+    use winapi::um::winbase::LookupPrivilegeValueW;
+    use winapi::um::winnt::{
+        HANDLE, SE_PRIVILEGE_ENABLED, TOKEN_ADJUST_PRIVILEGES, TOKEN_PRIVILEGES, TOKEN_QUERY,
+    };
+
+    const ERROR_NOT_ALL_ASSIGNED: u32 = 1300;
+
+    let wide_name: Vec<u16> = std::ffi::OsStr::new(name)
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+
+    unsafe {
+        let mut token: HANDLE = std::ptr::null_mut();
+        if OpenProcessToken(
+            GetCurrentProcess(),
+            TOKEN_ADJUST_PRIVILEGES | TOKEN_QUERY,
+            &mut token,
+        ) == 0
+        {
+            return Err(format!("打开进程令牌失败 (错误码 {})", GetLastError()));
+        }
+
+        let mut privileges: TOKEN_PRIVILEGES = std::mem::zeroed();
+        privileges.PrivilegeCount = 1;
+        privileges.Privileges[0].Attributes = SE_PRIVILEGE_ENABLED;
+        let result = if LookupPrivilegeValueW(
+            std::ptr::null(),
+            wide_name.as_ptr(),
+            &mut privileges.Privileges[0].Luid,
+        ) == 0
+        {
+            Err(format!(
+                "查询特权 {} 失败 (错误码 {})",
+                name,
+                GetLastError()
+            ))
+        } else if AdjustTokenPrivileges(
+            token,
+            0,
+            &mut privileges,
+            0,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        ) == 0
+        {
+            Err(format!(
+                "启用特权 {} 失败 (错误码 {})",
+                name,
+                GetLastError()
+            ))
+        } else if GetLastError() == ERROR_NOT_ALL_ASSIGNED {
+            // AdjustTokenPrivileges 在令牌不持有该特权时也返回成功，只能通过 GetLastError 判断
+            Err(format!("当前令牌不持有特权 {}", name))
+        } else {
+            Ok(())
+        };
+
+        CloseHandle(token);
+        result
+    }
+}
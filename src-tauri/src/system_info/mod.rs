@@ -1,10 +1,14 @@
 // ============================================================================
 // 系统信息模块
-// 获取 OS 版本、CPU、内存、架构、运行时间等系统信息
+// 获取 OS 版本、CPU、内存、架构、运行时间、系统盘类型等系统信息
 // ============================================================================
 
 use serde::{Deserialize, Serialize};
 
+mod memory_purge;
+
+pub use memory_purge::clear_standby_memory;
+
 /// 系统信息结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemInfo {
@@ -28,6 +32,8 @@ pub struct SystemInfo {
     pub available_memory: u64,
     /// 系统启动时间（秒）
     pub uptime_seconds: u64,
+    /// 系统盘类型："SSD" / "HDD"，无法识别时为"未知"
+    pub c_drive_type: String,
 }
 
 /// 获取系统信息
@@ -48,6 +54,11 @@ pub fn gather() -> Result<SystemInfo, String> {
         };
         let (total_memory, available_memory) = get_memory_info();
         let uptime_seconds = unsafe { winapi::um::sysinfoapi::GetTickCount64() / 1000 };
+        let system_drive = std::env::var("SYSTEMDRIVE").unwrap_or_else(|_| "C:".to_string());
+        let c_drive_type = get_drive_media_type(&system_drive).unwrap_or_else(|e| {
+            log::warn!("无法识别系统盘类型: {}", e);
+            "未知".to_string()
+        });
 
         Ok(SystemInfo {
             os_name: "Microsoft Windows".to_string(),
@@ -60,11 +71,113 @@ pub fn gather() -> Result<SystemInfo, String> {
             total_memory,
             available_memory,
             uptime_seconds,
+            c_drive_type,
+        })
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("此功能仅支持Windows系统".to_string())
+    }
+}
+
+/// 查询磁盘介质类型，返回 "SSD" 或 "HDD"
+///
+/// # 中文说明
+/// 通过 IOCTL_STORAGE_QUERY_PROPERTY 查询 StorageDeviceSeekPenaltyProperty：
+/// 无寻道开销即为 SSD。打开卷设备时不申请读写权限，普通用户也可以查询。
+pub fn get_drive_media_type(drive: &str) -> Result<String, String> {
+    let letter = drive
+        .trim()
+        .chars()
+        .next()
+        .filter(|ch| ch.is_ascii_alphabetic())
+        .ok_or_else(|| format!("无效的盘符: {}", drive))?
+        .to_ascii_uppercase();
+
+    #[cfg(target_os = "windows")]
+    {
+        use winapi::shared::minwindef::{DWORD, LPVOID};
+        use winapi::um::errhandlingapi::GetLastError;
+        use winapi::um::fileapi::{CreateFileW, OPEN_EXISTING};
+        use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+        use winapi::um::ioapiset::DeviceIoControl;
+        use winapi::um::winnt::{FILE_SHARE_READ, FILE_SHARE_WRITE};
+
+        const IOCTL_STORAGE_QUERY_PROPERTY: DWORD = 0x002D_1400;
+        const STORAGE_DEVICE_SEEK_PENALTY_PROPERTY: DWORD = 7;
+        const PROPERTY_STANDARD_QUERY: DWORD = 0;
+
+        #[repr(C)]
+        struct StoragePropertyQuery {
+            property_id: DWORD,
+            query_type: DWORD,
+            additional_parameters: [u8; 1],
+        }
+
+        #[repr(C)]
+        #[derive(Default)]
+        struct DeviceSeekPenaltyDescriptor {
+            version: DWORD,
+            size: DWORD,
+            incurs_seek_penalty: u8,
+        }
+
+        let device = format!(r"\\.\{}:", letter);
+        let wide: Vec<u16> = device.encode_utf16().chain(std::iter::once(0)).collect();
+        let handle = unsafe {
+            CreateFileW(
+                wide.as_ptr(),
+                0,
+                FILE_SHARE_READ | FILE_SHARE_WRITE,
+                std::ptr::null_mut(),
+                OPEN_EXISTING,
+                0,
+                std::ptr::null_mut(),
+            )
+        };
+        if handle == INVALID_HANDLE_VALUE {
+            let err = unsafe { GetLastError() };
+            return Err(format!("无法打开卷设备 {} (错误码: {})", device, err));
+        }
+
+        let mut query = StoragePropertyQuery {
+            property_id: STORAGE_DEVICE_SEEK_PENALTY_PROPERTY,
+            query_type: PROPERTY_STANDARD_QUERY,
+            additional_parameters: [0],
+        };
+        let mut descriptor = DeviceSeekPenaltyDescriptor::default();
+        let mut returned: DWORD = 0;
+        let ok = unsafe {
+            DeviceIoControl(
+                handle,
+                IOCTL_STORAGE_QUERY_PROPERTY,
+                &mut query as *mut _ as LPVOID,
+                std::mem::size_of::<StoragePropertyQuery>() as DWORD,
+                &mut descriptor as *mut _ as LPVOID,
+                std::mem::size_of::<DeviceSeekPenaltyDescriptor>() as DWORD,
+                &mut returned,
+                std::ptr::null_mut(),
+            )
+        };
+        let err = unsafe { GetLastError() };
+        unsafe { CloseHandle(handle) };
+
+        // 部分 RAID / 虚拟磁盘驱动不支持该属性，此时不猜测类型
+        if ok == 0 || (returned as usize) < std::mem::size_of::<DeviceSeekPenaltyDescriptor>() {
+            return Err(format!("{}: 不支持寻道开销查询 (错误码: {})", device, err));
+        }
+
+        Ok(if descriptor.incurs_seek_penalty == 0 {
+            "SSD".to_string()
+        } else {
+            "HDD".to_string()
         })
     }
 
     #[cfg(not(target_os = "windows"))]
     {
+        let _ = letter;
         Err("此功能仅支持Windows系统".to_string())
     }
 }
@@ -1,15 +1,21 @@
 // ============================================================================
 // 系统瘦身模块
-// 负责休眠文件管理、WinSxS 组件清理、虚拟内存迁移引导
+// 负责休眠文件管理、WinSxS 组件清理、虚拟内存迁移引导、系统还原点
 // ============================================================================
 
+mod restore_point;
+
+pub use restore_point::*;
+
+#[cfg(target_os = "windows")]
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
-use std::sync::{
-    atomic::{AtomicBool, Ordering},
-    LazyLock, RwLock,
-};
-use tauri::{Emitter, Manager, Window};
+#[cfg(target_os = "windows")]
+use std::sync::atomic::Ordering;
+use std::sync::{atomic::AtomicBool, LazyLock, RwLock};
+use tauri::Window;
+#[cfg(target_os = "windows")]
+use tauri::{Emitter, Manager};
 
 // ============================================================================
 // 数据结构
@@ -37,7 +43,30 @@ pub struct SystemSlimStatus {
     pub total_reclaimable: u64,
 }
 
+/// Windows 更新缓存重置结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WindowsUpdateResetResult {
+    /// 实际释放的空间（字节）
+    pub freed_size: u64,
+    /// 成功删除的顶层条目数
+    pub deleted_count: usize,
+    /// 删除失败的顶层条目数
+    pub failed_count: usize,
+    /// 被停止的服务是否已全部恢复运行
+    pub services_restored: bool,
+    pub message: String,
+}
+
 const WINSXS_ANALYZE_TIMEOUT_SECS: u64 = 30;
+// 页面只等待 30 秒，但后台 DISM 分析仍需硬上限，否则卡死后 WINSXS_ANALYZE_RUNNING 永远不会复位。
+#[cfg(target_os = "windows")]
+const DISM_ANALYZE_HARD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+// ResetBase 在老系统上可能运行一个多小时；超过该上限基本可判定 DISM 已卡死。
+#[cfg(target_os = "windows")]
+const DISM_CLEANUP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(2 * 60 * 60);
+// 机械硬盘碎片整理可能持续数小时；超过该上限按卡死处理，避免任务永远挂起。
+#[cfg(target_os = "windows")]
+const DEFRAG_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(4 * 60 * 60);
 const WINSXS_CACHE_TTL_SECS: u64 = 10 * 60;
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -62,15 +91,38 @@ static WINSXS_ANALYZE_RUNNING: AtomicBool = AtomicBool::new(false);
 // 权限检测
 // ============================================================================
 
-/// 检查是否以管理员权限运行（直接调用 shell32::IsUserAnAdmin）
+/// 检查是否以管理员权限运行
+///
+/// 直接读取进程令牌的 TokenElevation：不启动子进程，且反映的是当前进程是否真正提权，
+/// 而不是账户是否属于管理员组（UAC 下未提权的管理员账户会返回 false）。
 pub fn check_admin() -> bool {
     #[cfg(target_os = "windows")]
     {
-        #[link(name = "shell32")]
-        extern "system" {
-            fn IsUserAnAdmin() -> i32;
+        use winapi::shared::minwindef::{DWORD, LPVOID};
+        use winapi::shared::ntdef::HANDLE;
+        use winapi::um::handleapi::CloseHandle;
+        use winapi::um::processthreadsapi::{GetCurrentProcess, OpenProcessToken};
+        use winapi::um::securitybaseapi::GetTokenInformation;
+        use winapi::um::winnt::{TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY};
+
+        unsafe {
+            let mut token_handle: HANDLE = std::ptr::null_mut();
+            if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token_handle) == 0 {
+                return false;
+            }
+
+            let mut elevation = TOKEN_ELEVATION { TokenIsElevated: 0 };
+            let mut returned_size: DWORD = 0;
+            let success = GetTokenInformation(
+                token_handle,
+                TokenElevation,
+                &mut elevation as *mut _ as LPVOID,
+                std::mem::size_of::<TOKEN_ELEVATION>() as DWORD,
+                &mut returned_size,
+            );
+            CloseHandle(token_handle);
+            success != 0 && elevation.TokenIsElevated != 0
         }
-        unsafe { IsUserAnAdmin() != 0 }
     }
 
     #[cfg(not(target_os = "windows"))]
@@ -79,6 +131,88 @@ pub fn check_admin() -> bool {
     }
 }
 
+/// 只能在管理员权限下执行的命令，前端据此在按钮上显示盾牌图标
+const ADMIN_ONLY_ACTIONS: &[&str] = &[
+    "disable_hibernation",
+    "enable_hibernation",
+    "cleanup_winsxs",
+    "cleanup_winsxs_resetbase",
+    "create_restore_point",
+    "optimize_drive",
+    "clear_standby_memory",
+    "reset_windows_update_cache",
+    "delete_old_drivers",
+    "remove_driver_package",
+    "restore_all_driver_backups",
+    "scan_disk_growth",
+    "scan_all_user_profiles",
+    "clean_defender_cache",
+    "clean_cbs_logs",
+];
+
+/// 判断指定命令是否需要管理员权限
+pub fn action_requires_admin(action: &str) -> bool {
+    ADMIN_ONLY_ACTIONS.contains(&action)
+}
+
+/// 以管理员身份重新启动当前程序
+///
+/// 通过 `ShellExecuteW` 的 `runas` 动词触发 UAC；新实例启动成功后由命令层退出当前实例。
+/// 用户在 UAC 弹窗中取消时返回错误，当前实例保持运行。
+pub fn relaunch_as_admin() -> Result<(), String> {
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::ffi::OsStrExt;
+        use winapi::um::errhandlingapi::GetLastError;
+        use winapi::um::shellapi::ShellExecuteW;
+
+        const SW_SHOWNORMAL: i32 = 1;
+        const ERROR_CANCELLED: u32 = 1223;
+
+        let exe_path = std::env::current_exe().map_err(|e| format!("获取程序路径失败: {}", e))?;
+        let to_wide = |value: &std::ffi::OsStr| {
+            value
+                .encode_wide()
+                .chain(std::iter::once(0))
+                .collect::<Vec<u16>>()
+        };
+        let verb = to_wide(std::ffi::OsStr::new("runas"));
+        let file = to_wide(exe_path.as_os_str());
+
+        info!("请求以管理员身份重新启动: {}", exe_path.display());
+
+        // ShellExecuteW 返回值大于 32 表示成功，否则需要通过 GetLastError 区分用户取消。
+        let result = unsafe {
+            ShellExecuteW(
+                std::ptr::null_mut(),
+                verb.as_ptr(),
+                file.as_ptr(),
+                std::ptr::null(),
+                std::ptr::null(),
+                SW_SHOWNORMAL,
+            )
+        } as isize;
+        if result > 32 {
+            return Ok(());
+        }
+
+        let error_code = unsafe { GetLastError() };
+        if error_code == ERROR_CANCELLED {
+            Err("已取消管理员授权".to_string())
+        } else {
+            Err(format!(
+                "以管理员身份重新启动失败，错误代码: {}",
+                error_code
+            ))
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("此功能仅支持 Windows 系统".to_string())
+    }
+}
+
 // ============================================================================
 // 状态检测（异步：避免 DISM 阻塞主线程）
 // ============================================================================
@@ -140,7 +274,7 @@ fn get_hibernation_status() -> SlimItemStatus {
 }
 
 /// 通过注册表检测休眠功能是否启用（快速、编码无关、不依赖 powercfg）
-fn check_hibernation_enabled() -> bool {
+pub(crate) fn check_hibernation_enabled() -> bool {
     #[cfg(target_os = "windows")]
     {
         use winreg::{enums::*, RegKey};
@@ -284,6 +418,13 @@ fn get_cached_winsxs_result() -> Option<WinsxsAnalyzeResult> {
     None
 }
 
+/// 缓存中的 WinSxS 可回收大小（未分析、已过期或分析失败时为 None）
+pub(crate) fn cached_winsxs_reclaimable_size() -> Option<u64> {
+    get_cached_winsxs_result()
+        .filter(|result| result.analysis_succeeded)
+        .map(|result| result.reclaimable_size)
+}
+
 fn set_cached_winsxs_result(result: WinsxsAnalyzeResult) {
     if let Ok(mut cache) = WINSXS_ANALYZE_CACHE.write() {
         // 只缓存成功解析到的结果，失败和超时保持 0，避免把临时异常固化到检查结果里。
@@ -308,6 +449,7 @@ fn analyze_winsxs_sync() -> Result<WinsxsAnalyzeResult, String> {
         let output = run_hidden_utf8_command(
             "dism.exe",
             &["/online", "/cleanup-image", "/analyzecomponentstore"],
+            DISM_ANALYZE_HARD_TIMEOUT,
         )?;
 
         if !output.status.success() {
@@ -470,7 +612,7 @@ fn get_pagefile_status() -> SlimItemStatus {
         || pagefile_configs.len() == 1
             && pagefile_configs
                 .first()
-                .map_or(false, |c| c.path.to_lowercase().starts_with("c:"))
+                .is_some_and(|c| c.path.to_lowercase().starts_with("c:"))
         || pagefile_configs.is_empty()
     {
         let loc_text = if pagefile_configs.is_empty() {
@@ -541,15 +683,17 @@ fn get_pagefile_configs() -> Vec<PagefileConfig> {
         use std::os::windows::process::CommandExt;
         use std::process::Command;
 
-        let output = Command::new("reg")
-            .args([
-                "query",
-                r"HKLM\System\CurrentControlSet\Control\Session Manager\Memory Management",
-                "/v",
-                "PagingFiles",
-            ])
-            .creation_flags(0x08000000)
-            .output();
+        let output = crate::process_runner::run_with_timeout(
+            Command::new("reg")
+                .args([
+                    "query",
+                    r"HKLM\System\CurrentControlSet\Control\Session Manager\Memory Management",
+                    "/v",
+                    "PagingFiles",
+                ])
+                .creation_flags(0x08000000),
+            crate::process_runner::DEFAULT_COMMAND_TIMEOUT,
+        );
 
         match output {
             Ok(o) => {
@@ -584,11 +728,7 @@ fn parse_pagefile_configs(raw: &str) -> Vec<PagefileConfig> {
         let token = tokens[i];
         // 检查是否为盘符路径格式 (如 "C:\..." 或 "c:\...")
         if token.len() >= 3 && &token.as_bytes()[1..3] == b":\\" {
-            let path = if token.ends_with(',') {
-                token[..token.len() - 1].to_string()
-            } else {
-                token.to_string()
-            };
+            let path = token.strip_suffix(',').unwrap_or(token).to_string();
 
             let initial_size = if i + 1 < tokens.len() {
                 tokens[i + 1]
@@ -633,7 +773,11 @@ fn format_bytes(bytes: u64) -> String {
 }
 
 #[cfg(target_os = "windows")]
-fn run_hidden_utf8_command(program: &str, args: &[&str]) -> Result<std::process::Output, String> {
+fn run_hidden_utf8_command(
+    program: &str,
+    args: &[&str],
+    timeout: std::time::Duration,
+) -> Result<std::process::Output, String> {
     use std::os::windows::process::CommandExt;
     use std::process::Command;
 
@@ -644,11 +788,13 @@ fn run_hidden_utf8_command(program: &str, args: &[&str]) -> Result<std::process:
         .join(" ");
 
     // Windows 系统工具输出会跟随控制台代码页；先切到 UTF-8，避免中文系统下解析 DISM 文本时乱码。
-    Command::new("cmd")
-        .args(["/C", &format!("chcp 65001 >nul & {}", command_line)])
-        .creation_flags(0x08000000)
-        .output()
-        .map_err(|e| format!("执行命令失败: {}", e))
+    crate::process_runner::run_with_timeout(
+        Command::new("cmd")
+            .args(["/C", &format!("chcp 65001 >nul & {}", command_line)])
+            .creation_flags(0x08000000),
+        timeout,
+    )
+    .map_err(|e| format!("执行命令失败: {}", e))
 }
 
 #[cfg(target_os = "windows")]
@@ -683,7 +829,11 @@ pub fn disable_hibernation() -> Result<String, String> {
     {
         info!("正在关闭休眠功能...");
 
-        let output = run_hidden_utf8_command("powercfg", &["-h", "off"])?;
+        let output = run_hidden_utf8_command(
+            "powercfg",
+            &["-h", "off"],
+            crate::process_runner::DEFAULT_COMMAND_TIMEOUT,
+        )?;
 
         if output.status.success() {
             info!("休眠功能已关闭");
@@ -712,7 +862,11 @@ pub fn enable_hibernation() -> Result<String, String> {
     {
         info!("正在开启休眠功能...");
 
-        let output = run_hidden_utf8_command("powercfg", &["-h", "on"])?;
+        let output = run_hidden_utf8_command(
+            "powercfg",
+            &["-h", "on"],
+            crate::process_runner::DEFAULT_COMMAND_TIMEOUT,
+        )?;
 
         if output.status.success() {
             info!("休眠功能已开启");
@@ -745,12 +899,14 @@ async fn run_winsxs_cleanup(window: &Window, reset_base: bool) -> Result<String,
     if !check_admin() {
         return Err("需要管理员权限才能执行此操作，请以管理员身份运行程序".to_string());
     }
+    tokio::task::spawn_blocking(|| create_restore_point_if_enabled("清理 WinSxS 组件存储"))
+        .await
+        .map_err(|e| format!("创建系统还原点任务异常: {}", e))??;
 
     #[cfg(target_os = "windows")]
     {
-        use std::io::BufRead;
         use std::os::windows::process::CommandExt;
-        use std::process::{Command, Stdio};
+        use std::process::Command;
 
         info!("开始清理 WinSxS 组件存储，ResetBase: {}", reset_base);
 
@@ -775,21 +931,16 @@ async fn run_winsxs_cleanup(window: &Window, reset_base: bool) -> Result<String,
                 dism_args.push("/resetbase");
             }
 
-            let mut child = Command::new("dism.exe")
-                .args(&dism_args)
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .creation_flags(0x08000000)
-                .spawn()?;
-
-            let stdout = child.stdout.take().unwrap();
-            let reader = std::io::BufReader::new(stdout);
             let mut last_progress: u32 = 0;
 
-            for line in reader.lines() {
-                if let Ok(line) = line {
+            crate::process_runner::run_with_timeout_streaming(
+                Command::new("dism.exe")
+                    .args(&dism_args)
+                    .creation_flags(0x08000000),
+                DISM_CLEANUP_TIMEOUT,
+                |line| {
                     // DISM 进度格式: "[=====      15.0%                          ]"
-                    if let Some(pct) = parse_dism_progress(&line) {
+                    if let Some(pct) = parse_dism_progress(line) {
                         if pct > last_progress {
                             last_progress = pct;
                             let _ = handle.emit(
@@ -802,15 +953,28 @@ async fn run_winsxs_cleanup(window: &Window, reset_base: bool) -> Result<String,
                             );
                         }
                     }
-                }
-            }
-
-            let output = child.wait_with_output()?;
-            Ok::<std::process::Output, std::io::Error>(output)
+                },
+            )
         })
         .await
         .map_err(|e| format!("任务执行失败: {}", e))?
-        .map_err(|e| format!("执行 DISM 命令失败: {}", e))?;
+        .map_err(|e| format!("执行 DISM 命令失败: {}", e));
+
+        let result = match result {
+            Ok(output) => output,
+            Err(error) => {
+                // 超时或启动失败同样要通知前端结束进度，否则界面会一直停在“正在清理”。
+                let _ = window.emit(
+                    "winsxs-cleanup-progress",
+                    serde_json::json!({
+                        "status": "error",
+                        "message": error.clone(),
+                        "progress": 0
+                    }),
+                );
+                return Err(error);
+            }
+        };
 
         if result.status.success() {
             info!("WinSxS 清理完成，ResetBase: {}", reset_base);
@@ -845,6 +1009,147 @@ async fn run_winsxs_cleanup(window: &Window, reset_base: bool) -> Result<String,
 
     #[cfg(not(target_os = "windows"))]
     {
+        let _ = (window, reset_base);
+        Err("此功能仅支持 Windows 系统".to_string())
+    }
+}
+
+/// 优化驱动器：SSD 执行 TRIM（重新修剪），HDD 执行碎片整理（异步执行，实时推送进度）
+///
+/// `defrag X: /O` 由系统按介质类型选择合适的优化方式；这里先识别介质类型，
+/// 只用于进度提示和日志。返回 defrag 输出中的摘要（去掉进度行）。
+pub async fn optimize_drive(window: &Window, drive: String) -> Result<String, String> {
+    if !check_admin() {
+        return Err("需要管理员权限才能执行此操作，请以管理员身份运行程序".to_string());
+    }
+
+    // 只接受 "C" / "C:" / "C:\"，盘符会拼进命令行，不能放行其他字符
+    let letter = match drive.trim().trim_end_matches(['\\', ':']).as_bytes() {
+        [ch] if ch.is_ascii_alphabetic() => (*ch as char).to_ascii_uppercase(),
+        _ => return Err(format!("无效的盘符: {}", drive)),
+    };
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        use std::process::Command;
+
+        let media_type = crate::system_info::get_drive_media_type(&letter.to_string())
+            .unwrap_or_else(|e| {
+                warn!("识别 {}: 盘介质类型失败: {}", letter, e);
+                "未知".to_string()
+            });
+        let action = match media_type.as_str() {
+            "SSD" => "TRIM",
+            "HDD" => "碎片整理",
+            _ => "优化",
+        };
+        info!(
+            "开始优化 {}: 盘，介质类型: {}，操作: {}",
+            letter, media_type, action
+        );
+
+        let _ = window.emit(
+            "drive-optimize-progress",
+            serde_json::json!({
+                "status": "running",
+                "message": format!("正在对 {}: 盘执行{}，请耐心等待...", letter, action),
+                "media_type": media_type,
+                "progress": 0
+            }),
+        );
+
+        let handle = window.app_handle().clone();
+        let progress_media_type = media_type.clone();
+        let drive_arg = format!("{}:", letter);
+
+        let result = tokio::task::spawn_blocking(move || {
+            let mut last_progress: u32 = 0;
+            // defrag 输出跟随控制台代码页，先切到 UTF-8 避免中文系统下乱码
+            crate::process_runner::run_with_timeout_streaming(
+                Command::new("cmd")
+                    .args([
+                        "/C",
+                        &format!("chcp 65001 >nul & defrag {} /O /U /V", drive_arg),
+                    ])
+                    .creation_flags(0x08000000),
+                DEFRAG_TIMEOUT,
+                |line| {
+                    if let Some(pct) = parse_dism_progress(line) {
+                        if pct > last_progress {
+                            last_progress = pct;
+                            let _ = handle.emit(
+                                "drive-optimize-progress",
+                                serde_json::json!({
+                                    "status": "running",
+                                    "message": format!("正在优化: {}%", pct),
+                                    "media_type": progress_media_type,
+                                    "progress": pct
+                                }),
+                            );
+                        }
+                    }
+                },
+            )
+        })
+        .await
+        .map_err(|e| format!("任务执行失败: {}", e))?
+        .map_err(|e| format!("执行 defrag 命令失败: {}", e));
+
+        let output = match result {
+            Ok(output) => output,
+            Err(error) => {
+                let _ = window.emit(
+                    "drive-optimize-progress",
+                    serde_json::json!({
+                        "status": "error",
+                        "message": error.clone(),
+                        "media_type": media_type,
+                        "progress": 0
+                    }),
+                );
+                return Err(error);
+            }
+        };
+
+        let stdout = decode_command_output(&output.stdout);
+        let summary = stdout
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && parse_dism_progress(line).is_none())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if output.status.success() {
+            info!("{}: 盘优化完成", letter);
+            let _ = window.emit(
+                "drive-optimize-progress",
+                serde_json::json!({
+                    "status": "done",
+                    "message": "优化完成",
+                    "media_type": media_type,
+                    "progress": 100
+                }),
+            );
+            Ok(summary)
+        } else {
+            let stderr = decode_command_output(&output.stderr);
+            let _ = window.emit(
+                "drive-optimize-progress",
+                serde_json::json!({
+                    "status": "error",
+                    "message": format!("优化失败: {}", stderr),
+                    "media_type": media_type,
+                    "progress": 0
+                }),
+            );
+            Err(format!("优化失败: {} {}", summary, stderr))
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        let _ = (window, letter);
         Err("此功能仅支持 Windows 系统".to_string())
     }
 }
@@ -891,3 +1196,144 @@ pub fn open_virtual_memory_settings() -> Result<(), String> {
         Err("此功能仅支持 Windows 系统".to_string())
     }
 }
+
+/// 重置 Windows 更新下载缓存
+///
+/// # 中文说明
+/// wuauserv 和 BITS 运行时会锁定 SoftwareDistribution\Download 中的更新包，逐文件删除大多失败。
+/// 这里先停止两个服务再清空下载目录，只重启本次实际停止的服务；
+/// 清理过程出错时同样会恢复服务，避免系统更新被意外停用。
+pub fn reset_windows_update_cache() -> Result<WindowsUpdateResetResult, String> {
+    if !check_admin() {
+        return Err("需要管理员权限才能执行此操作，请以管理员身份运行程序".to_string());
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let windows_dir = std::env::var("SystemRoot").unwrap_or_else(|_| "C:\\Windows".to_string());
+        let download_dir = std::path::Path::new(&windows_dir)
+            .join("SoftwareDistribution")
+            .join("Download");
+
+        info!("开始重置 Windows 更新缓存: {}", download_dir.display());
+
+        // 先停 wuauserv 再停 BITS，恢复时按相反顺序启动。
+        let mut stopped_services = Vec::new();
+        for service in WINDOWS_UPDATE_SERVICES {
+            match run_hidden_utf8_command(
+                "net",
+                &["stop", service, "/y"],
+                crate::process_runner::DEFAULT_COMMAND_TIMEOUT,
+            ) {
+                Ok(output) if output.status.success() => stopped_services.push(*service),
+                // 服务原本未运行时 net stop 返回非零，无需恢复。
+                Ok(output) => info!(
+                    "服务 {} 未停止（可能原本未运行）: {}",
+                    service,
+                    decode_command_output(&output.stdout).trim()
+                ),
+                Err(e) => warn!("停止服务 {} 失败: {}", service, e),
+            }
+        }
+
+        let mut result = WindowsUpdateResetResult::default();
+        if download_dir.is_dir() {
+            clear_directory_contents(&download_dir, &mut result);
+        }
+
+        let mut services_restored = true;
+        for service in stopped_services.iter().rev() {
+            let started = run_hidden_utf8_command(
+                "net",
+                &["start", service],
+                crate::process_runner::DEFAULT_COMMAND_TIMEOUT,
+            )
+            .map(|output| output.status.success())
+            .unwrap_or(false);
+            if !started {
+                warn!("恢复服务 {} 失败", service);
+                services_restored = false;
+            }
+        }
+        result.services_restored = services_restored;
+
+        result.message = format!(
+            "已释放 {}，删除 {} 项，失败 {} 项",
+            format_bytes(result.freed_size),
+            result.deleted_count,
+            result.failed_count
+        );
+        if !services_restored {
+            result
+                .message
+                .push_str("；部分更新服务未能自动启动，请重启电脑或手动启动 Windows Update 服务");
+        }
+        info!("Windows 更新缓存重置完成: {}", result.message);
+        Ok(result)
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("此功能仅支持 Windows 系统".to_string())
+    }
+}
+
+/// 会锁定更新下载目录的服务
+#[cfg(target_os = "windows")]
+const WINDOWS_UPDATE_SERVICES: &[&str] = &["wuauserv", "bits"];
+
+/// 删除目录下的所有顶层条目，保留目录本身（Windows 更新依赖该目录存在）
+#[cfg(target_os = "windows")]
+fn clear_directory_contents(dir: &std::path::Path, result: &mut WindowsUpdateResetResult) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            warn!("读取目录失败 {}: {}", dir.display(), e);
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = std::fs::symlink_metadata(&path) else {
+            result.failed_count += 1;
+            continue;
+        };
+        let is_dir =
+            metadata.is_dir() && !crate::cleaner::safety_constants::is_reparse_point(&metadata);
+        let size = if is_dir {
+            walkdir::WalkDir::new(&path)
+                .follow_links(false)
+                .into_iter()
+                .filter_entry(|e| {
+                    e.depth() == 0 || !crate::cleaner::safety_constants::is_reparse_point_dir(e)
+                })
+                .filter_map(|e| e.ok())
+                .filter_map(|e| e.metadata().ok())
+                .filter(|m| m.is_file())
+                .map(|m| m.len())
+                .sum()
+        } else {
+            metadata.len()
+        };
+
+        let removed = if is_dir {
+            std::fs::remove_dir_all(&path)
+        } else if metadata.is_dir() {
+            // 目录联接只移除链接本身，不进入目标目录
+            std::fs::remove_dir(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+        match removed {
+            Ok(()) => {
+                result.deleted_count += 1;
+                result.freed_size += size;
+            }
+            Err(e) => {
+                warn!("删除更新缓存失败 {}: {}", path.display(), e);
+                result.failed_count += 1;
+            }
+        }
+    }
+}
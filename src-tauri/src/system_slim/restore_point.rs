@@ -0,0 +1,136 @@
+// ============================================================================
+// 系统还原点
+//
+// 注册表删除、旧驱动清理和 WinSxS 清理都会修改系统状态，谨慎的用户希望先有一个还原点。
+// 通过 WMI `root\default:SystemRestore.CreateRestorePoint` 创建，只看返回码，
+// 不解析 PowerShell 的本地化输出。
+//
+// Windows 默认 24 小时内只创建一个还原点（SystemRestorePointCreationFrequency），
+// 期间的请求返回成功但不会新建；此时最近的还原点仍可用于恢复，按成功处理。
+// ============================================================================
+
+use log::info;
+
+use super::check_admin;
+
+/// 还原点描述的最大长度（SRSetRestorePoint 限制为 256 个字符，留出前缀余量）
+const MAX_DESCRIPTION_CHARS: usize = 200;
+
+/// 还原点描述前缀，便于在"系统还原"列表中识别
+const DESCRIPTION_PREFIX: &str = "LightC";
+
+/// ERROR_SERVICE_DISABLED：系统盘未开启系统保护
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const ERROR_SERVICE_DISABLED: i32 = 1058;
+
+/// 创建还原点的超时时间，卷影复制在机械硬盘上可能需要数分钟
+#[cfg(target_os = "windows")]
+const RESTORE_POINT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
+/// 创建系统还原点（需要管理员权限，且系统盘已开启系统保护）
+pub fn create_restore_point(description: &str) -> Result<(), String> {
+    if !check_admin() {
+        return Err("需要管理员权限才能创建系统还原点，请以管理员身份运行程序".to_string());
+    }
+    let description = normalize_description(description);
+    info!("正在创建系统还原点: {}", description);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        use std::process::Command;
+
+        // 12 = MODIFY_SETTINGS，100 = BEGIN_SYSTEM_CHANGE；WMI 调用异常时以 1 退出
+        let script = format!(
+            "try {{ $r = ([wmiclass]'\\\\.\\root\\default:SystemRestore').CreateRestorePoint('{}', 12, 100); exit [int]$r.ReturnValue }} catch {{ exit 1 }}",
+            description.replace('\'', "''")
+        );
+        let output = crate::process_runner::run_with_timeout(
+            Command::new("powershell")
+                .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+                .creation_flags(0x08000000),
+            RESTORE_POINT_TIMEOUT,
+        )
+        .map_err(|e| format!("创建系统还原点失败: {}", e))?;
+
+        let code = output.status.code().unwrap_or(-1);
+        if code == 0 {
+            info!("系统还原点已创建: {}", description);
+            Ok(())
+        } else {
+            log::warn!("创建系统还原点失败，返回码 {}", code);
+            Err(restore_point_error_message(code))
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Err("此功能仅支持 Windows 系统".to_string())
+    }
+}
+
+/// 设置中开启了 `auto_restore_point_before_risky_ops` 时，在高风险操作前创建还原点
+///
+/// 用户明确要求先有还原点，因此创建失败时返回错误，调用方应中止后续操作。
+pub fn create_restore_point_if_enabled(operation: &str) -> Result<(), String> {
+    if !crate::settings::get_settings().auto_restore_point_before_risky_ops {
+        return Ok(());
+    }
+    create_restore_point(&format!("{}前自动创建", operation)).map_err(|e| {
+        format!(
+            "{}（已开启\"高风险操作前自动创建还原点\"，为安全起见已取消{}）",
+            e, operation
+        )
+    })
+}
+
+/// 加上前缀并截断，去掉换行等控制字符
+fn normalize_description(description: &str) -> String {
+    let cleaned: String = description
+        .trim()
+        .chars()
+        .filter(|ch| !ch.is_control())
+        .take(MAX_DESCRIPTION_CHARS)
+        .collect();
+    if cleaned.is_empty() {
+        DESCRIPTION_PREFIX.to_string()
+    } else {
+        format!("{}: {}", DESCRIPTION_PREFIX, cleaned)
+    }
+}
+
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn restore_point_error_message(code: i32) -> String {
+    match code {
+        ERROR_SERVICE_DISABLED => {
+            "系统还原已关闭，请在\"系统属性 > 系统保护\"中为系统盘启用保护后重试".to_string()
+        }
+        1 => "无法调用系统还原服务（WMI SystemRestore 不可用）".to_string(),
+        code => format!("创建系统还原点失败，错误码 {}", code),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_description_adds_prefix_and_strips_control_chars() {
+        assert_eq!(
+            normalize_description("  清理注册表\r\n"),
+            "LightC: 清理注册表"
+        );
+        assert_eq!(normalize_description(""), "LightC");
+        let long = "a".repeat(MAX_DESCRIPTION_CHARS + 10);
+        assert_eq!(
+            normalize_description(&long).chars().count(),
+            "LightC: ".len() + MAX_DESCRIPTION_CHARS
+        );
+    }
+
+    #[test]
+    fn test_disabled_system_restore_reported_clearly() {
+        assert!(restore_point_error_message(ERROR_SERVICE_DISABLED).contains("系统还原已关闭"));
+        assert!(restore_point_error_message(5).contains("5"));
+    }
+}
@@ -3,7 +3,7 @@
 // 灏佽鎵€鏈変笌Rust鍚庣鐨勯€氫俊鎺ュ彛
 // ============================================================================
 
-import { invoke } from '@tauri-apps/api/core';
+import { invoke as tauriInvoke, type InvokeArgs } from '@tauri-apps/api/core';
 import type {
   DiskInfo,
   ScanResult,
@@ -14,8 +14,56 @@ import type {
   ScanRequest,
   DeleteRequest,
   LargeFileEntry,
+  JunkCategory,
+  FileInfo,
 } from '../types';
 
+/** 扫描/删除命令返回的结构化错误码 */
+export type CommandErrorCode =
+  | 'needs_admin'
+  | 'not_found'
+  | 'permission_denied'
+  | 'cancelled'
+  | 'invalid_input'
+  | 'io'
+  | 'other';
+
+/** 结构化命令错误；toString 返回中文提示，兼容直接 String(err) 展示的旧代码 */
+export class CommandError extends Error {
+  readonly code: CommandErrorCode;
+
+  constructor(code: CommandErrorCode, message: string) {
+    super(message);
+    this.name = 'CommandError';
+    this.code = code;
+  }
+
+  toString(): string {
+    return this.message;
+  }
+}
+
+function isStructuredError(error: unknown): error is { code: CommandErrorCode; message: string } {
+  return (
+    typeof error === 'object' &&
+    error !== null &&
+    typeof (error as { code?: unknown }).code === 'string' &&
+    typeof (error as { message?: unknown }).message === 'string'
+  );
+}
+
+/** 调用后端命令，把 `{ code, message }` 形式的错误转换为 CommandError；字符串错误原样抛出 */
+async function invoke<T>(command: string, args?: InvokeArgs): Promise<T> {
+  try {
+    return await tauriInvoke<T>(command, args);
+  } catch (error) {
+    if (isStructuredError(error)) {
+      throw new CommandError(error.code, error.message);
+    }
+    throw error;
+  }
+}
+
 export type DistributionChannel = 'installer' | 'portable';
 export type VerifyIntegrityStatus =
   | 'verified'
@@ -106,6 +154,28 @@ export async function getLocalDrives(): Promise<LocalDriveInfo[]> {
   return invoke<LocalDriveInfo[]>('get_local_drives');
 }
 
+/** 清理后可用空间核对结果 */
+export interface VerifyResult {
+  drive_letter: string;
+  before_free: number;
+  after_free: number;
+  /** 可用空间实际增加量，清理期间其他程序写入时可能为负 */
+  actual_freed: number;
+  reported_freed: number | null;
+  /** 实际增加量明显少于报告值 */
+  shortfall: boolean;
+  note: string | null;
+}
+
+/** 清理完成后重新查询可用空间，与清理前的 free_space 和报告的释放大小核对。 */
+export async function verifyFreedSpace(
+  beforeFree: number,
+  reportedFreed?: number,
+  driveLetter?: string,
+): Promise<VerifyResult> {
+  return invoke<VerifyResult>('verify_freed_space', { beforeFree, reportedFreed, driveLetter });
+}
+
 export interface DiskVolumeInfo {
   drive_letter: string;
   volume_name: string;
@@ -168,6 +238,106 @@ export async function getDeepJunkCategoryPage(
   });
 }
 
+/** 分页读取快速扫描（设置了 max_files_per_category）中某个分类的其余文件。 */
+export async function getCategoryFiles(
+  categoryName: string,
+  offset: number,
+  limit = 500,
+): Promise<CategoryScanResult> {
+  return invoke<CategoryScanResult>('get_category_files', {
+    categoryName,
+    offset,
+    limit,
+  });
+}
+
+/** 将扫描结果导出为带 schema_version 的 JSON 文件，供外部脚本读取。 */
+export async function exportScanJson(result: ScanResult, outputPath: string): Promise<void> {
+  return invoke<void>('export_scan_json', { result, outputPath });
+}
+
+/** 单个来源的可回收空间估算 */
+export interface ReclaimSource {
+  /** 来源标识 */
+  id: 'temp' | 'thumbnails' | 'recycle_bin' | 'hibernation' | 'winsxs';
+  /** 显示名称 */
+  name: string;
+  /** 估算可回收大小（字节） */
+  size: number;
+  /** 是否计入保守总量（休眠文件和 WinSxS 需要额外操作，不计入） */
+  included_in_total: boolean;
+  /** 是否因达到条目上限提前停止（实际大小可能更大） */
+  truncated: boolean;
+}
+
+/** 可回收空间估算结果 */
+export interface ReclaimEstimate {
+  /** 保守估算总量（字节） */
+  total_size: number;
+  /** 需要额外操作才能回收的空间（字节） */
+  optional_size: number;
+  sources: ReclaimSource[];
+  duration_ms: number;
+}
+
+/** 快速估算可回收空间（首页标题数字），不删除任何文件，通常两秒内返回。 */
+export async function estimateReclaimable(): Promise<ReclaimEstimate> {
+  return invoke<ReclaimEstimate>('estimate_reclaimable');
+}
+
+/** 首页建议的单项操作 */
+export interface Recommendation {
+  /** hibernation / windows_old / recycle_bin / temp */
+  id: string;
+  title: string;
+  reason: string;
+  /** 预计释放的空间（字节） */
+  estimated_size: number;
+  /** 执行该操作要调用的命令 */
+  command: string;
+  /** 命令参数，可直接传给 invoke */
+  args: Record<string, unknown>;
+  requires_admin: boolean;
+}
+
+/** 返回预计释放空间最多的单项操作，各项都不足 100 MB 时为 null。 */
+export async function getTopRecommendation(): Promise<Recommendation | null> {
+  return invoke<Recommendation | null>('get_top_recommendation');
+}
+
+/** 单个应用的日志汇总 */
+export interface AppLogGroup {
+  app_name: string;
+  app_dir: string;
+  total_size: number;
+  file_count: number;
+  /** 超过阈值的日志文件（按大小降序），路径可直接交给 deleteFiles */
+  files: FileInfo[];
+}
+
+/** 扫描 AppData 下各应用超过阈值的 *.log（默认 10 MB），按应用汇总并按总大小降序 */
+export async function scanAppLogs(minSize?: number): Promise<AppLogGroup[]> {
+  return invoke<AppLogGroup[]>('scan_app_logs', { minSize });
+}
+
+/** 单个扫描根目录的权限检查结果 */
+export interface ScopePermission {
+  category_key: string;
+  display_name: string;
+  /** 扫描根目录 */
+  path: string;
+  readable: boolean;
+  /** 能否在目录中创建和删除文件 */
+  writable: boolean;
+  /** 当前未以管理员身份运行且权限不足 */
+  needs_elevation: boolean;
+}
+
+/** 扫描前检查各分类扫描根目录的读写权限，可据此先提示以管理员身份运行 */
+export async function checkScopePermissions(): Promise<ScopePermission[]> {
+  return invoke<ScopePermission[]>('check_scope_permissions');
+}
+
 /**
  * 鎵弿鍗曚釜鍒嗙被
  * @param categoryName 鍒嗙被鍚嶇О
@@ -180,11 +350,231 @@ export async function scanCategory(categoryName: string): Promise<CategoryScanRe
  * 鍒犻櫎鎸囧畾鏂囦欢
  * @param paths 瑕佸垹闄ょ殑鏂囦欢璺緞鍒楄〃
  */
-export async function deleteFiles(paths: string[]): Promise<DeleteResult> {
-  const request: DeleteRequest = { paths };
+export async function deleteFiles(paths: string[], pruneEmptyDirs?: boolean): Promise<DeleteResult> {
+  const request: DeleteRequest = { paths, prune_empty_dirs: pruneEmptyDirs };
   return invoke<DeleteResult>('delete_files', { request });
 }
 
+/** 占用文件的进程 */
+export interface LockingProcess {
+  pid: number;
+  app_name: string;
+  service_name: string | null;
+  restartable: boolean;
+}
+
+/** 删除诊断结果 */
+export interface DeleteProbe {
+  path: string;
+  exists: boolean;
+  is_dir: boolean;
+  protected: boolean;
+  protection_rule: string | null;
+  in_allowed_scope: boolean;
+  locked: boolean;
+  locked_files: string[];
+  locking_processes: LockingProcess[];
+  estimated_size: number;
+  summary: string;
+}
+
+/**
+ * 诊断单个路径为什么删不掉（不执行删除）
+ * @param path 要诊断的文件或目录
+ */
+export async function probeDelete(path: string): Promise<DeleteProbe> {
+  return invoke<DeleteProbe>('probe_delete', { path });
+}
+
+/** 路径说明 */
+export interface PathExplanation {
+  path: string;
+  exists: boolean;
+  /** 所属垃圾分类，不属于任何分类时为 null */
+  category: JunkCategory | null;
+  category_name: string | null;
+  /** 分类风险等级 (1-5, 1最安全) */
+  risk_level: number | null;
+  description: string | null;
+  /** 是否被删除引擎的安全规则拦截 */
+  protected: boolean;
+  protection_rule: string | null;
+  /** 是否在常规可清理范围内 */
+  in_allowed_scope: boolean;
+  /** 建议操作 */
+  recommended_action: 'delete' | 'review' | 'keep';
+  /** 建议的理由 */
+  reason: string;
+}
+
+/**
+ * 说明路径所属的垃圾分类、风险等级和建议的处理方式（不执行删除）
+ * @param path 要说明的文件或目录
+ */
+export async function explainPath(path: string): Promise<PathExplanation> {
+  return invoke<PathExplanation>('explain_path', { path });
+}
+
+/** 路径当前状态 */
+export interface PathStatus {
+  path: string;
+  exists: boolean;
+  is_dir: boolean;
+  /** 当前文件大小（字节），目录和不存在的路径为 0 */
+  size: number;
+  modified: number;
+}
+
+/** 删除前校验已选路径是否仍存在并刷新大小，只读取元数据，返回顺序与输入一致。 */
+export async function validatePaths(paths: string[]): Promise<PathStatus[]> {
+  return invoke<PathStatus[]>('validate_paths', { paths });
+}
+
+/** 删除计划树节点 */
+export interface DeletePlanNode {
+  /** 显示名称，只有一个子目录的中间目录合并显示 */
+  name: string;
+  path: string;
+  /** 该节点本身是否为待删除路径 */
+  is_target: boolean;
+  is_dir: boolean;
+  /** 将被删除的大小（字节），不含受保护的路径 */
+  size: number;
+  item_count: number;
+  protected_count: number;
+  protection_rule: string | null;
+  children: DeletePlanNode[];
+}
+
+/** 删除计划 */
+export interface DeletePlan {
+  roots: DeletePlanNode[];
+  total_size: number;
+  item_count: number;
+  protected_count: number;
+  /** 已被选中的上级目录覆盖的路径数 */
+  covered_count: number;
+  missing_paths: string[];
+}
+
+/** 按目录树预览删除计划，标出受保护的路径（不执行删除）。 */
+export async function buildDeletePlan(paths: string[]): Promise<DeletePlan> {
+  return invoke<DeletePlan>('build_delete_plan', { paths });
+}
+
+/**
+ * 按分类在后端重新扫描并整类删除，路径不经过 IPC 传输
+ * @param categoryName 分类显示名称
+ * @param maxRisk 允许的最高风险等级，超过时拒绝删除
+ */
+export async function deleteScanCategory(categoryName: string, maxRisk?: number): Promise<DeleteResult> {
+  return invoke<DeleteResult>('delete_scan_category', { categoryName, maxRisk });
+}
+
+/** 单个分类的一键清理结果 */
+export interface CategoryCleanSummary {
+  /** 分类显示名称 */
+  category: string;
+  /** 扫描到的文件数 */
+  file_count: number;
+  /** 释放的空间（字节）；模拟运行时为可释放的空间 */
+  freed_size: number;
+  /** 删除失败的文件数 */
+  failed_count: number;
+}
+
+/** 一键清理结果 */
+export interface CleanSummary {
+  dry_run: boolean;
+  categories: CategoryCleanSummary[];
+  /** 总释放空间（字节）；模拟运行时为可释放的空间 */
+  total_freed: number;
+  success_count: number;
+  failed_count: number;
+  reboot_pending_count: number;
+}
+
+/**
+ * 扫描并清理指定分类，一次调用完成扫描、删除和记录日志
+ * @param categoryNames 分类显示名称列表
+ * @param dryRun 为 true 时只扫描并返回可释放空间，不删除
+ */
+export async function cleanCategories(categoryNames: string[], dryRun: boolean): Promise<CleanSummary> {
+  return invoke<CleanSummary>('clean_categories', { categoryNames, dryRun });
+}
+
+/**
+ * 安全清理：只清理风险等级 1 的分类并清空回收站，写入清理日志后返回汇总
+ */
+export async function safeClean(): Promise<CleanSummary> {
+  return invoke<CleanSummary>('safe_clean');
+}
+
+/** 紧急释放空间结果 */
+export interface EmergencyFreeSpaceResult {
+  /** 目标释放量（字节） */
+  target_bytes: number;
+  /** 系统盘可用空间的实际增加量（字节） */
+  freed_bytes: number;
+  /** 结束时系统盘的可用空间（字节） */
+  free_space: number;
+  target_met: boolean;
+  /** 实际执行的各分类清理明细 */
+  summary: CleanSummary;
+}
+
+/**
+ * 系统盘空间告急时按回收站、临时文件、更新缓存等顺序逐类清理，达到目标后停止
+ * @param targetBytes 需要腾出的空间（字节）
+ */
+export async function emergencyFreeSpace(targetBytes: number): Promise<EmergencyFreeSpaceResult> {
+  return invoke<EmergencyFreeSpaceResult>('emergency_free_space', { targetBytes });
+}
+
+/** 清理流水线步骤：删除步骤之前必须有扫描步骤，核对步骤之前必须有删除步骤 */
+export type CleanStep =
+  | { kind: 'scan'; categories: string[] }
+  | { kind: 'delete' }
+  | { kind: 'verify' };
+
+/** 清理流水线总进度事件（cleanup-pipeline:progress）负载 */
+export interface OperationProgress {
+  operation_id: string | null;
+  step: 'scan' | 'delete' | 'verify';
+  step_index: number;
+  step_count: number;
+  /** 当前处理的分类显示名称 */
+  current_item: string | null;
+  /** 整条流水线的完成百分比（0-100） */
+  overall_percent: number;
+}
+
+/** 清理流水线执行结果 */
+export interface CleanupPipelineResult {
+  operation_id: string | null;
+  scanned_file_count: number;
+  scanned_size: number;
+  /** 没有删除步骤时为 null */
+  clean: CleanSummary | null;
+  /** 没有核对步骤或无法查询可用空间时为 null */
+  verify: VerifyResult | null;
+}
+
+/**
+ * 在后端依次执行扫描、删除、核对步骤，通过 cleanup-pipeline:progress 事件报告总进度
+ * @param steps 步骤列表
+ * @param operationId 写入进度事件的操作标识，用于区分同时进行的多条流水线
+ */
+export async function runCleanupPipeline(
+  steps: CleanStep[],
+  operationId?: string
+): Promise<CleanupPipelineResult> {
+  return invoke<CleanupPipelineResult>('run_cleanup_pipeline', {
+    steps,
+    operationId: operationId ?? null,
+  });
+}
+
 /**
  * 鑾峰彇鎵€鏈夊彲鐢ㄧ殑娓呯悊鍒嗙被
  */
@@ -199,6 +589,37 @@ export async function formatSizeFromRust(bytes: number): Promise<string> {
   return invoke<string>('format_size', { bytes });
 }
 
+/** NTFS 压缩结果（大小均为字节，before/after 为实际占用空间） */
+export interface CompressResult {
+  path: string;
+  logical_size: number;
+  before_size: number;
+  after_size: number;
+  saved_size: number;
+}
+
+/** 对文件开启 NTFS 压缩，作为大文件视图中删除之外的选择 */
+export async function compressFile(path: string): Promise<CompressResult> {
+  return invoke<CompressResult>('compress_file', { path });
+}
+
+/** 移动文件进度（事件 large-file:move-progress） */
+export interface MoveFileProgress {
+  src: string;
+  transferred: number;
+  total: number;
+}
+
+/** 将大文件移动到其他磁盘的目录，返回新路径；不覆盖目标目录中的同名文件 */
+export async function moveFile(src: string, destDir: string): Promise<string> {
+  return invoke<string>('move_file', { src, destDir });
+}
+
+/** 把目录迁移到 dest（不能已存在），并在原路径创建目录联接；系统目录和用户配置文件根目录会被拒绝 */
+export async function relocateFolderWithJunction(src: string, dest: string): Promise<void> {
+  return invoke<void>('relocate_folder_with_junction', { src, dest });
+}
+
 /**
  * 鎵撳紑Windows纾佺洏娓呯悊宸ュ叿
  */
@@ -210,9 +631,23 @@ export async function openDiskCleanup(): Promise<void> {
  * 扫描指定磁盘的大文件
  * @param topN 返回前 N 个最大文件（10-500，默认 50）
  * @param driveLetter 目标盘符，如 C: / D:
+ * @param allowNetwork 允许扫描网络驱动器、可移动磁盘或光驱（未传时使用设置中的 allow_network_scan）
+ * @param excludeDirs 额外跳过的目录（绝对路径），回收站、系统还原目录和 WinSxS 始终跳过
  */
-export async function scanLargeFiles(topN?: number, driveLetter?: string): Promise<LargeFileEntry[]> {
-  return invoke<LargeFileEntry[]>('scan_large_files', { topN, driveLetter });
+export async function scanLargeFiles(
+  topN?: number,
+  driveLetter?: string,
+  allowNetwork?: boolean,
+  lowPriority?: boolean,
+  excludeDirs?: string[],
+): Promise<LargeFileEntry[]> {
+  return invoke<LargeFileEntry[]>('scan_large_files', {
+    topN,
+    driveLetter,
+    allowNetwork,
+    lowPriority,
+    excludeDirs,
+  });
 }
 
 /**
@@ -221,6 +656,14 @@ export async function cancelLargeFileScan(): Promise<void> {
   return invoke<void>('cancel_large_file_scan');
 }
 
+/**
+ * 取消所有正在运行的扫描（大文件、垃圾、深度、社交软件、卸载残留、注册表、大目录）
+ * @returns 被取消的扫描名称，如 "junk"、"large_file"
+ */
+export async function cancelAllScans(): Promise<string[]> {
+  return invoke<string[]>('cancel_all_scans');
+}
+
 /**
  * 鍦ㄦ枃浠惰祫婧愮鐞嗗櫒涓墦寮€鏂囦欢鎵€鍦ㄧ洰褰? */
 export async function openInFolder(path: string): Promise<void> {
@@ -239,6 +682,21 @@ export async function openRecycleBin(): Promise<void> {
   return invoke<void>('open_recycle_bin');
 }
 
+/** 单个驱动器的回收站统计 */
+export interface RecycleBinInfo {
+  /** 盘符，如 "C:" */
+  drive: string;
+  /** 回收站中的条目数 */
+  item_count: number;
+  /** 回收站总大小（字节） */
+  total_size: number;
+}
+
+/** 按驱动器查询回收站的条目数和大小 */
+export async function getRecycleBinDetails(): Promise<RecycleBinInfo[]> {
+  return invoke<RecycleBinInfo[]>('get_recycle_bin_details');
+}
+
 // ============================================================================
 // 绯荤粺鐦﹁韩鐩稿叧
 // ============================================================================
@@ -269,6 +727,21 @@ export async function checkAdminPrivilege(): Promise<boolean> {
   return invoke<boolean>('check_admin_privilege');
 }
 
+/**
+ * 以管理员身份重新启动程序（触发 UAC），成功后当前实例会退出
+ */
+export async function relaunchAsAdmin(): Promise<void> {
+  return invoke<void>('relaunch_as_admin');
+}
+
+/**
+ * 判断执行指定命令前是否需要提权，用于显示盾牌图标
+ * @param action 后端命令名，如 cleanup_winsxs
+ */
+export async function requestElevationFor(action: string): Promise<boolean> {
+  return invoke<boolean>('request_elevation_for', { action });
+}
+
 /**
  * 鑾峰彇绯荤粺鐦﹁韩鐘舵€? */
 export async function getSystemSlimStatus(): Promise<SystemSlimStatus> {
@@ -302,6 +775,32 @@ export async function cleanupWinsxsResetbase(): Promise<string> {
   return invoke<string>('cleanup_winsxs_resetbase');
 }
 
+/**
+ * 创建系统还原点（需要管理员权限，系统盘需已开启系统保护）
+ * @param description 还原点描述，后端会加上 LightC 前缀
+ */
+export async function createRestorePoint(description: string): Promise<void> {
+  return invoke<void>('create_restore_point', { description });
+}
+
+/** 驱动器优化进度事件（drive-optimize-progress）负载 */
+export interface DriveOptimizeProgress {
+  status: 'running' | 'done' | 'error';
+  message: string;
+  /** 介质类型："SSD" / "HDD" / "未知" */
+  media_type: string;
+  progress: number;
+}
+
+/**
+ * 优化驱动器：SSD 执行 TRIM，HDD 执行碎片整理（需要管理员权限）
+ * @param drive 盘符，如 "C:"
+ * @returns defrag 输出摘要
+ */
+export async function optimizeDrive(drive: string): Promise<string> {
+  return invoke<string>('optimize_drive', { drive });
+}
+
 /**
  * 鎵撳紑绯荤粺铏氭嫙鍐呭瓨璁剧疆
  */
@@ -327,6 +826,8 @@ export interface DriverPackageInfo {
   installed_device_count: number;
   outranked_device_count: number;
   file_count: number;
+  /** Driver Store 中该驱动包目录占用的空间（字节） */
+  package_size: number;
   status: 'old_confirmed' | 'recommended' | 'in_use' | 'no_newer_version' | 'unknown';
   actionable: boolean;
   reason: string;
@@ -338,6 +839,8 @@ export interface DriverScanResult {
   total_count: number;
   candidate_count: number;
   high_confidence_count: number;
+  /** 全部可处理候选驱动包占用的空间（字节） */
+  candidate_size: number;
   device_match_data_available: boolean;
 }
 
@@ -373,6 +876,11 @@ export async function deleteOldDrivers(publishedNames: string[]): Promise<Driver
   return invoke<DriverDeleteResult>('delete_old_drivers', { publishedNames });
 }
 
+/** 备份并删除单个驱动包（如 oem42.inf），后端校验与批量清理一致。 */
+export async function removeDriverPackage(oemInf: string): Promise<DriverDeleteResult> {
+  return invoke<DriverDeleteResult>('remove_driver_package', { oemInf });
+}
+
 /** 递归恢复当前数据目录中所有已保存的驱动包备份。 */
 export async function restoreAllDriverBackups(): Promise<DriverRestoreResult> {
   return invoke<DriverRestoreResult>('restore_all_driver_backups');
@@ -394,6 +902,12 @@ export interface HealthScoreResult {
   hibernation_score: number;
   junk_score: number;
   disk_free_percent: number;
+  /** C 盘总容量（字节，获取失败时为 0） */
+  total_space: number;
+  /** C 盘可用空间（字节） */
+  free_space: number;
+  /** C 盘已用空间（字节） */
+  used_space: number;
   has_hibernation: boolean;
   hibernation_size: number;
   junk_size: number;
@@ -423,6 +937,8 @@ export interface SocialFileEntry {
   size: number;
   /** 鎵€灞炲簲鐢ㄥ悕绉?*/
   app_name: string;
+  /** 修改时间（Unix 时间戳，秒） */
+  modified?: number;
   /** 鏂囦欢鍒嗙被 */
   category: FileCategory;
   /** 椋庨櫓绛夌骇 */
@@ -452,7 +968,16 @@ export interface SocialCategoryStats {
 }
 
 /** 绀句氦杞欢鎵弿缁撴灉 V2 */
+/** 单个社交软件的占用汇总 */
+export interface AppSummary {
+  app_name: string;
+  file_count: number;
+  total_size: number;
+}
+
 export interface SocialScanResult {
+  /** JSON 格式版本，字段改名或删除时递增 */
+  schema_version: number;
   /** 鎸夊垎绫荤粺璁?*/
   categories: SocialCategoryStats[];
   /** 鎬绘枃浠舵暟 */
@@ -465,15 +990,153 @@ export interface SocialScanResult {
   deletable_size: number;
   /** 妫€娴嬪埌鐨勭ぞ浜よ蒋浠跺垪琛?*/
   detected_apps: string[];
+  /** 按应用统计（按总大小降序） */
+  by_app?: AppSummary[];
 }
 
 /**
  * 扫描社交软件缓存（带风险分级）。
  *
  * 这里保留中文说明，是为了让前端风险标签和后端分类语义保持一致，避免后续维护时误改删除策略。
+ * @param apps 只扫描这些软件：wechat / qq（含 NTQQ）/ dingtalk / feishu / wxwork / telegram，不传时扫描全部
+ * @throws CommandError 未知的软件 ID 为 `invalid_input`，被取消时为 `cancelled`
+ */
+export async function scanSocialCache(apps?: string[]): Promise<SocialScanResult> {
+  return invoke<SocialScanResult>('scan_social_cache', { apps });
+}
+
+/** 单个用户的扫描结果 */
+export interface UserProfileScanResult {
+  user_name: string;
+  profile_dir: string;
+  /** 是否为运行程序的用户 */
+  is_current_user: boolean;
+  /** 临时文件和浏览器缓存 */
+  junk: ScanResult;
+  /** 社交软件缓存 */
+  social: SocialScanResult;
+}
+
+/** 逐个扫描 C:\Users 下所有用户的临时文件、浏览器缓存和社交软件缓存（需要管理员权限）。 */
+export async function scanAllUserProfiles(): Promise<UserProfileScanResult[]> {
+  return invoke<UserProfileScanResult[]>('scan_all_user_profiles');
+}
+
+/** 单个 UWP 包的缓存汇总 */
+export interface UwpPackageCache {
+  package_name: string;
+  package_dir: string;
+  cache_size: number;
+  file_count: number;
+  cache_dirs: string[];
+}
+
+/** 按包汇总 %LocalAppData%\Packages 下的 LocalCache / TempState / AC\Temp 缓存（按大小降序） */
+export async function scanUwpCaches(): Promise<UwpPackageCache[]> {
+  return invoke<UwpPackageCache[]>('scan_uwp_caches');
+}
+
+/** 清理指定 UWP 包的缓存目录内容，LocalState / Settings 不会被删除 */
+export async function cleanUwpCaches(packageNames: string[]): Promise<DeleteResult> {
+  return invoke<DeleteResult>('clean_uwp_caches', { packageNames });
+}
+
+/**
+ * 按历史清理会话重新删除仍存在的同一批路径（经过删除引擎的保护检查）
+ * @param filename 清理历史中的日志文件名
  */
-export async function scanSocialCache(): Promise<SocialScanResult> {
-  return invoke<SocialScanResult>('scan_social_cache');
+export async function recleanSession(filename: string): Promise<DeleteResult> {
+  return invoke<DeleteResult>('reclean_session', { filename });
+}
+
+/** Windows Defender 扫描历史缓存汇总 */
+export interface DefenderCacheInfo {
+  /** Windows Defender 数据目录 */
+  defender_dir: string;
+  total_size: number;
+  file_count: number;
+}
+
+/** 统计 Windows Defender 可清理的扫描历史缓存（Scans\History 下的 CacheManager / Results 和 mpcache-*.bin） */
+export async function getDefenderCacheInfo(): Promise<DefenderCacheInfo> {
+  return invoke<DefenderCacheInfo>('get_defender_cache_info');
+}
+
+/** 清理 Windows Defender 扫描历史缓存（需要管理员权限），被占用的文件重启后删除；隔离区不受影响 */
+export async function cleanDefenderCache(): Promise<DeleteResult> {
+  return invoke<DeleteResult>('clean_defender_cache');
+}
+
+/** CBS 归档日志汇总 */
+export interface CbsLogInfo {
+  /** CBS 日志目录 */
+  cbs_dir: string;
+  total_size: number;
+  file_count: number;
+}
+
+/** 统计 Windows\Logs\CBS 下可清理的 CbsPersist_*.log / .cab 轮转归档 */
+export async function getCbsLogInfo(): Promise<CbsLogInfo> {
+  return invoke<CbsLogInfo>('get_cbs_log_info');
+}
+
+/** 清理 CBS 轮转归档日志（需要管理员权限），当前的 CBS.log 保留，被占用的归档重启后删除 */
+export async function cleanCbsLogs(): Promise<DeleteResult> {
+  return invoke<DeleteResult>('clean_cbs_logs');
+}
+
+/**
+ * 清理社交软件分类中的文件，只保留最新的 keepRecent 个
+ * @param categoryId 分类 ID（来自最近一次 scanSocialCache 的结果）
+ * @param keepRecent 按修改时间保留的最新文件数
+ */
+export async function deleteSocialFilesKeepRecent(
+  categoryId: string,
+  keepRecent: number,
+): Promise<EnhancedDeleteResult> {
+  return invoke<EnhancedDeleteResult>('delete_social_files_keep_recent', { categoryId, keepRecent });
+}
+
+/** 重复组中的一份文件 */
+export interface SocialDuplicateFile {
+  path: string;
+  app_name: string;
+  /** 修改时间（Unix 时间戳，秒） */
+  modified: number;
+  /** 建议保留的副本（每组一份） */
+  keep: boolean;
+}
+
+/** 一组内容相同的社交软件媒体文件 */
+export interface SocialDuplicateGroup {
+  /** 单个文件大小（字节） */
+  size: number;
+  /** 组内文件，建议保留的副本在最前 */
+  files: SocialDuplicateFile[];
+  /** 删除其余副本可释放的空间 */
+  reclaimable_size: number;
+  /** 副本是否分布在多个软件中 */
+  cross_app: boolean;
+}
+
+/** 社交软件重复媒体查找结果 */
+export interface SocialDuplicateReport {
+  /** 重复组（按可释放空间降序） */
+  groups: SocialDuplicateGroup[];
+  /** 建议删除的副本数 */
+  removable_count: number;
+  /** 删除全部建议副本可释放的空间 */
+  reclaimable_size: number;
+  /** 其中跨软件重复可释放的空间 */
+  cross_app_reclaimable_size: number;
+}
+
+/**
+ * 在最近一次 scanSocialCache 的图片视频中查找内容相同的文件
+ * @throws CommandError 被取消时为 `cancelled`
+ */
+export async function findDuplicateSocialMedia(): Promise<SocialDuplicateReport> {
+  return invoke<SocialDuplicateReport>('find_duplicate_social_media');
 }
 
 /** 获取风险等级的中文描述，用于社交专清列表里的风险标签展示。 */
@@ -502,12 +1165,26 @@ export function getRiskLevelTooltip(level: RiskLevel): string {
 
 /** 鍗歌浇娈嬬暀鎵弿缁撴灉 */
 export interface LeftoverScanResult {
+  /** JSON 格式版本，字段改名或删除时递增 */
+  schema_version: number;
   /** 鍙戠幇鐨勬畫鐣欐枃浠跺す鍒楄〃 */
   leftovers: LeftoverEntry[];
   /** 鎬诲ぇ灏忥紙瀛楄妭锛?*/
   total_size: number;
   /** 鎵弿鑰楁椂锛堟绉掞級 */
   scan_duration_ms: number;
+  /** 扫描是否被取消（取消时 leftovers 只包含已完成的部分结果） */
+  cancelled?: boolean;
+}
+
+/** 卸载残留扫描进度事件（leftover-scan:progress）负载 */
+export interface LeftoverScanProgress {
+  /** 当前正在分析的文件夹名称 */
+  current_folder: string;
+  /** 已分析的文件夹数量 */
+  scanned_count: number;
+  /** 已发现的残留数量 */
+  found_count: number;
 }
 
 /** 娈嬬暀绫诲瀷 */
@@ -542,6 +1219,10 @@ export interface LeftoverEntry {
   detection_category: DetectionCategory;
   /** 璇勫垎鐞嗙敱鍒楄〃锛堜腑鏂囷級 */
   reasons: string[];
+  /** 数据风险等级 (1-5)：1 = 配置/缓存，5 = 很可能包含用户数据 */
+  risk_level?: number;
+  /** 删除前需要用户额外确认（高风险条目不默认勾选） */
+  requires_confirmation?: boolean;
 }
 
 /** 鍗歌浇娈嬬暀鍒犻櫎缁撴灉 */
@@ -561,9 +1242,31 @@ export interface LeftoverDeleteResult {
 /**
  * 鎵弿鍗歌浇娈嬬暀
  * 鎵弿 AppData 鍜?ProgramData 涓凡鍗歌浇杞欢閬楃暀鐨勫绔嬫枃浠跺す
- * @param deepScan 鏄惁鍚敤娣卞害鎵弿妯″紡锛堟壂鎻忔ā鎷熷櫒娈嬬暀銆佽櫄鎷熺鐩樻枃浠剁瓑锛? */
-export async function scanUninstallLeftovers(deepScan?: boolean): Promise<LeftoverScanResult> {
-  return invoke<LeftoverScanResult>('scan_uninstall_leftovers', { deepScan });
+ * @param deepScan 鏄惁鍚敤娣卞害鎵弿妯″紡锛堟壂鎻忔ā鎷熷櫒娈嬬暀銆佽櫄鎷熺鐩樻枃浠剁瓑锛?
+ * @param aggressive 激进模式：已确认卸载的应用目录忽略修改时间，卸载后无需等待即可显示
+ * @throws CommandError 失败时抛出结构化错误
+ */
+export async function scanUninstallLeftovers(
+  deepScan?: boolean,
+  aggressive?: boolean,
+): Promise<LeftoverScanResult> {
+  return invoke<LeftoverScanResult>('scan_uninstall_leftovers', { deepScan, aggressive });
+}
+
+/**
+ * 取消卸载残留扫描，scanUninstallLeftovers 会返回已完成的部分结果
+ */
+export async function cancelLeftoverScan(): Promise<void> {
+  return invoke<void>('cancel_leftover_scan');
+}
+
+/**
+ * 将最近一次扫描的发现导出为 JSON（删除前审计用）
+ * @param kind 'leftovers' 卸载残留 / 'registry' 注册表残留
+ * @param outputPath 导出文件路径
+ */
+export async function exportFindings(kind: 'leftovers' | 'registry', outputPath: string): Promise<void> {
+  return invoke<void>('export_findings', { kind, outputPath });
 }
 
 /**
@@ -572,12 +1275,47 @@ export async function deleteLeftoverFolders(paths: string[]): Promise<LeftoverDe
   return invoke<LeftoverDeleteResult>('delete_leftover_folders', { paths });
 }
 
+/** 白名单作用的扫描器 */
+export type WhitelistKind = 'leftover_folder' | 'registry_key';
+
+/** 白名单条目 */
+export interface WhitelistEntry {
+  kind: WhitelistKind;
+  /** 匹配模式（小写）："name" 精确匹配，"name*" 前缀匹配，"*name" 后缀匹配 */
+  pattern: string;
+  /** 是否为内置条目（内置条目不可移除） */
+  builtin: boolean;
+}
+
+/**
+ * 获取卸载残留和注册表扫描的白名单（内置条目 + 用户条目）
+ */
+export async function getWhitelist(): Promise<WhitelistEntry[]> {
+  return invoke<WhitelistEntry[]>('get_whitelist');
+}
+
+/**
+ * 添加用户白名单条目，下次扫描生效
+ */
+export async function addWhitelistEntry(kind: WhitelistKind, pattern: string): Promise<void> {
+  return invoke<void>('add_whitelist_entry', { kind, pattern });
+}
+
+/**
+ * 移除用户白名单条目，内置条目不可移除
+ */
+export async function removeWhitelistEntry(kind: WhitelistKind, pattern: string): Promise<void> {
+  return invoke<void>('remove_whitelist_entry', { kind, pattern });
+}
+
 // ============================================================================
 // 娉ㄥ唽琛ㄥ啑浣欐壂鎻忕浉鍏?(v3 鈥?纭繃婊ゆ敹鏁?
 // ============================================================================
 
 /** 娉ㄥ唽琛ㄦ壂鎻忕粨鏋?*/
 export interface RegistryScanResult {
+  /** JSON 格式版本，字段改名或删除时递增 */
+  schema_version: number;
   entries: RegistryEntry[];
   total_count: number;
   scan_duration_ms: number;
@@ -596,6 +1334,18 @@ export interface RegistryEntry {
 }
 
 /** 娉ㄥ唽琛ㄥ垹闄ょ粨鏋?*/
+/** 注册表扫描进度事件（registry-scan:progress）负载 */
+export interface RegistryScanProgress {
+  /** 当前阶段：applications 或 software */
+  phase: 'applications' | 'software';
+  /** 当前正在读取的子键名，阶段开始时为空 */
+  current_key: string;
+  /** 当前阶段已读取的子键数量 */
+  scanned_count: number;
+  /** 已发现的残留数量 */
+  found_count: number;
+}
+
 export interface RegistryDeleteResult {
   backup_path: string;
   deleted_count: number;
@@ -605,9 +1355,11 @@ export interface RegistryDeleteResult {
 
 /**
  * 鎵弿娉ㄥ唽琛ㄥ啑浣? * 鍙壂鎻?MUI 缂撳瓨鍜?HKCR\Applications锛岄€氳繃閾佽瘉鏉′欢杩囨护
+ * @param softwareKeyDepth 传入时同时扫描 HKCU\Software 下的“厂商\产品”配置键（键树读取深度，至少 2）
+ * @throws CommandError 被取消时为 `cancelled`
  */
-export async function scanRegistryRedundancy(): Promise<RegistryScanResult> {
-  return invoke<RegistryScanResult>('scan_registry_redundancy');
+export async function scanRegistryRedundancy(softwareKeyDepth?: number): Promise<RegistryScanResult> {
+  return invoke<RegistryScanResult>('scan_registry_redundancy', { softwareKeyDepth });
 }
 
 /**
@@ -623,6 +1375,28 @@ export async function openRegistryBackupDir(): Promise<void> {
   return invoke<void>('open_registry_backup_dir');
 }
 
+/**
+ * 获取用户忽略的注册表条目完整路径（小写）
+ */
+export async function getIgnoredRegistryEntries(): Promise<string[]> {
+  return invoke<string[]>('get_ignored_registry_entries');
+}
+
+/**
+ * 忽略注册表条目，之后的扫描结果不再包含该键
+ * @param path 条目完整路径（RegistryEntry.path）
+ */
+export async function ignoreRegistryEntry(path: string): Promise<void> {
+  return invoke<void>('ignore_registry_entry', { path });
+}
+
+/**
+ * 取消忽略注册表条目
+ */
+export async function unignoreRegistryEntry(path: string): Promise<void> {
+  return invoke<void>('unignore_registry_entry', { path });
+}
+
 // ============================================================================
 // 澧炲己鍒犻櫎 API - 鏀寔閿佸畾鏂囦欢澶勭悊鍜岀墿鐞嗗ぇ灏忚绠?// ============================================================================
 
@@ -650,6 +1424,10 @@ export interface FileDeleteResult {
   failure_reason: DeleteFailureReason | null;
   /** 鏄惁鏍囪涓洪噸鍚垹闄?*/
   marked_for_reboot: boolean;
+  /** 占用文件的进程（文件被占用时返回） */
+  locking_processes?: LockingProcess[];
+  /** 失败的具体说明（如权限不足时 icacls 的输出） */
+  failure_detail?: string | null;
 }
 
 /** 澧炲己鍒犻櫎缁撴灉 */
@@ -670,6 +1448,8 @@ export interface EnhancedDeleteResult {
   file_results: FileDeleteResult[];
   /** 鏄惁闇€瑕侀噸鍚畬鎴愭竻鐞?*/
   needs_reboot: boolean;
+  /** 按失败原因统计的文件数（键为失败原因的中文描述） */
+  failure_breakdown?: Record<string, number>;
   /** 姹囨€绘秷鎭紙WeChat 椋庢牸锛?*/
   summary_message: string;
 }
@@ -677,9 +1457,19 @@ export interface EnhancedDeleteResult {
 /**
  * 澧炲己鍒犻櫎鏂囦欢
  * 鏀寔鐗╃悊澶у皬璁＄畻銆侀攣瀹氭枃浠跺鐞嗐€佽缁嗗け璐ュ師鍥犲弽棣? * @param paths 瑕佸垹闄ょ殑鏂囦欢璺緞鍒楄〃
+ * @param closeLockingApps 文件被占用时是否请求占用程序关闭后重试
+ * @param rebootDelete 被占用的文件是否标记为重启后删除，为 false 时作为失败返回
  */
-export async function enhancedDeleteFiles(paths: string[]): Promise<EnhancedDeleteResult> {
-  return invoke<EnhancedDeleteResult>('enhanced_delete_files', { paths });
+export async function enhancedDeleteFiles(
+  paths: string[],
+  closeLockingApps = false,
+  rebootDelete = true,
+): Promise<EnhancedDeleteResult> {
+  return invoke<EnhancedDeleteResult>('enhanced_delete_files', {
+    paths,
+    closeLockingApps,
+    rebootDelete,
+  });
 }
 
 export interface DeepJunkDeleteOptions {
@@ -779,6 +1569,16 @@ export interface LeftoverPermanentDeleteDetail {
   needs_manual_review: boolean;
   /** 瀹夊叏妫€鏌ョ粨鏋?*/
   safety_check: SafetyCheckResult;
+  /** 按一级子目录汇总的删除明细，部分失败时定位残留位置 */
+  subfolder_breakdown: SubfolderDeleteStat[];
+}
+
+/** 一级子目录的删除统计 */
+export interface SubfolderDeleteStat {
+  path: string;
+  deleted_files: number;
+  freed_size: number;
+  failed_files: number;
 }
 
 /** 姘镐箙鍒犻櫎鐨勬€讳綋缁撴灉 */
@@ -799,6 +1599,16 @@ export interface PermanentDeleteResult {
   duration_ms: number;
 }
 
+/** 永久删除进度事件 `leftover-delete:progress`（按文件计） */
+export interface PermanentDeleteProgress {
+  /** 已处理的文件数 */
+  done: number;
+  /** 删除前预先统计的文件总数 */
+  total: number;
+  /** 完成百分比（0-100） */
+  percent: number;
+}
+
 /**
  * 姘镐箙鍒犻櫎鍗歌浇娈嬬暀锛堟繁搴︽竻鐞嗭級
  * 
@@ -809,8 +1619,16 @@ export interface PermanentDeleteResult {
  * 2. 鍙墽琛屾枃浠舵鏌?- 鎵弿 .exe/.dll/.sys 鏂囦欢锛屽彂鐜板垯璺宠繃
  * 3. 鏍稿績鐧藉悕鍗曟鏌?- 纭繚璺緞涓嶅湪绯荤粺鍏抽敭鐩綍鍐? * 
  * @param paths 瑕佹案涔呭垹闄ょ殑鏂囦欢澶硅矾寰勫垪琛? */
-export async function deleteLeftoversPermanent(paths: string[]): Promise<PermanentDeleteResult> {
-  return invoke<PermanentDeleteResult>('delete_leftovers_permanent', { paths });
+export async function deleteLeftoversPermanent(
+  paths: string[],
+  maxConcurrency?: number,
+  rebootDelete = true,
+): Promise<PermanentDeleteResult> {
+  return invoke<PermanentDeleteResult>('delete_leftovers_permanent', {
+    paths,
+    maxConcurrency,
+    rebootDelete,
+  });
 }
 
 /**
@@ -875,6 +1693,8 @@ export interface SystemInfo {
   available_memory: number;
   /** 绯荤粺鍚姩鏃堕棿锛堢锛?*/
   uptime_seconds: number;
+  /** 系统盘类型："SSD" / "HDD" / "未知" */
+  c_drive_type: string;
 }
 
 /**
@@ -884,6 +1704,14 @@ export async function getSystemInfo(): Promise<SystemInfo> {
   return invoke<SystemInfo>('get_system_info');
 }
 
+/**
+ * 清空待机内存列表（需要管理员权限）
+ * @returns 可用物理内存增加的字节数
+ */
+export async function clearStandbyMemory(): Promise<number> {
+  return invoke<number>('clear_standby_memory');
+}
+
 // ============================================================================
 // 娓呯悊鏃ュ織鐩稿叧 API
 // ============================================================================
@@ -967,6 +1795,81 @@ export async function getCleanupHistory(): Promise<CleanupHistorySummary[]> {
   return invoke<CleanupHistorySummary[]>('get_cleanup_history');
 }
 
+/**
+ * 单条清理日志记录
+ */
+export interface CleanupLogEntry {
+  /** 操作时间 */
+  timestamp: string;
+  /** 清理模块分类 */
+  category: string;
+  /** 文件路径 */
+  path: string;
+  /** 释放的空间（字节） */
+  size: number;
+  /** 操作结果: Success / Failed / Locked_Pending_Reboot */
+  result: string;
+  /** 错误信息 */
+  error_message?: string;
+}
+
+/**
+ * 单次清理会话的完整日志
+ */
+export interface CleanupSession {
+  session_start: string;
+  session_end: string;
+  total_files: number;
+  success_count: number;
+  failed_count: number;
+  total_freed_bytes: number;
+  /** 逐条清理明细 */
+  entries: CleanupLogEntry[];
+}
+
+/**
+ * 读取一次清理会话的完整日志（含逐条明细）
+ * @param filename 清理历史中的日志文件名
+ */
+export async function getCleanupSession(filename: string): Promise<CleanupSession> {
+  return invoke<CleanupSession>('get_cleanup_session', { filename });
+}
+
+/**
+ * 系统级操作审计记录
+ */
+export interface AuditEntry {
+  /** 操作时间（ISO 8601） */
+  timestamp: string;
+  /** 命令名称 */
+  action: string;
+  /** 命令参数 */
+  params: Record<string, unknown>;
+  /** 执行时是否为管理员 */
+  elevated: boolean;
+  /** 是否成功 */
+  success: boolean;
+  /** 失败原因 */
+  error?: string;
+}
+
+/**
+ * 获取系统级操作审计日志（最新的在前）
+ * @param limit 最多返回条数，默认 500
+ */
+export async function getAuditLog(limit?: number): Promise<AuditEntry[]> {
+  return invoke<AuditEntry[]>('get_audit_log', { limit });
+}
+
+/**
+ * 导出清理报告为 HTML
+ * @param outputPath 报告保存路径
+ * @param filename 清理历史中的日志文件名，不传时导出最近一次清理
+ */
+export async function exportCleanupReportHtml(outputPath: string, filename?: string): Promise<void> {
+  return invoke<void>('export_cleanup_report_html', { outputPath, filename });
+}
+
 // ============================================================================
 // 澶х洰褰曞垎鏋愮浉鍏?API
 // ============================================================================
@@ -1056,8 +1959,9 @@ export async function scanHotspot(
   sizeThresholdMb?: number,
   ignoreSystemDirs?: boolean,
   driveLetter?: string,
+  allowNetwork?: boolean,
 ): Promise<HotspotScanResult> {
-  return invoke<HotspotScanResult>('scan_hotspot', { topN, fullScan, maxDepth, sizeThresholdMb, ignoreSystemDirs, driveLetter });
+  return invoke<HotspotScanResult>('scan_hotspot', { topN, fullScan, maxDepth, sizeThresholdMb, ignoreSystemDirs, driveLetter, allowNetwork });
 }
 
 /**
@@ -1072,8 +1976,8 @@ export async function cancelHotspotScan(): Promise<void> {
  * 鎵弿鎸囧畾璺緞鐨勭洿鎺ュ瓙鏂囦欢澶癸紝鐢ㄤ簬閫愬眰灞曞紑娣卞眰鐩綍缁撴瀯
  * @param path 瑕佹壂鎻忕殑鐩爣鐩綍缁濆璺緞
  */
-export async function scanPathDirect(path: string): Promise<HotspotScanResult> {
-  return invoke<HotspotScanResult>('scan_path_direct', { path });
+export async function scanPathDirect(path: string, allowNetwork?: boolean): Promise<HotspotScanResult> {
+  return invoke<HotspotScanResult>('scan_path_direct', { path, allowNetwork });
 }
 
 /**
@@ -1591,6 +2495,13 @@ export async function getDataDirectory(): Promise<string> {
   return invoke<string>('get_data_directory');
 }
 
+/**
+ * 在资源管理器中打开数据目录
+ */
+export async function openAppDataFolder(): Promise<void> {
+  return invoke<void>('open_app_data_folder');
+}
+
 /**
  * 璁剧疆鏁版嵁鐩綍骞惰縼绉诲凡鏈夋暟鎹? * @param path 鏂扮殑鏁版嵁鐩綍璺緞
  */
@@ -1637,3 +2548,49 @@ export async function clearSelectedLocalData(itemIds: string[]): Promise<ClearLo
 export async function pickFolderDialog(): Promise<string | null> {
   return invoke<string | null>('pick_folder_dialog');
 }
+
+/** 全局设置（后端 settings.json） */
+export interface AppSettings {
+  /** 设置文件格式版本，由后端维护 */
+  schema_version: number;
+  /** 删除后是否清理扫描根目录内留下的空目录 */
+  prune_empty_dirs: boolean;
+  /** 永久删除的最大并发线程数，null 表示使用 CPU 核心数 */
+  delete_max_concurrency: number | null;
+  /** 是否允许扫描网络驱动器和可移动磁盘 */
+  allow_network_scan: boolean;
+  /** 快速扫描每个分类首次返回的最大文件数，null 表示不限制 */
+  max_files_per_category: number | null;
+  /** 收藏的清理分类（显示名称），加载时默认勾选 */
+  favorite_categories: string[];
+  /** formatSizeFromRust 使用的单位：windows = 1024 进制标注 GB（默认），binary = GiB，decimal = 1000 进制 GB */
+  size_units: 'windows' | 'binary' | 'decimal';
+  /** 注册表删除、旧驱动清理、WinSxS 清理前自动创建系统还原点，创建失败时取消操作 */
+  auto_restore_point_before_risky_ops: boolean;
+}
+
+/**
+ * 读取全局设置
+ */
+export async function getSettings(): Promise<AppSettings> {
+  return invoke<AppSettings>('get_settings');
+}
+
+/**
+ * 更新部分设置项
+ * @param patch 只包含需要修改的字段
+ * @returns 保存后的完整设置
+ */
+export async function updateSettings(patch: Partial<Omit<AppSettings, 'schema_version'>>): Promise<AppSettings> {
+  return invoke<AppSettings>('update_settings', { patch });
+}
+
+/** 读取收藏的清理分类（显示名称） */
+export async function getFavoriteCategories(): Promise<string[]> {
+  return invoke<string[]>('get_favorite_categories');
+}
+
+/** 收藏或取消收藏一个清理分类，返回更新后的收藏列表 */
+export async function toggleFavoriteCategory(categoryName: string): Promise<string[]> {
+  return invoke<string[]>('toggle_favorite_category', { categoryName });
+}
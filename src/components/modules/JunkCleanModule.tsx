@@ -391,6 +391,7 @@ export function JunkCleanModule({ layoutMode = 'cards', isPageActive = true }: M
       phase: 'preparing',
       processed_count: 0,
       total_count: selectedFileCount,
+      percent: 0,
       success_count: 0,
       failed_count: 0,
       reboot_pending_count: 0,
@@ -441,6 +442,7 @@ export function JunkCleanModule({ layoutMode = 'cards', isPageActive = true }: M
           phase: 'cleaning',
           processed_count: result.success_count + result.failed_count + result.reboot_pending_count,
           total_count: selectedFileCount,
+          percent: 100,
           success_count: result.success_count,
           failed_count: result.failed_count,
           reboot_pending_count: result.reboot_pending_count,
@@ -21,6 +21,7 @@ import {
   type RegistryScanResult,
   type RegistryEntry,
   type CleanupLogEntryInput,
+  CommandError,
 } from '../../api/commands';
 import { shouldSkipInactivePageRender, type ModuleRenderProps } from './moduleProps';
 
@@ -68,6 +69,11 @@ export function RegistryModule({ layoutMode = 'cards', isPageActive = true }: Mo
 
       setExpandedModule('registry');
     } catch (err) {
+      // 用户停止扫描不算失败，回到初始状态
+      if (err instanceof CommandError && err.code === 'cancelled') {
+        updateModuleState('registry', { status: 'idle', error: null });
+        return;
+      }
       console.error('注册表扫描失败:', err);
       updateModuleState('registry', { status: 'error', error: String(err) });
     }
@@ -43,7 +43,8 @@ import {
   type RiskLevel,
   type CleanupLogEntryInput,
   getRiskLevelDescription,
-  getRiskLevelTooltip
+  getRiskLevelTooltip,
+  CommandError,
 } from '../../api/commands';
 import { formatSize } from '../../utils/format';
 import { shouldSkipInactivePageRender, type ModuleRenderProps } from './moduleProps';
@@ -147,6 +148,11 @@ export function SocialCleanModule({ layoutMode = 'cards', isPageActive = true }:
 
       setExpandedModule('social');
     } catch (err) {
+      // 用户停止扫描不算失败，回到初始状态
+      if (err instanceof CommandError && err.code === 'cancelled') {
+        updateModuleState('social', { status: 'idle', error: null });
+        return;
+      }
       console.error('扫描社交软件缓存失败:', err);
       updateModuleState('social', { status: 'error', error: String(err) });
     }
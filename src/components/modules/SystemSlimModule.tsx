@@ -27,6 +27,7 @@ import {
   cleanupWinsxs,
   cleanupWinsxsResetbase,
   openVirtualMemorySettings,
+  relaunchAsAdmin,
   SlimItemStatus,
   SystemSlimStatus
 } from '../../api/commands';
@@ -131,10 +132,19 @@ export function SystemSlimModule({ layoutMode = 'cards', isPageActive = true }:
     }
   }, [oneClickScanTrigger, loadStatus]);
 
+  // 以管理员身份重新启动；用户在 UAC 中取消时保留当前实例
+  const handleRelaunchAsAdmin = useCallback(async () => {
+    try {
+      await relaunchAsAdmin();
+    } catch (error) {
+      showToast({ title: '需要管理员权限', description: String(error), type: 'error' });
+    }
+  }, [showToast]);
+
   // 执行瘦身操作
   const handleAction = useCallback(async (item: SlimItemStatus) => {
     if (!status?.is_admin) {
-      showToast({ title: '需要管理员权限', description: '请以管理员身份运行程序', type: 'error' });
+      await handleRelaunchAsAdmin();
       return;
     }
 
@@ -175,7 +185,7 @@ export function SystemSlimModule({ layoutMode = 'cards', isPageActive = true }:
     } finally {
       setActionLoading(null);
     }
-  }, [status, triggerHealthRefresh, showToast, markItemsNeedRescan]);
+  }, [status, triggerHealthRefresh, showToast, markItemsNeedRescan, handleRelaunchAsAdmin]);
 
   const isExpanded = expandedModule === 'system';
 
@@ -226,8 +236,14 @@ export function SystemSlimModule({ layoutMode = 'cards', isPageActive = true }:
             <div className="flex-1">
               <p className="text-xs font-medium text-amber-600">需要管理员权限</p>
               <p className="text-[11px] text-[var(--fg-muted)] mt-0.5">
-                请关闭程序，右键点击程序图标选择"以管理员身份运行"。
+                执行下方操作时会请求以管理员身份重新启动 LightC。
               </p>
+              <button
+                onClick={handleRelaunchAsAdmin}
+                className="mt-1.5 text-[11px] font-medium text-amber-600 hover:text-amber-700 underline underline-offset-2"
+              >
+                立即以管理员身份重新启动
+              </button>
             </div>
             <button onClick={() => setShowAdminWarning(false)} className="text-amber-500 hover:text-amber-700 transition shrink-0">
               <X className="w-3.5 h-3.5" />
@@ -305,10 +321,11 @@ export function SystemSlimModule({ layoutMode = 'cards', isPageActive = true }:
                       <div className="shrink-0">
                         <button
                           onClick={() => handleAction(item)}
-                          disabled={!item.actionable || isLoading || !status.is_admin}
+                          disabled={!item.actionable || isLoading}
+                          title={status.is_admin ? undefined : '需要管理员权限，点击后将请求提权重新启动'}
                           className={`
                             px-3 py-1.5 rounded-lg text-xs font-medium transition-all flex items-center gap-1.5
-                            ${item.actionable && status.is_admin
+                            ${item.actionable
                               ? 'bg-emerald-500 text-white hover:bg-emerald-600 active:scale-95'
                               : 'bg-[var(--bg-hover)] text-[var(--fg-muted)] cursor-not-allowed'
                             }
@@ -321,6 +338,7 @@ export function SystemSlimModule({ layoutMode = 'cards', isPageActive = true }:
                             </>
                           ) : (
                             <>
+                              {!status.is_admin && <ShieldAlert className="w-3 h-3" />}
                               <span>{item.action_text}</span>
                               <ChevronRight className="w-3 h-3" />
                             </>
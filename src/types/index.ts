@@ -19,8 +19,14 @@ export type JunkCategory =
   | 'FontCache'
   | 'WindowsErrorReports'
   | 'InstallerTemp'
+  | 'InstallerRollback'
+  | 'InstallerPatchCache'
   | 'ClipboardCache'
-  | 'ShaderCache';
+  | 'ShaderCache'
+  | 'OfficeCache'
+  | 'DeveloperCache'
+  | 'GameCache'
+  | 'Custom';
 
 /** 单个文件信息 */
 export interface FileInfo {
@@ -42,6 +48,8 @@ export interface FileInfo {
 
 /** 分类扫描结果 */
 export interface CategoryScanResult {
+  /** 稳定标识（JunkCategory 变体名），多次扫描之间不变，可用作列表 key */
+  key: string;
   /** 分类 */
   category: JunkCategory;
   /** 分类显示名称 */
@@ -58,10 +66,20 @@ export interface CategoryScanResult {
   file_count: number;
   /** 深度扫描是否还有未加载的文件 */
   has_more?: boolean;
+  /** 低于最小文件大小、未列入 files 的文件数（其大小已计入 total_size） */
+  small_file_count?: number;
+  /** 未列入 files 的小文件总大小 */
+  small_file_size?: number;
+  /** 达到文件数或耗时上限后提前停止，结果不完整 */
+  truncated?: boolean;
+  /** 因权限不足无法读取的扫描根目录，可提示以管理员身份重新扫描 */
+  access_denied_paths?: string[];
 }
 
 /** 完整扫描结果 */
 export interface ScanResult {
+  /** JSON 格式版本，字段改名或删除时递增 */
+  schema_version: number;
   /** 各分类的扫描结果 */
   categories: CategoryScanResult[];
   /** 总大小（字节） */
@@ -109,6 +127,8 @@ export interface EnhancedDeleteProgress {
   processed_count: number;
   /** 去重后的待处理文件总数。 */
   total_count: number;
+  /** 完成百分比（0-100）。 */
+  percent: number;
   /** 已成功删除的文件数量。 */
   success_count: number;
   /** 已失败的文件数量。 */
@@ -129,6 +149,10 @@ export interface DeleteResult {
   failed_count: number;
   /** 标记为重启后删除的文件数 */
   reboot_pending_count: number;
+  /** 标记为重启后删除的文件大小（已计入 freed_size） */
+  reboot_pending_size: number;
+  /** 删除前已不存在而跳过的文件数（不计入失败） */
+  skipped_notfound: number;
   /** 释放的空间大小（字节） */
   freed_size: number;
   /** 是否需要重启完成清理 */
@@ -167,18 +191,36 @@ export interface CategoryInfo {
   description: string;
   /** 风险等级 */
   risk_level: number;
+  /** 是否已收藏，加载时默认勾选 */
+  is_favorite: boolean;
 }
 
 /** 扫描请求参数 */
 export interface ScanRequest {
   /** 要扫描的分类列表 */
   categories?: string[];
+  /** 只返回有发现的分类 */
+  skip_empty?: boolean;
+  /** 每个分类最多返回的文件数（按大小取最大的），其余通过 getCategoryFiles 分页读取；未传时使用设置中的值 */
+  max_files_per_category?: number;
+  /** 小于该大小（字节）的文件不列出，只计入分类总大小和小文件数量 */
+  min_file_size?: number;
+  /** 最大扫描深度（1-40，默认 10）；浏览器缓存、npm 缓存等嵌套很深的目录适合调高 */
+  max_depth?: number;
+  /** 单个分类最多统计的文件数，超出后该分类标记为 truncated */
+  max_files?: number;
+  /** 单个分类最长扫描时间（秒），超出后该分类标记为 truncated */
+  max_duration_secs?: number;
+  /** 低优先级扫描：降低 CPU/I/O 优先级并分批休眠，速度换流畅度 */
+  low_priority?: boolean;
 }
 
 /** 删除请求参数 */
 export interface DeleteRequest {
   /** 要删除的文件路径列表 */
   paths: string[];
+  /** 删除后清理扫描根目录内留下的空目录，未传时使用设置中的值 */
+  prune_empty_dirs?: boolean;
 }
 
 /** 大文件扫描结果条目 */